@@ -0,0 +1,182 @@
+#ifndef BASIC_GRAPH_H
+#define BASIC_GRAPH_H
+
+/* Generated with cbindgen from src/ffi.rs; do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Result of a fallible call. Output parameters are only written on `Ok`, unless stated
+ * otherwise.
+ */
+typedef enum BgStatus {
+  BG_STATUS_OK = 0,
+  BG_STATUS_NULL_POINTER,
+  /**
+   * A string isn't valid UTF-8, or a value can't be returned because it contains NUL.
+   */
+  BG_STATUS_INVALID_STRING,
+  BG_STATUS_NODE_NOT_FOUND,
+  BG_STATUS_NODE_EXISTS,
+  BG_STATUS_NO_PATH,
+  /**
+   * The output buffer is too short; the required length has been written.
+   */
+  BG_STATUS_BUFFER_TOO_SMALL,
+  BG_STATUS_PARSE_ERROR,
+} BgStatus;
+
+/**
+ * An opaque handle to a graph with string values.
+ */
+typedef struct BgGraph BgGraph;
+
+typedef uint64_t BgGraphId;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Creates an empty graph.
+ */
+struct BgGraph *bg_graph_new(void);
+
+/**
+ * Parses a graph from TGF into `*out`.
+ *
+ * # Safety
+ *
+ * `tgf` must be a NUL-terminated string and `out` must be valid for writes.
+ */
+enum BgStatus bg_graph_parse(const char *tgf, struct BgGraph **out);
+
+/**
+ * Frees a graph. Passing NULL does nothing.
+ *
+ * # Safety
+ *
+ * `graph` must be NULL or a handle returned by this library that hasn't been freed yet.
+ */
+void bg_graph_free(struct BgGraph *graph);
+
+/**
+ * Serializes the graph as TGF. Returns NULL if `graph` is NULL or a value contains NUL.
+ *
+ * # Safety
+ *
+ * `graph` must be NULL or a valid handle.
+ */
+char *bg_graph_serialize(const struct BgGraph *graph);
+
+/**
+ * Frees a string returned by this library. Passing NULL does nothing.
+ *
+ * # Safety
+ *
+ * `string` must be NULL or a string returned by this library that hasn't been freed yet.
+ */
+void bg_string_free(char *string);
+
+/**
+ * Adds a node with a copy of `value`.
+ *
+ * # Safety
+ *
+ * `graph` must be a valid handle and `value` a NUL-terminated string.
+ */
+enum BgStatus bg_graph_add_node(struct BgGraph *graph, BgGraphId id, const char *value);
+
+/**
+ * Removes a node along with its edges.
+ *
+ * # Safety
+ *
+ * `graph` must be a valid handle.
+ */
+enum BgStatus bg_graph_remove_node(struct BgGraph *graph, BgGraphId id);
+
+/**
+ * Adds an edge between two existing nodes.
+ *
+ * # Safety
+ *
+ * `graph` must be a valid handle.
+ */
+enum BgStatus bg_graph_add_edge(struct BgGraph *graph, BgGraphId from, BgGraphId to);
+
+/**
+ * Removes an edge. Removing a missing edge succeeds.
+ *
+ * # Safety
+ *
+ * `graph` must be a valid handle.
+ */
+enum BgStatus bg_graph_remove_edge(struct BgGraph *graph, BgGraphId from, BgGraphId to);
+
+/**
+ * Returns the number of nodes, or 0 if `graph` is NULL.
+ *
+ * # Safety
+ *
+ * `graph` must be NULL or a valid handle.
+ */
+size_t bg_graph_node_count(const struct BgGraph *graph);
+
+/**
+ * Returns the number of edges, or 0 if `graph` is NULL.
+ *
+ * # Safety
+ *
+ * `graph` must be NULL or a valid handle.
+ */
+size_t bg_graph_edge_count(const struct BgGraph *graph);
+
+/**
+ * Writes a copy of the node's value to `*out`, to be freed with [`bg_string_free`].
+ *
+ * # Safety
+ *
+ * `graph` must be a valid handle and `out` must be valid for writes.
+ */
+enum BgStatus bg_graph_node_value(const struct BgGraph *graph, BgGraphId id, char **out);
+
+/**
+ * Writes the sorted targets of the node's outgoing edges to `out`. The number of targets is
+ * written to `*len` even if `capacity` is too small.
+ *
+ * # Safety
+ *
+ * `graph` must be a valid handle, `len` must be valid for writes and `out` must be valid
+ * for `capacity` writes.
+ */
+enum BgStatus bg_graph_neighbours(const struct BgGraph *graph,
+                                  BgGraphId id,
+                                  BgGraphId *out,
+                                  size_t capacity,
+                                  size_t *len);
+
+/**
+ * Writes the IDs along a shortest path from `from` to `to` to `out`, like
+ * [`bg_graph_neighbours`].
+ *
+ * # Safety
+ *
+ * Same as [`bg_graph_neighbours`].
+ */
+enum BgStatus bg_graph_shortest_path(const struct BgGraph *graph,
+                                     BgGraphId from,
+                                     BgGraphId to,
+                                     BgGraphId *out,
+                                     size_t capacity,
+                                     size_t *len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* BASIC_GRAPH_H */
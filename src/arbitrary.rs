@@ -0,0 +1,107 @@
+use quickcheck::{Arbitrary, Gen};
+
+use super::{Graph, GraphId};
+
+impl<T: Arbitrary> Arbitrary for Graph<T> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let node_count = usize::arbitrary(g) % (g.size() + 1);
+        let mut graph = Graph::new();
+        for id in 0..node_count as GraphId {
+            graph.add_node(id, T::arbitrary(g));
+        }
+
+        if node_count > 0 {
+            let edge_count = usize::arbitrary(g) % (2 * g.size() + 1);
+            for _ in 0..edge_count {
+                let from = GraphId::arbitrary(g) % node_count as GraphId;
+                let to = GraphId::arbitrary(g) % node_count as GraphId;
+                graph.add_edge(from, to);
+            }
+        }
+
+        graph
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let graph = self.clone();
+        let without_nodes = graph
+            .nodes
+            .keys()
+            .copied()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map({
+                let graph = graph.clone();
+                move |id| {
+                    let mut shrunk = graph.clone();
+                    shrunk.delete_node(id);
+                    shrunk
+                }
+            });
+        let without_edges = graph
+            .edges
+            .iter()
+            .copied()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map({
+                let graph = graph.clone();
+                move |edge| {
+                    let mut shrunk = graph.clone();
+                    shrunk.delete_edge(edge.from, edge.to);
+                    shrunk
+                }
+            });
+        let with_smaller_values = graph
+            .nodes
+            .iter()
+            .map(|(&id, value)| (id, value.clone()))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flat_map(move |(id, value)| {
+                let graph = graph.clone();
+                value.shrink().map(move |smaller| {
+                    let mut shrunk = graph.clone();
+                    shrunk.nodes.insert(id, smaller);
+                    shrunk
+                })
+            });
+
+        Box::new(
+            without_nodes
+                .chain(without_edges)
+                .chain(with_smaller_values),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::QuickCheck;
+
+    use super::*;
+
+    #[test]
+    fn arbitrary_edges_have_endpoints() {
+        fn prop(graph: Graph<u8>) -> bool {
+            graph.edges.iter().all(|edge| {
+                graph.nodes.contains_key(&edge.from) && graph.nodes.contains_key(&edge.to)
+            })
+        }
+
+        QuickCheck::new().quickcheck(prop as fn(Graph<u8>) -> bool);
+    }
+
+    #[test]
+    fn shrink_removes_nodes_and_edges() {
+        let graph: Graph<u8> = Graph::from(([(1, 0), (2, 0)], [(1, 2)]));
+        let shrunk = graph.shrink().collect::<Vec<_>>();
+        assert_eq!(shrunk.len(), 3, "Expected two node and one edge shrinks");
+        assert!(
+            shrunk
+                .iter()
+                .all(|smaller| smaller.nodes.len() + smaller.edges.len() < 3),
+            "Expected every shrink to be smaller"
+        );
+    }
+}
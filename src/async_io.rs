@@ -0,0 +1,119 @@
+use std::{fmt::Display, path::Path, str::FromStr};
+
+use anyhow::{anyhow, Context};
+use nom::{combinator, Finish};
+use tokio::{
+    fs::File,
+    io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader, BufWriter},
+};
+
+use super::{serde::parse_pairs, Graph, GraphId};
+
+impl<T: FromStr> Graph<T> {
+    /// Reads TGF from `reader` line by line, so the whole file never has to be held in memory
+    /// as text. Accepts the same format as [`FromStr`].
+    pub async fn from_async_reader(reader: impl AsyncBufRead + Unpin) -> anyhow::Result<Self> {
+        let mut graph = Graph::new();
+        let mut lines = reader.lines();
+        let mut in_edges = false;
+        let mut number = 0;
+        while let Some(line) = lines.next_line().await.context("Failed to read graph")? {
+            number += 1;
+            if !in_edges && line == "#" {
+                in_edges = true;
+            } else if in_edges {
+                for (from, to) in parse_line::<GraphId>(&line, number)? {
+                    graph.add_edge(from, to);
+                }
+            } else {
+                for (id, value) in parse_line::<T>(&line, number)? {
+                    graph.add_node(id, value);
+                }
+            }
+        }
+
+        if in_edges {
+            Ok(graph)
+        } else {
+            Err(anyhow!("Parse error: missing \"#\" separator"))
+        }
+    }
+
+    pub async fn from_file_async(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let file = File::open(path)
+            .await
+            .context("Failed to open graph file")?;
+        Self::from_async_reader(BufReader::new(file)).await
+    }
+}
+
+impl<T: Display> Graph<T> {
+    /// Writes the graph as TGF, in the format of [`Graph::serialize`].
+    pub async fn write_async(&self, mut writer: impl AsyncWrite + Unpin) -> anyhow::Result<()> {
+        writer
+            .write_all(self.serialize().as_bytes())
+            .await
+            .context("Failed to write graph")?;
+        writer.flush().await.context("Failed to write graph")
+    }
+
+    pub async fn write_file_async(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let file = File::create(path)
+            .await
+            .context("Failed to create graph file")?;
+        self.write_async(BufWriter::new(file)).await
+    }
+}
+
+fn parse_line<T: FromStr>(line: &str, number: usize) -> anyhow::Result<Vec<(GraphId, T)>> {
+    combinator::all_consuming(parse_pairs)(line)
+        .finish()
+        .map(|(_, pairs)| pairs)
+        .map_err(|e| anyhow!("Parse error on line {number}: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn from_async_reader_matches_from_str() {
+        let text = "1 January\n2 March\n3 April\n#\n1 2\n3 2";
+        let graph: Graph<String> = Graph::from_async_reader(text.as_bytes()).await.unwrap();
+        assert_eq!(
+            graph,
+            text.parse::<Graph<String>>().unwrap(),
+            "Graph doesn't match"
+        );
+    }
+
+    #[tokio::test]
+    async fn from_async_reader_reports_line() {
+        let error = Graph::<i32>::from_async_reader("1 2\n2 banana\n#\n".as_bytes())
+            .await
+            .unwrap_err();
+        assert!(
+            error.to_string().contains("line 2"),
+            "Expected line number in {error}"
+        );
+        assert!(
+            Graph::<i32>::from_async_reader("1 2\n".as_bytes())
+                .await
+                .is_err(),
+            "Expected missing separator error"
+        );
+    }
+
+    #[tokio::test]
+    async fn file_round_trip() {
+        let path = std::env::temp_dir().join(format!("bgraph-async-{}.tgf", std::process::id()));
+        let graph: Graph<String> = Graph::from((
+            [(1, "a".to_string()), (2, "b c".to_string())],
+            [(1, 2), (2, 2)],
+        ));
+        graph.write_file_async(&path).await.unwrap();
+        let loaded = Graph::from_file_async(&path).await;
+        tokio::fs::remove_file(&path).await.unwrap();
+        assert_eq!(loaded.unwrap(), graph, "Graph doesn't match");
+    }
+}
@@ -0,0 +1,256 @@
+use alloc::vec::Vec;
+
+use super::collections::{HashMap, HashSet};
+
+use super::{GraphId, WeightedGraph};
+
+/// A graph with two node partitions holding different payload types, where edges always join
+/// a left node to a right node.
+///
+/// Left and right IDs live in separate ID spaces, and edges are undirected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BipartiteGraph<L, R> {
+    left: HashMap<GraphId, L>,
+    right: HashMap<GraphId, R>,
+    edges: HashSet<(GraphId, GraphId)>,
+}
+
+impl<L, R> BipartiteGraph<L, R> {
+    pub fn new() -> Self {
+        Self {
+            left: HashMap::new(),
+            right: HashMap::new(),
+            edges: HashSet::new(),
+        }
+    }
+
+    pub fn add_left(&mut self, id: GraphId, value: L) {
+        self.left.entry(id).or_insert(value);
+    }
+
+    pub fn add_right(&mut self, id: GraphId, value: R) {
+        self.right.entry(id).or_insert(value);
+    }
+
+    pub fn get_left(&self, id: GraphId) -> Option<&L> {
+        self.left.get(&id)
+    }
+
+    pub fn get_right(&self, id: GraphId) -> Option<&R> {
+        self.right.get(&id)
+    }
+
+    pub fn delete_left(&mut self, id: GraphId) {
+        self.edges.retain(|(left, _)| *left != id);
+        self.left.remove(&id);
+    }
+
+    pub fn delete_right(&mut self, id: GraphId) {
+        self.edges.retain(|(_, right)| *right != id);
+        self.right.remove(&id);
+    }
+
+    /// Joins a left node to a right node. Does nothing if either doesn't exist.
+    pub fn add_edge(&mut self, left: GraphId, right: GraphId) {
+        if self.left.contains_key(&left) && self.right.contains_key(&right) {
+            self.edges.insert((left, right));
+        }
+    }
+
+    pub fn delete_edge(&mut self, left: GraphId, right: GraphId) {
+        self.edges.remove(&(left, right));
+    }
+
+    pub fn left_count(&self) -> usize {
+        self.left.len()
+    }
+
+    pub fn right_count(&self) -> usize {
+        self.right.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Returns the sorted IDs of right nodes joined to the left node `id`.
+    pub fn left_neighbours(&self, id: GraphId) -> Vec<GraphId> {
+        let mut neighbours = self
+            .edges
+            .iter()
+            .filter_map(|(left, right)| (*left == id).then_some(*right))
+            .collect::<Vec<_>>();
+        neighbours.sort_unstable();
+
+        neighbours
+    }
+
+    /// Returns the sorted IDs of left nodes joined to the right node `id`.
+    pub fn right_neighbours(&self, id: GraphId) -> Vec<GraphId> {
+        let mut neighbours = self
+            .edges
+            .iter()
+            .filter_map(|(left, right)| (*right == id).then_some(*left))
+            .collect::<Vec<_>>();
+        neighbours.sort_unstable();
+
+        neighbours
+    }
+
+    /// Projects onto the left partition: two left nodes are joined in both directions if they
+    /// share at least one right neighbour, weighted by the number of right neighbours they
+    /// share.
+    pub fn project_left(&self) -> WeightedGraph<&L> {
+        project(
+            &self.left,
+            self.edges.iter().map(|(left, right)| (*right, *left)),
+        )
+    }
+
+    /// Projects onto the right partition: two right nodes are joined in both directions if
+    /// they share at least one left neighbour, weighted by the number of left neighbours they
+    /// share.
+    pub fn project_right(&self) -> WeightedGraph<&R> {
+        project(&self.right, self.edges.iter().copied())
+    }
+}
+
+impl<L, R> Default for BipartiteGraph<L, R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Joins nodes of `nodes` that are attached to the same pivot in `memberships`, given as
+/// `(pivot, node)` pairs, weighting every edge by the number of pivots its endpoints share.
+fn project<T>(
+    nodes: &HashMap<GraphId, T>,
+    memberships: impl Iterator<Item = (GraphId, GraphId)>,
+) -> WeightedGraph<&T> {
+    let mut groups: HashMap<GraphId, Vec<GraphId>> = HashMap::new();
+    for (pivot, id) in memberships {
+        groups.entry(pivot).or_default().push(id);
+    }
+    let mut shared: HashMap<(GraphId, GraphId), usize> = HashMap::new();
+    for members in groups.values() {
+        for a in members {
+            for b in members {
+                if a != b {
+                    *shared.entry((*a, *b)).or_default() += 1;
+                }
+            }
+        }
+    }
+
+    let mut graph = WeightedGraph::new();
+    for (id, value) in nodes {
+        graph.add_node(*id, value);
+    }
+    for ((a, b), count) in shared {
+        graph.add_edge(a, b, count as f64);
+    }
+
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_graph() -> BipartiteGraph<&'static str, u32> {
+        let mut graph = BipartiteGraph::new();
+        graph.add_left(1, "Alice");
+        graph.add_left(2, "Bob");
+        graph.add_left(3, "Carol");
+        graph.add_right(1, 2019);
+        graph.add_right(2, 2021);
+        graph.add_edge(1, 1);
+        graph.add_edge(2, 1);
+        graph.add_edge(2, 2);
+        graph.add_edge(3, 2);
+
+        graph
+    }
+
+    #[test]
+    fn edges_cross_partitions() {
+        let mut graph = get_test_graph();
+        graph.add_edge(1, 3);
+        assert_eq!(
+            graph.edge_count(),
+            4,
+            "Expected edge to missing node to be ignored"
+        );
+        assert_eq!(
+            graph.left_neighbours(2),
+            vec![1, 2],
+            "Left neighbours mismatch"
+        );
+        assert_eq!(
+            graph.right_neighbours(2),
+            vec![2, 3],
+            "Right neighbours mismatch"
+        );
+    }
+
+    #[test]
+    fn delete_removes_incident_edges() {
+        let mut graph = get_test_graph();
+        graph.delete_right(1);
+        assert_eq!(graph.edge_count(), 2, "Edges count mismatch");
+        assert_eq!(graph.right_count(), 1, "Right count mismatch");
+        graph.delete_left(2);
+        assert_eq!(
+            graph.right_neighbours(2),
+            vec![3],
+            "Right neighbours mismatch"
+        );
+    }
+
+    #[test]
+    fn project_left_shares_right() {
+        let graph = get_test_graph();
+        let projection = graph.project_left();
+        assert_eq!(projection[1], &"Alice", "Expected left payloads");
+        assert_eq!(
+            projection.edges().collect::<HashSet<_>>(),
+            HashSet::from([(1, 2), (2, 1), (2, 3), (3, 2)]),
+            "Projected edges mismatch"
+        );
+    }
+
+    #[test]
+    fn project_weights_shared_neighbours() {
+        let mut graph = get_test_graph();
+        graph.add_edge(1, 2);
+        assert_eq!(
+            graph.project_left().weighted_edges(),
+            vec![
+                (1, 2, 2.0),
+                (1, 3, 1.0),
+                (2, 1, 2.0),
+                (2, 3, 1.0),
+                (3, 1, 1.0),
+                (3, 2, 1.0)
+            ],
+            "Weights mismatch"
+        );
+        assert_eq!(
+            graph.project_right().weight(1, 2),
+            Some(2.0),
+            "Weight mismatch"
+        );
+    }
+
+    #[test]
+    fn project_right_shares_left() {
+        let graph = get_test_graph();
+        let projection = graph.project_right();
+        assert_eq!(projection.nodes.len(), 2, "Nodes count mismatch");
+        assert_eq!(
+            projection.edges().collect::<HashSet<_>>(),
+            HashSet::from([(1, 2), (2, 1)]),
+            "Projected edges mismatch"
+        );
+    }
+}
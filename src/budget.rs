@@ -0,0 +1,119 @@
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+/// Limits for iterative algorithms such as [`Graph::page_rank_with_budget`], which stop at
+/// whichever limit is hit first. Built with [`new`](Self::new) and the setters, since the
+/// time budget is only available with the `std` feature.
+///
+/// ```
+/// use basic_graph_lib::IterationBudget;
+///
+/// let budget = IterationBudget::new().max_iterations(50).tolerance(1e-9);
+/// assert_eq!(budget.max_iterations, 50);
+/// ```
+///
+/// [`Graph::page_rank_with_budget`]: crate::Graph::page_rank_with_budget
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IterationBudget {
+    pub max_iterations: usize,
+    /// The algorithm has converged once an iteration changes the result by at most this much,
+    /// measured as the sum of absolute changes of all scores.
+    pub tolerance: f64,
+    #[cfg(feature = "std")]
+    time_budget: Option<Duration>,
+}
+
+/// Result of an iterative algorithm along with how it stopped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Convergence<R> {
+    pub result: R,
+    /// Number of iterations run.
+    pub iterations: usize,
+    /// Whether the result settled within the tolerance, rather than running out of iterations
+    /// or time.
+    pub converged: bool,
+}
+
+impl Convergence<()> {
+    /// Attaches the result of the run this describes.
+    pub(crate) fn with<R>(self, result: R) -> Convergence<R> {
+        Convergence {
+            result,
+            iterations: self.iterations,
+            converged: self.converged,
+        }
+    }
+}
+
+impl IterationBudget {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the wall-clock time after which the algorithm stops with its latest result.
+    #[cfg(feature = "std")]
+    pub fn time_budget(mut self, time_budget: Duration) -> Self {
+        self.time_budget = Some(time_budget);
+        self
+    }
+
+    /// Starts the clock for the time budget.
+    pub(crate) fn start(&self) -> Deadline {
+        Deadline {
+            #[cfg(feature = "std")]
+            end: self.time_budget.map(|budget| Instant::now() + budget),
+        }
+    }
+}
+
+impl Default for IterationBudget {
+    fn default() -> Self {
+        Self {
+            max_iterations: 100,
+            tolerance: 1e-6,
+            #[cfg(feature = "std")]
+            time_budget: None,
+        }
+    }
+}
+
+pub(crate) struct Deadline {
+    #[cfg(feature = "std")]
+    end: Option<Instant>,
+}
+
+impl Deadline {
+    pub(crate) fn expired(&self) -> bool {
+        #[cfg(feature = "std")]
+        let expired = self.end.is_some_and(|end| Instant::now() >= end);
+        #[cfg(not(feature = "std"))]
+        let expired = false;
+
+        expired
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::Graph;
+
+    #[test]
+    fn time_budget_stops_iteration() {
+        let graph = Graph::from(([(1, ()), (2, ())], [(1, 2)]));
+        let convergence =
+            graph.page_rank_with_budget(0.85, &IterationBudget::new().time_budget(Duration::ZERO));
+        assert_eq!(convergence.iterations, 0, "Expected no iterations");
+        assert!(!convergence.converged, "Expected budget to run out");
+    }
+}
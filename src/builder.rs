@@ -0,0 +1,176 @@
+use alloc::vec::Vec;
+use core::{error::Error, fmt};
+
+use super::collections::HashSet;
+
+use super::{Graph, GraphId};
+
+/// Collects nodes and edges in any order and validates them all at once on [`build`].
+///
+/// Unlike [`Graph::add_edge`], an edge may be declared before its endpoints; it is only
+/// rejected if an endpoint is still missing when the graph is built.
+///
+/// [`build`]: GraphBuilder::build
+#[derive(Debug, Clone)]
+pub struct GraphBuilder<T> {
+    nodes: Vec<(GraphId, T)>,
+    edges: Vec<(GraphId, GraphId)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildError {
+    DuplicateNode(GraphId),
+    MissingEndpoint {
+        from: GraphId,
+        to: GraphId,
+        missing: GraphId,
+    },
+}
+
+impl<T> Graph<T> {
+    pub fn builder() -> GraphBuilder<T> {
+        GraphBuilder::new()
+    }
+}
+
+impl<T> GraphBuilder<T> {
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    pub fn node(mut self, id: GraphId, value: T) -> Self {
+        self.nodes.push((id, value));
+        self
+    }
+
+    pub fn edge(mut self, from: GraphId, to: GraphId) -> Self {
+        self.edges.push((from, to));
+        self
+    }
+
+    /// Builds the graph, or returns every problem found in declaration order.
+    pub fn build(self) -> Result<Graph<T>, Vec<BuildError>> {
+        let mut errors = Vec::new();
+        let mut graph = Graph::new();
+        for (id, value) in self.nodes {
+            if graph.nodes.contains_key(&id) {
+                errors.push(BuildError::DuplicateNode(id));
+            } else {
+                graph.add_node(id, value);
+            }
+        }
+
+        let mut reported = HashSet::new();
+        for (from, to) in self.edges {
+            for missing in [from, to] {
+                if !graph.nodes.contains_key(&missing) && reported.insert((from, to, missing)) {
+                    errors.push(BuildError::MissingEndpoint { from, to, missing });
+                }
+            }
+            graph.add_edge(from, to);
+        }
+
+        if errors.is_empty() {
+            Ok(graph)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl<T> Default for GraphBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::DuplicateNode(id) => write!(f, "Node {id} is declared more than once"),
+            BuildError::MissingEndpoint { from, to, missing } => {
+                write!(
+                    f,
+                    "Edge {from} -> {to} refers to nonexistent node {missing}"
+                )
+            }
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_forward_references() {
+        let graph = Graph::builder()
+            .edge(1, 2)
+            .node(1, "a")
+            .node(2, "b")
+            .build();
+        assert_eq!(
+            graph,
+            Ok(Graph::from(([(1, "a"), (2, "b")], [(1, 2)]))),
+            "Graph doesn't match"
+        );
+    }
+
+    #[test]
+    fn build_reports_all_errors() {
+        let errors = Graph::builder()
+            .node(1, "a")
+            .node(1, "b")
+            .edge(1, 2)
+            .edge(3, 4)
+            .edge(1, 2)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                BuildError::DuplicateNode(1),
+                BuildError::MissingEndpoint {
+                    from: 1,
+                    to: 2,
+                    missing: 2
+                },
+                BuildError::MissingEndpoint {
+                    from: 3,
+                    to: 4,
+                    missing: 3
+                },
+                BuildError::MissingEndpoint {
+                    from: 3,
+                    to: 4,
+                    missing: 4
+                },
+            ],
+            "Errors don't match"
+        );
+    }
+
+    #[test]
+    fn build_empty() {
+        let graph: Result<Graph<i32>, _> = GraphBuilder::new().build();
+        assert_eq!(graph, Ok(Graph::new()), "Expected empty graph");
+    }
+
+    #[test]
+    fn build_error_display() {
+        assert_eq!(
+            BuildError::MissingEndpoint {
+                from: 1,
+                to: 2,
+                missing: 2
+            }
+            .to_string(),
+            "Edge 1 -> 2 refers to nonexistent node 2"
+        );
+    }
+}
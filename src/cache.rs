@@ -0,0 +1,180 @@
+use core::ops::Deref;
+
+use super::collections::HashMap;
+
+use super::{Graph, GraphId, Mutation, Path, ShortestPathTree};
+
+/// Wraps a graph and memoizes shortest-path searches per source node.
+///
+/// Mutations only drop the cached searches they can affect: adding an edge invalidates
+/// sources for which it is a shortcut, removing an edge invalidates sources whose search tree
+/// used it, and removing a node invalidates sources that reached it.
+#[derive(Debug, Clone)]
+pub struct PathCache<T> {
+    graph: Graph<T>,
+    trees: HashMap<GraphId, ShortestPathTree>,
+}
+
+impl<T> PathCache<T> {
+    pub fn new(graph: Graph<T>) -> Self {
+        Self {
+            graph,
+            trees: HashMap::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> Graph<T> {
+        self.graph
+    }
+
+    pub fn distance(&mut self, from: GraphId, to: GraphId) -> Option<usize> {
+        self.tree(from)?.distance.get(&to).copied()
+    }
+
+    pub fn path(&mut self, from: GraphId, to: GraphId) -> Option<Path> {
+        self.tree(from)?.path_to(to)
+    }
+
+    pub fn is_cached(&self, source: GraphId) -> bool {
+        self.trees.contains_key(&source)
+    }
+
+    pub fn clear(&mut self) {
+        self.trees.clear();
+    }
+
+    pub fn apply(&mut self, mutation: Mutation<T>) {
+        match &mutation {
+            Mutation::AddNode { .. } => {}
+            Mutation::RemoveNode { id } => {
+                self.trees.retain(|_, tree| !tree.distance.contains_key(id));
+            }
+            Mutation::AddEdge { from, to } => {
+                if self.graph.nodes.contains_key(to) {
+                    self.trees.retain(|_, tree| match tree.distance.get(from) {
+                        Some(distance) => tree
+                            .distance
+                            .get(to)
+                            .is_some_and(|current| *current <= distance + 1),
+                        None => true,
+                    });
+                }
+            }
+            Mutation::RemoveEdge { from, to } => {
+                self.trees
+                    .retain(|_, tree| tree.parent.get(to).is_none_or(|parent| parent != from));
+            }
+        }
+        self.graph.apply(mutation);
+    }
+
+    pub fn add_node(&mut self, id: GraphId, value: T) {
+        self.apply(Mutation::AddNode { id, value });
+    }
+
+    pub fn delete_node(&mut self, id: GraphId) {
+        self.apply(Mutation::RemoveNode { id });
+    }
+
+    pub fn add_edge(&mut self, from: GraphId, to: GraphId) {
+        self.apply(Mutation::AddEdge { from, to });
+    }
+
+    pub fn delete_edge(&mut self, from: GraphId, to: GraphId) {
+        self.apply(Mutation::RemoveEdge { from, to });
+    }
+
+    fn tree(&mut self, source: GraphId) -> Option<&ShortestPathTree> {
+        if !self.trees.contains_key(&source) {
+            let tree = self.graph.shortest_path_tree(source)?;
+            self.trees.insert(source, tree);
+        }
+
+        self.trees.get(&source)
+    }
+}
+
+impl<T> Deref for PathCache<T> {
+    type Target = Graph<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_cache() -> PathCache<()> {
+        PathCache::new(Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ())],
+            [(1, 2), (2, 3), (3, 4)],
+        )))
+    }
+
+    #[test]
+    fn cache_memoizes_queries() {
+        let mut cache = get_test_cache();
+        assert_eq!(cache.distance(1, 4), Some(3), "Distance mismatch");
+        assert!(cache.is_cached(1), "Expected source to be cached");
+        assert_eq!(
+            cache.path(1, 3),
+            Some(Path::with_cost(vec![1, 2, 3], 2)),
+            "Path mismatch"
+        );
+        assert_eq!(cache.distance(4, 1), None, "Expected no path");
+    }
+
+    #[test]
+    fn add_edge_invalidates_only_shortcuts() {
+        let mut cache = get_test_cache();
+        cache.distance(1, 4);
+        cache.distance(3, 4);
+        cache.add_edge(1, 4);
+        assert!(
+            !cache.is_cached(1),
+            "Expected shortcut to invalidate source"
+        );
+        assert!(
+            cache.is_cached(3),
+            "Expected unrelated source to stay cached"
+        );
+        assert_eq!(cache.distance(1, 4), Some(1), "Distance mismatch");
+    }
+
+    #[test]
+    fn delete_edge_invalidates_tree_edges_only() {
+        let mut cache = get_test_cache();
+        cache.add_edge(1, 3);
+        cache.distance(1, 4);
+        cache.delete_edge(2, 3);
+        assert!(
+            cache.is_cached(1),
+            "Expected non-tree edge removal to keep cache"
+        );
+
+        cache.delete_edge(1, 3);
+        assert!(
+            !cache.is_cached(1),
+            "Expected tree edge removal to invalidate"
+        );
+        assert_eq!(cache.distance(1, 4), None, "Expected no path");
+    }
+
+    #[test]
+    fn delete_node_invalidates_reaching_sources() {
+        let mut cache = get_test_cache();
+        cache.distance(1, 4);
+        cache.distance(4, 4);
+        cache.delete_node(3);
+        assert!(
+            !cache.is_cached(1),
+            "Expected reaching source to be invalidated"
+        );
+        assert!(
+            cache.is_cached(4),
+            "Expected unrelated source to stay cached"
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// A flag for asking a running algorithm to stop, e.g. from another thread.
+///
+/// Clones share the flag, so one can be handed to the algorithm while another is kept to
+/// call [`cancel`](Self::cancel). Algorithms check it regularly and return
+/// [`GraphError::Cancelled`](crate::GraphError::Cancelled) once it's set.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_is_shared_by_clones() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!token.is_cancelled(), "Expected new token to be active");
+        clone.cancel();
+        assert!(token.is_cancelled(), "Expected clone to cancel token");
+    }
+}
@@ -0,0 +1,319 @@
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+use super::{Graph, GraphId};
+
+/// Graphs with at most this many nodes are canonicalized by an exhaustive search.
+const MAX_EXACT_NODES: usize = 8;
+
+/// A graph renumbered so that isomorphic graphs become equal, as returned by
+/// [`Graph::canonical_form`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanonicalForm<T> {
+    /// The graph with its nodes renumbered from 0 in canonical order.
+    pub graph: Graph<T>,
+    /// The original ID of every node of `graph`, by new ID.
+    pub order: Vec<GraphId>,
+    /// Whether every graph isomorphic to this one is guaranteed the same form. If not,
+    /// isomorphic graphs may still get different forms, but equal forms always mean
+    /// isomorphic graphs.
+    pub exact: bool,
+}
+
+impl<T: Clone + Ord> Graph<T> {
+    /// Renumbers the nodes from 0 so that graphs which only differ in their IDs become equal.
+    /// Node values take part, so nodes are only matched to nodes with equal values.
+    ///
+    /// Nodes are ordered by Weisfeiler-Lehman colour refinement over their values and
+    /// neighbours. Nodes it can't tell apart are ordered by trying every choice on graphs
+    /// with up to 8 nodes, which is exact, and by picking the lowest ID on larger ones,
+    /// which is exact only if refinement alone tells every node apart.
+    pub fn canonical_form(&self) -> CanonicalForm<T> {
+        let nodes = self.sorted_nodes();
+        debug_span!("canonical_form", nodes = nodes.len());
+        let index = |id: GraphId| {
+            nodes
+                .binary_search_by_key(&id, |(id, _)| *id)
+                .expect("edges only join existing nodes")
+        };
+        let mut successors = vec![Vec::new(); nodes.len()];
+        let mut predecessors = vec![Vec::new(); nodes.len()];
+        for (from, to) in self.edges() {
+            successors[index(from)].push(index(to));
+            predecessors[index(to)].push(index(from));
+        }
+
+        let refiner = Refiner {
+            successors,
+            predecessors,
+        };
+        let colours = refiner.refine(ranks(nodes.iter().map(|(_, value)| *value)));
+        let exact = nodes.len() <= MAX_EXACT_NODES || refiner.target_cell(&colours).is_none();
+        let (colours, _) = if nodes.len() <= MAX_EXACT_NODES {
+            refiner.search(colours)
+        } else {
+            refiner.descend(colours)
+        };
+
+        let mut order = vec![0; nodes.len()];
+        for (position, colour) in colours.iter().enumerate() {
+            order[*colour] = nodes[position].0;
+        }
+        let mut graph = Graph::new();
+        for (position, colour) in colours.iter().enumerate() {
+            graph.add_node(*colour as GraphId, nodes[position].1.clone());
+        }
+        for (from, to) in self.edges() {
+            graph.add_edge(
+                colours[index(from)] as GraphId,
+                colours[index(to)] as GraphId,
+            );
+        }
+
+        CanonicalForm {
+            graph,
+            order,
+            exact,
+        }
+    }
+}
+
+/// Groups the indices of isomorphic graphs in `graphs`, comparing their
+/// [canonical forms](Graph::canonical_form). Groups are ordered by their first index, and
+/// indices within a group ascend.
+///
+/// Isomorphic graphs with more than 8 nodes that colour refinement can't fully tell apart may
+/// end up in different groups, but a group never holds graphs that aren't isomorphic.
+pub fn dedup_graphs<'a, T>(graphs: impl IntoIterator<Item = &'a Graph<T>>) -> Vec<Vec<usize>>
+where
+    T: Clone + Ord + 'a,
+{
+    let mut groups = BTreeMap::<_, Vec<usize>>::new();
+    for (position, graph) in graphs.into_iter().enumerate() {
+        let form = graph.canonical_form().graph;
+        let values = form
+            .sorted_nodes()
+            .into_iter()
+            .map(|(_, value)| value.clone())
+            .collect::<Vec<_>>();
+        groups
+            .entry((values, form.sorted_edges()))
+            .or_default()
+            .push(position);
+    }
+
+    let mut groups = groups.into_values().collect::<Vec<_>>();
+    groups.sort_unstable_by_key(|group| group[0]);
+
+    groups
+}
+
+/// Colour refinement over nodes numbered by position. A colouring assigns every node a rank,
+/// with ranks running from 0 without gaps.
+struct Refiner {
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+}
+
+impl Refiner {
+    /// Splits colours by the colours of their successors and predecessors until stable.
+    /// Splitting keeps the order of the colours it splits, so it doesn't depend on positions.
+    fn refine(&self, mut colours: Vec<usize>) -> Vec<usize> {
+        loop {
+            let signatures = (0..colours.len()).map(|node| {
+                let mut successors = self.successors[node]
+                    .iter()
+                    .map(|other| colours[*other])
+                    .collect::<Vec<_>>();
+                let mut predecessors = self.predecessors[node]
+                    .iter()
+                    .map(|other| colours[*other])
+                    .collect::<Vec<_>>();
+                successors.sort_unstable();
+                predecessors.sort_unstable();
+
+                (colours[node], successors, predecessors)
+            });
+            let refined = ranks(signatures);
+            if count(&refined) == count(&colours) {
+                return refined;
+            }
+            colours = refined;
+        }
+    }
+
+    /// Returns the smallest colour shared by several nodes, if any.
+    fn target_cell(&self, colours: &[usize]) -> Option<usize> {
+        let mut sizes = vec![0; colours.len()];
+        for colour in colours {
+            sizes[*colour] += 1;
+        }
+
+        sizes.iter().position(|size| *size > 1)
+    }
+
+    /// Gives `node` a colour of its own, just before the rest of its cell, and refines.
+    fn individualize(&self, colours: &[usize], node: usize) -> Vec<usize> {
+        let split = colours
+            .iter()
+            .enumerate()
+            .map(|(other, colour)| (*colour, other != node && *colour == colours[node]));
+
+        self.refine(ranks(split))
+    }
+
+    /// Tries every way of telling the nodes apart and returns the one giving the smallest
+    /// sorted edges, with those edges.
+    fn search(&self, colours: Vec<usize>) -> (Vec<usize>, Vec<(usize, usize)>) {
+        let Some(cell) = self.target_cell(&colours) else {
+            let code = self.code(&colours);
+            return (colours, code);
+        };
+
+        (0..colours.len())
+            .filter(|node| colours[*node] == cell)
+            .map(|node| self.search(self.individualize(&colours, node)))
+            .min_by(|a, b| a.1.cmp(&b.1))
+            .expect("a cell has members")
+    }
+
+    /// Tells the nodes apart by always picking the first node of the first shared colour.
+    fn descend(&self, mut colours: Vec<usize>) -> (Vec<usize>, Vec<(usize, usize)>) {
+        while let Some(cell) = self.target_cell(&colours) {
+            let node = colours
+                .iter()
+                .position(|colour| *colour == cell)
+                .expect("a cell has members");
+            colours = self.individualize(&colours, node);
+        }
+        let code = self.code(&colours);
+
+        (colours, code)
+    }
+
+    /// Returns the edges renumbered by colour, sorted.
+    fn code(&self, colours: &[usize]) -> Vec<(usize, usize)> {
+        let mut edges = self
+            .successors
+            .iter()
+            .enumerate()
+            .flat_map(|(from, successors)| {
+                successors
+                    .iter()
+                    .map(move |to| (colours[from], colours[*to]))
+            })
+            .collect::<Vec<_>>();
+        edges.sort_unstable();
+
+        edges
+    }
+}
+
+/// Replaces every key by its rank among the distinct keys.
+pub(crate) fn ranks<K: Ord>(keys: impl IntoIterator<Item = K>) -> Vec<usize> {
+    let keys = keys.into_iter().collect::<Vec<_>>();
+    let mut distinct = keys.iter().collect::<Vec<_>>();
+    distinct.sort_unstable();
+    distinct.dedup();
+
+    keys.iter()
+        .map(|key| distinct.binary_search(&key).expect("key is listed"))
+        .collect()
+}
+
+/// Returns the number of distinct colours.
+pub(crate) fn count(colours: &[usize]) -> usize {
+    colours.iter().max().map_or(0, |max| max + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 4-cycle with a chord, its nodes numbered in the given order.
+    fn get_test_graph(order: [GraphId; 4]) -> Graph<char> {
+        let [a, b, c, d] = order;
+        Graph::from((
+            [(a, 'x'), (b, 'x'), (c, 'x'), (d, 'y')],
+            [(a, b), (b, c), (c, d), (d, a), (a, c)],
+        ))
+    }
+
+    #[test]
+    fn canonical_form_ignores_ids() {
+        let graph = get_test_graph([40, 10, 30, 20]);
+        let form = graph.canonical_form();
+        assert!(form.exact, "Expected exact form");
+        assert_eq!(
+            form.graph,
+            get_test_graph([1, 2, 3, 4]).canonical_form().graph,
+            "Expected same form"
+        );
+        for (id, original) in form.order.iter().enumerate() {
+            assert_eq!(
+                form.graph[id as GraphId], graph[*original],
+                "Expected order to map back to the original nodes"
+            );
+        }
+    }
+
+    #[test]
+    fn canonical_form_separates_values_and_directions() {
+        let graph = get_test_graph([1, 2, 3, 4]);
+        let mut relabelled = graph.clone();
+        relabelled[4] = 'x';
+        relabelled[1] = 'y';
+        assert_ne!(
+            graph.canonical_form().graph,
+            relabelled.canonical_form().graph,
+            "Expected values to matter"
+        );
+
+        let mut reversed = graph.clone();
+        reversed.delete_edge(1, 3);
+        reversed.add_edge(3, 1);
+        assert_ne!(
+            graph.canonical_form().graph,
+            reversed.canonical_form().graph,
+            "Expected directions to matter"
+        );
+    }
+
+    #[test]
+    fn dedup_graphs_groups_isomorphic_graphs() {
+        let cycle = |ids: [GraphId; 3]| {
+            Graph::from((
+                ids.map(|id| (id, ())),
+                [(ids[0], ids[1]), (ids[1], ids[2]), (ids[2], ids[0])],
+            ))
+        };
+        let path = Graph::from(([(1, ()), (2, ()), (3, ())], [(1, 2), (2, 3)]));
+        let graphs = [cycle([1, 2, 3]), path, cycle([9, 7, 8]), cycle([3, 1, 2])];
+        assert_eq!(
+            dedup_graphs(&graphs),
+            vec![vec![0, 2, 3], vec![1]],
+            "Groups mismatch"
+        );
+    }
+
+    #[test]
+    fn large_regular_graph_is_canonical_after_refinement() {
+        // A directed cycle on 12 nodes, which refinement can't split on its own.
+        let cycle = |offset: GraphId| {
+            let mut graph = Graph::new();
+            for id in 0..12 {
+                graph.add_node(id, 0);
+            }
+            for id in 0..12 {
+                graph.add_edge((id + offset) % 12, (id + offset + 1) % 12);
+            }
+            graph
+        };
+        let form = cycle(0).canonical_form();
+        assert!(!form.exact, "Expected an inexact form");
+        assert_eq!(
+            form.graph,
+            cycle(5).canonical_form().graph,
+            "Expected vertex-transitive graph to get the same form"
+        );
+    }
+}
@@ -0,0 +1,7 @@
+//! Hash-based collections used throughout the crate: the `std` ones when available, and
+//! `hashbrown` otherwise.
+
+#[cfg(not(feature = "std"))]
+pub(crate) use hashbrown::{hash_map, hash_set, HashMap, HashSet};
+#[cfg(feature = "std")]
+pub(crate) use std::collections::{hash_map, hash_set, HashMap, HashSet};
@@ -0,0 +1,119 @@
+use alloc::{vec, vec::Vec};
+
+use super::{
+    collections::HashMap, connectivity::sorted, rank::iterate, CancellationToken, Convergence,
+    Graph, GraphId, IterationBudget,
+};
+
+impl<T> Graph<T> {
+    /// Detects communities by label propagation, ignoring edge directions: every node starts
+    /// in its own community and repeatedly joins the one most of its neighbours are in, ties
+    /// going to the community of the largest ID. Nodes are visited in ID order, so the result is
+    /// deterministic.
+    ///
+    /// Converges once an iteration moves no node; the budget's tolerance isn't used.
+    /// Communities are returned as sorted lists, ordered by their smallest ID.
+    pub fn label_propagation(&self, budget: &IterationBudget) -> Convergence<Vec<Vec<GraphId>>> {
+        debug_span!(
+            "label_propagation",
+            nodes = self.nodes.len(),
+            budget.max_iterations
+        );
+        let mut ids = self.nodes.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        let index_of = ids
+            .iter()
+            .enumerate()
+            .map(|(index, id)| (*id, index))
+            .collect::<HashMap<_, _>>();
+        let mut neighbours = vec![Vec::new(); ids.len()];
+        for edge in self.edges.iter().filter(|edge| edge.from != edge.to) {
+            let (from, to) = (index_of[&edge.from], index_of[&edge.to]);
+            neighbours[from].push(to);
+            neighbours[to].push(from);
+        }
+
+        let mut labels = (0..ids.len()).collect::<Vec<_>>();
+        let budget = budget.tolerance(0.0);
+        let convergence = iterate(&budget, &mut |_| {}, &CancellationToken::new(), || {
+            let mut moved = 0;
+            for index in 0..ids.len() {
+                let mut counts: HashMap<usize, usize> = HashMap::new();
+                for neighbour in &neighbours[index] {
+                    *counts.entry(labels[*neighbour]).or_default() += 1;
+                }
+                let best = counts
+                    .into_iter()
+                    .max_by_key(|(label, count)| (*count, *label));
+                if let Some((label, count)) = best {
+                    let current = neighbours[index]
+                        .iter()
+                        .filter(|neighbour| labels[**neighbour] == labels[index])
+                        .count();
+                    // Only move to a label that is more common, or as common and larger, so that
+                    // ties can't flip back and forth.
+                    if (count, label) > (current, labels[index]) {
+                        labels[index] = label;
+                        moved += 1;
+                    }
+                }
+            }
+
+            moved as f64
+        })
+        .expect("token is never cancelled");
+
+        let mut communities: HashMap<usize, Vec<GraphId>> = HashMap::new();
+        for (index, id) in ids.into_iter().enumerate() {
+            communities.entry(labels[index]).or_default().push(id);
+        }
+
+        convergence.with(sorted(communities.into_values().collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn label_propagation_finds_cliques() {
+        // Two complete graphs of four nodes joined by a single edge.
+        let mut graph = Graph::new();
+        for id in 1..=8 {
+            graph.add_node(id, ());
+        }
+        for clique in [1..=4, 5..=8] {
+            for from in clique.clone() {
+                for to in clique.clone().filter(|to| *to > from) {
+                    graph.add_edge(from, to);
+                }
+            }
+        }
+        graph.add_edge(4, 5);
+
+        let convergence = graph.label_propagation(&IterationBudget::new());
+        assert!(convergence.converged, "Expected convergence");
+        assert_eq!(
+            convergence.result,
+            vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8]],
+            "Communities mismatch"
+        );
+    }
+
+    #[test]
+    fn label_propagation_isolated_nodes() {
+        let graph = Graph::from(([(1, ()), (2, ())], []));
+        let convergence = graph.label_propagation(&IterationBudget::new());
+        assert_eq!(convergence.result, vec![vec![1], vec![2]]);
+        assert_eq!(convergence.iterations, 1, "Expected a single iteration");
+    }
+
+    #[test]
+    fn label_propagation_budget_exhausted() {
+        let graph = Graph::from(([(1, ()), (2, ()), (3, ())], [(1, 2), (2, 3)]));
+        let convergence = graph.label_propagation(&IterationBudget::new().max_iterations(1));
+        assert_eq!(convergence.iterations, 1, "Iterations mismatch");
+        assert!(!convergence.converged, "Expected budget to run out");
+    }
+}
@@ -0,0 +1,112 @@
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    path::Path,
+};
+
+use flate2::{read::GzDecoder, write::GzEncoder};
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// How [`Graph::write_to`](crate::Graph::write_to) compresses its output. Readers detect the
+/// compression by itself, so it only has to be chosen when writing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Compression {
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// Picks the compression matching the extension of `path`: `.gz` for gzip and `.zst` for
+    /// zstd.
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        match path
+            .as_ref()
+            .extension()
+            .and_then(|extension| extension.to_str())
+        {
+            Some("gz") => Compression::Gzip,
+            Some("zst" | "zstd") => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+
+    /// Writes `bytes` to `writer`, compressed.
+    pub(crate) fn write_all(self, mut writer: impl Write, bytes: &[u8]) -> io::Result<()> {
+        match self {
+            Compression::None => writer.write_all(bytes),
+            Compression::Gzip => {
+                let mut encoder = GzEncoder::new(writer, flate2::Compression::default());
+                encoder.write_all(bytes)?;
+                encoder.finish().map(drop)
+            }
+            Compression::Zstd => zstd::stream::copy_encode(bytes, writer, 0),
+        }
+    }
+}
+
+/// Reads everything from `reader`, decompressing it if it starts with the magic bytes of gzip
+/// or zstd.
+pub(crate) fn read_to_end(reader: impl Read) -> io::Result<Vec<u8>> {
+    let mut reader = BufReader::new(reader);
+    let start = reader.fill_buf()?;
+    let mut bytes = Vec::new();
+    if start.starts_with(GZIP_MAGIC) {
+        GzDecoder::new(reader).read_to_end(&mut bytes)?;
+    } else if start.starts_with(ZSTD_MAGIC) {
+        zstd::stream::copy_decode(reader, &mut bytes)?;
+    } else {
+        reader.read_to_end(&mut bytes)?;
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Graph, SerializeOptions};
+
+    #[test]
+    fn compression_from_path() {
+        assert_eq!(Compression::from_path("graph.tgf.gz"), Compression::Gzip);
+        assert_eq!(Compression::from_path("graph.tgf.zst"), Compression::Zstd);
+        assert_eq!(Compression::from_path("graph.tgf"), Compression::None);
+    }
+
+    #[test]
+    fn compressed_round_trip() {
+        let mut graph = Graph::new();
+        for id in 1..=100 {
+            graph.add_node(id, format!("node {id}"));
+            graph.add_edge(id - 1, id);
+        }
+        for compression in [Compression::None, Compression::Gzip, Compression::Zstd] {
+            let options = SerializeOptions {
+                compression,
+                ..SerializeOptions::default()
+            };
+            let mut bytes = Vec::new();
+            graph.write_to(&mut bytes, &options).unwrap();
+            assert_eq!(
+                Graph::from_reader(bytes.as_slice()).ok().as_ref(),
+                Some(&graph),
+                "Round trip mismatch for {compression:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn compressed_output_is_smaller() {
+        let text = b"1 2\n".repeat(1000);
+        let mut compressed = Vec::new();
+        Compression::Gzip.write_all(&mut compressed, &text).unwrap();
+        assert!(
+            compressed.len() < text.len() / 10,
+            "Expected gzip to compress"
+        );
+        assert_eq!(read_to_end(compressed.as_slice()).unwrap(), text);
+    }
+}
@@ -0,0 +1,523 @@
+use alloc::{vec, vec::Vec};
+use core::{cmp, ops::Deref};
+
+use super::collections::{hash_map::Entry, HashMap, HashSet};
+
+use super::{Graph, GraphId, Mutation};
+
+/// Disjoint sets of node IDs with union by size and path halving.
+#[derive(Debug, Clone, Default)]
+pub struct UnionFind {
+    parent: HashMap<GraphId, GraphId>,
+    size: HashMap<GraphId, usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `id` as a singleton set. Does nothing if it is already present.
+    pub fn insert(&mut self, id: GraphId) {
+        if let Entry::Vacant(entry) = self.parent.entry(id) {
+            entry.insert(id);
+            self.size.insert(id, 1);
+            self.sets += 1;
+        }
+    }
+
+    /// Returns the representative of the set containing `id`.
+    pub fn find(&mut self, id: GraphId) -> Option<GraphId> {
+        let mut current = id;
+        loop {
+            let parent = *self.parent.get(&current)?;
+            if parent == current {
+                return Some(current);
+            }
+            let grandparent = self.parent[&parent];
+            self.parent.insert(current, grandparent);
+            current = grandparent;
+        }
+    }
+
+    /// Merges the sets containing `a` and `b`. Returns `true` if they were separate.
+    pub fn union(&mut self, a: GraphId, b: GraphId) -> bool {
+        let (Some(a), Some(b)) = (self.find(a), self.find(b)) else {
+            return false;
+        };
+        if a == b {
+            return false;
+        }
+
+        let (large, small) = if self.size[&a] >= self.size[&b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parent.insert(small, large);
+        let small_size = self.size.remove(&small).unwrap_or_default();
+        *self.size.entry(large).or_default() += small_size;
+        self.sets -= 1;
+
+        true
+    }
+
+    pub fn same_set(&mut self, a: GraphId, b: GraphId) -> bool {
+        matches!((self.find(a), self.find(b)), (Some(a), Some(b)) if a == b)
+    }
+
+    pub fn set_count(&self) -> usize {
+        self.sets
+    }
+
+    /// Returns the number of elements in the set containing `id`.
+    pub fn set_size(&mut self, id: GraphId) -> Option<usize> {
+        self.find(id).map(|root| self.size[&root])
+    }
+}
+
+/// Index of a weakly connected component in [`ComponentLabels`], counting components in the
+/// order of their smallest ID.
+pub type ComponentId = usize;
+
+/// Size and representative of a weakly connected component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentSummary {
+    pub size: usize,
+    /// The smallest ID in the component.
+    pub representative: GraphId,
+}
+
+/// The weakly connected component of every node of a graph, computed once so that grouping
+/// by component doesn't partition the graph again.
+///
+/// The labels don't follow changes to the graph; [`refresh`](Self::refresh) recomputes them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComponentLabels {
+    labels: HashMap<GraphId, ComponentId>,
+    summaries: Vec<ComponentSummary>,
+}
+
+impl ComponentLabels {
+    pub fn new<T>(graph: &Graph<T>) -> Self {
+        let mut labels = HashMap::new();
+        let mut summaries = Vec::new();
+        for (component, members) in graph.connected_components().into_iter().enumerate() {
+            summaries.push(ComponentSummary {
+                size: members.len(),
+                representative: members[0],
+            });
+            labels.extend(members.into_iter().map(|id| (id, component)));
+        }
+
+        Self { labels, summaries }
+    }
+
+    /// Recomputes the labels for the current state of `graph`.
+    pub fn refresh<T>(&mut self, graph: &Graph<T>) {
+        *self = Self::new(graph);
+    }
+
+    /// Returns the component of `id`, or `None` if it didn't exist when the labels were
+    /// computed.
+    pub fn component_of(&self, id: GraphId) -> Option<ComponentId> {
+        self.labels.get(&id).copied()
+    }
+
+    pub fn summary(&self, component: ComponentId) -> Option<ComponentSummary> {
+        self.summaries.get(component).copied()
+    }
+
+    /// Returns the summaries of all components, indexed by [`ComponentId`].
+    pub fn summaries(&self) -> &[ComponentSummary] {
+        &self.summaries
+    }
+
+    /// Returns the nodes of `component` in ascending ID order.
+    pub fn members(&self, component: ComponentId) -> Vec<GraphId> {
+        let mut members = self
+            .labels
+            .iter()
+            .filter_map(|(id, label)| (*label == component).then_some(*id))
+            .collect::<Vec<_>>();
+        members.sort_unstable();
+
+        members
+    }
+
+    /// Returns the number of components.
+    pub fn len(&self) -> usize {
+        self.summaries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.summaries.is_empty()
+    }
+}
+
+/// Wraps a graph and keeps its weakly connected components up to date.
+///
+/// Added nodes and edges update the components in near-constant time. Deleting an edge or a
+/// node may split a component, so it marks the components stale and they are rebuilt on the
+/// next query.
+#[derive(Debug, Clone)]
+pub struct ConnectedGraph<T> {
+    graph: Graph<T>,
+    components: UnionFind,
+    stale: bool,
+    labels: Option<ComponentLabels>,
+}
+
+impl<T> ConnectedGraph<T> {
+    pub fn new(graph: Graph<T>) -> Self {
+        let mut connected = Self {
+            graph,
+            components: UnionFind::new(),
+            stale: true,
+            labels: None,
+        };
+        connected.refresh();
+
+        connected
+    }
+
+    pub fn into_inner(self) -> Graph<T> {
+        self.graph
+    }
+
+    pub fn apply(&mut self, mutation: Mutation<T>) {
+        self.labels = None;
+        match mutation {
+            Mutation::AddNode { id, value } => {
+                self.graph.add_node(id, value);
+                self.components.insert(id);
+            }
+            Mutation::AddEdge { from, to } => {
+                self.graph.add_edge(from, to);
+                if self.graph.edges.contains(&super::Edge { from, to }) {
+                    self.components.union(from, to);
+                }
+            }
+            mutation => {
+                self.stale |= !self.graph.apply(mutation).is_empty();
+            }
+        }
+    }
+
+    pub fn add_node(&mut self, id: GraphId, value: T) {
+        self.apply(Mutation::AddNode { id, value });
+    }
+
+    pub fn delete_node(&mut self, id: GraphId) {
+        self.apply(Mutation::RemoveNode { id });
+    }
+
+    pub fn add_edge(&mut self, from: GraphId, to: GraphId) {
+        self.apply(Mutation::AddEdge { from, to });
+    }
+
+    pub fn delete_edge(&mut self, from: GraphId, to: GraphId) {
+        self.apply(Mutation::RemoveEdge { from, to });
+    }
+
+    /// Returns `true` if `a` and `b` are connected, ignoring edge directions.
+    pub fn same_component(&mut self, a: GraphId, b: GraphId) -> bool {
+        self.refresh();
+        self.components.same_set(a, b)
+    }
+
+    pub fn component_count(&mut self) -> usize {
+        self.refresh();
+        self.components.set_count()
+    }
+
+    /// Returns the component labels, computing them again only if the graph changed since
+    /// the last call.
+    pub fn component_labels(&mut self) -> &ComponentLabels {
+        self.labels
+            .get_or_insert_with(|| ComponentLabels::new(&self.graph))
+    }
+
+    fn refresh(&mut self) {
+        if !self.stale {
+            return;
+        }
+
+        self.components = UnionFind::new();
+        for id in self.graph.nodes.keys() {
+            self.components.insert(*id);
+        }
+        for edge in &self.graph.edges {
+            self.components.union(edge.from, edge.to);
+        }
+        self.stale = false;
+    }
+}
+
+impl<T> Deref for ConnectedGraph<T> {
+    type Target = Graph<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.graph
+    }
+}
+
+impl<T> Graph<T> {
+    /// Returns the weakly connected component of every node with a summary of each, see
+    /// [`ComponentLabels`].
+    pub fn component_labels(&self) -> ComponentLabels {
+        ComponentLabels::new(self)
+    }
+
+    /// Returns the weakly connected components as sorted lists, ordered by their smallest ID.
+    pub fn connected_components(&self) -> Vec<Vec<GraphId>> {
+        debug_span!("connected_components", nodes = self.nodes.len());
+        let mut sets = UnionFind::new();
+        for id in self.nodes.keys() {
+            sets.insert(*id);
+        }
+        for edge in &self.edges {
+            sets.union(edge.from, edge.to);
+        }
+
+        let mut ids = self.nodes.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        let mut components: HashMap<GraphId, Vec<GraphId>> = HashMap::new();
+        for id in ids {
+            let root = sets.find(id).expect("every node was inserted");
+            components.entry(root).or_default().push(id);
+        }
+
+        debug_event!(components = components.len(), "found components");
+        sorted(components.into_values().collect())
+    }
+
+    /// Returns the strongly connected components as sorted lists, ordered by their smallest
+    /// ID.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<GraphId>> {
+        debug_span!("strongly_connected_components", nodes = self.nodes.len());
+        let mut ids = self.nodes.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+
+        let components = strong_components(&ids, &self.adjacency());
+        debug_event!(components = components.len(), "found components");
+        sorted(components)
+    }
+
+    /// Returns `true` if every node can reach every other node. The empty graph isn't
+    /// strongly connected.
+    ///
+    /// Searches forwards and backwards from a single node instead of finding all components.
+    pub fn is_strongly_connected(&self) -> bool {
+        let Some(&start) = self.nodes.keys().next() else {
+            return false;
+        };
+
+        [self.adjacency(), self.reverse_adjacency()]
+            .iter()
+            .all(|adjacency| {
+                let mut reached: HashSet<GraphId> = HashSet::from_iter([start]);
+                let mut stack = vec![start];
+                while let Some(id) = stack.pop() {
+                    for next in adjacency.get(&id).into_iter().flatten() {
+                        if reached.insert(*next) {
+                            stack.push(*next);
+                        }
+                    }
+                }
+
+                reached.len() == self.nodes.len()
+            })
+    }
+}
+
+/// Tarjan's algorithm over the subgraph of `adjacency` induced by `ids`, without recursion.
+pub(crate) fn strong_components(
+    ids: &[GraphId],
+    adjacency: &HashMap<GraphId, Vec<GraphId>>,
+) -> Vec<Vec<GraphId>> {
+    let allowed = ids.iter().copied().collect::<HashSet<_>>();
+    let mut index: HashMap<GraphId, usize> = HashMap::new();
+    let mut low: HashMap<GraphId, usize> = HashMap::new();
+    let mut stack = Vec::new();
+    let mut on_stack = HashSet::new();
+    let mut components = Vec::new();
+
+    for &root in ids {
+        if index.contains_key(&root) {
+            continue;
+        }
+
+        index.insert(root, index.len());
+        low.insert(root, index[&root]);
+        stack.push(root);
+        on_stack.insert(root);
+        let mut work = Vec::from([(root, 0)]);
+        while let Some((id, next)) = work.last_mut() {
+            let id = *id;
+            let neighbours = adjacency.get(&id).map_or(&[][..], Vec::as_slice);
+            if let Some(&neighbour) = neighbours.get(*next) {
+                *next += 1;
+                if !allowed.contains(&neighbour) {
+                    continue;
+                }
+                if let Some(&neighbour_index) = index.get(&neighbour) {
+                    if on_stack.contains(&neighbour) {
+                        low.insert(id, cmp::min(low[&id], neighbour_index));
+                    }
+                } else {
+                    index.insert(neighbour, index.len());
+                    low.insert(neighbour, index[&neighbour]);
+                    stack.push(neighbour);
+                    on_stack.insert(neighbour);
+                    work.push((neighbour, 0));
+                }
+                continue;
+            }
+
+            work.pop();
+            if let Some(&(parent, _)) = work.last() {
+                low.insert(parent, cmp::min(low[&parent], low[&id]));
+            }
+            if low[&id] == index[&id] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack.remove(&member);
+                    component.push(member);
+                    if member == id {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+pub(crate) fn sorted(mut components: Vec<Vec<GraphId>>) -> Vec<Vec<GraphId>> {
+    for component in &mut components {
+        component.sort_unstable();
+    }
+    components.sort_unstable();
+
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_find_merges_sets() {
+        let mut sets = UnionFind::new();
+        for id in 1..=5 {
+            sets.insert(id);
+        }
+        assert!(sets.union(1, 2), "Expected sets to merge");
+        assert!(sets.union(2, 3), "Expected sets to merge");
+        assert!(!sets.union(1, 3), "Expected sets to be merged already");
+        assert!(!sets.union(1, 9), "Expected unknown ID to be ignored");
+        assert_eq!(sets.set_count(), 3, "Sets count mismatch");
+        assert_eq!(sets.set_size(3), Some(3), "Set size mismatch");
+        assert!(sets.same_set(1, 3), "Expected same set");
+        assert!(!sets.same_set(1, 4), "Expected different sets");
+    }
+
+    #[test]
+    fn connected_graph_incremental_additions() {
+        let mut graph = ConnectedGraph::new(Graph::from(([(1, ()), (2, ()), (3, ())], [(2, 1)])));
+        assert!(
+            graph.same_component(1, 2),
+            "Expected reversed edge to connect"
+        );
+        assert!(!graph.same_component(1, 3), "Expected separate components");
+        assert_eq!(graph.component_count(), 2, "Components count mismatch");
+
+        graph.add_node(4, ());
+        graph.add_edge(3, 4);
+        graph.add_edge(4, 1);
+        assert!(!graph.stale, "Expected additions to keep components fresh");
+        assert!(graph.same_component(2, 3), "Expected components to merge");
+        assert_eq!(graph.component_count(), 1, "Components count mismatch");
+    }
+
+    #[test]
+    fn connected_graph_deletions_split() {
+        let mut graph =
+            ConnectedGraph::new(Graph::from(([(1, ()), (2, ()), (3, ())], [(1, 2), (2, 3)])));
+        graph.delete_edge(2, 3);
+        assert!(!graph.same_component(1, 3), "Expected component to split");
+
+        graph.add_edge(1, 3);
+        graph.delete_node(1);
+        assert_eq!(graph.component_count(), 2, "Components count mismatch");
+        assert!(
+            !graph.same_component(1, 1),
+            "Expected deleted node to be unknown"
+        );
+    }
+
+    #[test]
+    fn components_weak_and_strong() {
+        let graph = Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ()), (5, ()), (6, ())],
+            [(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 4)],
+        ));
+        assert_eq!(
+            graph.connected_components(),
+            vec![vec![1, 2, 3, 4, 5], vec![6]],
+            "Weak components mismatch"
+        );
+        assert_eq!(
+            graph.strongly_connected_components(),
+            vec![vec![1, 2, 3], vec![4, 5], vec![6]],
+            "Strong components mismatch"
+        );
+    }
+
+    #[test]
+    fn component_labels_summarise_components() {
+        let mut graph = ConnectedGraph::new(Graph::from((
+            [(4, ()), (5, ()), (6, ()), (7, ())],
+            [(5, 4), (6, 7)],
+        )));
+        let labels = graph.component_labels();
+        assert_eq!(labels.len(), 2, "Component count mismatch");
+        assert_eq!(labels.component_of(5), Some(0), "Label mismatch");
+        assert_eq!(
+            labels.summary(1),
+            Some(ComponentSummary {
+                size: 2,
+                representative: 6
+            }),
+            "Summary mismatch"
+        );
+        assert_eq!(labels.members(1), vec![6, 7], "Members mismatch");
+
+        graph.add_edge(4, 7);
+        let labels = graph.component_labels();
+        assert_eq!(labels.len(), 1, "Expected labels to be recomputed");
+        assert_eq!(labels.component_of(9), None, "Expected unknown node");
+    }
+
+    #[test]
+    fn is_strongly_connected_checks_both_directions() {
+        let mut graph = Graph::from(([(1, ()), (2, ()), (3, ())], [(1, 2), (2, 3)]));
+        assert!(
+            !graph.is_strongly_connected(),
+            "Expected 1 to be unreachable"
+        );
+        graph.add_edge(3, 1);
+        assert!(
+            graph.is_strongly_connected(),
+            "Expected a cycle through all"
+        );
+        assert!(
+            !Graph::<()>::new().is_strongly_connected(),
+            "Expected empty graph not to be strongly connected"
+        );
+    }
+}
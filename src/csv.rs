@@ -0,0 +1,387 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    io::{BufRead, BufReader, Read},
+};
+
+use anyhow::{anyhow, Context};
+
+use super::{GraphId, WeightedGraph};
+
+/// Which columns of a CSV edge list hold what, matched by their name in the header row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvSchema {
+    /// Column holding the ID of the source node of every edge.
+    pub source: String,
+    /// Column holding the ID of the target node of every edge.
+    pub target: String,
+    /// Column holding the weight of every edge. Edges without one get weight 1.
+    pub weight: Option<String>,
+    /// Column holding a label for every edge, such as the kind of relation.
+    pub label: Option<String>,
+    pub delimiter: char,
+}
+
+impl Default for CsvSchema {
+    fn default() -> Self {
+        Self {
+            source: "source".into(),
+            target: "target".into(),
+            weight: None,
+            label: None,
+            delimiter: ',',
+        }
+    }
+}
+
+/// An edge list read by [`WeightedGraph::from_csv`], with the rows that couldn't be read.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvImport {
+    pub graph: WeightedGraph<()>,
+    /// The label of every edge that had one, if the schema has a label column.
+    pub labels: BTreeMap<(GraphId, GraphId), String>,
+    /// Every row that was skipped, in order.
+    pub errors: Vec<CsvRowError>,
+}
+
+/// A row of a CSV edge list that was skipped, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvRowError {
+    /// Line of the row, counting the header as line 1.
+    pub line: usize,
+    pub kind: CsvRowErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CsvRowErrorKind {
+    /// The row has no field for the named column.
+    MissingField(String),
+    /// The field of the named column isn't a node ID.
+    InvalidId { column: String, value: String },
+    /// The weight field isn't a number.
+    InvalidWeight(String),
+    /// A quoted field isn't closed by the end of the line.
+    UnclosedQuote,
+}
+
+impl WeightedGraph<()> {
+    /// Reads an edge list from CSV with a header row, taking the columns named in `schema`
+    /// and ignoring any others. Nodes are created for every ID that appears. A later row for
+    /// the same edge replaces its weight and label.
+    ///
+    /// Spreadsheet exports are read leniently: fields are trimmed, IDs written as whole
+    /// decimals like `12.0` are accepted, and weights may use a decimal comma when the
+    /// delimiter isn't a comma. Rows that still can't be read are skipped and reported in
+    /// [`CsvImport::errors`] instead of failing the import. Quoted fields may contain the
+    /// delimiter and doubled quotes, but not line breaks.
+    ///
+    /// Fails only if the input can't be read or the header lacks a column of `schema`.
+    pub fn from_csv(reader: impl Read, schema: &CsvSchema) -> anyhow::Result<CsvImport> {
+        debug_span!("from_csv");
+        let mut lines = BufReader::new(reader).lines();
+        let header = lines.next().context("CSV has no header row")??;
+        let header = split_row(&header, schema.delimiter)
+            .map_err(|_| anyhow!("CSV header has an unclosed quote"))?;
+        let position = |name: &String| {
+            header
+                .iter()
+                .position(|column| column == name)
+                .ok_or_else(|| anyhow!("CSV header has no column {name:?}"))
+        };
+        let columns = Columns {
+            source: (position(&schema.source)?, &schema.source),
+            target: (position(&schema.target)?, &schema.target),
+            weight: schema.weight.as_ref().map(position).transpose()?,
+            label: schema.label.as_ref().map(position).transpose()?,
+        };
+
+        let mut import = CsvImport {
+            graph: WeightedGraph::new(),
+            labels: BTreeMap::new(),
+            errors: Vec::new(),
+        };
+        for (index, line) in lines.enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let row =
+                split_row(&line, schema.delimiter).and_then(|fields| columns.read(&fields, schema));
+            match row {
+                Ok(row) => {
+                    for id in [row.source, row.target] {
+                        import.graph.add_node(id, ());
+                    }
+                    import.graph.add_edge(row.source, row.target, row.weight);
+                    if let Some(label) = row.label {
+                        import.labels.insert((row.source, row.target), label);
+                    }
+                }
+                Err(kind) => import.errors.push(CsvRowError {
+                    line: index + 2,
+                    kind,
+                }),
+            }
+        }
+        debug_event!(
+            edges = import.graph.edges.len(),
+            errors = import.errors.len(),
+            "csv read"
+        );
+
+        Ok(import)
+    }
+}
+
+/// Positions of the columns of a schema in the header, with the names of the required ones.
+struct Columns<'a> {
+    source: (usize, &'a String),
+    target: (usize, &'a String),
+    weight: Option<usize>,
+    label: Option<usize>,
+}
+
+struct Row {
+    source: GraphId,
+    target: GraphId,
+    weight: f64,
+    label: Option<String>,
+}
+
+impl Columns<'_> {
+    fn read(&self, fields: &[String], schema: &CsvSchema) -> Result<Row, CsvRowErrorKind> {
+        let field = |position: usize, name: &String| {
+            fields
+                .get(position)
+                .ok_or_else(|| CsvRowErrorKind::MissingField(name.clone()))
+        };
+        let id = |(position, name): (usize, &String)| {
+            let value = field(position, name)?;
+            parse_id(value).ok_or_else(|| CsvRowErrorKind::InvalidId {
+                column: name.clone(),
+                value: value.clone(),
+            })
+        };
+
+        let source = id(self.source)?;
+        let target = id(self.target)?;
+        let weight = match (self.weight, &schema.weight) {
+            (Some(position), Some(name)) => {
+                let value = field(position, name)?;
+                parse_weight(value, schema.delimiter)
+                    .ok_or_else(|| CsvRowErrorKind::InvalidWeight(value.clone()))?
+            }
+            _ => 1.0,
+        };
+        let label = match (self.label, &schema.label) {
+            (Some(position), Some(name)) => {
+                Some(field(position, name)?.clone()).filter(|label| !label.is_empty())
+            }
+            _ => None,
+        };
+
+        Ok(Row {
+            source,
+            target,
+            weight,
+            label,
+        })
+    }
+}
+
+/// Splits a line into trimmed fields, unquoting quoted ones.
+fn split_row(line: &str, delimiter: char) -> Result<Vec<String>, CsvRowErrorKind> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            c if c == delimiter && !quoted => {
+                fields.push(field.trim().to_string());
+                field.clear();
+            }
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(CsvRowErrorKind::UnclosedQuote);
+    }
+    fields.push(field.trim().to_string());
+
+    Ok(fields)
+}
+
+/// Reads an ID, also accepting a whole number written as a decimal such as `12.0`.
+fn parse_id(value: &str) -> Option<GraphId> {
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    fraction
+        .chars()
+        .all(|c| c == '0')
+        .then(|| whole.parse().ok())
+        .flatten()
+}
+
+/// Reads a finite weight, defaulting an empty field to 1 and accepting a decimal comma unless
+/// the comma is the delimiter.
+fn parse_weight(value: &str, delimiter: char) -> Option<f64> {
+    if value.is_empty() {
+        return Some(1.0);
+    }
+
+    value
+        .parse()
+        .ok()
+        .or_else(|| {
+            (delimiter != ',')
+                .then(|| value.replace(',', ".").parse().ok())
+                .flatten()
+        })
+        .filter(|weight: &f64| weight.is_finite())
+}
+
+impl fmt::Display for CsvRowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Line {}: ", self.line)?;
+        match &self.kind {
+            CsvRowErrorKind::MissingField(column) => write!(f, "missing field {column:?}"),
+            CsvRowErrorKind::InvalidId { column, value } => {
+                write!(f, "{value:?} in column {column:?} isn't a node ID")
+            }
+            CsvRowErrorKind::InvalidWeight(value) => write!(f, "{value:?} isn't a weight"),
+            CsvRowErrorKind::UnclosedQuote => write!(f, "unclosed quote"),
+        }
+    }
+}
+
+impl std::error::Error for CsvRowError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_schema() -> CsvSchema {
+        CsvSchema {
+            source: "From".into(),
+            target: "To".into(),
+            weight: Some("Cost".into()),
+            label: Some("Kind".into()),
+            delimiter: ';',
+        }
+    }
+
+    #[test]
+    fn from_csv_maps_columns() {
+        let text = "Note;From;To;Cost;Kind\n\
+                    x; 1 ;2;1,5;\"road; paved\"\n\
+                    ;2.0;3;;\n\
+                    \n\
+                    ;3;1;2;rail\n";
+        let import = WeightedGraph::from_csv(text.as_bytes(), &get_schema()).unwrap();
+        assert!(
+            import.errors.is_empty(),
+            "Unexpected errors {:?}",
+            import.errors
+        );
+        assert_eq!(
+            import.graph.weighted_edges(),
+            vec![(1, 2, 1.5), (2, 3, 1.0), (3, 1, 2.0)],
+            "Edges mismatch"
+        );
+        assert_eq!(
+            import.labels,
+            BTreeMap::from([((1, 2), "road; paved".into()), ((3, 1), "rail".into())]),
+            "Labels mismatch"
+        );
+    }
+
+    #[test]
+    fn from_csv_reports_bad_rows() {
+        let text = "From;To;Cost;Kind\n1;2;x;a\n1;-2;1;a\n3\n\"4;5;1;a\n4;5;1;a\n";
+        let import = WeightedGraph::from_csv(text.as_bytes(), &get_schema()).unwrap();
+        assert_eq!(
+            import.errors.iter().map(|e| e.line).collect::<Vec<_>>(),
+            vec![2, 3, 4, 5],
+            "Error lines mismatch"
+        );
+        assert_eq!(
+            import.errors[1].to_string(),
+            "Line 3: \"-2\" in column \"To\" isn't a node ID",
+            "Message mismatch"
+        );
+        assert_eq!(
+            import.errors[2].kind,
+            CsvRowErrorKind::MissingField("To".into()),
+            "Kind mismatch"
+        );
+        assert_eq!(
+            import.graph.weighted_edges(),
+            vec![(4, 5, 1.0)],
+            "Expected only the valid row"
+        );
+    }
+
+    #[test]
+    fn from_csv_rejects_out_of_range_values() {
+        let text = "From;To;Cost;Kind\n\
+                    18446744073709551616;1;1;\n\
+                    18446744073709551615.0;1;1;\n\
+                    1.5;1;1;\n\
+                    1;2;NaN;\n\
+                    1;2;inf;\n\
+                    1;2;-1e400;\n";
+        let import = WeightedGraph::from_csv(text.as_bytes(), &get_schema()).unwrap();
+        assert_eq!(
+            import
+                .errors
+                .iter()
+                .map(|e| (e.line, &e.kind))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    2,
+                    &CsvRowErrorKind::InvalidId {
+                        column: "From".into(),
+                        value: "18446744073709551616".into()
+                    }
+                ),
+                (
+                    4,
+                    &CsvRowErrorKind::InvalidId {
+                        column: "From".into(),
+                        value: "1.5".into()
+                    }
+                ),
+                (5, &CsvRowErrorKind::InvalidWeight("NaN".into())),
+                (6, &CsvRowErrorKind::InvalidWeight("inf".into())),
+                (7, &CsvRowErrorKind::InvalidWeight("-1e400".into())),
+            ],
+            "Errors mismatch"
+        );
+        assert_eq!(
+            import.graph.weighted_edges(),
+            vec![(GraphId::MAX, 1, 1.0)],
+            "Expected only the largest valid ID"
+        );
+    }
+
+    #[test]
+    fn from_csv_rejects_missing_header_columns() {
+        let error =
+            WeightedGraph::from_csv("a,b\n1,2\n".as_bytes(), &CsvSchema::default()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "CSV header has no column \"source\"",
+            "Message mismatch"
+        );
+    }
+}
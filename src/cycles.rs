@@ -0,0 +1,259 @@
+use alloc::{vec, vec::Vec};
+
+use super::{
+    collections::{HashMap, HashSet},
+    connectivity::strong_components,
+    CancellationToken, Graph, GraphError, GraphId,
+};
+
+impl<T> Graph<T> {
+    /// Returns every elementary cycle, each starting at its smallest ID and without repeating
+    /// it at the end, in sorted order. A self-loop is a cycle of one node.
+    ///
+    /// Uses Johnson's algorithm, so the running time grows with the number of cycles, which
+    /// can be exponential in the size of the graph.
+    pub fn cycles(&self) -> Vec<Vec<GraphId>> {
+        self.cycles_cancellable(&CancellationToken::new())
+            .expect("token is never cancelled")
+    }
+
+    /// Like [`cycles`](Self::cycles), but stops with [`GraphError::Cancelled`] soon after
+    /// `cancel` is set.
+    pub fn cycles_cancellable(
+        &self,
+        cancel: &CancellationToken,
+    ) -> Result<Vec<Vec<GraphId>>, GraphError> {
+        debug_span!("cycles", nodes = self.nodes.len(), edges = self.edges.len());
+        let adjacency = self.adjacency();
+        let mut cycles = self
+            .edges
+            .iter()
+            .filter(|edge| edge.from == edge.to)
+            .map(|edge| vec![edge.from])
+            .collect::<Vec<_>>();
+
+        let mut ids = self.nodes.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        let mut pending = strong_components(&ids, &adjacency)
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .collect::<Vec<_>>();
+        while let Some(mut component) = pending.pop() {
+            component.sort_unstable();
+            let start = component.remove(0);
+            let members = component.iter().copied().chain([start]).collect();
+            cycles.extend(cycles_through(start, &members, &adjacency, cancel)?);
+            pending.extend(
+                strong_components(&component, &adjacency)
+                    .into_iter()
+                    .filter(|component| component.len() > 1),
+            );
+        }
+
+        for cycle in &mut cycles {
+            let smallest = (0..cycle.len())
+                .min_by_key(|index| cycle[*index])
+                .expect("cycles aren't empty");
+            cycle.rotate_left(smallest);
+        }
+        cycles.sort_unstable();
+        debug_event!(cycles = cycles.len(), "found cycles");
+
+        Ok(cycles)
+    }
+
+    /// Returns `true` if the graph has at least one cycle, including self-loops.
+    pub fn has_cycle(&self) -> bool {
+        self.edges.iter().any(|edge| edge.from == edge.to)
+            || self
+                .strongly_connected_components()
+                .iter()
+                .any(|component| component.len() > 1)
+    }
+
+    /// Returns `true` if the graph has no cycles, including self-loops. Unlike
+    /// [`has_cycle`](Self::has_cycle), only counts in-degrees instead of finding components.
+    pub fn is_dag(&self) -> bool {
+        let adjacency = self.adjacency();
+        let mut in_degree: HashMap<GraphId, usize> = self.nodes.keys().map(|id| (*id, 0)).collect();
+        for edge in &self.edges {
+            *in_degree
+                .get_mut(&edge.to)
+                .expect("edges only join existing nodes") += 1;
+        }
+
+        // Kahn's algorithm: every node is removed eventually if and only if there's no cycle.
+        let mut ready = in_degree
+            .iter()
+            .filter_map(|(id, degree)| (*degree == 0).then_some(*id))
+            .collect::<Vec<_>>();
+        let mut removed = 0;
+        while let Some(id) = ready.pop() {
+            removed += 1;
+            for next in adjacency.get(&id).into_iter().flatten() {
+                let degree = in_degree.get_mut(next).expect("counted above");
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.push(*next);
+                }
+            }
+        }
+
+        removed == self.nodes.len()
+    }
+
+    /// Returns `true` if the graph is a set of rooted trees with edges pointing away from the
+    /// roots, i.e. it has no cycles and every node has at most one incoming edge. The empty
+    /// graph is a forest.
+    pub fn is_forest(&self) -> bool {
+        let mut has_parent = HashSet::new();
+        self.edges.iter().all(|edge| has_parent.insert(edge.to)) && self.is_dag()
+    }
+
+    /// Returns `true` if the graph is a [forest](Self::is_forest) with a single root, from
+    /// which every node can be reached. The empty graph isn't a tree.
+    pub fn is_tree(&self) -> bool {
+        self.edges.len() + 1 == self.nodes.len() && self.is_forest()
+    }
+}
+
+/// Finds the elementary cycles through `start` that stay within `members`, with Johnson's
+/// blocking scheme to avoid re-exploring dead ends.
+fn cycles_through(
+    start: GraphId,
+    members: &HashSet<GraphId>,
+    adjacency: &HashMap<GraphId, Vec<GraphId>>,
+    cancel: &CancellationToken,
+) -> Result<Vec<Vec<GraphId>>, GraphError> {
+    let neighbours = |id: GraphId| {
+        adjacency
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|neighbour| members.contains(neighbour) && *neighbour != id)
+            .collect::<Vec<_>>()
+    };
+
+    let mut cycles = Vec::new();
+    let mut path = vec![start];
+    let mut blocked: HashSet<GraphId> = HashSet::from_iter([start]);
+    let mut closed = HashSet::new();
+    let mut blocked_by: HashMap<GraphId, HashSet<GraphId>> = HashMap::new();
+    let mut stack = vec![(start, neighbours(start))];
+    while let Some((id, remaining)) = stack.last_mut() {
+        if cancel.is_cancelled() {
+            return Err(GraphError::Cancelled);
+        }
+        let id = *id;
+        if let Some(next) = remaining.pop() {
+            if next == start {
+                cycles.push(path.clone());
+                closed.extend(path.iter().copied());
+            } else if !blocked.contains(&next) {
+                path.push(next);
+                closed.remove(&next);
+                blocked.insert(next);
+                stack.push((next, neighbours(next)));
+            }
+            continue;
+        }
+
+        if closed.contains(&id) {
+            let mut unblock = vec![id];
+            while let Some(id) = unblock.pop() {
+                if blocked.remove(&id) {
+                    unblock.extend(blocked_by.remove(&id).into_iter().flatten());
+                }
+            }
+        } else {
+            for neighbour in neighbours(id) {
+                blocked_by.entry(neighbour).or_default().insert(id);
+            }
+        }
+        stack.pop();
+        path.pop();
+    }
+
+    Ok(cycles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycles_lists_elementary_cycles() {
+        let graph = Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ())],
+            [(1, 2), (2, 3), (3, 1), (2, 1), (3, 4), (4, 4)],
+        ));
+        assert_eq!(
+            graph.cycles(),
+            vec![vec![1, 2], vec![1, 2, 3], vec![4]],
+            "Cycles mismatch"
+        );
+        assert!(graph.has_cycle(), "Expected a cycle");
+        assert!(!graph.is_dag(), "Expected no DAG");
+    }
+
+    #[test]
+    fn structural_predicates() {
+        let mut graph = Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ())],
+            [(1, 2), (1, 3), (3, 4)],
+        ));
+        assert!(graph.is_tree(), "Expected a tree");
+        graph.add_node(5, ());
+        assert!(
+            !graph.is_tree() && graph.is_forest(),
+            "Expected a forest of two trees"
+        );
+        graph.add_edge(2, 4);
+        assert!(
+            graph.is_dag() && !graph.is_forest(),
+            "Expected a DAG with a node of two parents"
+        );
+        graph.add_edge(5, 5);
+        assert!(!graph.is_dag(), "Expected self-loop to be a cycle");
+        assert!(
+            Graph::<()>::new().is_forest() && !Graph::<()>::new().is_tree(),
+            "Expected empty graph to be a forest only"
+        );
+    }
+
+    #[test]
+    fn cycles_complete_graph() {
+        let mut graph = Graph::new();
+        for id in 1..=4 {
+            graph.add_node(id, ());
+        }
+        for from in 1..=4 {
+            for to in 1..=4 {
+                if from != to {
+                    graph.add_edge(from, to);
+                }
+            }
+        }
+        // 6 cycles of length 2, 8 of length 3 and 6 of length 4.
+        assert_eq!(graph.cycles().len(), 20, "Cycles count mismatch");
+    }
+
+    #[test]
+    fn cycles_cancelled() {
+        let graph = Graph::from(([(1, ()), (2, ())], [(1, 2), (2, 1)]));
+        let token = CancellationToken::new();
+        token.cancel();
+        assert!(
+            matches!(graph.cycles_cancellable(&token), Err(GraphError::Cancelled)),
+            "Expected cancellation"
+        );
+    }
+
+    #[test]
+    fn cycles_dag_has_none() {
+        let graph = Graph::from(([(1, ()), (2, ()), (3, ())], [(1, 2), (1, 3), (2, 3)]));
+        assert!(graph.cycles().is_empty(), "Expected no cycles");
+        assert!(!graph.has_cycle(), "Expected no cycle");
+    }
+}
@@ -0,0 +1,193 @@
+use alloc::vec::Vec;
+use core::{error::Error, fmt};
+
+use super::{Graph, GraphId};
+
+/// Changes that turn one graph into another, as returned by [`Graph::diff`]. Every list is
+/// sorted by ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphDiff<'a, T> {
+    pub added_nodes: Vec<(GraphId, &'a T)>,
+    pub removed_nodes: Vec<(GraphId, &'a T)>,
+    /// Nodes present in both graphs with different values, as `(id, old, new)`.
+    pub changed_nodes: Vec<(GraphId, &'a T, &'a T)>,
+    pub added_edges: Vec<(GraphId, GraphId)>,
+    pub removed_edges: Vec<(GraphId, GraphId)>,
+}
+
+impl<T> GraphDiff<'_, T> {
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.changed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+    }
+}
+
+/// What [`Graph::merge`] does with a node that both graphs have with different values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Keep the value of the graph being merged into.
+    KeepOurs,
+    /// Take the value of the graph being merged in.
+    KeepTheirs,
+    /// Leave the graph unchanged and report every conflict.
+    #[default]
+    Fail,
+}
+
+/// Nodes that both merged graphs have with different values, sorted by ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+    pub ids: Vec<GraphId>,
+}
+
+impl<T: PartialEq> Graph<T> {
+    /// Compares the graph with `other`, which is treated as the newer version.
+    pub fn diff<'a>(&'a self, other: &'a Graph<T>) -> GraphDiff<'a, T> {
+        let mut added_nodes = other
+            .nodes
+            .iter()
+            .filter(|(id, _)| !self.nodes.contains_key(*id))
+            .map(|(id, value)| (*id, value))
+            .collect::<Vec<_>>();
+        let mut removed_nodes = self
+            .nodes
+            .iter()
+            .filter(|(id, _)| !other.nodes.contains_key(*id))
+            .map(|(id, value)| (*id, value))
+            .collect::<Vec<_>>();
+        let mut changed_nodes = self
+            .nodes
+            .iter()
+            .filter_map(|(id, old)| match other.nodes.get(id) {
+                Some(new) if new != old => Some((*id, old, new)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let mut added_edges = other
+            .edges
+            .difference(&self.edges)
+            .map(|edge| (edge.from, edge.to))
+            .collect::<Vec<_>>();
+        let mut removed_edges = self
+            .edges
+            .difference(&other.edges)
+            .map(|edge| (edge.from, edge.to))
+            .collect::<Vec<_>>();
+
+        added_nodes.sort_unstable_by_key(|(id, _)| *id);
+        removed_nodes.sort_unstable_by_key(|(id, _)| *id);
+        changed_nodes.sort_unstable_by_key(|(id, _, _)| *id);
+        added_edges.sort_unstable();
+        removed_edges.sort_unstable();
+
+        GraphDiff {
+            added_nodes,
+            removed_nodes,
+            changed_nodes,
+            added_edges,
+            removed_edges,
+        }
+    }
+
+    /// Adds every node and edge of `other` to the graph. Nodes that both graphs have with
+    /// different values are resolved by `policy`; with [`MergePolicy::Fail`] the graph is
+    /// left unchanged if there are any.
+    pub fn merge(&mut self, other: Graph<T>, policy: MergePolicy) -> Result<(), MergeConflict> {
+        if policy == MergePolicy::Fail {
+            let mut ids = other
+                .nodes
+                .iter()
+                .filter(|(id, value)| self.nodes.get(*id).is_some_and(|ours| ours != *value))
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>();
+            if !ids.is_empty() {
+                ids.sort_unstable();
+                return Err(MergeConflict { ids });
+            }
+        }
+
+        for (id, value) in other.nodes {
+            if policy == MergePolicy::KeepTheirs || !self.nodes.contains_key(&id) {
+                self.nodes.insert(id, value);
+            }
+        }
+        self.edges.extend(other.edges);
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for MergeConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Conflicting values for node")?;
+        if self.ids.len() > 1 {
+            write!(f, "s")?;
+        }
+        for (index, id) in self.ids.iter().enumerate() {
+            write!(f, "{}{id}", if index == 0 { " " } else { ", " })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for MergeConflict {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_graphs() -> (Graph<&'static str>, Graph<&'static str>) {
+        (
+            Graph::from(([(1, "a"), (2, "b"), (3, "c")], [(1, 2), (2, 3)])),
+            Graph::from(([(1, "a"), (2, "B"), (4, "d")], [(1, 2), (2, 4)])),
+        )
+    }
+
+    #[test]
+    fn diff_lists_changes() {
+        let (ours, theirs) = get_test_graphs();
+        let diff = ours.diff(&theirs);
+        assert_eq!(diff.added_nodes, vec![(4, &"d")], "Added nodes mismatch");
+        assert_eq!(
+            diff.removed_nodes,
+            vec![(3, &"c")],
+            "Removed nodes mismatch"
+        );
+        assert_eq!(
+            diff.changed_nodes,
+            vec![(2, &"b", &"B")],
+            "Changed nodes mismatch"
+        );
+        assert_eq!(diff.added_edges, vec![(2, 4)], "Added edges mismatch");
+        assert_eq!(diff.removed_edges, vec![(2, 3)], "Removed edges mismatch");
+        assert!(ours.diff(&ours).is_empty(), "Expected no changes");
+    }
+
+    #[test]
+    fn merge_policies() {
+        let (ours, theirs) = get_test_graphs();
+        let mut kept = ours.clone();
+        kept.merge(theirs.clone(), MergePolicy::KeepOurs).unwrap();
+        assert_eq!(kept[2], "b", "Expected our value");
+        assert_eq!(kept.nodes.len(), 4, "Nodes count mismatch");
+        assert_eq!(kept.edges.len(), 3, "Edges count mismatch");
+
+        let mut taken = ours.clone();
+        taken.merge(theirs, MergePolicy::KeepTheirs).unwrap();
+        assert_eq!(taken[2], "B", "Expected their value");
+    }
+
+    #[test]
+    fn merge_fail_reports_conflicts() {
+        let (mut ours, theirs) = get_test_graphs();
+        let original = ours.clone();
+        let error = ours.merge(theirs, MergePolicy::Fail).unwrap_err();
+        assert_eq!(error, MergeConflict { ids: vec![2] });
+        assert_eq!(error.to_string(), "Conflicting values for node 2");
+        assert_eq!(ours, original, "Expected graph to be unchanged");
+    }
+}
@@ -0,0 +1,286 @@
+use alloc::{collections::BinaryHeap, vec::Vec};
+use core::{cmp::Ordering, ops::Add};
+
+use super::{
+    collections::{HashMap, HashSet},
+    Distances, Graph, GraphId, Path, SearchFilter, ShortestPathTree, WeightedGraph,
+};
+
+/// A node waiting in the queue, ordered so that the cheapest, then lowest ID, pops first.
+struct Candidate<W> {
+    cost: W,
+    id: GraphId,
+}
+
+impl<W: PartialOrd> Ord for Candidate<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .partial_cmp(&self.cost)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.id.cmp(&self.id))
+    }
+}
+
+impl<W: PartialOrd> PartialOrd for Candidate<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: PartialOrd> PartialEq for Candidate<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<W: PartialOrd> Eq for Candidate<W> {}
+
+impl<T> Graph<T> {
+    /// Returns a cheapest path from `from` to `to`, including both endpoints, with its total
+    /// cost.
+    ///
+    /// `cost` is asked for the cost of every edge as it is followed, so costs can be computed
+    /// from the node values instead of being stored. Edges it returns `None` for are skipped,
+    /// as are negative and incomparable costs such as NaN. `W::default()` is the cost of the
+    /// empty path.
+    pub fn shortest_path_by<W>(
+        &self,
+        from: GraphId,
+        to: GraphId,
+        cost: impl Fn(GraphId, GraphId) -> Option<W>,
+    ) -> Option<Path<W>>
+    where
+        W: Copy + Default + PartialOrd + Add<Output = W>,
+    {
+        self.cost_tree(from, Some(to), &self.adjacency(), cost)?
+            .path_to(to)
+    }
+
+    /// Like [`shortest_path_by`](Self::shortest_path_by), but avoids the nodes and edges
+    /// hidden by `filter`.
+    pub fn shortest_path_by_filtered<W>(
+        &self,
+        from: GraphId,
+        to: GraphId,
+        filter: &SearchFilter<'_, T>,
+        cost: impl Fn(GraphId, GraphId) -> Option<W>,
+    ) -> Option<Path<W>>
+    where
+        W: Copy + Default + PartialOrd + Add<Output = W>,
+    {
+        if !filter.allows_node(self, from) {
+            return None;
+        }
+
+        self.cost_tree(from, Some(to), &self.filtered_adjacency(filter), cost)?
+            .path_to(to)
+    }
+
+    /// Returns the cost of a cheapest path from `source` to every node it can reach, itself
+    /// included at `W::default()`, with edge costs as in
+    /// [`shortest_path_by`](Self::shortest_path_by).
+    pub fn distances_by<W>(
+        &self,
+        source: GraphId,
+        cost: impl Fn(GraphId, GraphId) -> Option<W>,
+    ) -> Distances<W>
+    where
+        W: Copy + Default + PartialOrd + Add<Output = W>,
+    {
+        self.shortest_path_tree_by(source, cost)
+            .map(ShortestPathTree::into_distances)
+            .unwrap_or_default()
+    }
+
+    /// Returns the costs and parents of every node reachable from `source`, with edge costs
+    /// as in [`shortest_path_by`](Self::shortest_path_by), or `None` if it doesn't exist.
+    pub fn shortest_path_tree_by<W>(
+        &self,
+        source: GraphId,
+        cost: impl Fn(GraphId, GraphId) -> Option<W>,
+    ) -> Option<ShortestPathTree<W>>
+    where
+        W: Copy + Default + PartialOrd + Add<Output = W>,
+    {
+        self.cost_tree(source, None, &self.adjacency(), cost)
+    }
+
+    /// Runs Dijkstra's algorithm from `source` over `adjacency`, stopping once `target` is
+    /// settled if given.
+    pub(crate) fn cost_tree<W>(
+        &self,
+        source: GraphId,
+        target: Option<GraphId>,
+        adjacency: &HashMap<GraphId, Vec<GraphId>>,
+        cost: impl Fn(GraphId, GraphId) -> Option<W>,
+    ) -> Option<ShortestPathTree<W>>
+    where
+        W: Copy + Default + PartialOrd + Add<Output = W>,
+    {
+        if !self.nodes.contains_key(&source) {
+            return None;
+        }
+
+        debug_span!("cost_tree", source);
+        let zero = W::default();
+        let mut tree = ShortestPathTree::new(source, zero);
+        let mut settled = HashSet::new();
+        let mut queue = BinaryHeap::from([Candidate {
+            cost: zero,
+            id: source,
+        }]);
+        while let Some(Candidate { cost: current, id }) = queue.pop() {
+            if !settled.insert(id) {
+                continue;
+            }
+            if target == Some(id) {
+                break;
+            }
+
+            for &next in adjacency.get(&id).into_iter().flatten() {
+                let Some(step) = cost(id, next) else {
+                    continue;
+                };
+                if !matches!(
+                    step.partial_cmp(&zero),
+                    Some(Ordering::Greater | Ordering::Equal)
+                ) {
+                    continue;
+                }
+
+                let candidate = current + step;
+                let improves = tree
+                    .distance
+                    .get(&next)
+                    .is_none_or(|known| candidate < *known);
+                if improves && !settled.contains(&next) {
+                    tree.distance.insert(next, candidate);
+                    tree.parent.insert(next, id);
+                    queue.push(Candidate {
+                        cost: candidate,
+                        id: next,
+                    });
+                }
+            }
+        }
+        debug_event!(reached = tree.distance.len(), "cost tree built");
+
+        Some(tree)
+    }
+}
+
+impl<T> WeightedGraph<T> {
+    /// Returns a path from `from` to `to` with the lowest total weight, unlike
+    /// [`Graph::shortest_path`], which counts hops. Edges with a negative weight are skipped.
+    pub fn shortest_path(&self, from: GraphId, to: GraphId) -> Option<Path<f64>> {
+        self.shortest_path_by(from, to, |from, to| self.weight(from, to))
+    }
+
+    /// Returns the lowest total weight of a path from `source` to every node it can reach.
+    pub fn distances(&self, source: GraphId) -> Distances<f64> {
+        self.distances_by(source, |from, to| self.weight(from, to))
+    }
+
+    /// Returns the lowest total weights and parents of every node reachable from `source`,
+    /// or `None` if it doesn't exist.
+    pub fn shortest_path_tree(&self, source: GraphId) -> Option<ShortestPathTree<f64>> {
+        self.shortest_path_tree_by(source, |from, to| self.weight(from, to))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_graph() -> Graph<&'static str> {
+        Graph::from((
+            [
+                (1, "kitten"),
+                (2, "sitten"),
+                (3, "sittin"),
+                (4, "sitting"),
+                (5, "mitten"),
+            ],
+            [(1, 2), (2, 3), (3, 4), (1, 5), (5, 2), (5, 4)],
+        ))
+    }
+
+    /// Counts the positions at which two labels differ, plus their difference in length.
+    fn label_cost(graph: &Graph<&str>, from: GraphId, to: GraphId) -> Option<usize> {
+        let (from, to) = (graph[from], graph[to]);
+        let differing = from.chars().zip(to.chars()).filter(|(a, b)| a != b).count();
+
+        Some(differing + from.len().abs_diff(to.len()))
+    }
+
+    #[test]
+    fn shortest_path_by_node_values() {
+        let graph = get_test_graph();
+        assert_eq!(
+            graph.shortest_path_by(1, 4, |from, to| label_cost(&graph, from, to)),
+            Some(Path::with_cost(vec![1, 2, 3, 4], 3)),
+            "Path mismatch"
+        );
+        assert_eq!(
+            graph.shortest_path_by(1, 1, |from, to| label_cost(&graph, from, to)),
+            Some(Path::with_cost(vec![1], 0)),
+            "Expected trivial path"
+        );
+        assert_eq!(
+            graph.shortest_path_by(4, 1, |from, to| label_cost(&graph, from, to)),
+            None,
+            "Expected no path"
+        );
+    }
+
+    #[test]
+    fn distances_by_skips_excluded_edges() {
+        let graph = get_test_graph();
+        let distances = graph.distances_by(1, |from, to| ((from, to) != (1, 2)).then_some(1));
+        assert_eq!(
+            distances,
+            Distances::from_iter([(1, 0), (2, 2), (3, 3), (4, 2), (5, 1)]),
+            "Distances mismatch"
+        );
+        assert!(
+            graph.distances_by(9, |_, _| Some(1)).is_empty(),
+            "Expected no distances from missing node"
+        );
+    }
+
+    #[test]
+    fn weighted_shortest_path_uses_weights() {
+        let mut graph = WeightedGraph::new();
+        for id in 1..=4 {
+            graph.add_node(id, ());
+        }
+        graph.add_edge(1, 2, 1.0);
+        graph.add_edge(2, 3, 1.5);
+        graph.add_edge(1, 3, 3.0);
+        graph.add_edge(3, 4, f64::NAN);
+        assert_eq!(
+            graph.shortest_path(1, 3),
+            Some(Path::with_cost(vec![1, 2, 3], 2.5)),
+            "Path mismatch"
+        );
+        assert_eq!(
+            graph.shortest_path(1, 4),
+            None,
+            "Expected NaN to be skipped"
+        );
+        assert_eq!(
+            graph.distances(2),
+            Distances::from_iter([(2, 0.0), (3, 1.5)]),
+            "Distances mismatch"
+        );
+        let tree = graph.shortest_path_tree(1).unwrap();
+        assert_eq!(tree.parent(3), Some(2), "Parent mismatch");
+        assert_eq!(tree.distance(3), Some(2.5), "Distance mismatch");
+        assert_eq!(
+            tree.path_to(2),
+            Some(Path::with_cost(vec![1, 2], 1.0)),
+            "Path mismatch"
+        );
+    }
+}
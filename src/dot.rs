@@ -0,0 +1,177 @@
+use alloc::string::String;
+use core::fmt::Write;
+
+use super::{collections::HashSet, Graph, GraphId, NodeAttributes, NodeLabel};
+
+impl<T: NodeLabel> Graph<T> {
+    /// Writes the graph in Graphviz DOT format, with nodes styled as their values describe
+    /// through [`NodeLabel`] and everything sorted by ID.
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with_path(&[])
+    }
+
+    /// Like [`to_dot`](Self::to_dot), but outlines the nodes of `path` and colors the edges
+    /// between consecutive ones red.
+    pub fn to_dot_with_path(&self, path: &[GraphId]) -> String {
+        self.to_dot_by(path, |_, value| NodeAttributes::of(value))
+    }
+}
+
+impl<T> Graph<T> {
+    /// Like [`to_dot_with_path`](Self::to_dot_with_path), styling every node as returned by
+    /// `attributes` for its ID and value instead of through [`NodeLabel`].
+    pub fn to_dot_by(
+        &self,
+        path: &[GraphId],
+        mut attributes: impl FnMut(GraphId, &T) -> NodeAttributes,
+    ) -> String {
+        let path_nodes = path.iter().copied().collect::<HashSet<_>>();
+        let path_edges = path
+            .windows(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect::<HashSet<_>>();
+
+        let mut dot = String::from("digraph {\n");
+        for (id, value) in self.sorted_nodes() {
+            let NodeAttributes {
+                label,
+                tooltip,
+                color,
+            } = attributes(id, value);
+            let _ = write!(dot, "    {id} [label=\"{}\"", escape(&label));
+            if let Some(tooltip) = tooltip {
+                let _ = write!(dot, ", tooltip=\"{}\"", escape(&tooltip));
+            }
+            if let Some(color) = color {
+                let _ = write!(dot, ", style=filled, fillcolor=\"{}\"", escape(&color));
+            }
+            if path_nodes.contains(&id) {
+                dot.push_str(", color=red");
+            }
+            dot.push_str("];\n");
+        }
+
+        for (from, to) in self.sorted_edges() {
+            let attributes = if path_edges.contains(&(from, to)) {
+                " [color=red]"
+            } else {
+                ""
+            };
+            let _ = writeln!(dot, "    {from} -> {to}{attributes};");
+        }
+        dot.push_str("}\n");
+
+        dot
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_graph() -> Graph<&'static str> {
+        Graph::from(([(2, "b"), (1, "a \"quoted\""), (3, "c")], [(2, 3), (1, 2)]))
+    }
+
+    #[test]
+    fn to_dot_sorted() {
+        assert_eq!(
+            get_test_graph().to_dot(),
+            "digraph {\n    1 [label=\"a \\\"quoted\\\"\"];\n    2 [label=\"b\"];\n    \
+             3 [label=\"c\"];\n    1 -> 2;\n    2 -> 3;\n}\n",
+            "DOT mismatch"
+        );
+    }
+
+    #[test]
+    fn to_dot_with_path_colors() {
+        let dot = get_test_graph().to_dot_with_path(&[2, 3]);
+        assert!(
+            dot.contains("    2 [label=\"b\", color=red];"),
+            "Expected highlighted node"
+        );
+        assert!(
+            dot.contains("    2 -> 3 [color=red];"),
+            "Expected highlighted edge"
+        );
+        assert!(dot.contains("    1 -> 2;\n"), "Expected plain edge");
+    }
+
+    #[test]
+    fn to_dot_uses_node_label() {
+        struct Service {
+            name: &'static str,
+            healthy: bool,
+        }
+
+        impl NodeLabel for Service {
+            fn label(&self) -> String {
+                self.name.into()
+            }
+
+            fn color(&self) -> Option<String> {
+                (!self.healthy).then(|| "orange".into())
+            }
+        }
+
+        let graph = Graph::from((
+            [
+                (
+                    1,
+                    Service {
+                        name: "api",
+                        healthy: true,
+                    },
+                ),
+                (
+                    2,
+                    Service {
+                        name: "db",
+                        healthy: false,
+                    },
+                ),
+            ],
+            [(1, 2)],
+        ));
+        let dot = graph.to_dot_with_path(&[2]);
+        assert!(
+            dot.contains("    1 [label=\"api\"];"),
+            "Expected plain node"
+        );
+        assert!(
+            dot.contains("    2 [label=\"db\", style=filled, fillcolor=\"orange\", color=red];"),
+            "Expected colored node, got {dot}"
+        );
+
+        let dot = graph.to_dot_by(&[], |id, service| NodeAttributes {
+            label: format!("#{id}"),
+            tooltip: Some(service.name.into()),
+            color: None,
+        });
+        assert!(
+            dot.contains("    2 [label=\"#2\", tooltip=\"db\"];"),
+            "Expected attributes of the callback, got {dot}"
+        );
+    }
+
+    #[test]
+    fn to_dot_empty() {
+        assert_eq!(Graph::<&str>::new().to_dot(), "digraph {\n}\n");
+    }
+}
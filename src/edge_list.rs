@@ -0,0 +1,151 @@
+use std::io::Read;
+
+use anyhow::{anyhow, Context};
+
+use super::{
+    collections::{HashMap, HashSet},
+    Edge, Graph, GraphId,
+};
+
+impl<T: Default> Graph<T> {
+    /// Reads a plain edge list from `reader`, as in the SNAP datasets: a `from to` pair of
+    /// node IDs per line, separated by spaces or tabs. See
+    /// [`parse_edge_list`](Self::parse_edge_list).
+    pub fn from_edge_list(reader: impl Read) -> anyhow::Result<Self> {
+        let mut bytes = Vec::new();
+        { reader }.read_to_end(&mut bytes)?;
+
+        Self::parse_edge_list(&bytes)
+    }
+
+    /// Parses a plain edge list: a `from to` pair of node IDs per line, separated by spaces
+    /// or tabs. Lines starting with `#` and blank lines are skipped, as are any columns after
+    /// the second, such as timestamps. Every node gets the default value.
+    ///
+    /// Edge lists of tens of millions of lines are common, so IDs are read straight from the
+    /// bytes and the graph is allocated once for all of them.
+    pub fn parse_edge_list(bytes: &[u8]) -> anyhow::Result<Self> {
+        debug_span!("parse_edge_list", bytes = bytes.len());
+        let edges = parse_edges(bytes, 1)?;
+        let graph = Self::from_edge_chunks(&[edges]);
+        debug_event!(
+            nodes = graph.nodes.len(),
+            edges = graph.edges.len(),
+            "parsed edge list"
+        );
+
+        Ok(graph)
+    }
+
+    /// Builds a graph with every edge of `chunks` and a node with the default value for every
+    /// ID in them.
+    pub(crate) fn from_edge_chunks(chunks: &[Vec<(GraphId, GraphId)>]) -> Self {
+        // Most edge lists have far fewer nodes than edges, so the edge count bounds the
+        // nodes without reserving twice as much as needed.
+        let count = chunks.iter().map(Vec::len).sum();
+        let mut nodes = HashMap::with_capacity(count);
+        let mut edge_set = HashSet::with_capacity(count);
+        for &(from, to) in chunks.iter().flatten() {
+            nodes.entry(from).or_insert_with(T::default);
+            nodes.entry(to).or_insert_with(T::default);
+            edge_set.insert(Edge { from, to });
+        }
+        nodes.shrink_to_fit();
+
+        Self {
+            nodes,
+            edges: edge_set,
+        }
+    }
+}
+
+/// Reads the edges of the lines of an edge list, numbering them from `first_line` in errors.
+pub(crate) fn parse_edges(
+    bytes: &[u8],
+    first_line: usize,
+) -> anyhow::Result<Vec<(GraphId, GraphId)>> {
+    // Lines are rarely shorter than 8 bytes, so this avoids most reallocation.
+    let mut edges = Vec::with_capacity(bytes.len() / 8);
+    for (index, line) in bytes.split(|b| *b == b'\n').enumerate() {
+        let mut fields = line
+            .split(|b| b.is_ascii_whitespace())
+            .filter(|field| !field.is_empty());
+        let Some(from) = fields.next() else {
+            continue;
+        };
+        if from.starts_with(b"#") {
+            continue;
+        }
+
+        let context = || format!("Line {}", first_line + index);
+        let from = parse_id(from).with_context(context)?;
+        let to = fields
+            .next()
+            .ok_or_else(|| anyhow!("Missing target"))
+            .and_then(parse_id)
+            .with_context(context)?;
+        edges.push((from, to));
+    }
+
+    Ok(edges)
+}
+
+fn parse_id(field: &[u8]) -> anyhow::Result<GraphId> {
+    let invalid = || {
+        anyhow!(
+            "Expected a node ID, got {:?}",
+            String::from_utf8_lossy(field)
+        )
+    };
+    field.iter().try_fold(0 as GraphId, |id, b| {
+        let digit = b
+            .checked_sub(b'0')
+            .filter(|digit| *digit < 10)
+            .ok_or_else(invalid)?;
+        id.checked_mul(10)
+            .and_then(|id| id.checked_add(GraphId::from(digit)))
+            .ok_or_else(invalid)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_edge_list_skips_comments() {
+        let text = "# Directed graph: example.txt\n# FromNodeId\tToNodeId\n\
+                    1\t2\n2 3 1700000000\r\n\n  # indented comment\n3\t1\n1\t2\n";
+        let graph = Graph::<u8>::parse_edge_list(text.as_bytes()).unwrap();
+        assert_eq!(
+            graph.sorted_edges(),
+            vec![(1, 2), (2, 3), (3, 1)],
+            "Edges mismatch"
+        );
+        assert_eq!(
+            graph.sorted_nodes(),
+            vec![(1, &0), (2, &0), (3, &0)],
+            "Expected nodes with default values"
+        );
+    }
+
+    #[test]
+    fn parse_edge_list_reports_line() {
+        let error = Graph::<()>::parse_edge_list(b"# header\n1 2\n3 x\n").unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "Line 3: Expected a node ID, got \"x\"",
+            "Message mismatch"
+        );
+        let error = Graph::<()>::from_edge_list("1 2\n3\n".as_bytes()).unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "Line 2: Missing target",
+            "Message mismatch"
+        );
+        assert!(
+            Graph::<()>::parse_edge_list(b"1 18446744073709551616\n").is_err(),
+            "Expected overflow to fail"
+        );
+    }
+}
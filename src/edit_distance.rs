@@ -0,0 +1,419 @@
+use alloc::{collections::BinaryHeap, vec::Vec};
+use core::cmp::{Ordering, Reverse};
+
+use super::{collections::HashSet, Graph, GraphId};
+
+/// Result of [`Graph::edit_distance`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditDistance {
+    /// Number of node and edge insertions, deletions and value changes that turn one graph
+    /// into the other.
+    pub cost: usize,
+    /// Whether `cost` is the exact distance rather than an upper bound.
+    pub exact: bool,
+    /// The nodes of the first graph paired with the nodes of the second they become, sorted.
+    /// Deleted nodes are paired with `None`, and inserted ones follow with `None` first.
+    pub mapping: Vec<(Option<GraphId>, Option<GraphId>)>,
+}
+
+impl<T: PartialEq> Graph<T> {
+    /// Returns the graph edit distance to `other`, where inserting or deleting a node or an
+    /// edge and changing a node's value each cost 1. Node IDs don't need to match; the nodes
+    /// are paired up to minimize the cost.
+    ///
+    /// If neither graph has more than `max_exact_nodes` nodes, an A* search finds the exact
+    /// distance, which takes time exponential in the number of nodes. Otherwise every node is
+    /// greedily paired with the cheapest node left, which gives an upper bound.
+    pub fn edit_distance(&self, other: &Graph<T>, max_exact_nodes: usize) -> EditDistance {
+        let problem = EditProblem::new(self, other);
+        let exact = self.nodes.len().max(other.nodes.len()) <= max_exact_nodes;
+        debug_span!("edit_distance", nodes = self.nodes.len(), exact);
+        let assignment = if exact {
+            problem.search()
+        } else {
+            problem.greedy()
+        };
+
+        let cost = problem.cost(&assignment);
+        let mut mapping = assignment
+            .iter()
+            .enumerate()
+            .map(|(index, target)| {
+                (
+                    Some(problem.ids.0[index]),
+                    target.map(|target| problem.ids.1[target]),
+                )
+            })
+            .collect::<Vec<_>>();
+        mapping.extend(
+            problem
+                .unused(&assignment)
+                .into_iter()
+                .map(|target| (None, Some(problem.ids.1[target]))),
+        );
+        mapping.sort_unstable();
+
+        EditDistance {
+            cost,
+            exact,
+            mapping,
+        }
+    }
+}
+
+/// Both graphs with their nodes numbered in ID order. An assignment maps the first nodes of
+/// the first graph, by number, to a node of the second or `None` for a deletion.
+struct EditProblem<'a, T> {
+    ids: (Vec<GraphId>, Vec<GraphId>),
+    values: (Vec<&'a T>, Vec<&'a T>),
+    edges: (NumberedEdges, NumberedEdges),
+}
+
+type NumberedEdges = HashSet<(usize, usize)>;
+
+impl<'a, T: PartialEq> EditProblem<'a, T> {
+    fn new(first: &'a Graph<T>, second: &'a Graph<T>) -> Self {
+        let (ids_first, values_first, edges_first) = numbered(first);
+        let (ids_second, values_second, edges_second) = numbered(second);
+
+        Self {
+            ids: (ids_first, ids_second),
+            values: (values_first, values_second),
+            edges: (edges_first, edges_second),
+        }
+    }
+
+    /// Finds a cheapest complete assignment with A*.
+    fn search(&self) -> Vec<Option<usize>> {
+        let mut queue = BinaryHeap::from([State {
+            estimate: self.heuristic(&[]),
+            cost: 0,
+            assignment: Vec::new(),
+        }]);
+        while let Some(state) = queue.pop() {
+            if state.assignment.len() == self.ids.0.len() {
+                return state.assignment;
+            }
+            for (target, step) in self.steps(&state.assignment) {
+                let mut assignment = state.assignment.clone();
+                assignment.push(target);
+                let mut cost = state.cost + step;
+                // A complete assignment has no cost left to estimate: the heuristic would
+                // count the insertions of the completion cost a second time.
+                let estimate = if assignment.len() == self.ids.0.len() {
+                    cost += self.completion_cost(&assignment);
+                    cost
+                } else {
+                    cost + self.heuristic(&assignment)
+                };
+                queue.push(State {
+                    estimate,
+                    cost,
+                    assignment,
+                });
+            }
+        }
+
+        unreachable!("the queue holds a complete assignment before it runs empty")
+    }
+
+    /// Assigns every node in turn to the cheapest choice left.
+    fn greedy(&self) -> Vec<Option<usize>> {
+        let mut assignment = Vec::with_capacity(self.ids.0.len());
+        while assignment.len() < self.ids.0.len() {
+            let (target, _) = self
+                .steps(&assignment)
+                .into_iter()
+                .min_by_key(|(_, step)| *step)
+                .expect("deletion is always possible");
+            assignment.push(target);
+        }
+
+        assignment
+    }
+
+    /// Returns the cost of a complete assignment.
+    fn cost(&self, assignment: &[Option<usize>]) -> usize {
+        let mut cost = 0;
+        for length in 0..assignment.len() {
+            cost += self.step_cost(&assignment[..length], assignment[length]);
+        }
+
+        cost + self.completion_cost(assignment)
+    }
+
+    /// Returns every choice for the next node of the first graph with its cost: the unused
+    /// nodes of the second graph in order, then deletion.
+    fn steps(&self, assignment: &[Option<usize>]) -> Vec<(Option<usize>, usize)> {
+        self.unused(assignment)
+            .into_iter()
+            .map(Some)
+            .chain([None])
+            .map(|target| (target, self.step_cost(assignment, target)))
+            .collect()
+    }
+
+    /// Returns the cost of mapping the next node of the first graph to `target`: its own
+    /// cost, and the cost of its edges to the nodes assigned so far and itself.
+    fn step_cost(&self, assignment: &[Option<usize>], target: Option<usize>) -> usize {
+        let node = assignment.len();
+        let mut cost = match target {
+            Some(target) => usize::from(self.values.0[node] != self.values.1[target]),
+            None => 1,
+        };
+        let earlier = assignment.iter().copied().enumerate();
+        for (other, other_target) in earlier.chain([(node, target)]) {
+            let pairs = [
+                (node, other, target, other_target),
+                (other, node, other_target, target),
+            ];
+            let pairs = if other == node {
+                &pairs[..1]
+            } else {
+                &pairs[..]
+            };
+            for &(from, to, from_target, to_target) in pairs {
+                let present = self.edges.0.contains(&(from, to));
+                let image = match (from_target, to_target) {
+                    (Some(from), Some(to)) => self.edges.1.contains(&(from, to)),
+                    _ => false,
+                };
+                cost += usize::from(present != image);
+            }
+        }
+
+        cost
+    }
+
+    /// Returns the cost of inserting the unused nodes of the second graph and their edges,
+    /// once every node of the first graph is assigned.
+    fn completion_cost(&self, assignment: &[Option<usize>]) -> usize {
+        let unused = self.unused(assignment);
+        let unused = unused.iter().collect::<HashSet<_>>();
+        let edges = self
+            .edges
+            .1
+            .iter()
+            .filter(|(from, to)| unused.contains(from) || unused.contains(to))
+            .count();
+
+        unused.len() + edges
+    }
+
+    /// A lower bound on the cost still to come: the difference in the number of nodes left,
+    /// and in the number of edges touching them.
+    fn heuristic(&self, assignment: &[Option<usize>]) -> usize {
+        let unused = self.unused(assignment);
+        let unused_set = unused.iter().collect::<HashSet<_>>();
+        let nodes_left = self.ids.0.len() - assignment.len();
+        let edges_left = (
+            self.edges
+                .0
+                .iter()
+                .filter(|(from, to)| *from >= assignment.len() || *to >= assignment.len())
+                .count(),
+            self.edges
+                .1
+                .iter()
+                .filter(|(from, to)| unused_set.contains(from) || unused_set.contains(to))
+                .count(),
+        );
+
+        nodes_left.abs_diff(unused.len()) + edges_left.0.abs_diff(edges_left.1)
+    }
+
+    /// Returns the nodes of the second graph not assigned yet, in order.
+    fn unused(&self, assignment: &[Option<usize>]) -> Vec<usize> {
+        let used = assignment.iter().flatten().collect::<HashSet<_>>();
+
+        (0..self.ids.1.len())
+            .filter(|target| !used.contains(target))
+            .collect()
+    }
+}
+
+/// A partial assignment in the A* queue, ordered so that the lowest estimate, then the most
+/// complete assignment, pops first.
+#[derive(PartialEq, Eq)]
+struct State {
+    estimate: usize,
+    cost: usize,
+    assignment: Vec<Option<usize>>,
+}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (
+            Reverse(self.estimate),
+            self.assignment.len(),
+            Reverse(&self.assignment),
+        )
+            .cmp(&(
+                Reverse(other.estimate),
+                other.assignment.len(),
+                Reverse(&other.assignment),
+            ))
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Returns the sorted IDs of `graph`, its values and its edges by position in the IDs.
+fn numbered<T>(graph: &Graph<T>) -> (Vec<GraphId>, Vec<&T>, NumberedEdges) {
+    let ids = graph.sorted_nodes();
+    let index = |id: GraphId| {
+        ids.binary_search_by_key(&id, |(id, _)| *id)
+            .expect("edges only join existing nodes")
+    };
+    let edges = graph
+        .edges
+        .iter()
+        .map(|edge| (index(edge.from), index(edge.to)))
+        .collect();
+
+    (
+        ids.iter().map(|(id, _)| *id).collect(),
+        ids.into_iter().map(|(_, value)| value).collect(),
+        edges,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_triangle(base: GraphId) -> Graph<&'static str> {
+        Graph::from((
+            [(base, "a"), (base + 1, "b"), (base + 2, "c")],
+            [(base, base + 1), (base + 1, base + 2), (base + 2, base)],
+        ))
+    }
+
+    #[test]
+    fn edit_distance_ignores_ids() {
+        let distance = get_triangle(1).edit_distance(&get_triangle(10), 8);
+        assert_eq!(distance.cost, 0, "Expected isomorphic graphs");
+        assert!(distance.exact, "Expected exact search");
+        assert_eq!(
+            distance.mapping,
+            vec![
+                (Some(1), Some(10)),
+                (Some(2), Some(11)),
+                (Some(3), Some(12))
+            ],
+            "Mapping mismatch"
+        );
+    }
+
+    #[test]
+    fn edit_distance_counts_operations() {
+        let mut other = get_triangle(1);
+        other.delete_edge(3, 1);
+        other.add_node(4, "d");
+        other.add_edge(3, 4);
+        other[2] = "x";
+        // Delete an edge, change a value, insert a node and an edge.
+        let distance = get_triangle(1).edit_distance(&other, 8);
+        assert_eq!(distance.cost, 4, "Cost mismatch");
+        assert_eq!(
+            distance.mapping.first(),
+            Some(&(None, Some(4))),
+            "Expected inserted node"
+        );
+        assert_eq!(
+            Graph::new().edit_distance(&other, 8).cost,
+            7,
+            "Expected every node and edge to be inserted"
+        );
+    }
+
+    #[test]
+    fn greedy_edit_distance_is_upper_bound() {
+        let first = Graph::from((
+            [(1, "a"), (2, "a"), (3, "a"), (4, "a")],
+            [(1, 2), (2, 3), (3, 4)],
+        ));
+        let second = Graph::from((
+            [(1, "a"), (2, "a"), (3, "a"), (4, "a")],
+            [(4, 3), (3, 2), (2, 1), (1, 4)],
+        ));
+        let exact = first.edit_distance(&second, 8);
+        let greedy = first.edit_distance(&second, 3);
+        assert_eq!(exact.cost, 1, "Expected a single inserted edge");
+        assert!(!greedy.exact, "Expected greedy assignment");
+        assert!(greedy.cost >= exact.cost, "Expected an upper bound");
+    }
+
+    #[test]
+    fn edit_distance_finds_optimum_with_insertions() {
+        let first = Graph::from(([(100, 1)], []));
+        let second = Graph::from((
+            [(14, 0), (400, 0), (407, 1)],
+            [(14, 14), (407, 14), (407, 400)],
+        ));
+        let distance = first.edit_distance(&second, 8);
+        assert_eq!(distance.cost, 5, "Expected 100 to become 407");
+        assert_eq!(
+            distance.mapping,
+            vec![(None, Some(14)), (None, Some(400)), (Some(100), Some(407))],
+            "Mapping mismatch"
+        );
+    }
+
+    /// Returns the cost of the cheapest assignment, trying every one.
+    #[cfg(feature = "std")]
+    fn brute_force(problem: &EditProblem<u8>, assignment: &mut Vec<Option<usize>>) -> usize {
+        if assignment.len() == problem.ids.0.len() {
+            return problem.cost(assignment);
+        }
+
+        let mut best = usize::MAX;
+        for target in problem
+            .unused(assignment)
+            .into_iter()
+            .map(Some)
+            .chain([None])
+        {
+            assignment.push(target);
+            best = best.min(brute_force(problem, assignment));
+            assignment.pop();
+        }
+
+        best
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn edit_distance_matches_brute_force() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(5);
+        let random_graph = |rng: &mut StdRng| {
+            let mut graph = Graph::new();
+            for id in 0..rng.gen_range(0..4) {
+                graph.add_node(id, rng.gen_range(0..2u8));
+            }
+            let nodes = graph.nodes.len() as GraphId;
+            for _ in 0..rng.gen_range(0..5) {
+                if nodes > 0 {
+                    graph.add_edge(rng.gen_range(0..nodes), rng.gen_range(0..nodes));
+                }
+            }
+            graph
+        };
+        for _ in 0..300 {
+            let first = random_graph(&mut rng);
+            let second = random_graph(&mut rng);
+            let expected = brute_force(&EditProblem::new(&first, &second), &mut Vec::new());
+            assert_eq!(
+                first.edit_distance(&second, 8).cost,
+                expected,
+                "Cost mismatch for {first:?} and {second:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,306 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng};
+
+use super::{Graph, GraphId};
+
+/// Parameters of node2vec's second-order random walks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WalkOptions {
+    /// Maximum number of nodes in a walk, including the start.
+    pub length: usize,
+    pub walks_per_node: usize,
+    /// `p`: a higher value makes stepping straight back to the previous node less likely.
+    pub return_param: f64,
+    /// `q`: a higher value keeps walks close to the previous node (BFS-like), a lower one
+    /// pushes them outwards (DFS-like).
+    pub in_out_param: f64,
+}
+
+impl Default for WalkOptions {
+    fn default() -> Self {
+        Self {
+            length: 40,
+            walks_per_node: 10,
+            return_param: 1.0,
+            in_out_param: 1.0,
+        }
+    }
+}
+
+/// Parameters of the skip-gram model trained with negative sampling.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkipGramOptions {
+    pub dimensions: usize,
+    /// Number of nodes on each side of the centre node that count as its context.
+    pub window: usize,
+    pub negative_samples: usize,
+    pub epochs: usize,
+    pub learning_rate: f32,
+}
+
+impl Default for SkipGramOptions {
+    fn default() -> Self {
+        Self {
+            dimensions: 64,
+            window: 5,
+            negative_samples: 5,
+            epochs: 5,
+            learning_rate: 0.025,
+        }
+    }
+}
+
+impl<T> Graph<T> {
+    /// Generates node2vec walks along outgoing edges, starting `walks_per_node` times from
+    /// every node in ID order. A walk ends early at a node without outgoing edges.
+    pub fn biased_walks<R>(&self, options: &WalkOptions, rng: &mut R) -> Vec<Vec<GraphId>>
+    where
+        R: Rng + ?Sized,
+    {
+        assert!(
+            options.return_param > 0.0 && options.in_out_param > 0.0,
+            "Expected positive walk parameters"
+        );
+
+        let adjacency = self.adjacency();
+        let edges = self
+            .edges
+            .iter()
+            .map(|edge| (edge.from, edge.to))
+            .collect::<HashSet<_>>();
+        let mut ids = self.nodes.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+
+        let mut walks = Vec::with_capacity(ids.len() * options.walks_per_node);
+        for _ in 0..options.walks_per_node {
+            for &start in &ids {
+                let mut walk = vec![start];
+                while walk.len() < options.length {
+                    let current = walk[walk.len() - 1];
+                    let Some(neighbours) = adjacency.get(&current) else {
+                        break;
+                    };
+                    let next = match walk.len().checked_sub(2).map(|i| walk[i]) {
+                        None => neighbours[rng.gen_range(0..neighbours.len())],
+                        Some(previous) => {
+                            let weights = neighbours.iter().map(|&candidate| {
+                                if candidate == previous {
+                                    1.0 / options.return_param
+                                } else if edges.contains(&(previous, candidate)) {
+                                    1.0
+                                } else {
+                                    1.0 / options.in_out_param
+                                }
+                            });
+                            let index = WeightedIndex::new(weights)
+                                .expect("weights are positive")
+                                .sample(rng);
+                            neighbours[index]
+                        }
+                    };
+                    walk.push(next);
+                }
+                walks.push(walk);
+            }
+        }
+
+        walks
+    }
+
+    /// Embeds every node as a vector of `skip_gram.dimensions` values by training a skip-gram
+    /// model on node2vec walks. Nodes that appear together on walks get similar vectors.
+    pub fn node2vec<R>(
+        &self,
+        walks: &WalkOptions,
+        skip_gram: &SkipGramOptions,
+        rng: &mut R,
+    ) -> HashMap<GraphId, Vec<f32>>
+    where
+        R: Rng + ?Sized,
+    {
+        let walks = self.biased_walks(walks, rng);
+        train_skip_gram(&walks, skip_gram, rng)
+    }
+}
+
+/// Trains skip-gram vectors with negative sampling on `walks`, e.g. the output of
+/// [`Graph::biased_walks`]. Negative samples are drawn proportionally to the node frequency
+/// raised to the power 0.75, as in word2vec.
+pub fn train_skip_gram<R>(
+    walks: &[Vec<GraphId>],
+    options: &SkipGramOptions,
+    rng: &mut R,
+) -> HashMap<GraphId, Vec<f32>>
+where
+    R: Rng + ?Sized,
+{
+    let mut ids = walks.iter().flatten().copied().collect::<Vec<_>>();
+    ids.sort_unstable();
+    ids.dedup();
+    if ids.is_empty() {
+        return HashMap::new();
+    }
+    let index_of: HashMap<GraphId, usize> =
+        ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+    let mut counts = vec![0.0f64; ids.len()];
+    for id in walks.iter().flatten() {
+        counts[index_of[id]] += 1.0;
+    }
+    let noise = WeightedIndex::new(counts.iter().map(|count| count.powf(0.75)))
+        .expect("every node appears at least once");
+
+    let dimensions = options.dimensions;
+    let mut input = (0..ids.len() * dimensions)
+        .map(|_| (rng.gen::<f32>() - 0.5) / dimensions as f32)
+        .collect::<Vec<_>>();
+    let mut output = vec![0.0f32; ids.len() * dimensions];
+    let mut gradient = vec![0.0f32; dimensions];
+
+    for _ in 0..options.epochs {
+        for walk in walks {
+            let walk = walk.iter().map(|id| index_of[id]).collect::<Vec<_>>();
+            for (position, &centre) in walk.iter().enumerate() {
+                let first = position.saturating_sub(options.window);
+                let last = (position + options.window).min(walk.len() - 1);
+                for (context_position, &context) in
+                    walk.iter().enumerate().take(last + 1).skip(first)
+                {
+                    if context_position == position {
+                        continue;
+                    }
+
+                    gradient.fill(0.0);
+                    let centre_vector = centre * dimensions..(centre + 1) * dimensions;
+                    let samples = std::iter::once((context, 1.0))
+                        .chain((0..options.negative_samples).map(|_| (noise.sample(rng), 0.0)));
+                    for (target, label) in samples.collect::<Vec<_>>() {
+                        let target_vector = target * dimensions..(target + 1) * dimensions;
+                        let dot = input[centre_vector.clone()]
+                            .iter()
+                            .zip(&output[target_vector.clone()])
+                            .map(|(a, b)| a * b)
+                            .sum::<f32>();
+                        let step = options.learning_rate * (label - sigmoid(dot));
+                        for i in 0..dimensions {
+                            gradient[i] += step * output[target_vector.start + i];
+                            output[target_vector.start + i] +=
+                                step * input[centre_vector.start + i];
+                        }
+                    }
+                    for (value, delta) in input[centre_vector].iter_mut().zip(&gradient) {
+                        *value += delta;
+                    }
+                }
+            }
+        }
+    }
+
+    ids.iter()
+        .enumerate()
+        .map(|(i, id)| (*id, input[i * dimensions..(i + 1) * dimensions].to_vec()))
+        .collect()
+}
+
+fn sigmoid(x: f32) -> f32 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::generators;
+
+    use super::*;
+
+    fn cosine(a: &[f32], b: &[f32]) -> f32 {
+        let dot = a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>();
+        let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+        dot / (norm(a) * norm(b))
+    }
+
+    /// Two 5-cliques, 0..5 and 5..10, joined by a single edge between 4 and 5.
+    fn get_test_graph() -> Graph<()> {
+        let mut graph = generators::complete(10, |_| ());
+        for a in 0..10 {
+            for b in 0..10 {
+                if (a < 5) != (b < 5) && (a, b) != (4, 5) && (a, b) != (5, 4) {
+                    graph.delete_edge(a, b);
+                }
+            }
+        }
+
+        graph
+    }
+
+    #[test]
+    fn biased_walks_follow_edges() {
+        let graph = get_test_graph();
+        let options = WalkOptions {
+            length: 8,
+            walks_per_node: 3,
+            ..WalkOptions::default()
+        };
+        let walks = graph.biased_walks(&options, &mut StdRng::seed_from_u64(1));
+        assert_eq!(walks.len(), 30, "Walks count mismatch");
+        assert!(
+            walks.iter().all(|walk| walk.len() == 8
+                && walk
+                    .windows(2)
+                    .all(|step| graph.adjacency()[&step[0]].contains(&step[1]))),
+            "Expected every step to follow an edge"
+        );
+    }
+
+    #[test]
+    fn biased_walks_stop_at_sinks() {
+        let graph: Graph<()> = Graph::from(([(1, ()), (2, ())], [(1, 2)]));
+        let walks = graph.biased_walks(&WalkOptions::default(), &mut StdRng::seed_from_u64(1));
+        assert_eq!(walks[0], vec![1, 2], "Expected walk to stop at sink");
+        assert_eq!(walks[1], vec![2], "Expected single-node walk");
+    }
+
+    #[test]
+    fn biased_walks_low_return_param_backtracks() {
+        let graph = generators::path(3, |_| ());
+        let options = WalkOptions {
+            length: 3,
+            walks_per_node: 50,
+            return_param: 1e-6,
+            in_out_param: 1.0,
+        };
+        let walks = graph.biased_walks(&options, &mut StdRng::seed_from_u64(2));
+        assert!(
+            walks
+                .iter()
+                .filter(|walk| walk[..2] == [1, 2])
+                .all(|walk| walk[2] == 1),
+            "Expected walks to return to the previous node"
+        );
+    }
+
+    #[test]
+    fn node2vec_separates_communities() {
+        let graph = get_test_graph();
+        let skip_gram = SkipGramOptions {
+            dimensions: 16,
+            ..SkipGramOptions::default()
+        };
+        let embedding = graph.node2vec(
+            &WalkOptions::default(),
+            &skip_gram,
+            &mut StdRng::seed_from_u64(7),
+        );
+        assert_eq!(embedding.len(), 10, "Expected a vector for every node");
+        assert!(
+            embedding.values().all(|vector| vector.len() == 16),
+            "Expected fixed-length vectors"
+        );
+        assert!(
+            cosine(&embedding[&0], &embedding[&1]) > cosine(&embedding[&0], &embedding[&9]),
+            "Expected nodes of the same clique to be closer"
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use core::{error::Error, fmt};
+
+use super::{BuildError, CollisionError, GraphId, MergeConflict};
+
+/// Errors returned by graph operations, so that callers can propagate them with `?` into a
+/// single type.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum GraphError {
+    /// An operation referred to a node that isn't in the graph.
+    NodeNotFound(GraphId),
+    Build(BuildError),
+    MergeConflict(MergeConflict),
+    Collision(CollisionError),
+    /// Nesting the node under a group would make it its own ancestor.
+    NestingCycle(GraphId),
+    /// A [`CancellationToken`](crate::CancellationToken) stopped the operation.
+    Cancelled,
+    /// A node ID doesn't fit in half of a [product ID](crate::product_id).
+    ProductIdOutOfRange(GraphId),
+    /// Writing output failed.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::NodeNotFound(id) => write!(f, "Node {id} doesn't exist"),
+            GraphError::Build(e) => write!(f, "{e}"),
+            GraphError::MergeConflict(e) => write!(f, "{e}"),
+            GraphError::Collision(e) => write!(f, "{e}"),
+            GraphError::NestingCycle(id) => write!(f, "Node {id} can't be nested under itself"),
+            GraphError::Cancelled => write!(f, "Operation was cancelled"),
+            GraphError::ProductIdOutOfRange(id) => {
+                write!(
+                    f,
+                    "Node {id} doesn't fit in a graph product, which needs IDs below 2^32"
+                )
+            }
+            #[cfg(feature = "std")]
+            GraphError::Io(e) => write!(f, "Failed to write output: {e}"),
+        }
+    }
+}
+
+impl Error for GraphError {}
+
+impl From<BuildError> for GraphError {
+    fn from(e: BuildError) -> Self {
+        GraphError::Build(e)
+    }
+}
+
+impl From<MergeConflict> for GraphError {
+    fn from(e: MergeConflict) -> Self {
+        GraphError::MergeConflict(e)
+    }
+}
+
+impl From<CollisionError> for GraphError {
+    fn from(e: CollisionError) -> Self {
+        GraphError::Collision(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for GraphError {
+    fn from(e: std::io::Error) -> Self {
+        GraphError::Io(e)
+    }
+}
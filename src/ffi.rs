@@ -0,0 +1,407 @@
+//! C bindings. Node values are NUL-terminated UTF-8 strings; strings returned by the library
+//! must be released with [`bg_string_free`] and graphs with [`bg_graph_free`].
+//!
+//! Build a shared or static library with `cargo rustc --features ffi --crate-type cdylib`
+//! (or `staticlib`). The matching header is `include/basic_graph.h`, generated with
+//! `cbindgen --config cbindgen.toml --output include/basic_graph.h`.
+
+use std::{
+    ffi::{c_char, CStr, CString},
+    ptr, slice,
+};
+
+use super::{Graph, GraphId};
+
+/// An opaque handle to a graph with string values.
+pub struct BgGraph(Graph<String>);
+
+/// Result of a fallible call. Output parameters are only written on `Ok`, unless stated
+/// otherwise.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BgStatus {
+    Ok = 0,
+    NullPointer,
+    /// A string isn't valid UTF-8, or a value can't be returned because it contains NUL.
+    InvalidString,
+    NodeNotFound,
+    NodeExists,
+    NoPath,
+    /// The output buffer is too short; the required length has been written.
+    BufferTooSmall,
+    ParseError,
+}
+
+/// Creates an empty graph.
+#[no_mangle]
+pub extern "C" fn bg_graph_new() -> *mut BgGraph {
+    Box::into_raw(Box::new(BgGraph(Graph::new())))
+}
+
+/// Parses a graph from TGF into `*out`.
+///
+/// # Safety
+///
+/// `tgf` must be a NUL-terminated string and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn bg_graph_parse(tgf: *const c_char, out: *mut *mut BgGraph) -> BgStatus {
+    if out.is_null() {
+        return BgStatus::NullPointer;
+    }
+    let tgf = match read_str(tgf) {
+        Ok(tgf) => tgf,
+        Err(status) => return status,
+    };
+    match tgf.parse() {
+        Ok(graph) => {
+            *out = Box::into_raw(Box::new(BgGraph(graph)));
+            BgStatus::Ok
+        }
+        Err(_) => BgStatus::ParseError,
+    }
+}
+
+/// Frees a graph. Passing NULL does nothing.
+///
+/// # Safety
+///
+/// `graph` must be NULL or a handle returned by this library that hasn't been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn bg_graph_free(graph: *mut BgGraph) {
+    if !graph.is_null() {
+        drop(Box::from_raw(graph));
+    }
+}
+
+/// Serializes the graph as TGF. Returns NULL if `graph` is NULL or a value contains NUL.
+///
+/// # Safety
+///
+/// `graph` must be NULL or a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn bg_graph_serialize(graph: *const BgGraph) -> *mut c_char {
+    match graph.as_ref() {
+        Some(graph) => CString::new(graph.0.serialize()).map_or(ptr::null_mut(), CString::into_raw),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Frees a string returned by this library. Passing NULL does nothing.
+///
+/// # Safety
+///
+/// `string` must be NULL or a string returned by this library that hasn't been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn bg_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// Adds a node with a copy of `value`.
+///
+/// # Safety
+///
+/// `graph` must be a valid handle and `value` a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn bg_graph_add_node(
+    graph: *mut BgGraph,
+    id: GraphId,
+    value: *const c_char,
+) -> BgStatus {
+    let Some(graph) = graph.as_mut() else {
+        return BgStatus::NullPointer;
+    };
+    let value = match read_str(value) {
+        Ok(value) => value,
+        Err(status) => return status,
+    };
+    if graph.0.nodes.contains_key(&id) {
+        return BgStatus::NodeExists;
+    }
+
+    graph.0.add_node(id, value.to_string());
+    BgStatus::Ok
+}
+
+/// Removes a node along with its edges.
+///
+/// # Safety
+///
+/// `graph` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn bg_graph_remove_node(graph: *mut BgGraph, id: GraphId) -> BgStatus {
+    let Some(graph) = graph.as_mut() else {
+        return BgStatus::NullPointer;
+    };
+    if !graph.0.nodes.contains_key(&id) {
+        return BgStatus::NodeNotFound;
+    }
+
+    graph.0.delete_node(id);
+    BgStatus::Ok
+}
+
+/// Adds an edge between two existing nodes.
+///
+/// # Safety
+///
+/// `graph` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn bg_graph_add_edge(
+    graph: *mut BgGraph,
+    from: GraphId,
+    to: GraphId,
+) -> BgStatus {
+    let Some(graph) = graph.as_mut() else {
+        return BgStatus::NullPointer;
+    };
+    if !graph.0.nodes.contains_key(&from) || !graph.0.nodes.contains_key(&to) {
+        return BgStatus::NodeNotFound;
+    }
+
+    graph.0.add_edge(from, to);
+    BgStatus::Ok
+}
+
+/// Removes an edge. Removing a missing edge succeeds.
+///
+/// # Safety
+///
+/// `graph` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn bg_graph_remove_edge(
+    graph: *mut BgGraph,
+    from: GraphId,
+    to: GraphId,
+) -> BgStatus {
+    let Some(graph) = graph.as_mut() else {
+        return BgStatus::NullPointer;
+    };
+
+    graph.0.delete_edge(from, to);
+    BgStatus::Ok
+}
+
+/// Returns the number of nodes, or 0 if `graph` is NULL.
+///
+/// # Safety
+///
+/// `graph` must be NULL or a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn bg_graph_node_count(graph: *const BgGraph) -> usize {
+    graph.as_ref().map_or(0, |graph| graph.0.nodes.len())
+}
+
+/// Returns the number of edges, or 0 if `graph` is NULL.
+///
+/// # Safety
+///
+/// `graph` must be NULL or a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn bg_graph_edge_count(graph: *const BgGraph) -> usize {
+    graph.as_ref().map_or(0, |graph| graph.0.edges.len())
+}
+
+/// Writes a copy of the node's value to `*out`, to be freed with [`bg_string_free`].
+///
+/// # Safety
+///
+/// `graph` must be a valid handle and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn bg_graph_node_value(
+    graph: *const BgGraph,
+    id: GraphId,
+    out: *mut *mut c_char,
+) -> BgStatus {
+    let (Some(graph), false) = (graph.as_ref(), out.is_null()) else {
+        return BgStatus::NullPointer;
+    };
+    let Some(value) = graph.0.nodes.get(&id) else {
+        return BgStatus::NodeNotFound;
+    };
+
+    match CString::new(value.as_str()) {
+        Ok(value) => {
+            *out = value.into_raw();
+            BgStatus::Ok
+        }
+        Err(_) => BgStatus::InvalidString,
+    }
+}
+
+/// Writes the sorted targets of the node's outgoing edges to `out`. The number of targets is
+/// written to `*len` even if `capacity` is too small.
+///
+/// # Safety
+///
+/// `graph` must be a valid handle, `len` must be valid for writes and `out` must be valid
+/// for `capacity` writes.
+#[no_mangle]
+pub unsafe extern "C" fn bg_graph_neighbours(
+    graph: *const BgGraph,
+    id: GraphId,
+    out: *mut GraphId,
+    capacity: usize,
+    len: *mut usize,
+) -> BgStatus {
+    let Some(graph) = graph.as_ref() else {
+        return BgStatus::NullPointer;
+    };
+    if !graph.0.nodes.contains_key(&id) {
+        return BgStatus::NodeNotFound;
+    }
+
+    let neighbours = graph.0.adjacency().remove(&id).unwrap_or_default();
+    write_ids(&neighbours, out, capacity, len)
+}
+
+/// Writes the IDs along a shortest path from `from` to `to` to `out`, like
+/// [`bg_graph_neighbours`].
+///
+/// # Safety
+///
+/// Same as [`bg_graph_neighbours`].
+#[no_mangle]
+pub unsafe extern "C" fn bg_graph_shortest_path(
+    graph: *const BgGraph,
+    from: GraphId,
+    to: GraphId,
+    out: *mut GraphId,
+    capacity: usize,
+    len: *mut usize,
+) -> BgStatus {
+    let Some(graph) = graph.as_ref() else {
+        return BgStatus::NullPointer;
+    };
+    if !graph.0.nodes.contains_key(&from) || !graph.0.nodes.contains_key(&to) {
+        return BgStatus::NodeNotFound;
+    }
+
+    match graph.0.shortest_path(from, to) {
+        Some(path) => write_ids(&path, out, capacity, len),
+        None => BgStatus::NoPath,
+    }
+}
+
+unsafe fn read_str<'a>(string: *const c_char) -> Result<&'a str, BgStatus> {
+    if string.is_null() {
+        return Err(BgStatus::NullPointer);
+    }
+
+    CStr::from_ptr(string)
+        .to_str()
+        .map_err(|_| BgStatus::InvalidString)
+}
+
+unsafe fn write_ids(
+    ids: &[GraphId],
+    out: *mut GraphId,
+    capacity: usize,
+    len: *mut usize,
+) -> BgStatus {
+    if len.is_null() || (out.is_null() && capacity > 0) {
+        return BgStatus::NullPointer;
+    }
+
+    *len = ids.len();
+    if ids.len() > capacity {
+        return BgStatus::BufferTooSmall;
+    }
+    if !ids.is_empty() {
+        slice::from_raw_parts_mut(out, ids.len()).copy_from_slice(ids);
+    }
+
+    BgStatus::Ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_graph() -> *mut BgGraph {
+        let mut graph = ptr::null_mut();
+        let status =
+            unsafe { bg_graph_parse(c"1 a\n2 b\n3 c\n#\n1 2\n2 3\n1 3".as_ptr(), &mut graph) };
+        assert_eq!(status, BgStatus::Ok, "Parse failed");
+
+        graph
+    }
+
+    #[test]
+    fn edit_and_count() {
+        let graph = bg_graph_new();
+        unsafe {
+            assert_eq!(bg_graph_add_node(graph, 1, c"a".as_ptr()), BgStatus::Ok);
+            assert_eq!(
+                bg_graph_add_node(graph, 1, c"b".as_ptr()),
+                BgStatus::NodeExists
+            );
+            assert_eq!(bg_graph_add_edge(graph, 1, 2), BgStatus::NodeNotFound);
+            assert_eq!(bg_graph_add_node(graph, 2, c"b".as_ptr()), BgStatus::Ok);
+            assert_eq!(bg_graph_add_edge(graph, 1, 2), BgStatus::Ok);
+            assert_eq!(bg_graph_edge_count(graph), 1, "Edges count mismatch");
+            assert_eq!(bg_graph_remove_node(graph, 2), BgStatus::Ok);
+            assert_eq!(bg_graph_remove_node(graph, 2), BgStatus::NodeNotFound);
+            assert_eq!(bg_graph_node_count(graph), 1, "Nodes count mismatch");
+            assert_eq!(bg_graph_edge_count(graph), 0, "Expected edge removal");
+            bg_graph_free(graph);
+        }
+    }
+
+    #[test]
+    fn query_into_buffers() {
+        let graph = get_test_graph();
+        let mut buffer = [0; 2];
+        let mut len = 0;
+        unsafe {
+            assert_eq!(
+                bg_graph_neighbours(graph, 1, buffer.as_mut_ptr(), 2, &mut len),
+                BgStatus::Ok
+            );
+            assert_eq!(buffer, [2, 3], "Neighbours mismatch");
+            assert_eq!(
+                bg_graph_shortest_path(graph, 2, 3, buffer.as_mut_ptr(), 1, &mut len),
+                BgStatus::BufferTooSmall
+            );
+            assert_eq!(len, 2, "Expected required length");
+            assert_eq!(
+                bg_graph_shortest_path(graph, 3, 1, buffer.as_mut_ptr(), 2, &mut len),
+                BgStatus::NoPath
+            );
+
+            let mut value = ptr::null_mut();
+            assert_eq!(bg_graph_node_value(graph, 2, &mut value), BgStatus::Ok);
+            assert_eq!(CStr::from_ptr(value), c"b", "Value mismatch");
+            bg_string_free(value);
+            bg_graph_free(graph);
+        }
+    }
+
+    #[test]
+    fn serialize_and_errors() {
+        let graph = get_test_graph();
+        unsafe {
+            let tgf = bg_graph_serialize(graph);
+            assert_eq!(
+                CStr::from_ptr(tgf).to_str().unwrap(),
+                (*graph).0.serialize(),
+                "TGF mismatch"
+            );
+            bg_string_free(tgf);
+            bg_graph_free(graph);
+
+            let mut parsed = ptr::null_mut();
+            assert_eq!(
+                bg_graph_parse(c"1 a".as_ptr(), &mut parsed),
+                BgStatus::ParseError
+            );
+            assert!(parsed.is_null(), "Expected no graph");
+            assert_eq!(
+                bg_graph_add_node(ptr::null_mut(), 1, c"a".as_ptr()),
+                BgStatus::NullPointer
+            );
+            assert!(bg_graph_serialize(ptr::null()).is_null(), "Expected NULL");
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt;
+
+use super::{collections::HashMap, Graph, GraphId};
+
+/// Predicates that hide nodes and edges from a search without copying the graph, e.g. to
+/// avoid nodes under maintenance.
+///
+/// A hidden node is never visited, and neither is any edge to or from it. Both predicates
+/// are optional, so `SearchFilter::new()` hides nothing.
+pub struct SearchFilter<'a, T> {
+    node: Option<NodePredicate<'a, T>>,
+    edge: Option<EdgePredicate<'a>>,
+}
+
+type NodePredicate<'a, T> = Box<dyn Fn(GraphId, &T) -> bool + 'a>;
+type EdgePredicate<'a> = Box<dyn Fn(GraphId, GraphId) -> bool + 'a>;
+
+impl<'a, T> SearchFilter<'a, T> {
+    pub fn new() -> Self {
+        Self {
+            node: None,
+            edge: None,
+        }
+    }
+
+    /// Keeps only the nodes for which `keep` returns `true`, given their ID and value.
+    pub fn nodes(mut self, keep: impl Fn(GraphId, &T) -> bool + 'a) -> Self {
+        self.node = Some(Box::new(keep));
+        self
+    }
+
+    /// Keeps only the edges for which `keep` returns `true`, given their endpoints.
+    pub fn edges(mut self, keep: impl Fn(GraphId, GraphId) -> bool + 'a) -> Self {
+        self.edge = Some(Box::new(keep));
+        self
+    }
+
+    /// Returns whether the node exists in `graph` and isn't hidden.
+    pub(crate) fn allows_node(&self, graph: &Graph<T>, id: GraphId) -> bool {
+        graph
+            .nodes
+            .get(&id)
+            .is_some_and(|value| self.node.as_ref().is_none_or(|keep| keep(id, value)))
+    }
+
+    /// Returns whether the edge and both of its endpoints aren't hidden. Doesn't check that
+    /// the edge exists.
+    pub(crate) fn allows_edge(&self, graph: &Graph<T>, from: GraphId, to: GraphId) -> bool {
+        self.edge.as_ref().is_none_or(|keep| keep(from, to))
+            && self.allows_node(graph, from)
+            && self.allows_node(graph, to)
+    }
+}
+
+impl<T> Default for SearchFilter<'_, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for SearchFilter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SearchFilter")
+            .field("node", &self.node.is_some())
+            .field("edge", &self.edge.is_some())
+            .finish()
+    }
+}
+
+impl<T> Graph<T> {
+    /// Like [`adjacency`](Self::adjacency), but without the nodes and edges hidden by
+    /// `filter`.
+    pub(crate) fn filtered_adjacency(
+        &self,
+        filter: &SearchFilter<'_, T>,
+    ) -> HashMap<GraphId, Vec<GraphId>> {
+        let mut adjacency = self.adjacency();
+        if filter.node.is_none() && filter.edge.is_none() {
+            return adjacency;
+        }
+
+        adjacency.retain(|from, neighbours| {
+            neighbours.retain(|to| filter.allows_edge(self, *from, *to));
+            !neighbours.is_empty()
+        });
+
+        adjacency
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Path;
+
+    fn get_test_graph() -> Graph<&'static str> {
+        Graph::from((
+            [(1, "up"), (2, "maintenance"), (3, "up"), (4, "up")],
+            [(1, 2), (2, 4), (1, 3), (3, 4), (4, 1)],
+        ))
+    }
+
+    #[test]
+    fn filter_hides_nodes_and_edges() {
+        let graph = get_test_graph();
+        let filter = SearchFilter::new()
+            .nodes(|_, status: &&str| *status != "maintenance")
+            .edges(|from, to| (from, to) != (4, 1));
+        let adjacency = graph.filtered_adjacency(&filter);
+        assert_eq!(
+            adjacency,
+            HashMap::from([(1, vec![3]), (3, vec![4])]),
+            "Adjacency mismatch"
+        );
+        assert!(
+            !filter.allows_node(&graph, 5),
+            "Expected missing node to be hidden"
+        );
+    }
+
+    #[test]
+    fn filtered_searches_avoid_hidden_nodes() {
+        let graph = get_test_graph();
+        let filter = SearchFilter::new().nodes(|id, _| id != 3);
+        assert_eq!(
+            graph.bfs_order_filtered(1, &filter),
+            vec![1, 2, 4],
+            "BFS order mismatch"
+        );
+        assert!(
+            graph.dfs_order_filtered(3, &filter).is_empty(),
+            "Expected hidden source to be skipped"
+        );
+        assert_eq!(
+            graph.shortest_path_filtered(1, 4, &filter),
+            Some(Path::with_cost(vec![1, 2, 4], 2)),
+            "Path mismatch"
+        );
+        assert_eq!(
+            graph.shortest_path_by_filtered(3, 1, &SearchFilter::new(), |_, _| Some(1)),
+            Some(Path::with_cost(vec![3, 4, 1], 2)),
+            "Cost path mismatch"
+        );
+        assert_eq!(
+            graph.shortest_path_by_filtered(1, 4, &filter.edges(|_, to| to != 2), |_, _| Some(1)),
+            None,
+            "Expected no path"
+        );
+    }
+}
@@ -0,0 +1,365 @@
+use rand::{seq::SliceRandom, Rng};
+
+use super::{Graph, GraphId};
+
+/// Builds a Barabási–Albert preferential attachment graph with `n` nodes, where every new node
+/// attaches to `m` distinct existing nodes chosen proportionally to their degree.
+///
+/// The model is undirected, so every edge is stored in both directions.
+pub fn barabasi_albert<T, R>(
+    n: usize,
+    m: usize,
+    rng: &mut R,
+    value: impl FnMut(GraphId) -> T,
+) -> Graph<T>
+where
+    R: Rng + ?Sized,
+{
+    assert!(m >= 1 && m < n, "Expected 1 <= m < n, got m = {m}, n = {n}");
+
+    let mut graph = with_nodes(n, value);
+    let mut targets: Vec<GraphId> = (0..m as GraphId).collect();
+    let mut repeated: Vec<GraphId> = Vec::with_capacity(2 * m * n);
+    for source in m as GraphId..n as GraphId {
+        for &target in &targets {
+            add_undirected_edge(&mut graph, source, target);
+        }
+        repeated.extend(&targets);
+        repeated.extend(std::iter::repeat_n(source, m));
+
+        targets.clear();
+        while targets.len() < m {
+            let candidate = *repeated.choose(rng).expect("not empty");
+            if !targets.contains(&candidate) {
+                targets.push(candidate);
+            }
+        }
+    }
+
+    graph
+}
+
+/// Builds a Watts–Strogatz small-world graph: a ring of `n` nodes, each joined to its `k`
+/// nearest neighbours (`k / 2` on each side), with every ring edge rewired to a random node
+/// with probability `beta`.
+///
+/// The model is undirected, so every edge is stored in both directions.
+pub fn watts_strogatz<T, R>(
+    n: usize,
+    k: usize,
+    beta: f64,
+    rng: &mut R,
+    value: impl FnMut(GraphId) -> T,
+) -> Graph<T>
+where
+    R: Rng + ?Sized,
+{
+    assert!(k < n, "Expected k < n, got k = {k}, n = {n}");
+    assert!(
+        (0.0..=1.0).contains(&beta),
+        "Expected beta in [0, 1], got {beta}"
+    );
+
+    let mut graph = with_nodes(n, value);
+    let n = n as GraphId;
+    for offset in 1..=(k / 2) as GraphId {
+        for source in 0..n {
+            add_undirected_edge(&mut graph, source, (source + offset) % n);
+        }
+    }
+
+    for offset in 1..=(k / 2) as GraphId {
+        for source in 0..n {
+            if !rng.gen_bool(beta) {
+                continue;
+            }
+
+            let old_target = (source + offset) % n;
+            let degree = graph
+                .edges
+                .iter()
+                .filter(|edge| edge.from == source)
+                .count();
+            if degree as GraphId >= n - 1 {
+                continue;
+            }
+
+            let new_target = loop {
+                let candidate = rng.gen_range(0..n);
+                if candidate != source && !graph.edges.contains(&edge(source, candidate)) {
+                    break candidate;
+                }
+            };
+            graph.delete_edge(source, old_target);
+            graph.delete_edge(old_target, source);
+            add_undirected_edge(&mut graph, source, new_target);
+        }
+    }
+
+    graph
+}
+
+/// Builds a complete graph on `n` nodes.
+pub fn complete<T>(n: usize, value: impl FnMut(GraphId) -> T) -> Graph<T> {
+    let mut graph = with_nodes(n, value);
+    for a in 0..n as GraphId {
+        for b in a + 1..n as GraphId {
+            add_undirected_edge(&mut graph, a, b);
+        }
+    }
+
+    graph
+}
+
+/// Builds a cycle `0 - 1 - ... - (n - 1) - 0`.
+pub fn cycle<T>(n: usize, value: impl FnMut(GraphId) -> T) -> Graph<T> {
+    let mut graph = path(n, value);
+    if n > 2 {
+        add_undirected_edge(&mut graph, n as GraphId - 1, 0);
+    }
+
+    graph
+}
+
+/// Builds a path `0 - 1 - ... - (n - 1)`.
+pub fn path<T>(n: usize, value: impl FnMut(GraphId) -> T) -> Graph<T> {
+    let mut graph = with_nodes(n, value);
+    for id in 1..n as GraphId {
+        add_undirected_edge(&mut graph, id - 1, id);
+    }
+
+    graph
+}
+
+/// Builds a star of `n` nodes where node `0` is the centre.
+pub fn star<T>(n: usize, value: impl FnMut(GraphId) -> T) -> Graph<T> {
+    let mut graph = with_nodes(n, value);
+    for id in 1..n as GraphId {
+        add_undirected_edge(&mut graph, 0, id);
+    }
+
+    graph
+}
+
+/// Builds a `rows` x `cols` grid where the node at (`row`, `col`) has ID `row * cols + col`.
+pub fn grid<T>(rows: usize, cols: usize, value: impl FnMut(GraphId) -> T) -> Graph<T> {
+    lattice(rows, cols, false, value)
+}
+
+/// Builds a `rows` x `cols` grid whose borders wrap around, with the same IDs as [`grid`].
+pub fn torus<T>(rows: usize, cols: usize, value: impl FnMut(GraphId) -> T) -> Graph<T> {
+    lattice(rows, cols, true, value)
+}
+
+/// Builds a tree of the given `height` where every inner node has `branching` children.
+/// The root has ID `0` and children are numbered level by level.
+pub fn balanced_tree<T>(
+    branching: usize,
+    height: usize,
+    value: impl FnMut(GraphId) -> T,
+) -> Graph<T> {
+    let n = (0..=height as u32).map(|level| branching.pow(level)).sum();
+    let mut graph = with_nodes(n, value);
+    for id in 1..n as GraphId {
+        add_undirected_edge(&mut graph, (id - 1) / branching as GraphId, id);
+    }
+
+    graph
+}
+
+/// Builds a complete bipartite graph with left nodes `0..left` and right nodes
+/// `left..left + right`.
+pub fn complete_bipartite<T>(
+    left: usize,
+    right: usize,
+    value: impl FnMut(GraphId) -> T,
+) -> Graph<T> {
+    let mut graph = with_nodes(left + right, value);
+    for a in 0..left as GraphId {
+        for b in left as GraphId..(left + right) as GraphId {
+            add_undirected_edge(&mut graph, a, b);
+        }
+    }
+
+    graph
+}
+
+fn lattice<T>(
+    rows: usize,
+    cols: usize,
+    periodic: bool,
+    value: impl FnMut(GraphId) -> T,
+) -> Graph<T> {
+    let mut graph = with_nodes(rows * cols, value);
+    let id = |row: usize, col: usize| (row * cols + col) as GraphId;
+    for row in 0..rows {
+        for col in 0..cols {
+            if col + 1 < cols {
+                add_undirected_edge(&mut graph, id(row, col), id(row, col + 1));
+            } else if periodic && cols > 2 {
+                add_undirected_edge(&mut graph, id(row, col), id(row, 0));
+            }
+            if row + 1 < rows {
+                add_undirected_edge(&mut graph, id(row, col), id(row + 1, col));
+            } else if periodic && rows > 2 {
+                add_undirected_edge(&mut graph, id(row, col), id(0, col));
+            }
+        }
+    }
+
+    graph
+}
+
+pub(crate) fn with_nodes<T>(n: usize, mut value: impl FnMut(GraphId) -> T) -> Graph<T> {
+    let mut graph = Graph::new();
+    for id in 0..n as GraphId {
+        graph.add_node(id, value(id));
+    }
+
+    graph
+}
+
+pub(crate) fn add_undirected_edge<T>(graph: &mut Graph<T>, a: GraphId, b: GraphId) {
+    graph.add_edge(a, b);
+    graph.add_edge(b, a);
+}
+
+fn edge(from: GraphId, to: GraphId) -> super::Edge {
+    super::Edge { from, to }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn barabasi_albert_counts() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let graph = barabasi_albert(50, 3, &mut rng, |_| ());
+        assert_eq!(graph.nodes.len(), 50, "Nodes count mismatch");
+        assert_eq!(graph.edges.len(), 2 * 3 * 47, "Edges count mismatch");
+    }
+
+    #[test]
+    fn barabasi_albert_no_self_loops() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let graph = barabasi_albert(100, 2, &mut rng, |_| ());
+        assert!(
+            graph.edges.iter().all(|edge| edge.from != edge.to),
+            "Expected no self loops"
+        );
+    }
+
+    #[test]
+    fn barabasi_albert_deterministic_with_seed() {
+        let first = barabasi_albert(30, 2, &mut StdRng::seed_from_u64(1), |id| id);
+        let second = barabasi_albert(30, 2, &mut StdRng::seed_from_u64(1), |id| id);
+        assert_eq!(
+            first.edges, second.edges,
+            "Expected same edges for same seed"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn barabasi_albert_invalid_m() {
+        barabasi_albert(3, 3, &mut StdRng::seed_from_u64(0), |_| ());
+    }
+
+    #[test]
+    fn watts_strogatz_lattice() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let graph = watts_strogatz(10, 4, 0.0, &mut rng, |_| ());
+        assert_eq!(graph.edges.len(), 40, "Edges count mismatch");
+        let node = graph.get_node(0).unwrap();
+        let mut neighbours = node.neighbour_ids().to_vec();
+        neighbours.sort();
+        assert_eq!(neighbours, vec![1, 2, 8, 9], "Ring neighbours mismatch");
+    }
+
+    #[test]
+    fn watts_strogatz_rewired_keeps_edge_count() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let graph = watts_strogatz(30, 4, 0.5, &mut rng, |_| ());
+        assert_eq!(graph.edges.len(), 120, "Edges count mismatch");
+        assert!(
+            graph
+                .edges
+                .iter()
+                .all(|e| graph.edges.contains(&edge(e.to, e.from))),
+            "Expected every edge to have a reverse edge"
+        );
+    }
+
+    #[test]
+    fn complete_counts() {
+        let graph = complete(5, |_| ());
+        assert_eq!(graph.nodes.len(), 5, "Nodes count mismatch");
+        assert_eq!(graph.edges.len(), 20, "Edges count mismatch");
+    }
+
+    #[test]
+    fn cycle_and_path_counts() {
+        assert_eq!(
+            cycle(6, |_| ()).edges.len(),
+            12,
+            "Cycle edges count mismatch"
+        );
+        assert_eq!(path(6, |_| ()).edges.len(), 10, "Path edges count mismatch");
+        assert_eq!(
+            cycle(2, |_| ()).edges.len(),
+            2,
+            "Expected no duplicate edges"
+        );
+    }
+
+    #[test]
+    fn star_neighbours() {
+        let graph = star(4, |_| ());
+        let mut neighbours = graph.get_node(0).unwrap().neighbour_ids().to_vec();
+        neighbours.sort();
+        assert_eq!(neighbours, vec![1, 2, 3], "Centre neighbours mismatch");
+        assert_eq!(
+            graph.get_node(2).unwrap().neighbour_ids(),
+            &[0],
+            "Leaf neighbours mismatch"
+        );
+    }
+
+    #[test]
+    fn grid_and_torus_counts() {
+        assert_eq!(
+            grid(3, 4, |_| ()).edges.len(),
+            2 * 17,
+            "Grid edges count mismatch"
+        );
+        assert_eq!(
+            torus(3, 4, |_| ()).edges.len(),
+            2 * 24,
+            "Torus edges count mismatch"
+        );
+    }
+
+    #[test]
+    fn balanced_tree_structure() {
+        let graph = balanced_tree(2, 3, |id| id);
+        assert_eq!(graph.nodes.len(), 15, "Nodes count mismatch");
+        assert_eq!(graph.edges.len(), 28, "Edges count mismatch");
+        let mut neighbours = graph.get_node(1).unwrap().neighbour_ids().to_vec();
+        neighbours.sort();
+        assert_eq!(neighbours, vec![0, 3, 4], "Inner node neighbours mismatch");
+    }
+
+    #[test]
+    fn complete_bipartite_counts() {
+        let graph = complete_bipartite(2, 3, |_| ());
+        assert_eq!(graph.nodes.len(), 5, "Nodes count mismatch");
+        assert_eq!(graph.edges.len(), 12, "Edges count mismatch");
+        assert!(
+            !graph.edges.contains(&edge(0, 1)),
+            "Expected no edges inside a partition"
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use core::{fmt::Display, str::FromStr};
+#[cfg(feature = "std")]
+use std::{fs, path::Path};
+
+#[cfg(feature = "std")]
+use anyhow::{anyhow, Context};
+
+use super::{Edge, Graph, GraphId};
+
+/// A named collection of graphs over one node ID space, such as daily snapshots of the same
+/// network, where node 7 is the same node in every graph.
+///
+/// Graphs are kept in name order, so naming snapshots by date keeps them in time order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphSet<T> {
+    graphs: BTreeMap<String, Graph<T>>,
+}
+
+impl<T> GraphSet<T> {
+    pub fn new() -> Self {
+        Self {
+            graphs: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a graph under `name`, returning the graph it replaces.
+    pub fn insert(&mut self, name: impl Into<String>, graph: Graph<T>) -> Option<Graph<T>> {
+        self.graphs.insert(name.into(), graph)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Graph<T>> {
+        self.graphs.remove(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Graph<T>> {
+        self.graphs.get(name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Graph<T>> {
+        self.graphs.get_mut(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.graphs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.graphs.is_empty()
+    }
+
+    /// Returns the names of the graphs, sorted.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.graphs.keys().map(String::as_str)
+    }
+
+    /// Iterates over the graphs with their names, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Graph<T>)> {
+        self.graphs
+            .iter()
+            .map(|(name, graph)| (name.as_str(), graph))
+    }
+
+    /// Returns the names of the graphs that contain node `id`, sorted.
+    pub fn graphs_with_node(&self, id: GraphId) -> Vec<&str> {
+        self.iter()
+            .filter(|(_, graph)| graph.nodes.contains_key(&id))
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// Returns the names of the graphs that contain the edge from `from` to `to`, sorted.
+    pub fn graphs_with_edge(&self, from: GraphId, to: GraphId) -> Vec<&str> {
+        let edge = Edge { from, to };
+        self.iter()
+            .filter(|(_, graph)| graph.edges.contains(&edge))
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// Returns every edge of any graph with the number of graphs that contain it, sorted by
+    /// edge, e.g. to tell stable links from ones seen on a single day.
+    pub fn edge_counts(&self) -> BTreeMap<(GraphId, GraphId), usize> {
+        let mut counts = BTreeMap::new();
+        for graph in self.graphs.values() {
+            for edge in &graph.edges {
+                *counts.entry((edge.from, edge.to)).or_default() += 1;
+            }
+        }
+
+        counts
+    }
+}
+
+impl<T> Default for GraphSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: FromStr> GraphSet<T> {
+    /// Reads every `.tgf` file in `directory` as in [`Graph::from_reader`], naming every graph
+    /// after its file without the extension. Other files and subdirectories are ignored.
+    pub fn load_dir(directory: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let directory = directory.as_ref();
+        debug_span!("graph_set_load", path = %directory.display());
+        let entries = fs::read_dir(directory)
+            .with_context(|| format!("Failed to read directory {}", directory.display()))?;
+        let mut set = Self::new();
+        for entry in entries {
+            let path = entry?.path();
+            if !path.is_file() || path.extension().is_none_or(|extension| extension != "tgf") {
+                continue;
+            }
+            let name = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .ok_or_else(|| anyhow!("File name {} isn't valid UTF-8", path.display()))?;
+            let file = fs::File::open(&path)
+                .with_context(|| format!("Failed to open {}", path.display()))?;
+            let graph = Graph::from_reader(file)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            set.insert(name, graph);
+        }
+
+        Ok(set)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Display> GraphSet<T> {
+    /// Writes every graph to `directory` as `<name>.tgf`, in the format of
+    /// [`Graph::serialize`], creating the directory if needed. Existing files of the same
+    /// names are replaced.
+    ///
+    /// Fails before writing anything if a name can't be used as a file name.
+    pub fn save_dir(&self, directory: impl AsRef<Path>) -> anyhow::Result<()> {
+        let directory = directory.as_ref();
+        debug_span!("graph_set_save", path = %directory.display());
+        if let Some(name) = self.names().find(|name| {
+            name.is_empty() || *name == "." || *name == ".." || name.contains(['/', '\\'])
+        }) {
+            return Err(anyhow!("{name:?} can't be used as a file name"));
+        }
+
+        fs::create_dir_all(directory)
+            .with_context(|| format!("Failed to create directory {}", directory.display()))?;
+        for (name, graph) in self.iter() {
+            let path = directory.join(format!("{name}.tgf"));
+            fs::write(&path, graph.serialize())
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two days of a network where the link between 1 and 2 is seen on both.
+    fn get_test_set() -> GraphSet<String> {
+        let mut set = GraphSet::new();
+        set.insert(
+            "2024-01-01",
+            Graph::from((
+                [(1, "a".to_string()), (2, "b".to_string())],
+                [(1, 2), (2, 1)],
+            )),
+        );
+        set.insert(
+            "2024-01-02",
+            Graph::from((
+                [
+                    (1, "a".to_string()),
+                    (2, "b".to_string()),
+                    (3, "c".to_string()),
+                ],
+                [(1, 2), (2, 3)],
+            )),
+        );
+
+        set
+    }
+
+    #[test]
+    fn cross_graph_queries() {
+        let set = get_test_set();
+        assert_eq!(
+            set.graphs_with_edge(1, 2),
+            vec!["2024-01-01", "2024-01-02"],
+            "Graphs mismatch"
+        );
+        assert_eq!(
+            set.graphs_with_edge(2, 1),
+            vec!["2024-01-01"],
+            "Graphs mismatch"
+        );
+        assert_eq!(
+            set.graphs_with_node(3),
+            vec!["2024-01-02"],
+            "Graphs mismatch"
+        );
+        assert_eq!(
+            set.edge_counts(),
+            BTreeMap::from([((1, 2), 2), ((2, 1), 1), ((2, 3), 1)]),
+            "Counts mismatch"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn save_and_load_dir() {
+        let mut set = get_test_set();
+        set.insert("2024-01-03", Graph::new());
+        let directory = std::env::temp_dir().join(format!("bgraph-set-{}", std::process::id()));
+        set.save_dir(&directory).unwrap();
+        fs::write(directory.join("notes.txt"), "not a graph").unwrap();
+        let loaded = GraphSet::load_dir(&directory);
+        fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(loaded.unwrap(), set, "Set mismatch");
+
+        let mut set = GraphSet::new();
+        set.insert("../escape", Graph::<String>::new());
+        assert!(
+            set.save_dir(&directory).is_err(),
+            "Expected path in name to fail"
+        );
+        assert!(!directory.exists(), "Expected nothing written");
+    }
+}
@@ -0,0 +1,269 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::{
+    any,
+    error::Error,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Context};
+
+/// Marks the first line of TGF as a [`Header`].
+const MAGIC: &str = "%bgraph-tgf";
+
+/// Metadata that can precede TGF, written when [`SerializeOptions::header`] is set.
+///
+/// The header is a single line such as
+/// `%bgraph-tgf version=1 directed=true weighted=false type=alloc::string::String`. Parsers
+/// check it against what they are reading into, and text without a header is read as
+/// before. The node type is informational only, so text can be read into any type that
+/// parses its values.
+///
+/// [`SerializeOptions::header`]: crate::SerializeOptions::header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Header {
+    pub version: u32,
+    /// Whether every edge line is a single directed edge. Edge lines of undirected text
+    /// are read as edges in both directions.
+    pub directed: bool,
+    /// Whether edge lines carry a weight after the target.
+    pub weighted: bool,
+    /// CRC32 checksum of everything after the header line. Parsing fails with an
+    /// [`IntegrityError`] if the text doesn't match it.
+    pub checksum: Option<u32>,
+    /// The type name of the node values when written, from [`std::any::type_name`]. It is
+    /// only a hint for readers: type names aren't stable across compiler versions, and text
+    /// written from one type can often be read into another, such as `&str` into `String`.
+    pub node_type: Option<String>,
+}
+
+/// The text after a [`Header`] doesn't match its checksum, e.g. because the file was
+/// truncated. Parsers return it inside their [`anyhow::Error`], so it can be told apart from
+/// other errors with `downcast_ref`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntegrityError {
+    pub expected: u32,
+    pub actual: u32,
+}
+
+impl Header {
+    /// The format version written by this version of the crate.
+    pub const VERSION: u32 = 1;
+
+    /// Returns the header written for a graph with node values of type `T`.
+    pub fn new<T>(weighted: bool) -> Self {
+        Self {
+            version: Self::VERSION,
+            directed: true,
+            weighted,
+            checksum: None,
+            node_type: Some(any::type_name::<T>().to_string()),
+        }
+    }
+
+    /// Rewrites TGF from an older version of the format in the current one. Text without a
+    /// header gets one, flagged as weighted if any edge line has a weight.
+    pub fn migrate(s: &str) -> anyhow::Result<String> {
+        let (header, body) = split_header(s)?;
+        let header = match header {
+            Some(header) => Header {
+                version: Self::VERSION,
+                ..header
+            },
+            None => {
+                let weighted = body
+                    .lines()
+                    .skip_while(|line| line.trim() != "#")
+                    .skip(1)
+                    .any(|line| line.split_whitespace().count() > 2);
+                Header {
+                    version: Self::VERSION,
+                    directed: true,
+                    weighted,
+                    checksum: None,
+                    node_type: None,
+                }
+            }
+        };
+
+        Ok(format!("{header}\n{body}"))
+    }
+
+    /// Fails if text with this header can't be read into a graph, which keeps weights only if
+    /// `weighted` is set. The node type isn't checked, see [`node_type`](Self::node_type).
+    pub(crate) fn check(&self, weighted: bool) -> anyhow::Result<()> {
+        if self.weighted && !weighted {
+            return Err(anyhow!(
+                "Edges are weighted, read them into a WeightedGraph to keep the weights"
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Header {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{MAGIC} version={} directed={} weighted={}",
+            self.version, self.directed, self.weighted
+        )?;
+        if let Some(checksum) = self.checksum {
+            write!(f, " checksum={checksum:08x}")?;
+        }
+        // The type name may contain spaces, so it goes last and takes the rest of the line.
+        if let Some(node_type) = &self.node_type {
+            write!(f, " type={node_type}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Header {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields = s
+            .trim_end()
+            .strip_prefix(MAGIC)
+            .ok_or_else(|| anyhow!("Expected header to start with {MAGIC}"))?;
+        let (fields, node_type) = match fields.split_once(" type=") {
+            Some((fields, node_type)) => (fields, Some(node_type.to_string())),
+            None => (fields, None),
+        };
+
+        let mut version = None;
+        let mut directed = true;
+        let mut weighted = false;
+        let mut checksum = None;
+        for field in fields.split_whitespace() {
+            let (key, value) = field
+                .split_once('=')
+                .ok_or_else(|| anyhow!("Expected key=value in header, got {field:?}"))?;
+            match key {
+                "version" => version = Some(value.parse().context("Invalid format version")?),
+                "directed" => directed = value.parse().context("Invalid directed flag")?,
+                "weighted" => weighted = value.parse().context("Invalid weighted flag")?,
+                "checksum" => {
+                    checksum = Some(u32::from_str_radix(value, 16).context("Invalid checksum")?)
+                }
+                _ => return Err(anyhow!("Unknown header field {key:?}")),
+            }
+        }
+        let version = version.ok_or_else(|| anyhow!("Missing format version in header"))?;
+        if version > Self::VERSION {
+            return Err(anyhow!(
+                "Unsupported format version {version}, expected at most {}",
+                Self::VERSION
+            ));
+        }
+
+        Ok(Self {
+            version,
+            directed,
+            weighted,
+            checksum,
+            node_type,
+        })
+    }
+}
+
+impl fmt::Display for IntegrityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Checksum mismatch: expected {:08x}, got {:08x}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl Error for IntegrityError {}
+
+/// Splits the header line off TGF text, if there is one, and verifies the checksum of the
+/// rest.
+pub(crate) fn split_header(s: &str) -> anyhow::Result<(Option<Header>, &str)> {
+    if !s.starts_with(MAGIC) {
+        return Ok((None, s));
+    }
+    let (line, body) = s.split_once('\n').unwrap_or((s, ""));
+    let header = line.parse::<Header>()?;
+    if let Some(expected) = header.checksum {
+        let actual = crc32(body.as_bytes());
+        if actual != expected {
+            return Err(IntegrityError { expected, actual }.into());
+        }
+    }
+
+    Ok((Some(header), body))
+}
+
+/// Computes the CRC-32 checksum used by zlib and PNG.
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_round_trip() {
+        let header = Header::new::<(u8, u8)>(true);
+        assert_eq!(
+            header.to_string(),
+            "%bgraph-tgf version=1 directed=true weighted=true type=(u8, u8)",
+            "Header mismatch"
+        );
+        assert_eq!(header.to_string().parse::<Header>().ok(), Some(header));
+    }
+
+    #[test]
+    fn header_rejects_newer_version() {
+        let error = "%bgraph-tgf version=2".parse::<Header>().unwrap_err();
+        assert!(
+            error.to_string().contains("Unsupported format version 2"),
+            "Unexpected error: {error}"
+        );
+        assert!(
+            "%bgraph-tgf version=1 colour=red"
+                .parse::<Header>()
+                .is_err(),
+            "Expected unknown field to fail"
+        );
+    }
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926, "Checksum mismatch");
+        assert_eq!(crc32(b""), 0, "Expected empty checksum to be 0");
+    }
+
+    #[test]
+    fn migrate_headerless() {
+        assert_eq!(
+            Header::migrate("1 a\n2 b\n#\n1 2 0.5\n").unwrap(),
+            "%bgraph-tgf version=1 directed=true weighted=true\n1 a\n2 b\n#\n1 2 0.5\n",
+            "Migrated text mismatch"
+        );
+        let current = Header::migrate("1 a\n#\n").unwrap();
+        assert_eq!(
+            Header::migrate(&current).unwrap(),
+            current,
+            "Expected current text to be unchanged"
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+use super::{
+    collections::{HashMap, HashSet},
+    Graph, GraphError, GraphId, WeightedGraph,
+};
+
+/// Wraps a graph and nests its nodes under group nodes, which can be collapsed to hide their
+/// contents, e.g. to explore a large architecture graph one level at a time.
+///
+/// Group nodes are ordinary nodes of the graph and may have edges of their own. Collapsing a
+/// group folds every node nested under it, at any depth, into the group in the
+/// [`view`](Self::view). Expanding it again restores the previous state of the groups nested
+/// under it.
+#[derive(Debug, Clone)]
+pub struct HierarchicalGraph<T> {
+    graph: Graph<T>,
+    parent: HashMap<GraphId, GraphId>,
+    collapsed: HashSet<GraphId>,
+}
+
+impl<T> HierarchicalGraph<T> {
+    /// Wraps `graph` with every node at the top level.
+    pub fn new(graph: Graph<T>) -> Self {
+        Self {
+            graph,
+            parent: HashMap::new(),
+            collapsed: HashSet::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> Graph<T> {
+        self.graph
+    }
+
+    pub fn add_node(&mut self, id: GraphId, value: T) {
+        self.graph.add_node(id, value);
+    }
+
+    /// Deletes the node and its edges. Its children move up to its parent.
+    pub fn delete_node(&mut self, id: GraphId) {
+        self.graph.delete_node(id);
+        self.collapsed.remove(&id);
+        let parent = self.parent.remove(&id);
+        let children = self.children(id);
+        for child in children {
+            match parent {
+                Some(parent) => self.parent.insert(child, parent),
+                None => self.parent.remove(&child),
+            };
+        }
+    }
+
+    pub fn add_edge(&mut self, from: GraphId, to: GraphId) {
+        self.graph.add_edge(from, to);
+    }
+
+    pub fn delete_edge(&mut self, from: GraphId, to: GraphId) {
+        self.graph.delete_edge(from, to);
+    }
+
+    /// Nests `child` directly under `group`, moving it out of its previous group.
+    ///
+    /// Fails if either node doesn't exist, or with [`GraphError::NestingCycle`] if `group`
+    /// is `child` or nested under it.
+    pub fn set_parent(&mut self, child: GraphId, group: GraphId) -> Result<(), GraphError> {
+        for id in [child, group] {
+            if !self.graph.nodes.contains_key(&id) {
+                return Err(GraphError::NodeNotFound(id));
+            }
+        }
+        if self.ancestors(group).any(|ancestor| ancestor == child) || group == child {
+            return Err(GraphError::NestingCycle(child));
+        }
+
+        self.parent.insert(child, group);
+
+        Ok(())
+    }
+
+    /// Moves `child` back to the top level, returning its previous group.
+    pub fn remove_parent(&mut self, child: GraphId) -> Option<GraphId> {
+        self.parent.remove(&child)
+    }
+
+    /// Returns the group `id` is directly nested under, if any.
+    pub fn parent(&self, id: GraphId) -> Option<GraphId> {
+        self.parent.get(&id).copied()
+    }
+
+    /// Returns the nodes directly nested under `group`, sorted.
+    pub fn children(&self, group: GraphId) -> Vec<GraphId> {
+        let mut children = self
+            .parent
+            .iter()
+            .filter_map(|(child, parent)| (*parent == group).then_some(*child))
+            .collect::<Vec<_>>();
+        children.sort_unstable();
+
+        children
+    }
+
+    /// Hides everything nested under `group` in the view. Returns `false` if the node doesn't
+    /// exist or is already collapsed.
+    pub fn collapse(&mut self, group: GraphId) -> bool {
+        self.graph.nodes.contains_key(&group) && self.collapsed.insert(group)
+    }
+
+    /// Shows the nodes directly nested under `group` in the view again. Returns `false` if it
+    /// wasn't collapsed.
+    pub fn expand(&mut self, group: GraphId) -> bool {
+        self.collapsed.remove(&group)
+    }
+
+    pub fn is_collapsed(&self, group: GraphId) -> bool {
+        self.collapsed.contains(&group)
+    }
+
+    /// Returns the node that stands for `id` in the view: its outermost collapsed ancestor,
+    /// or itself if there is none. `None` if the node doesn't exist.
+    pub fn representative(&self, id: GraphId) -> Option<GraphId> {
+        if !self.graph.nodes.contains_key(&id) {
+            return None;
+        }
+
+        let outermost = self
+            .ancestors(id)
+            .filter(|ancestor| self.collapsed.contains(ancestor))
+            .last();
+
+        Some(outermost.unwrap_or(id))
+    }
+
+    /// Returns the visible graph: every node that is its own
+    /// [`representative`](Self::representative), holding the sorted IDs of the nodes it
+    /// stands for, itself included. Edges are aggregated as in [`Graph::quotient`], so every
+    /// visible edge is weighted by the number of edges it stands for, and edges within a
+    /// collapsed group become a self-loop on it.
+    pub fn view(&self) -> WeightedGraph<Vec<GraphId>> {
+        self.graph.quotient_by(
+            |id| self.representative(id).expect("node exists"),
+            |_, _| 1.0,
+        )
+    }
+
+    /// Returns the groups `id` is nested under, innermost first.
+    fn ancestors(&self, id: GraphId) -> impl Iterator<Item = GraphId> + '_ {
+        core::iter::successors(self.parent(id), |group| self.parent(*group))
+    }
+}
+
+impl<T> Deref for HierarchicalGraph<T> {
+    type Target = Graph<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Services 11 and 12 in module 10, 21 in module 20, and both modules in system 1.
+    fn get_test_graph() -> HierarchicalGraph<&'static str> {
+        let graph = Graph::from((
+            [
+                (1, "system"),
+                (10, "api"),
+                (11, "auth"),
+                (12, "users"),
+                (20, "storage"),
+                (21, "db"),
+                (30, "client"),
+            ],
+            [(11, 12), (11, 21), (12, 21), (30, 11)],
+        ));
+        let mut graph = HierarchicalGraph::new(graph);
+        for (child, group) in [(11, 10), (12, 10), (21, 20), (10, 1), (20, 1)] {
+            graph.set_parent(child, group).unwrap();
+        }
+
+        graph
+    }
+
+    #[test]
+    fn collapse_aggregates_edges() {
+        let mut graph = get_test_graph();
+        assert!(graph.collapse(10), "Expected group to collapse");
+        let view = graph.view();
+        assert_eq!(view[10], vec![10, 11, 12], "Members mismatch");
+        assert_eq!(
+            view.weighted_edges(),
+            vec![(10, 10, 1.0), (10, 21, 2.0), (30, 10, 1.0)],
+            "Edges mismatch"
+        );
+
+        assert!(graph.collapse(1), "Expected group to collapse");
+        assert_eq!(graph.representative(21), Some(1), "Representative mismatch");
+        assert_eq!(
+            graph.view().weighted_edges(),
+            vec![(1, 1, 3.0), (30, 1, 1.0)],
+            "Edges mismatch"
+        );
+    }
+
+    #[test]
+    fn expand_restores_nested_state() {
+        let mut graph = get_test_graph();
+        graph.collapse(10);
+        graph.collapse(1);
+        assert!(graph.expand(1), "Expected group to expand");
+        assert!(!graph.expand(1), "Expected group to be expanded already");
+        assert_eq!(
+            graph.representative(12),
+            Some(10),
+            "Expected inner group to stay collapsed"
+        );
+        graph.expand(10);
+        assert_eq!(
+            graph.view(),
+            graph.quotient(|id| id),
+            "Expected the full graph"
+        );
+    }
+
+    #[test]
+    fn set_parent_rejects_cycles() {
+        let mut graph = get_test_graph();
+        assert!(
+            matches!(graph.set_parent(1, 11), Err(GraphError::NestingCycle(1))),
+            "Expected a cycle"
+        );
+        assert!(
+            matches!(graph.set_parent(11, 99), Err(GraphError::NodeNotFound(99))),
+            "Expected a missing node"
+        );
+
+        graph.delete_node(10);
+        assert_eq!(graph.children(1), vec![11, 12, 20], "Children mismatch");
+    }
+}
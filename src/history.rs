@@ -0,0 +1,166 @@
+use alloc::{collections::VecDeque, vec::Vec};
+use core::ops::Deref;
+
+use super::{Graph, GraphId, Mutation};
+
+/// Wraps a graph and journals every change so it can be undone and redone.
+///
+/// At most `depth` changes are kept; the oldest ones are forgotten first. Making a new change
+/// after undoing discards the redo history.
+#[derive(Debug, Clone)]
+pub struct History<T> {
+    graph: Graph<T>,
+    depth: usize,
+    undo: VecDeque<Vec<Mutation<T>>>,
+    redo: Vec<Vec<Mutation<T>>>,
+}
+
+impl<T> History<T> {
+    pub fn new(graph: Graph<T>, depth: usize) -> Self {
+        Self {
+            graph,
+            depth,
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> Graph<T> {
+        self.graph
+    }
+
+    pub fn apply(&mut self, mutation: Mutation<T>) {
+        let inverse = self.graph.apply(mutation);
+        if inverse.is_empty() {
+            return;
+        }
+
+        self.redo.clear();
+        self.undo.push_back(inverse);
+        if self.undo.len() > self.depth {
+            self.undo.pop_front();
+        }
+    }
+
+    pub fn add_node(&mut self, id: GraphId, value: T) {
+        self.apply(Mutation::AddNode { id, value });
+    }
+
+    pub fn delete_node(&mut self, id: GraphId) {
+        self.apply(Mutation::RemoveNode { id });
+    }
+
+    pub fn add_edge(&mut self, from: GraphId, to: GraphId) {
+        self.apply(Mutation::AddEdge { from, to });
+    }
+
+    pub fn delete_edge(&mut self, from: GraphId, to: GraphId) {
+        self.apply(Mutation::RemoveEdge { from, to });
+    }
+
+    /// Reverts the latest change. Returns `false` if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(inverse) = self.undo.pop_back() else {
+            return false;
+        };
+        self.redo.push(self.graph.apply_all(inverse));
+
+        true
+    }
+
+    /// Reapplies the latest undone change. Returns `false` if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(mutations) = self.redo.pop() else {
+            return false;
+        };
+        self.undo.push_back(self.graph.apply_all(mutations));
+
+        true
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+impl<T> Deref for History<T> {
+    type Target = Graph<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_graph() -> Graph<&'static str> {
+        Graph::from(([(1, "a"), (2, "b"), (3, "c")], [(1, 2), (2, 3), (3, 1)]))
+    }
+
+    #[test]
+    fn undo_redo_node_removal() {
+        let mut history = History::new(get_test_graph(), 10);
+        history.delete_node(2);
+        let edited = (*history).clone();
+
+        assert!(history.undo(), "Expected undo to succeed");
+        assert_eq!(*history, get_test_graph(), "Expected original graph");
+        assert!(history.redo(), "Expected redo to succeed");
+        assert_eq!(*history, edited, "Expected edited graph");
+        assert!(history.undo(), "Expected undo to succeed again");
+        assert_eq!(*history, get_test_graph(), "Expected original graph");
+    }
+
+    #[test]
+    fn undo_multiple_steps() {
+        let mut history = History::new(Graph::new(), 10);
+        history.add_node(1, "a");
+        history.add_node(2, "b");
+        history.add_edge(1, 2);
+        history.add_edge(1, 2);
+
+        assert!(history.undo(), "Expected edge to be undone");
+        assert_eq!(history.edges.len(), 0, "Edges count mismatch");
+        assert!(history.undo(), "Expected node to be undone");
+        assert!(history.undo(), "Expected node to be undone");
+        assert!(!history.undo(), "Expected nothing left to undo");
+        assert_eq!(*history, Graph::new(), "Expected empty graph");
+    }
+
+    #[test]
+    fn history_depth_bound() {
+        let mut history = History::new(Graph::new(), 2);
+        history.add_node(1, "a");
+        history.add_node(2, "b");
+        history.add_node(3, "c");
+
+        assert!(history.undo() && history.undo(), "Expected two undos");
+        assert!(
+            !history.can_undo(),
+            "Expected oldest change to be forgotten"
+        );
+        assert_eq!(history.nodes.len(), 1, "Nodes count mismatch");
+    }
+
+    #[test]
+    fn new_change_clears_redo() {
+        let mut history = History::new(get_test_graph(), 10);
+        history.delete_edge(1, 2);
+        history.undo();
+        assert!(history.can_redo(), "Expected redo to be available");
+
+        history.delete_edge(2, 3);
+        assert!(!history.can_redo(), "Expected redo history to be discarded");
+    }
+}
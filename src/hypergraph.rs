@@ -0,0 +1,227 @@
+use alloc::{collections::BTreeSet, vec::Vec};
+
+use super::collections::HashMap;
+
+use super::{Graph, GraphId};
+
+/// A graph where every edge joins an arbitrary set of nodes.
+///
+/// Hyperedges have their own IDs, separate from node IDs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hypergraph<T> {
+    nodes: HashMap<GraphId, T>,
+    edges: HashMap<GraphId, BTreeSet<GraphId>>,
+}
+
+/// A node of [`Hypergraph::bipartite_expansion`], which stands for either a node or a
+/// hyperedge of the hypergraph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IncidenceNode<'a, T> {
+    Node(&'a T),
+    /// A hyperedge with its ID in the hypergraph.
+    Hyperedge(GraphId),
+}
+
+impl<T> Hypergraph<T> {
+    pub fn new() -> Self {
+        Self {
+            nodes: HashMap::new(),
+            edges: HashMap::new(),
+        }
+    }
+
+    pub fn add_node(&mut self, id: GraphId, value: T) {
+        self.nodes.entry(id).or_insert(value);
+    }
+
+    pub fn get_node(&self, id: GraphId) -> Option<&T> {
+        self.nodes.get(&id)
+    }
+
+    /// Removes the node and drops it from every hyperedge. Hyperedges left empty are removed.
+    pub fn delete_node(&mut self, id: GraphId) {
+        self.nodes.remove(&id);
+        self.edges.retain(|_, members| {
+            members.remove(&id);
+            !members.is_empty()
+        });
+    }
+
+    /// Adds a hyperedge joining `members`. Does nothing if the ID is taken, the member set is
+    /// empty, or any member doesn't exist.
+    pub fn add_edge(&mut self, id: GraphId, members: impl IntoIterator<Item = GraphId>) {
+        let members = members.into_iter().collect::<BTreeSet<_>>();
+        if self.edges.contains_key(&id)
+            || members.is_empty()
+            || !members.iter().all(|member| self.nodes.contains_key(member))
+        {
+            return;
+        }
+
+        self.edges.insert(id, members);
+    }
+
+    pub fn delete_edge(&mut self, id: GraphId) {
+        self.edges.remove(&id);
+    }
+
+    pub fn members(&self, id: GraphId) -> Option<&BTreeSet<GraphId>> {
+        self.edges.get(&id)
+    }
+
+    /// Returns the sorted IDs of hyperedges containing the node `id`.
+    pub fn incident_edges(&self, id: GraphId) -> Vec<GraphId> {
+        let mut incident = self
+            .edges
+            .iter()
+            .filter_map(|(edge, members)| members.contains(&id).then_some(*edge))
+            .collect::<Vec<_>>();
+        incident.sort_unstable();
+
+        incident
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Replaces every hyperedge with a clique: all pairs of its members are joined in both
+    /// directions.
+    pub fn clique_expansion(&self) -> Graph<&T> {
+        let mut graph = self
+            .nodes
+            .iter()
+            .map(|(id, value)| (*id, value))
+            .collect::<Graph<_>>();
+        for members in self.edges.values() {
+            for a in members {
+                for b in members {
+                    if a != b {
+                        graph.add_edge(*a, *b);
+                    }
+                }
+            }
+        }
+
+        graph
+    }
+
+    /// Turns every hyperedge into a node joined to its members in both directions. Nodes
+    /// keep their IDs, and hyperedges in ascending order take the smallest IDs that no node
+    /// uses, since the two ID spaces overlap.
+    pub fn bipartite_expansion(&self) -> Graph<IncidenceNode<'_, T>> {
+        let mut graph = self
+            .nodes
+            .iter()
+            .map(|(id, value)| (*id, IncidenceNode::Node(value)))
+            .collect::<Graph<_>>();
+        let mut edges = self.edges.iter().collect::<Vec<_>>();
+        edges.sort_unstable_by_key(|(edge, _)| **edge);
+        let mut free_ids = (0..).filter(|id| !self.nodes.contains_key(id));
+        for (edge, members) in edges {
+            let id = free_ids.next().expect("fewer nodes than IDs");
+            graph.add_node(id, IncidenceNode::Hyperedge(*edge));
+            for member in members {
+                graph.add_edge(*member, id);
+                graph.add_edge(id, *member);
+            }
+        }
+
+        graph
+    }
+}
+
+impl<T> Default for Hypergraph<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_graph() -> Hypergraph<&'static str> {
+        let mut graph = Hypergraph::new();
+        for (id, name) in [(1, "Alice"), (2, "Bob"), (3, "Carol"), (4, "Dave")] {
+            graph.add_node(id, name);
+        }
+        graph.add_edge(10, [1, 2, 3]);
+        graph.add_edge(11, [3, 4]);
+
+        graph
+    }
+
+    #[test]
+    fn add_edge_validates_members() {
+        let mut graph = get_test_graph();
+        graph.add_edge(12, [1, 9]);
+        graph.add_edge(13, []);
+        graph.add_edge(10, [4]);
+        assert_eq!(graph.edge_count(), 2, "Edges count mismatch");
+        assert_eq!(
+            graph.members(10),
+            Some(&BTreeSet::from([1, 2, 3])),
+            "Members mismatch"
+        );
+        assert_eq!(
+            graph.incident_edges(3),
+            vec![10, 11],
+            "Incident edges mismatch"
+        );
+    }
+
+    #[test]
+    fn delete_node_shrinks_edges() {
+        let mut graph = get_test_graph();
+        graph.delete_node(3);
+        graph.delete_node(4);
+        assert_eq!(
+            graph.edge_count(),
+            1,
+            "Expected empty hyperedge to be removed"
+        );
+        assert_eq!(
+            graph.members(10),
+            Some(&BTreeSet::from([1, 2])),
+            "Members mismatch"
+        );
+    }
+
+    #[test]
+    fn clique_expansion_joins_members() {
+        let hypergraph = get_test_graph();
+        let graph = hypergraph.clique_expansion();
+        assert_eq!(graph.nodes.len(), 4, "Nodes count mismatch");
+        assert_eq!(graph.edges.len(), 8, "Edges count mismatch");
+        assert_eq!(graph[4], &"Dave", "Expected node payloads");
+    }
+
+    #[test]
+    fn bipartite_expansion_incidence() {
+        let graph = get_test_graph();
+        let expansion = graph.bipartite_expansion();
+        assert_eq!(expansion.nodes.len(), 6, "Nodes count mismatch");
+        assert_eq!(expansion.edges.len(), 10, "Edges count mismatch");
+        assert_eq!(expansion[1], IncidenceNode::Node(&"Alice"), "Node mismatch");
+        assert_eq!(
+            (expansion[0], expansion[5]),
+            (IncidenceNode::Hyperedge(10), IncidenceNode::Hyperedge(11)),
+            "Expected hyperedges on unused IDs"
+        );
+        assert_eq!(
+            expansion.get_node(0).unwrap().neighbour_ids(),
+            [1, 2, 3],
+            "Members mismatch"
+        );
+        assert_eq!(
+            expansion.get_node(3).unwrap().neighbour_ids(),
+            [0, 5],
+            "Incident hyperedges mismatch"
+        );
+    }
+}
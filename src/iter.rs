@@ -0,0 +1,268 @@
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+use super::collections::{hash_map, hash_set, HashSet};
+
+use super::{Edge, Graph, GraphId};
+
+impl<T> Graph<T> {
+    pub fn iter(&self) -> Nodes<'_, T> {
+        Nodes {
+            inner: self.nodes.iter(),
+        }
+    }
+
+    pub fn edges(&self) -> Edges<'_> {
+        Edges {
+            inner: self.edges.iter(),
+        }
+    }
+
+    /// Returns the pairs of nodes joined by edges in both directions, once each as
+    /// `(smaller, larger)`, in arbitrary order. Self-loops aren't included.
+    pub fn mutual_edges(&self) -> MutualEdges<'_> {
+        MutualEdges {
+            inner: self.edges.iter(),
+            edges: &self.edges,
+        }
+    }
+
+    /// Returns the nodes sorted by ID. Unlike [`iter`](Self::iter), the order doesn't depend
+    /// on hashing, so it is the same on every run.
+    pub fn sorted_nodes(&self) -> Vec<(GraphId, &T)> {
+        let mut nodes = self.iter().collect::<Vec<_>>();
+        nodes.sort_unstable_by_key(|(id, _)| *id);
+
+        nodes
+    }
+
+    /// Returns the edges sorted by source and then target. Unlike [`edges`](Self::edges), the
+    /// order is the same on every run.
+    pub fn sorted_edges(&self) -> Vec<(GraphId, GraphId)> {
+        let mut edges = self.edges().collect::<Vec<_>>();
+        edges.sort_unstable();
+
+        edges
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Nodes<'a, T> {
+    inner: hash_map::Iter<'a, GraphId, T>,
+}
+
+impl<'a, T> Iterator for Nodes<'a, T> {
+    type Item = (GraphId, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(id, value)| (*id, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for Nodes<'_, T> {}
+
+impl<T> FusedIterator for Nodes<'_, T> {}
+
+#[derive(Debug)]
+pub struct IntoNodes<T> {
+    inner: hash_map::IntoIter<GraphId, T>,
+}
+
+impl<T> Iterator for IntoNodes<T> {
+    type Item = (GraphId, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoNodes<T> {}
+
+impl<T> FusedIterator for IntoNodes<T> {}
+
+#[derive(Debug, Clone)]
+pub struct Edges<'a> {
+    inner: hash_set::Iter<'a, Edge>,
+}
+
+impl Iterator for Edges<'_> {
+    type Item = (GraphId, GraphId);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|edge| (edge.from, edge.to))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Edges<'_> {}
+
+impl FusedIterator for Edges<'_> {}
+
+#[derive(Debug, Clone)]
+pub struct MutualEdges<'a> {
+    inner: hash_set::Iter<'a, Edge>,
+    edges: &'a HashSet<Edge>,
+}
+
+impl Iterator for MutualEdges<'_> {
+    type Item = (GraphId, GraphId);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.find_map(|edge| {
+            let reverse = Edge {
+                from: edge.to,
+                to: edge.from,
+            };
+            (edge.from < edge.to && self.edges.contains(&reverse)).then_some((edge.from, edge.to))
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+impl FusedIterator for MutualEdges<'_> {}
+
+impl<T> IntoIterator for Graph<T> {
+    type Item = (GraphId, T);
+    type IntoIter = IntoNodes<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoNodes {
+            inner: self.nodes.into_iter(),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Graph<T> {
+    type Item = (GraphId, &'a T);
+    type IntoIter = Nodes<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> FromIterator<(GraphId, T)> for Graph<T> {
+    fn from_iter<I: IntoIterator<Item = (GraphId, T)>>(iter: I) -> Self {
+        let mut graph = Graph::new();
+        graph.extend(iter);
+
+        graph
+    }
+}
+
+impl<T> Extend<(GraphId, T)> for Graph<T> {
+    fn extend<I: IntoIterator<Item = (GraphId, T)>>(&mut self, iter: I) {
+        for (id, value) in iter {
+            self.add_node(id, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn get_test_graph() -> Graph<&'static str> {
+        Graph::from((
+            [(1, "January"), (2, "March"), (3, "April")],
+            [(1, 2), (3, 2)],
+        ))
+    }
+
+    #[test]
+    fn iter_borrowed_nodes() {
+        let graph = get_test_graph();
+        let nodes = (&graph).into_iter().collect::<HashSet<_>>();
+        assert_eq!(
+            nodes,
+            HashSet::from([(1, &"January"), (2, &"March"), (3, &"April")]),
+            "Nodes don't match"
+        );
+        assert_eq!(graph.iter().len(), 3, "Nodes count mismatch");
+    }
+
+    #[test]
+    fn iter_consumed_nodes() {
+        let nodes = get_test_graph().into_iter().collect::<HashSet<_>>();
+        assert_eq!(
+            nodes,
+            HashSet::from([(1, "January"), (2, "March"), (3, "April")]),
+            "Nodes don't match"
+        );
+    }
+
+    #[test]
+    fn iter_edges() {
+        let graph = get_test_graph();
+        assert_eq!(
+            graph.edges().collect::<HashSet<_>>(),
+            HashSet::from([(1, 2), (3, 2)]),
+            "Edges don't match"
+        );
+    }
+
+    #[test]
+    fn iter_mutual_edges() {
+        let graph = Graph::from((
+            [(1, ()), (2, ()), (3, ())],
+            [(1, 2), (2, 1), (2, 3), (3, 3), (3, 1), (1, 3)],
+        ));
+        assert_eq!(
+            graph.mutual_edges().collect::<HashSet<_>>(),
+            HashSet::from([(1, 2), (1, 3)]),
+            "Mutual edges mismatch"
+        );
+    }
+
+    #[test]
+    fn sorted_nodes_and_edges() {
+        let graph = Graph::from((
+            [(30, "c"), (10, "a"), (20, "b")],
+            [(30, 10), (10, 30), (10, 20), (20, 10)],
+        ));
+        assert_eq!(
+            graph.sorted_nodes(),
+            vec![(10, &"a"), (20, &"b"), (30, &"c")],
+            "Nodes mismatch"
+        );
+        assert_eq!(
+            graph.sorted_edges(),
+            vec![(10, 20), (10, 30), (20, 10), (30, 10)],
+            "Edges mismatch"
+        );
+    }
+
+    #[test]
+    fn collect_graph_from_nodes() {
+        let graph = get_test_graph()
+            .into_iter()
+            .filter(|(id, _)| *id != 2)
+            .collect::<Graph<_>>();
+        assert_eq!(graph.nodes.len(), 2, "Nodes count mismatch");
+        assert!(graph.edges.is_empty(), "Expected edges to be empty");
+    }
+
+    #[test]
+    fn extend_keeps_existing_values() {
+        let mut graph = get_test_graph();
+        graph.extend([(1, "Other"), (4, "May")]);
+        assert_eq!(graph.nodes.len(), 4, "Nodes count mismatch");
+        assert_eq!(graph.nodes[&1], "January", "Existing value changed");
+    }
+}
@@ -0,0 +1,159 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt::Display, str::FromStr};
+
+use anyhow::{anyhow, Context};
+use serde_json::{json, Value};
+
+use super::{Graph, GraphId};
+
+impl<T> Graph<T> {
+    /// Serializes the graph as `{"nodes": [{"id", "value"}], "edges": [{"from", "to"}]}`,
+    /// with node values written through `Display` and everything sorted by ID.
+    pub fn to_json(&self) -> String
+    where
+        T: Display,
+    {
+        json!({
+            "nodes": self.nodes_to_json(),
+            "edges": self
+                .sorted_edges()
+                .into_iter()
+                .map(|(from, to)| json!({ "from": from, "to": to }))
+                .collect::<Vec<_>>(),
+        })
+        .to_string()
+    }
+
+    /// Parses the format written by [`Graph::to_json`], reading node values with `FromStr`.
+    pub fn from_json(s: &str) -> anyhow::Result<Self>
+    where
+        T: FromStr,
+    {
+        debug_span!("parse_json", bytes = s.len());
+        #[cfg(all(feature = "tracing", feature = "std"))]
+        let start = std::time::Instant::now();
+
+        let document: Value = serde_json::from_str(s).context("Invalid JSON")?;
+        let mut graph = Graph::new();
+        for (id, value) in parse_nodes(&document)? {
+            graph.add_node(id, value);
+        }
+        for edge in array(&document, "edges")? {
+            graph.add_edge(id_field(edge, "from")?, id_field(edge, "to")?);
+        }
+        #[cfg(feature = "std")]
+        debug_event!(
+            nodes = graph.nodes.len(),
+            edges = graph.edges.len(),
+            elapsed = ?start.elapsed(),
+            "parsed JSON"
+        );
+        // Without std there is no clock to time parsing with.
+        #[cfg(not(feature = "std"))]
+        debug_event!(
+            nodes = graph.nodes.len(),
+            edges = graph.edges.len(),
+            "parsed JSON"
+        );
+
+        Ok(graph)
+    }
+
+    pub(crate) fn nodes_to_json(&self) -> Vec<Value>
+    where
+        T: Display,
+    {
+        self.sorted_nodes()
+            .into_iter()
+            .map(|(id, value)| json!({ "id": id, "value": value.to_string() }))
+            .collect()
+    }
+}
+
+pub(crate) fn parse_nodes<T: FromStr>(document: &Value) -> anyhow::Result<Vec<(GraphId, T)>> {
+    array(document, "nodes")?
+        .iter()
+        .map(|node| {
+            let raw = match node.get("value") {
+                Some(Value::String(value)) => value.clone(),
+                Some(value) => value.to_string(),
+                None => return Err(anyhow!("Node is missing \"value\": {node}")),
+            };
+            let value = raw
+                .parse()
+                .map_err(|_| anyhow!("Failed to parse node value {raw:?}"))?;
+
+            Ok((id_field(node, "id")?, value))
+        })
+        .collect()
+}
+
+pub(crate) fn array<'a>(document: &'a Value, key: &str) -> anyhow::Result<&'a Vec<Value>> {
+    document
+        .get(key)
+        .and_then(Value::as_array)
+        .ok_or_else(|| anyhow!("Expected \"{key}\" to be an array"))
+}
+
+pub(crate) fn id_field(object: &Value, key: &str) -> anyhow::Result<GraphId> {
+    object
+        .get(key)
+        .and_then(Value::as_u64)
+        .ok_or_else(|| anyhow!("Expected \"{key}\" to be a node ID in {object}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_json_sorted() {
+        let graph: Graph<String> = include_str!("../test-data/test-graph-simple")
+            .parse()
+            .unwrap();
+        assert_eq!(
+            graph.to_json(),
+            r#"{"edges":[{"from":1,"to":2}],"nodes":[{"id":1,"value":"First node"},{"id":2,"value":"Second node"}]}"#,
+            "JSON output mismatch"
+        );
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let graph: Graph<String> = include_str!("../test-data/test-graph").parse().unwrap();
+        assert_eq!(
+            Graph::from_json(&graph.to_json()).ok(),
+            Some(graph),
+            "Expected graph to survive a round trip"
+        );
+    }
+
+    #[test]
+    fn from_json_non_string_values() {
+        let graph = Graph::<i32>::from_json(r#"{"nodes":[{"id":1,"value":5}],"edges":[]}"#);
+        assert_eq!(
+            graph.ok(),
+            Some(Graph::from(([(1, 5)], []))),
+            "Graph mismatch"
+        );
+    }
+
+    #[test]
+    fn from_json_invalid() {
+        assert!(
+            Graph::<String>::from_json("[]").is_err(),
+            "Expected missing nodes"
+        );
+        assert!(
+            Graph::<i32>::from_json(r#"{"nodes":[{"id":1,"value":"x"}],"edges":[]}"#).is_err(),
+            "Expected invalid value"
+        );
+        assert!(
+            Graph::<String>::from_json(r#"{"nodes":[],"edges":[{"from":-1,"to":2}]}"#).is_err(),
+            "Expected invalid ID"
+        );
+    }
+}
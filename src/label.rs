@@ -0,0 +1,55 @@
+use alloc::string::{String, ToString};
+use core::fmt::Display;
+
+/// Text and styling that exporters such as [`Graph::to_dot`] and [`Graph::to_svg`] derive
+/// from a node value.
+///
+/// Implemented for every [`Display`] type, labelling nodes with their displayed value. Other
+/// types, such as structured payloads, can implement it to pick a field as the label and add
+/// a tooltip or color. To style nodes differently per export, pass a callback to an exporter
+/// such as [`Graph::to_dot_by`] instead.
+///
+/// [`Graph::to_dot`]: crate::Graph::to_dot
+/// [`Graph::to_svg`]: crate::Graph::to_svg
+/// [`Graph::to_dot_by`]: crate::Graph::to_dot_by
+pub trait NodeLabel {
+    fn label(&self) -> String;
+
+    /// Text shown when hovering over the node, where the format supports it.
+    fn tooltip(&self) -> Option<String> {
+        None
+    }
+
+    /// Fill color of the node, as a color name or `#rrggbb`.
+    fn color(&self) -> Option<String> {
+        None
+    }
+}
+
+impl<T: Display + ?Sized> NodeLabel for T {
+    fn label(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// How an exporter draws a node, as returned by the callbacks of exporters such as
+/// [`Graph::to_dot_by`](crate::Graph::to_dot_by).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NodeAttributes {
+    pub label: String,
+    /// Text shown when hovering over the node, where the format supports it.
+    pub tooltip: Option<String>,
+    /// Fill color of the node, as a color name or `#rrggbb`.
+    pub color: Option<String>,
+}
+
+impl NodeAttributes {
+    /// Returns the attributes `value` describes through [`NodeLabel`].
+    pub fn of(value: &(impl NodeLabel + ?Sized)) -> Self {
+        Self {
+            label: value.label(),
+            tooltip: value.tooltip(),
+            color: value.color(),
+        }
+    }
+}
@@ -0,0 +1,460 @@
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+
+use super::{Graph, GraphId, PlanarEmbedding};
+
+pub type Layout = HashMap<GraphId, (f32, f32)>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ForceDirectedOptions {
+    pub width: f32,
+    pub height: f32,
+    pub iterations: usize,
+}
+
+impl Default for ForceDirectedOptions {
+    fn default() -> Self {
+        Self {
+            width: 800.0,
+            height: 600.0,
+            iterations: 200,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayeredOptions {
+    pub layer_spacing: f32,
+    pub node_spacing: f32,
+    pub sweeps: usize,
+}
+
+impl Default for LayeredOptions {
+    fn default() -> Self {
+        Self {
+            layer_spacing: 100.0,
+            node_spacing: 120.0,
+            sweeps: 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlanarOptions {
+    pub radius: f32,
+    pub iterations: usize,
+}
+
+impl Default for PlanarOptions {
+    fn default() -> Self {
+        Self {
+            radius: 300.0,
+            iterations: 500,
+        }
+    }
+}
+
+/// Places nodes using a planar embedding, or returns `None` if the graph isn't planar.
+///
+/// Every component is drawn as a Tutte embedding: the longest face of the embedding is fixed
+/// on a circle of `options.radius` and every other node is repeatedly moved to the barycenter
+/// of its neighbours. The drawing is crossing-free for 3-connected graphs; other planar graphs
+/// may have nodes placed on top of each other. Components are placed side by side.
+pub fn planar<T>(graph: &Graph<T>, options: PlanarOptions) -> Option<Layout> {
+    let embedding = graph.planar_embedding()?;
+    let mut ids = graph.nodes.keys().copied().collect::<Vec<_>>();
+    ids.sort_unstable();
+
+    let mut component_of: HashMap<GraphId, usize> = HashMap::new();
+    let mut components = Vec::new();
+    for root in ids {
+        if component_of.contains_key(&root) {
+            continue;
+        }
+
+        let mut members = vec![root];
+        component_of.insert(root, components.len());
+        let mut next = 0;
+        while let Some(&id) = members.get(next) {
+            next += 1;
+            for &neighbour in embedding.neighbours(id) {
+                if let Entry::Vacant(entry) = component_of.entry(neighbour) {
+                    entry.insert(components.len());
+                    members.push(neighbour);
+                }
+            }
+        }
+        components.push(members);
+    }
+
+    let mut outer_faces: Vec<Vec<GraphId>> = vec![Vec::new(); components.len()];
+    for face in embedding.faces() {
+        let mut distinct = Vec::new();
+        for id in face {
+            if !distinct.contains(&id) {
+                distinct.push(id);
+            }
+        }
+        let outer = &mut outer_faces[component_of[&distinct[0]]];
+        if distinct.len() > outer.len() {
+            *outer = distinct;
+        }
+    }
+
+    let mut layout = Layout::new();
+    for (index, (members, outer)) in components.iter().zip(outer_faces).enumerate() {
+        let centre = (index as f32 * options.radius * 2.5, 0.0);
+        tutte(&embedding, members, &outer, centre, options, &mut layout);
+    }
+
+    Some(layout)
+}
+
+fn tutte(
+    embedding: &PlanarEmbedding,
+    members: &[GraphId],
+    outer: &[GraphId],
+    centre: (f32, f32),
+    options: PlanarOptions,
+    layout: &mut Layout,
+) {
+    for (i, id) in outer.iter().enumerate() {
+        let angle = std::f32::consts::TAU * i as f32 / outer.len() as f32;
+        layout.insert(
+            *id,
+            (
+                centre.0 + options.radius * angle.cos(),
+                centre.1 + options.radius * angle.sin(),
+            ),
+        );
+    }
+
+    let inner = members
+        .iter()
+        .filter(|id| !outer.contains(id))
+        .copied()
+        .collect::<Vec<_>>();
+    for id in &inner {
+        layout.insert(*id, centre);
+    }
+    for _ in 0..options.iterations {
+        for id in &inner {
+            let neighbours = embedding.neighbours(*id);
+            let (x, y) = neighbours.iter().fold((0.0, 0.0), |(x, y), neighbour| {
+                let position = layout[neighbour];
+                (x + position.0, y + position.1)
+            });
+            layout.insert(
+                *id,
+                (x / neighbours.len() as f32, y / neighbours.len() as f32),
+            );
+        }
+    }
+}
+
+/// Places nodes with the Fruchterman–Reingold force-directed algorithm, treating edges as
+/// undirected springs. Positions are centred on the origin and stay inside
+/// `options.width` x `options.height`.
+///
+/// The starting positions are spread on a circle in ID order, so the result is deterministic.
+pub fn fruchterman_reingold<T>(graph: &Graph<T>, options: ForceDirectedOptions) -> Layout {
+    let mut ids = graph.nodes.keys().copied().collect::<Vec<_>>();
+    ids.sort_unstable();
+    if ids.is_empty() {
+        return Layout::new();
+    }
+
+    let index_of: HashMap<GraphId, usize> =
+        ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+    let springs = graph
+        .edges
+        .iter()
+        .filter(|edge| edge.from != edge.to)
+        .map(|edge| (index_of[&edge.from], index_of[&edge.to]))
+        .collect::<Vec<_>>();
+
+    let radius = options.width.min(options.height) / 4.0;
+    let mut positions = (0..ids.len())
+        .map(|i| {
+            let angle = std::f32::consts::TAU * i as f32 / ids.len() as f32;
+            (radius * angle.cos(), radius * angle.sin())
+        })
+        .collect::<Vec<_>>();
+
+    let k = (options.width * options.height / ids.len() as f32).sqrt();
+    let initial_temperature = options.width / 10.0;
+    for iteration in 0..options.iterations {
+        let mut displacement = vec![(0.0f32, 0.0f32); ids.len()];
+        for a in 0..ids.len() {
+            for b in a + 1..ids.len() {
+                let (dx, dy, distance) = delta(positions[a], positions[b]);
+                let force = k * k / distance;
+                displacement[a].0 += dx / distance * force;
+                displacement[a].1 += dy / distance * force;
+                displacement[b].0 -= dx / distance * force;
+                displacement[b].1 -= dy / distance * force;
+            }
+        }
+        for &(a, b) in &springs {
+            let (dx, dy, distance) = delta(positions[a], positions[b]);
+            let force = distance * distance / k;
+            displacement[a].0 -= dx / distance * force;
+            displacement[a].1 -= dy / distance * force;
+            displacement[b].0 += dx / distance * force;
+            displacement[b].1 += dy / distance * force;
+        }
+
+        let temperature =
+            initial_temperature * (1.0 - iteration as f32 / options.iterations as f32);
+        for (position, (dx, dy)) in positions.iter_mut().zip(displacement) {
+            let length = (dx * dx + dy * dy).sqrt().max(f32::EPSILON);
+            let step = length.min(temperature);
+            position.0 =
+                (position.0 + dx / length * step).clamp(-options.width / 2.0, options.width / 2.0);
+            position.1 = (position.1 + dy / length * step)
+                .clamp(-options.height / 2.0, options.height / 2.0);
+        }
+    }
+
+    ids.into_iter().zip(positions).collect()
+}
+
+/// Places nodes in horizontal layers in the spirit of Sugiyama: cycles are broken by ignoring
+/// DFS back edges, nodes are layered by longest path so edges point downwards, and the order
+/// inside every layer is improved with barycenter sweeps to reduce crossings.
+pub fn layered<T>(graph: &Graph<T>, options: LayeredOptions) -> Layout {
+    let layers = layer_order(graph, options.sweeps);
+
+    let mut layout = Layout::new();
+    for (depth, layer) in layers.iter().enumerate() {
+        let offset = (layer.len() as f32 - 1.0) / 2.0;
+        for (index, id) in layer.iter().enumerate() {
+            layout.insert(
+                *id,
+                (
+                    (index as f32 - offset) * options.node_spacing,
+                    depth as f32 * options.layer_spacing,
+                ),
+            );
+        }
+    }
+
+    layout
+}
+
+pub(crate) fn layer_order<T>(graph: &Graph<T>, sweeps: usize) -> Vec<Vec<GraphId>> {
+    let back_edges = back_edges(graph);
+    let mut acyclic: Graph<()> = Graph::new();
+    for id in graph.nodes.keys() {
+        acyclic.add_node(*id, ());
+    }
+    for edge in graph.edges.difference(&back_edges) {
+        acyclic.add_edge(edge.from, edge.to);
+    }
+    let mut layers = acyclic
+        .dag_layers()
+        .expect("back edges were removed, so the graph is acyclic");
+
+    let mut neighbours: HashMap<GraphId, Vec<GraphId>> = HashMap::new();
+    for edge in &graph.edges {
+        neighbours.entry(edge.from).or_default().push(edge.to);
+        neighbours.entry(edge.to).or_default().push(edge.from);
+    }
+
+    for sweep in 0..sweeps {
+        let order: Vec<usize> = if sweep % 2 == 0 {
+            (1..layers.len()).collect()
+        } else {
+            (0..layers.len().saturating_sub(1)).rev().collect()
+        };
+        for index in order {
+            let fixed = if sweep % 2 == 0 { index - 1 } else { index + 1 };
+            let positions: HashMap<GraphId, usize> = layers[fixed]
+                .iter()
+                .enumerate()
+                .map(|(position, id)| (*id, position))
+                .collect();
+
+            let mut keyed = layers[index]
+                .iter()
+                .enumerate()
+                .map(|(current, id)| {
+                    let adjacent = neighbours
+                        .get(id)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|neighbour| positions.get(neighbour))
+                        .collect::<Vec<_>>();
+                    let barycenter = if adjacent.is_empty() {
+                        current as f32
+                    } else {
+                        adjacent.iter().map(|p| **p as f32).sum::<f32>() / adjacent.len() as f32
+                    };
+                    (barycenter, *id)
+                })
+                .collect::<Vec<_>>();
+            keyed.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+            layers[index] = keyed.into_iter().map(|(_, id)| id).collect();
+        }
+    }
+
+    layers
+}
+
+fn back_edges<T>(graph: &Graph<T>) -> HashSet<super::Edge> {
+    let mut adjacency: HashMap<GraphId, Vec<GraphId>> = HashMap::new();
+    for edge in &graph.edges {
+        adjacency.entry(edge.from).or_default().push(edge.to);
+    }
+    for neighbours in adjacency.values_mut() {
+        neighbours.sort_unstable();
+    }
+
+    let mut ids = graph.nodes.keys().copied().collect::<Vec<_>>();
+    ids.sort_unstable();
+
+    let mut back = HashSet::new();
+    let mut finished = HashSet::new();
+    let mut on_stack = HashSet::new();
+    for root in ids {
+        if finished.contains(&root) {
+            continue;
+        }
+
+        let mut stack = vec![(root, 0)];
+        on_stack.insert(root);
+        while let Some((id, next)) = stack.pop() {
+            let neighbours = adjacency.get(&id).map_or(&[][..], Vec::as_slice);
+            if let Some(&to) = neighbours.get(next) {
+                stack.push((id, next + 1));
+                if on_stack.contains(&to) {
+                    back.insert(super::Edge { from: id, to });
+                } else if !finished.contains(&to) {
+                    on_stack.insert(to);
+                    stack.push((to, 0));
+                }
+            } else {
+                on_stack.remove(&id);
+                finished.insert(id);
+            }
+        }
+    }
+
+    back
+}
+
+fn delta(a: (f32, f32), b: (f32, f32)) -> (f32, f32, f32) {
+    let dx = a.0 - b.0;
+    let dy = a.1 - b.1;
+
+    (dx, dy, (dx * dx + dy * dy).sqrt().max(0.01))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generators;
+
+    use super::*;
+
+    fn distance(layout: &Layout, a: GraphId, b: GraphId) -> f32 {
+        delta(layout[&a], layout[&b]).2
+    }
+
+    #[test]
+    fn fruchterman_reingold_places_all_nodes() {
+        let graph = generators::grid(3, 3, |_| ());
+        let options = ForceDirectedOptions::default();
+        let layout = fruchterman_reingold(&graph, options);
+        assert_eq!(layout.len(), 9, "Expected a position for every node");
+        assert!(
+            layout
+                .values()
+                .all(|(x, y)| x.abs() <= options.width / 2.0 && y.abs() <= options.height / 2.0),
+            "Expected positions inside the frame"
+        );
+    }
+
+    #[test]
+    fn fruchterman_reingold_neighbours_closer() {
+        let graph = generators::path(6, |_| ());
+        let layout = fruchterman_reingold(&graph, ForceDirectedOptions::default());
+        assert!(
+            distance(&layout, 0, 1) < distance(&layout, 0, 5),
+            "Expected adjacent nodes to be closer than path ends"
+        );
+    }
+
+    #[test]
+    fn fruchterman_reingold_empty() {
+        let graph: Graph<()> = Graph::new();
+        assert!(fruchterman_reingold(&graph, ForceDirectedOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn planar_wheel_inside_rim() {
+        let mut graph = generators::cycle(6, |_| ());
+        graph.add_node(6, ());
+        for i in 0..6 {
+            graph.add_edge(i, 6);
+            graph.add_edge(6, i);
+        }
+        let options = PlanarOptions::default();
+        let layout = planar(&graph, options).expect("Expected a planar layout");
+        assert_eq!(layout.len(), 7, "Expected a position for every node");
+        for i in 0..6 {
+            let (x, y) = layout[&i];
+            assert!(
+                ((x * x + y * y).sqrt() - options.radius).abs() < 0.01,
+                "Expected the rim on the circle"
+            );
+        }
+        assert!(
+            distance(&layout, 6, 0) < options.radius * 1.01,
+            "Expected the hub inside the rim"
+        );
+    }
+
+    #[test]
+    fn planar_rejects_non_planar() {
+        let graph = generators::complete(5, |_| ());
+        assert_eq!(planar(&graph, PlanarOptions::default()), None);
+    }
+
+    #[test]
+    fn layered_edges_point_down() {
+        let graph: Graph<()> = Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ())],
+            [(1, 2), (1, 3), (2, 4), (3, 4)],
+        ));
+        let layout = layered(&graph, LayeredOptions::default());
+        assert!(
+            graph
+                .edges
+                .iter()
+                .all(|edge| layout[&edge.from].1 < layout[&edge.to].1),
+            "Expected every edge to point to a lower layer"
+        );
+    }
+
+    #[test]
+    fn layered_breaks_cycles() {
+        let graph: Graph<()> = Graph::from(([(1, ()), (2, ()), (3, ())], [(1, 2), (2, 3), (3, 1)]));
+        let layout = layered(&graph, LayeredOptions::default());
+        assert_eq!(layout.len(), 3, "Expected a position for every node");
+        assert_eq!(
+            layout[&1].1, 0.0,
+            "Expected the DFS root on the first layer"
+        );
+        assert_eq!(layout[&3].1, 200.0, "Expected the cycle to be unrolled");
+    }
+
+    #[test]
+    fn layer_order_reduces_crossings() {
+        let graph: Graph<()> =
+            Graph::from(([(1, ()), (2, ()), (3, ()), (4, ())], [(1, 4), (2, 3)]));
+        assert_eq!(
+            layer_order(&graph, 4),
+            vec![vec![1, 2], vec![4, 3]],
+            "Expected children ordered under their parents"
+        );
+    }
+}
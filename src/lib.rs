@@ -1,13 +1,189 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    fmt::{Debug, Display},
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::{
+    collections::{BTreeMap, VecDeque},
+    string::{String, ToString},
+    vec::Vec,
 };
+use core::{
+    cmp::Ordering,
+    fmt::{self, Debug, Display},
+    hash::{Hash, Hasher},
+    ops::{ControlFlow, Index, IndexMut},
+};
+
+use collections::{HashMap, HashSet};
 
+#[macro_use]
+mod macros;
+
+#[cfg(feature = "quickcheck")]
+mod arbitrary;
+#[cfg(feature = "tokio")]
+mod async_io;
+mod bipartite;
+mod budget;
+mod builder;
+mod cache;
+mod cancel;
+mod canonical;
+mod collections;
+mod communities;
+#[cfg(feature = "compression")]
+mod compression;
+mod connectivity;
+#[cfg(feature = "std")]
+mod csv;
+mod cycles;
+mod diff;
+mod dijkstra;
+mod dot;
+#[cfg(feature = "std")]
+mod edge_list;
+mod edit_distance;
+#[cfg(feature = "embedding")]
+mod embedding;
+mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod filter;
+#[cfg(feature = "std")]
+pub mod generators;
+mod graph_set;
+mod header;
+mod hierarchy;
+mod history;
+mod hypergraph;
+mod iter;
+mod json;
+mod label;
+#[cfg(feature = "std")]
+pub mod layout;
+mod matrix;
+#[cfg(feature = "mmap")]
+mod mmap;
+mod motifs;
+mod multiplex;
+mod mutation;
+mod neighbourhood;
+mod observe;
+mod oracle;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod paths;
+#[cfg(feature = "std")]
+mod pattern;
+mod planarity;
+mod products;
+mod progress;
+mod prune;
+#[cfg(feature = "python")]
+mod python;
+mod query;
+mod quotient;
+mod rank;
+mod relabel;
+#[cfg(feature = "std")]
+mod render;
+mod rewrite;
+#[cfg(feature = "std")]
+mod sampling;
 mod serde;
+#[cfg(feature = "std")]
+mod sharded;
+#[cfg(feature = "arc-swap")]
+mod shared;
+mod similarity;
+mod snapshot;
+mod stats;
+#[cfg(feature = "std")]
+mod streaming;
+mod subgraphs;
+mod summary;
+#[cfg(feature = "std")]
+mod svg;
+mod temporal;
+mod transaction;
+#[cfg(feature = "async")]
+mod traversal;
+#[cfg(feature = "std")]
+mod wal;
+mod walker;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod weighted;
+
+pub use bipartite::BipartiteGraph;
+pub use budget::{Convergence, IterationBudget};
+pub use builder::{BuildError, GraphBuilder};
+pub use cache::PathCache;
+pub use cancel::CancellationToken;
+pub use canonical::{dedup_graphs, CanonicalForm};
+#[cfg(feature = "compression")]
+pub use compression::Compression;
+pub use connectivity::{ComponentId, ComponentLabels, ComponentSummary, ConnectedGraph, UnionFind};
+#[cfg(feature = "std")]
+pub use csv::{CsvImport, CsvRowError, CsvRowErrorKind, CsvSchema};
+pub use diff::{GraphDiff, MergeConflict, MergePolicy};
+pub use edit_distance::EditDistance;
+#[cfg(feature = "embedding")]
+pub use embedding::{train_skip_gram, SkipGramOptions, WalkOptions};
+pub use error::GraphError;
+pub use filter::SearchFilter;
+pub use graph_set::GraphSet;
+pub use header::{Header, IntegrityError};
+pub use hierarchy::HierarchicalGraph;
+pub use history::History;
+pub use hypergraph::{Hypergraph, IncidenceNode};
+pub use iter::{Edges, IntoNodes, MutualEdges, Nodes};
+pub use label::{NodeAttributes, NodeLabel};
+pub use matrix::GraphMatrix;
+#[cfg(feature = "mmap")]
+pub use mmap::MmapGraph;
+pub use multiplex::MultiplexGraph;
+pub use mutation::Mutation;
+pub use neighbourhood::Direction;
+pub use observe::{GraphEvent, GraphListener, ObservedGraph};
+pub use oracle::DistanceOracle;
+pub use paths::{Distances, Path, ShortestPathTree};
+pub use planarity::PlanarEmbedding;
+pub use products::{product_id, product_parts};
+pub use progress::{Progress, ProgressSink};
+#[cfg(feature = "python")]
+pub use python::PyGraph;
+pub use query::Query;
+pub use quotient::GroupId;
+pub use rank::HitsScores;
+pub use relabel::CollisionError;
+pub use rewrite::{Match, RewriteMode, Rule};
+pub use serde::SerializeOptions;
+#[cfg(feature = "std")]
+pub use sharded::ShardedGraph;
+#[cfg(feature = "arc-swap")]
+pub use shared::{GraphWriter, SharedGraph};
+pub use snapshot::{Snapshot, VersionedGraph};
+pub use stats::GraphStats;
+#[cfg(feature = "std")]
+pub use streaming::{EdgeStream, StreamItem};
+pub use subgraphs::ConnectedSubgraphs;
+#[cfg(feature = "std")]
+pub use svg::{SvgLayout, SvgOptions};
+pub use temporal::{Interval, TemporalGraph, Timestamp};
+pub use transaction::Transaction;
+#[cfg(feature = "async")]
+pub use traversal::AsyncTraversal;
+#[cfg(feature = "std")]
+pub use wal::PersistentGraph;
+pub use walker::BfsWalker;
+#[cfg(feature = "wasm")]
+pub use wasm::WasmGraph;
+pub use weighted::WeightedGraph;
 
 pub type GraphId = u64;
 
-#[derive(Debug, Clone)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct Graph<T> {
     nodes: HashMap<GraphId, T>,
     edges: HashSet<Edge>,
@@ -25,23 +201,59 @@ impl<T> Graph<T> {
         self.nodes.entry(id).or_insert(value);
     }
 
+    /// Returns the node with its neighbours in ascending ID order.
     pub fn get_node(&self, id: GraphId) -> Option<GraphNode<&T>> {
-        self.nodes.get(&id).map(|value| GraphNode {
-            id,
-            value,
-            neighbours: self
+        self.nodes.get(&id).map(|value| {
+            let mut neighbours = self
                 .edges
                 .iter()
                 .filter_map(|edge| if edge.from == id { Some(edge.to) } else { None })
-                .collect(),
+                .collect::<Vec<_>>();
+            neighbours.sort_unstable();
+
+            GraphNode {
+                id,
+                value,
+                neighbours,
+            }
         })
     }
 
+    /// Returns the sorted IDs of nodes with an edge pointing to `id`.
+    pub fn predecessors(&self, id: GraphId) -> Vec<GraphId> {
+        let mut predecessors = self
+            .edges
+            .iter()
+            .filter_map(|edge| (edge.to == id).then_some(edge.from))
+            .collect::<Vec<_>>();
+        predecessors.sort_unstable();
+
+        predecessors
+    }
+
     pub fn delete_node(&mut self, id: GraphId) {
         self.edges.retain(|edge| edge.from != id && edge.to != id);
         self.nodes.remove(&id);
     }
 
+    /// Removes a node like [`delete_node`](Self::delete_node), returning its value and the
+    /// edges that were attached to it as sorted `(from, to)` pairs, or `None` if it doesn't
+    /// exist.
+    pub fn remove_node_full(&mut self, id: GraphId) -> Option<(T, Vec<(GraphId, GraphId)>)> {
+        let value = self.nodes.remove(&id)?;
+        let mut detached = Vec::new();
+        self.edges.retain(|edge| {
+            let incident = edge.from == id || edge.to == id;
+            if incident {
+                detached.push((edge.from, edge.to));
+            }
+            !incident
+        });
+        detached.sort_unstable();
+
+        Some((value, detached))
+    }
+
     pub fn add_edge(&mut self, from: GraphId, to: GraphId) {
         if self.nodes.contains_key(&from) && self.nodes.contains_key(&to) {
             self.edges.insert(Edge { from, to });
@@ -52,36 +264,256 @@ impl<T> Graph<T> {
         self.edges.retain(|edge| edge.from != from || edge.to != to);
     }
 
-    pub fn bfs(&self, source: GraphId)
+    pub(crate) fn adjacency(&self) -> HashMap<GraphId, Vec<GraphId>> {
+        let mut adjacency: HashMap<GraphId, Vec<GraphId>> = HashMap::new();
+        for edge in &self.edges {
+            adjacency.entry(edge.from).or_default().push(edge.to);
+        }
+        for neighbours in adjacency.values_mut() {
+            neighbours.sort_unstable();
+        }
+
+        adjacency
+    }
+
+    /// Like [`adjacency`](Self::adjacency), but maps every node to its sorted predecessors.
+    pub(crate) fn reverse_adjacency(&self) -> HashMap<GraphId, Vec<GraphId>> {
+        let mut adjacency: HashMap<GraphId, Vec<GraphId>> = HashMap::new();
+        for edge in &self.edges {
+            adjacency.entry(edge.to).or_default().push(edge.from);
+        }
+        for neighbours in adjacency.values_mut() {
+            neighbours.sort_unstable();
+        }
+
+        adjacency
+    }
+
+    /// Returns the nodes reachable from `source` in breadth-first order, visiting neighbours in
+    /// ascending ID order. Empty if `source` doesn't exist.
+    pub fn bfs_order(&self, source: GraphId) -> Vec<GraphId> {
+        self.bfs_order_filtered(source, &SearchFilter::new())
+    }
+
+    /// Like [`bfs_order`](Self::bfs_order), but skips the nodes and edges hidden by `filter`.
+    /// Empty if `source` is hidden.
+    pub fn bfs_order_filtered(
+        &self,
+        source: GraphId,
+        filter: &SearchFilter<'_, T>,
+    ) -> Vec<GraphId> {
+        if !filter.allows_node(self, source) {
+            return Vec::new();
+        }
+
+        let adjacency = self.filtered_adjacency(filter);
+        let mut order = Vec::new();
+        let mut visited = HashSet::new();
+        visited.insert(source);
+        let mut queue = VecDeque::from([source]);
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+            for &neighbour in adjacency.get(&id).into_iter().flatten() {
+                if visited.insert(neighbour) {
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        order
+    }
+
+    /// Returns the nodes reachable from `source` grouped by their distance in hops, each
+    /// layer sorted by ID: `source` alone, then its successors, then theirs, and so on. Layers
+    /// are computed as the iterator advances, so stopping early skips the rest of the search.
+    /// Empty if `source` doesn't exist.
+    pub fn bfs_layers(&self, source: GraphId) -> impl Iterator<Item = Vec<GraphId>> {
+        let adjacency = self.adjacency();
+        let mut visited = HashSet::new();
+        let mut layer = Vec::new();
+        if self.nodes.contains_key(&source) {
+            visited.insert(source);
+            layer.push(source);
+        }
+
+        core::iter::from_fn(move || {
+            if layer.is_empty() {
+                return None;
+            }
+
+            let mut next = layer
+                .iter()
+                .flat_map(|id| adjacency.get(id).into_iter().flatten())
+                .copied()
+                .filter(|neighbour| visited.insert(*neighbour))
+                .collect::<Vec<_>>();
+            next.sort_unstable();
+
+            Some(core::mem::replace(&mut layer, next))
+        })
+    }
+
+    /// Returns the nodes reachable from `source` in depth-first preorder, visiting neighbours
+    /// in ascending ID order. Empty if `source` doesn't exist.
+    pub fn dfs_order(&self, source: GraphId) -> Vec<GraphId> {
+        self.dfs_order_filtered(source, &SearchFilter::new())
+    }
+
+    /// Like [`dfs_order`](Self::dfs_order), but skips the nodes and edges hidden by `filter`.
+    /// Empty if `source` is hidden.
+    pub fn dfs_order_filtered(
+        &self,
+        source: GraphId,
+        filter: &SearchFilter<'_, T>,
+    ) -> Vec<GraphId> {
+        if !filter.allows_node(self, source) {
+            return Vec::new();
+        }
+
+        let adjacency = self.filtered_adjacency(filter);
+        let mut order = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = Vec::from([source]);
+        while let Some(id) = stack.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            order.push(id);
+            // Pushed in reverse, so that the smallest neighbour is popped first.
+            for &neighbour in adjacency.get(&id).into_iter().flatten().rev() {
+                if !visited.contains(&neighbour) {
+                    stack.push(neighbour);
+                }
+            }
+        }
+
+        order
+    }
+
+    /// Visits the nodes reachable from `source` in breadth-first order, stopping as soon as
+    /// `visitor` returns [`ControlFlow::Break`]. Neighbours are visited, and listed in the
+    /// visited nodes, in ascending ID order.
+    ///
+    /// Returns the value `visitor` broke with, or `None` if it visited every reachable node.
+    pub fn bfs_visit<B>(
+        &self,
+        source: GraphId,
+        visitor: impl FnMut(&GraphNode<&T>) -> ControlFlow<B>,
+    ) -> Result<Option<B>, GraphError> {
+        self.bfs_visit_by(source, Ord::cmp, visitor)
+    }
+
+    /// Like [`bfs_visit`](Self::bfs_visit), but visits and lists neighbours in the order given
+    /// by `compare`.
+    pub fn bfs_visit_by<B>(
+        &self,
+        source: GraphId,
+        mut compare: impl FnMut(&GraphId, &GraphId) -> Ordering,
+        mut visitor: impl FnMut(&GraphNode<&T>) -> ControlFlow<B>,
+    ) -> Result<Option<B>, GraphError> {
+        if !self.nodes.contains_key(&source) {
+            return Err(GraphError::NodeNotFound(source));
+        }
+        debug_span!("bfs", source);
+
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::from([source]);
+        #[cfg(feature = "tracing")]
+        let mut max_frontier = 1;
+        while let Some(id) = queue.pop_front() {
+            if !visited.insert(id) {
+                continue;
+            }
+            let mut node = self.get_node(id).expect("edges only join existing nodes");
+            node.neighbours.sort_by(&mut compare);
+            if let ControlFlow::Break(value) = visitor(&node) {
+                debug_event!(visited = visited.len(), "bfs stopped by visitor");
+                return Ok(Some(value));
+            }
+            queue.extend(&node.neighbours);
+            #[cfg(feature = "tracing")]
+            {
+                max_frontier = max_frontier.max(queue.len());
+            }
+        }
+        debug_event!(visited = visited.len(), max_frontier, "bfs finished");
+
+        Ok(None)
+    }
+
+    /// Like [`bfs_visit`](Self::bfs_visit), but in depth-first preorder, as
+    /// [`dfs_order`](Self::dfs_order).
+    pub fn dfs_visit<B>(
+        &self,
+        source: GraphId,
+        mut visitor: impl FnMut(&GraphNode<&T>) -> ControlFlow<B>,
+    ) -> Result<Option<B>, GraphError> {
+        if !self.nodes.contains_key(&source) {
+            return Err(GraphError::NodeNotFound(source));
+        }
+        debug_span!("dfs", source);
+
+        let mut visited = HashSet::new();
+        let mut stack = Vec::from([source]);
+        while let Some(id) = stack.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            let node = self.get_node(id).expect("edges only join existing nodes");
+            if let ControlFlow::Break(value) = visitor(&node) {
+                debug_event!(visited = visited.len(), "dfs stopped by visitor");
+                return Ok(Some(value));
+            }
+            // Pushed in reverse, so that the smallest neighbour is popped first.
+            for &neighbour in node.neighbours.iter().rev() {
+                if !visited.contains(&neighbour) {
+                    stack.push(neighbour);
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn bfs(&self, source: GraphId) -> Result<(), GraphError>
     where
         T: Display,
     {
-        let mut visited = HashSet::new();
-        let mut queue = VecDeque::from([source]);
-        while !queue.is_empty() {
-            let id = queue.pop_front().unwrap_or_default();
-            if !visited.contains(&id) {
-                visited.insert(id);
-                let node = if let Some(node) = self.get_node(id) {
-                    node
-                } else {
-                    eprintln!("Error: Tried to access nonexistent node");
-                    continue;
-                };
-
-                println!(
-                    "ID: {}\nValue: {}\nNeighbours: {}\n",
-                    node.id,
-                    &node.value,
-                    node.neighbours
-                        .iter()
-                        .map(ToString::to_string)
-                        .collect::<Vec<String>>()
-                        .join(", ")
-                );
-
-                queue.extend(node.neighbours);
+        self.write_bfs(source, &mut std::io::stdout())
+    }
+
+    /// Like [`bfs`](Self::bfs), but writes every visited node to `out` instead of stdout.
+    #[cfg(feature = "std")]
+    pub fn write_bfs(
+        &self,
+        source: GraphId,
+        out: &mut (impl std::io::Write + ?Sized),
+    ) -> Result<(), GraphError>
+    where
+        T: Display,
+    {
+        let failure = self.bfs_visit(source, |node| {
+            let written = writeln!(
+                out,
+                "ID: {}\nValue: {}\nNeighbours: {}\n",
+                node.id,
+                &node.value,
+                node.neighbours
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            );
+            match written {
+                Ok(()) => ControlFlow::Continue(()),
+                Err(e) => ControlFlow::Break(e),
             }
+        })?;
+
+        match failure {
+            Some(e) => Err(e.into()),
+            None => Ok(()),
         }
     }
 }
@@ -92,6 +524,67 @@ impl<T> Default for Graph<T> {
     }
 }
 
+impl<T> Index<GraphId> for Graph<T> {
+    type Output = T;
+
+    fn index(&self, id: GraphId) -> &Self::Output {
+        self.nodes
+            .get(&id)
+            .unwrap_or_else(|| panic!("No node with ID {id}"))
+    }
+}
+
+impl<T> IndexMut<GraphId> for Graph<T> {
+    fn index_mut(&mut self, id: GraphId) -> &mut Self::Output {
+        self.nodes
+            .get_mut(&id)
+            .unwrap_or_else(|| panic!("No node with ID {id}"))
+    }
+}
+
+impl<T: Display> Display for Graph<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut adjacency: BTreeMap<GraphId, Vec<GraphId>> = BTreeMap::new();
+        for edge in &self.edges {
+            adjacency.entry(edge.from).or_default().push(edge.to);
+        }
+
+        for (index, (id, value)) in self.sorted_nodes().into_iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{id}: {value}")?;
+            if let Some(neighbours) = adjacency.get_mut(&id) {
+                neighbours.sort_unstable();
+                let neighbours = neighbours
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(f, " -> {neighbours}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Debug> Debug for Graph<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Graph")
+            .field("nodes", &self.nodes.iter().collect::<BTreeMap<_, _>>())
+            .field("edges", &self.sorted_edges())
+            .finish()
+    }
+}
+
+impl<T: Hash> Hash for Graph<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.sorted_nodes().hash(state);
+        self.sorted_edges().hash(state);
+    }
+}
+
 impl<T, const N: usize, const M: usize> From<([(GraphId, T); N], [(GraphId, GraphId); M])>
     for Graph<T>
 {
@@ -108,7 +601,7 @@ impl<T, const N: usize, const M: usize> From<([(GraphId, T); N], [(GraphId, Grap
     }
 }
 
-#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Ord, PartialOrd)]
 struct Edge {
     from: GraphId,
     to: GraphId,
@@ -202,7 +695,7 @@ mod tests {
         assert_eq!(node.id, 7, "Node ID doesn't match");
         assert_eq!(node.value, "September", "Node value doesn't match");
         assert_eq!(
-            HashSet::from_iter(node.neighbours),
+            HashSet::<_>::from_iter(node.neighbours),
             HashSet::from([1, 5, 6]),
             "Node neighbours doesn't match"
         );
@@ -215,6 +708,17 @@ mod tests {
         assert!(node.is_none(), "Expected node to be empty");
     }
 
+    #[test]
+    fn predecessors_sorted() {
+        let graph: Graph<String> = get_test_graph();
+        assert_eq!(
+            graph.predecessors(3),
+            vec![4, 5, 6],
+            "Predecessors mismatch"
+        );
+        assert!(graph.predecessors(7).is_empty(), "Expected no predecessors");
+    }
+
     #[test]
     fn delete_node_existing() {
         let mut graph: Graph<i32> = Graph::new();
@@ -274,6 +778,236 @@ mod tests {
         assert_eq!(graph.edges.len(), 10, "Edges count changed");
     }
 
+    #[test]
+    fn graph_eq_same_structure() {
+        let mut graph = get_test_graph();
+        assert_eq!(graph, get_test_graph(), "Expected graphs to be equal");
+
+        graph.delete_edge(1, 2);
+        assert_ne!(graph, get_test_graph(), "Expected graphs to differ by edge");
+    }
+
+    #[test]
+    fn graph_eq_different_values() {
+        let graph: Graph<i32> = Graph::from(([(1, 2)], []));
+        let other: Graph<i32> = Graph::from(([(1, 3)], []));
+        assert_ne!(graph, other, "Expected graphs to differ by value");
+    }
+
+    #[test]
+    fn graph_hash_dedup() {
+        let mut reversed = Graph::new();
+        let mut nodes = get_test_graph().nodes.into_iter().collect::<Vec<_>>();
+        nodes.sort_by_key(|(id, _)| std::cmp::Reverse(*id));
+        for (id, value) in nodes {
+            reversed.add_node(id, value);
+        }
+        for edge in get_test_graph().edges {
+            reversed.add_edge(edge.from, edge.to);
+        }
+
+        let set = HashSet::<_>::from([get_test_graph(), reversed, Graph::new()]);
+        assert_eq!(set.len(), 2, "Expected equal graphs to be deduplicated");
+    }
+
+    #[test]
+    fn index_existing() {
+        let graph: Graph<String> = get_test_graph();
+        assert_eq!(graph[7], "September", "Node value doesn't match");
+    }
+
+    #[test]
+    fn index_mut_existing() {
+        let mut graph: Graph<String> = get_test_graph();
+        graph[7].push_str(" 1st");
+        assert_eq!(graph[7], "September 1st", "Node value wasn't updated");
+    }
+
+    #[test]
+    #[should_panic(expected = "No node with ID 9")]
+    fn index_nonexistent() {
+        let graph: Graph<String> = get_test_graph();
+        let _ = &graph[9];
+    }
+
+    #[test]
+    fn display_graph() {
+        let graph: Graph<String> = get_test_graph();
+        assert_eq!(
+            graph.to_string(),
+            "1: January -> 2\n\
+             2: March\n\
+             3: April -> 2\n\
+             4: May -> 3\n\
+             5: December -> 1, 3\n\
+             6: June -> 1, 3\n\
+             7: September -> 1, 5, 6",
+            "Display output mismatch"
+        );
+    }
+
+    #[test]
+    fn display_graph_empty() {
+        let graph: Graph<String> = Graph::new();
+        assert_eq!(graph.to_string(), "", "Expected empty output");
+    }
+
+    #[test]
+    fn debug_graph_sorted() {
+        let graph: Graph<i32> = Graph::from(([(2, 20), (1, 10)], [(2, 1), (1, 2)]));
+        assert_eq!(
+            format!("{graph:?}"),
+            "Graph { nodes: {1: 10, 2: 20}, edges: [(1, 2), (2, 1)] }",
+            "Debug output mismatch"
+        );
+        assert!(
+            format!("{graph:#?}").contains("\n    nodes: {\n        1: 10,"),
+            "Expected alternate Debug to be pretty-printed"
+        );
+    }
+
+    #[test]
+    fn remove_node_full_returns_edges() {
+        let mut graph = Graph::from((
+            [(1, "a"), (2, "b"), (3, "c")],
+            [(2, 1), (1, 3), (2, 3), (1, 1)],
+        ));
+        assert_eq!(
+            graph.remove_node_full(1),
+            Some(("a", vec![(1, 1), (1, 3), (2, 1)])),
+            "Detached edges mismatch"
+        );
+        assert_eq!(
+            graph,
+            Graph::from(([(2, "b"), (3, "c")], [(2, 3)])),
+            "Graph doesn't match"
+        );
+        assert_eq!(graph.remove_node_full(1), None, "Expected missing node");
+    }
+
+    #[test]
+    fn bfs_visit_stops_early() {
+        let graph = Graph::from(([(1, "a"), (2, "b"), (3, "c")], [(1, 2), (2, 3)]));
+        let mut visited = Vec::new();
+        graph
+            .bfs_visit(1, |node| {
+                visited.push(*node.id());
+                if **node.value() == "b" {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            })
+            .unwrap();
+        assert_eq!(visited, vec![1, 2], "Visited nodes mismatch");
+
+        assert!(
+            matches!(
+                graph.bfs_visit::<()>(4, |_| panic!("Expected nothing to be visited")),
+                Err(GraphError::NodeNotFound(4))
+            ),
+            "Expected missing source error"
+        );
+    }
+
+    #[test]
+    fn traversals_visit_neighbours_in_order() {
+        let graph = Graph::from((
+            [(1, "a"), (2, "b"), (3, "c"), (4, "d"), (5, "e")],
+            [(1, 4), (1, 2), (1, 3), (2, 5), (4, 5)],
+        ));
+        let mut visited = Vec::new();
+        graph
+            .bfs_visit(1, |node| {
+                visited.push((*node.id(), node.neighbour_ids().to_vec()));
+                ControlFlow::<()>::Continue(())
+            })
+            .unwrap();
+        assert_eq!(
+            visited[0],
+            (1, vec![2, 3, 4]),
+            "Expected neighbours in ascending order"
+        );
+        assert_eq!(
+            graph.dfs_order(1),
+            vec![1, 2, 5, 3, 4],
+            "DFS order mismatch"
+        );
+
+        let mut reversed = Vec::new();
+        graph
+            .bfs_visit_by(
+                1,
+                |a, b| b.cmp(a),
+                |node| {
+                    reversed.push(*node.id());
+                    ControlFlow::<()>::Continue(())
+                },
+            )
+            .unwrap();
+        assert_eq!(reversed, vec![1, 4, 3, 2, 5], "Expected descending order");
+    }
+
+    #[test]
+    fn bfs_layers_group_by_distance() {
+        let graph = get_test_graph();
+        assert_eq!(
+            graph.bfs_layers(7).collect::<Vec<_>>(),
+            vec![vec![7], vec![1, 5, 6], vec![2, 3]],
+            "Layers mismatch"
+        );
+        assert_eq!(
+            graph.bfs_layers(7).nth(2),
+            Some(vec![2, 3]),
+            "Expected nodes exactly 2 hops away"
+        );
+        assert_eq!(graph.bfs_layers(8).count(), 0, "Expected no layers");
+    }
+
+    #[test]
+    fn visitors_propagate_break_values() {
+        let graph = get_test_graph();
+        let mut visited = 0;
+        let found = graph.bfs_visit(7, |node| {
+            visited += 1;
+            if node.value().starts_with('M') {
+                ControlFlow::Break(*node.id())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(found.unwrap(), Some(2), "Expected first match in BFS order");
+        assert_eq!(visited, 5, "Expected search to stop at the match");
+
+        let mut order = Vec::new();
+        let finished = graph.dfs_visit(7, |node| {
+            order.push(*node.id());
+            ControlFlow::<()>::Continue(())
+        });
+        assert_eq!(finished.unwrap(), None, "Expected full traversal");
+        assert_eq!(order, graph.dfs_order(7), "Expected DFS order");
+        assert!(
+            matches!(
+                graph.dfs_visit(9, |_| ControlFlow::<()>::Continue(())),
+                Err(GraphError::NodeNotFound(9))
+            ),
+            "Expected missing source"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn write_bfs_output() {
+        let graph = Graph::from(([(1, "a"), (2, "b"), (3, "c")], [(1, 2), (2, 3)]));
+        let mut out = Vec::new();
+        graph.write_bfs(2, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "ID: 2\nValue: b\nNeighbours: 3\n\nID: 3\nValue: c\nNeighbours: \n\n",
+            "Output mismatch"
+        );
+    }
+
     fn get_test_graph() -> Graph<String> {
         Graph::from((
             [
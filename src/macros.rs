@@ -0,0 +1,82 @@
+/// Builds a [`Graph`](crate::Graph) from a list of nodes followed by an optional list of edges.
+///
+/// ```
+/// use basic_graph_lib::graph;
+///
+/// let graph = graph! { 1 => "a", 2 => "b"; 1 -> 2, 2 -> 1 };
+/// assert_eq!(graph[1], "a");
+/// assert_eq!(graph.get_node(2).unwrap().neighbour_ids(), &[1]);
+/// ```
+///
+/// Edge endpoints are single tokens, so wrap computed IDs in parentheses: `(base + 1) -> 2`.
+#[macro_export]
+macro_rules! graph {
+    (
+        $($id:expr => $value:expr),* $(,)?
+        $(; $($from:tt -> $to:tt),* $(,)?)?
+    ) => {{
+        #[allow(unused_mut)]
+        let mut graph = $crate::Graph::new();
+        $(graph.add_node($id, $value);)*
+        $($(
+            #[allow(unused_parens)]
+            graph.add_edge($from, $to);
+        )*)?
+        graph
+    }};
+}
+
+/// Enters a `tracing` debug span until the end of the enclosing block. Expands to nothing
+/// without the `tracing` feature.
+macro_rules! debug_span {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!($($arg)*).entered();
+    };
+}
+
+/// Emits a `tracing` debug event. Expands to nothing without the `tracing` feature.
+macro_rules! debug_event {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Graph;
+
+    #[test]
+    fn graph_macro_empty() {
+        let graph: Graph<i32> = graph! {};
+        assert_eq!(graph, Graph::new(), "Expected empty graph");
+    }
+
+    #[test]
+    fn graph_macro_nodes_only() {
+        let graph = graph! { 1 => "a", 2 => "b", };
+        assert_eq!(
+            graph,
+            Graph::from(([(1, "a"), (2, "b")], [])),
+            "Graph doesn't match"
+        );
+    }
+
+    #[test]
+    fn graph_macro_nodes_and_edges() {
+        let base = 1;
+        let graph = graph! { base => "a", base + 1 => "b"; 1 -> (base + 1), 2 -> 1 };
+        assert_eq!(
+            graph,
+            Graph::from(([(1, "a"), (2, "b")], [(1, 2), (2, 1)])),
+            "Graph doesn't match"
+        );
+    }
+
+    #[test]
+    fn graph_macro_ignores_dangling_edges() {
+        let graph = graph! { 1 => "a"; 1 -> 2 };
+        assert!(graph.edges.is_empty(), "Expected edges to be empty");
+    }
+}
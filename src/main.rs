@@ -1,35 +1,798 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process,
+    sync::mpsc,
+    time::Duration,
+};
 
-use basic_graph_lib::{Graph, GraphId};
+use basic_graph_lib::{
+    Direction, Graph, GraphError, GraphId, MergePolicy, Mutation, Progress, SvgOptions,
+    WeightedGraph,
+};
+use clap::ArgMatches;
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde_json::json;
+
+mod repl;
 
 fn main() {
+    let file_arg = || {
+        clap::arg!(<file> "Path to TGF file, or - to read from stdin")
+            .value_parser(clap::value_parser!(PathBuf))
+    };
     let matches = clap::command!()
-        .about("Traverses graph from provided TGF file and starting node")
-        .arg(clap::arg!(<file> "Path to TGF file").value_parser(clap::value_parser!(PathBuf)))
+        .about("Inspects graphs stored in TGF files")
+        .arg(
+            clap::arg!(-o --output <file> "Write results to a file instead of stdout")
+                .value_parser(clap::value_parser!(PathBuf))
+                .global(true),
+        )
         .arg(
-            clap::arg!([source] "Starting node ID")
-                .value_parser(clap::value_parser!(GraphId))
-                .default_value("1"),
+            clap::arg!(--format <format> "Output format of reports; graphs and images are unaffected")
+                .value_parser(["plain", "json"])
+                .default_value("plain")
+                .global(true),
+        )
+        .arg(clap::arg!(--json "Shorthand for --format json").global(true))
+        .arg(clap::arg!(--progress "Show a progress bar on stderr while reading graphs").global(true))
+        .subcommand(
+            edit_command("add-node", "Adds a node to a TGF file")
+                .arg(id_arg("id", "Node ID"))
+                .arg(clap::arg!(<value> "Node value")),
+        )
+        .subcommand(
+            edit_command(
+                "add-edge",
+                "Adds an edge between existing nodes to a TGF file",
+            )
+            .arg(id_arg("from", "Source node ID"))
+            .arg(id_arg("to", "Target node ID")),
+        )
+        .subcommand(
+            edit_command("rm-node", "Removes a node and its edges from a TGF file")
+                .arg(id_arg("id", "Node ID")),
+        )
+        .subcommand(
+            edit_command("rm-edge", "Removes an edge from a TGF file")
+                .arg(id_arg("from", "Source node ID"))
+                .arg(id_arg("to", "Target node ID")),
+        )
+        .subcommand(
+            clap::Command::new("bfs")
+                .about("Traverses graph from provided starting node")
+                .arg(file_arg())
+                .arg(
+                    clap::arg!([source] "Starting node ID")
+                        .value_parser(clap::value_parser!(GraphId))
+                        .default_value("1"),
+                )
+                .arg(watch_arg()),
+        )
+        .subcommand(
+            clap::Command::new("query")
+                .about("Prints the bindings of a pattern query as JSON")
+                .long_about(
+                    "Prints the bindings of a pattern query as JSON, \
+                     e.g. MATCH (a)->(b) WHERE a.value = \"September\"",
+                )
+                .arg(file_arg())
+                .arg(clap::arg!(<query> "Pattern query")),
+        )
+        .subcommand(
+            clap::Command::new("components")
+                .about("Lists connected components, one per line")
+                .arg(file_arg())
+                .arg(clap::arg!(--strong "List strongly connected components instead"))
+                .arg(watch_arg()),
+        )
+        .subcommand(
+            clap::Command::new("cycles")
+                .about("Prints every cycle, failing if there is any")
+                .arg(file_arg()),
+        )
+        .subcommand(
+            clap::Command::new("diff")
+                .about("Prints the nodes and edges added, removed or changed in the second file")
+                .arg(file_arg())
+                .arg(clap::arg!(<other> "Path to the newer TGF file").value_parser(clap::value_parser!(PathBuf))),
+        )
+        .subcommand(
+            clap::Command::new("merge")
+                .about("Prints the union of two graphs as TGF")
+                .arg(file_arg())
+                .arg(clap::arg!(<other> "Path to the TGF file to merge in").value_parser(clap::value_parser!(PathBuf)))
+                .arg(
+                    clap::arg!(--"on-conflict" <policy> "What to do with nodes whose values differ")
+                        .value_parser(["fail", "ours", "theirs"])
+                        .default_value("fail"),
+                ),
+        )
+        .subcommand(
+            clap::Command::new("neighbors")
+                .about("Lists the nodes within a number of hops of a node")
+                .arg(file_arg())
+                .arg(id_arg("id", "Node ID"))
+                .arg(clap::arg!(--out "Follow outgoing edges (default)"))
+                .arg(clap::arg!(--in "Follow incoming edges"))
+                .arg(clap::arg!(--both "Follow edges either way"))
+                .group(clap::ArgGroup::new("direction").args(["out", "in", "both"]))
+                .arg(
+                    clap::arg!(--depth <n> "Maximum number of hops")
+                        .value_parser(clap::value_parser!(usize))
+                        .default_value("1"),
+                ),
+        )
+        .subcommand(
+            clap::Command::new("node")
+                .about("Prints a node's value and the IDs of its neighbors")
+                .arg(file_arg())
+                .arg(id_arg("id", "Node ID")),
+        )
+        .subcommand(
+            clap::Command::new("path")
+                .about("Prints a shortest path and its total weight or hop count, failing if there is none")
+                .arg(file_arg())
+                .arg(clap::arg!(<from> "Start node ID").value_parser(clap::value_parser!(GraphId)))
+                .arg(clap::arg!(<to> "Target node ID").value_parser(clap::value_parser!(GraphId)))
+                .arg(watch_arg()),
+        )
+        .subcommand(
+            clap::Command::new("render")
+                .about("Draws the graph as DOT, SVG or PNG (PNG requires Graphviz)")
+                .arg(file_arg())
+                .arg(
+                    clap::arg!(--format <format> "Output format")
+                        .value_parser(["dot", "svg", "png"])
+                        .default_value("svg"),
+                )
+                .arg(
+                    clap::arg!(--"highlight-path" <ids> "Highlight a shortest path")
+                        .num_args(2)
+                        .value_names(["from", "to"])
+                        .value_parser(clap::value_parser!(GraphId)),
+                ),
         )
+        .subcommand(
+            clap::Command::new("repl")
+                .about("Opens an interactive shell for exploring and editing a TGF file")
+                .arg(file_arg()),
+        )
+        .subcommand(
+            clap::Command::new("stats")
+                .about("Prints node and edge counts, density, degrees, components and DAG-ness")
+                .arg(file_arg())
+                .arg(watch_arg()),
+        )
+        .subcommand_required(true)
         .arg_required_else_help(true)
         .get_matches();
 
-    let path = matches.get_one::<PathBuf>("file").expect("required");
+    let (name, matches) = matches.subcommand().expect("subcommand is required");
+    // Output for --output is kept in memory and saved only once the command succeeds, so it
+    // can replace one of the input files.
+    let output = matches.get_one::<PathBuf>("output");
+    let mut buffer = Vec::new();
+    let mut stdout = io::stdout().lock();
+    let out: &mut dyn Write = if output.is_some() {
+        &mut buffer
+    } else {
+        &mut stdout
+    };
+    let result = match name {
+        "add-node" | "add-edge" | "rm-node" | "rm-edge" => edit_graph(name, matches, out),
+        "bfs" => watch(traverse_graph, matches, out),
+        "components" => watch(list_components, matches, out),
+        "cycles" => list_cycles(matches, out),
+        "diff" => diff_graphs(matches, out),
+        "merge" => merge_graphs(matches, out),
+        "neighbors" => list_neighbours(matches, out),
+        "node" => print_node(matches, out),
+        "path" => watch(find_path, matches, out),
+        "query" => query_graph(matches, out),
+        "render" => render_graph(matches, out),
+        "repl" => repl::run_repl(matches, out),
+        "stats" => watch(print_stats, matches, out),
+        _ => unreachable!("subcommands are listed above"),
+    }
+    .and_then(|_| out.flush().map_err(write_error))
+    .and_then(|_| match output {
+        Some(path) => {
+            write_atomically(path, &buffer).map_err(|e| format!("Failed to write output file: {e}"))
+        }
+        None => Ok(()),
+    });
+    if let Err(e) = result {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+}
+
+fn edit_command(name: &'static str, about: &'static str) -> clap::Command {
+    clap::Command::new(name)
+        .about(about)
+        .long_about(format!(
+            "{about}. The file is rewritten in place unless it's - or --dry-run or --output \
+             is given, in which case the result is written to the output instead."
+        ))
+        .arg(
+            clap::arg!(<file> "Path to TGF file, or - to read from stdin")
+                .value_parser(clap::value_parser!(PathBuf)),
+        )
+        .arg(clap::arg!(--"dry-run" "Print the edited graph instead of saving it"))
+}
+
+fn watch_arg() -> clap::Arg {
+    clap::arg!(--watch "Re-run whenever the file changes, clearing the screen between runs")
+}
+
+fn id_arg(name: &'static str, help: &'static str) -> clap::Arg {
+    clap::Arg::new(name)
+        .help(help)
+        .required(true)
+        .value_parser(clap::value_parser!(GraphId))
+}
+
+/// Runs `command`, and with `--watch` keeps re-running it on every change to the graph file
+/// until interrupted. Failed runs are reported without stopping the watch.
+fn watch(
+    command: fn(&ArgMatches, &mut dyn Write) -> Result<(), String>,
+    matches: &ArgMatches,
+    out: &mut dyn Write,
+) -> Result<(), String> {
+    if !matches.get_flag("watch") {
+        return command(matches, out);
+    }
+    let file = matches.get_one::<PathBuf>("file").expect("required");
+    if file.as_os_str() == "-" {
+        return Err("Can't watch stdin, --watch needs a graph file".to_string());
+    }
+    if matches.contains_id("output") {
+        return Err("--watch redraws the terminal, so it can't be used with --output".to_string());
+    }
+
+    // Editors often save by replacing the file, which ends a watch on the file itself, so
+    // watch its directory instead.
+    let directory = match file.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)
+        .map_err(|e| format!("Failed to watch graph file: {e}"))?;
+    watcher
+        .watch(directory, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch graph file: {e}"))?;
+
+    let changed = |event: notify::Result<notify::Event>| {
+        event.is_ok_and(|event| {
+            matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            ) && event
+                .paths
+                .iter()
+                .any(|path| path.file_name() == file.file_name())
+        })
+    };
+    loop {
+        write!(out, "\x1b[2J\x1b[H").map_err(write_error)?;
+        if let Err(e) = command(matches, out) {
+            writeln!(out, "{e}").map_err(write_error)?;
+        }
+        out.flush().map_err(write_error)?;
+
+        while !receiver
+            .recv()
+            .map(changed)
+            .map_err(|_| "Stopped watching graph file".to_string())?
+        {}
+        // A single save usually comes as a burst of events, so wait for it to settle.
+        while receiver.recv_timeout(Duration::from_millis(100)).is_ok() {}
+    }
+}
+
+fn read_graph(matches: &ArgMatches) -> Result<Graph<String>, String> {
+    read_graph_arg(matches, "file")
+}
+
+fn read_graph_arg(matches: &ArgMatches, name: &str) -> Result<Graph<String>, String> {
+    let text = read_text(matches, name)?;
+    let graph = if matches.get_flag("progress") {
+        let graph = Graph::parse_with_progress(&text, &mut draw_progress);
+        eprintln!();
+        graph
+    } else {
+        text.parse()
+    };
+
+    graph.map_err(|e| format!("Failed to parse graph: {e}"))
+}
+
+/// Reads a graph whose edges may carry weights, giving the others weight 1.
+fn read_weighted_graph(matches: &ArgMatches) -> Result<WeightedGraph<String>, String> {
+    let text = read_text(matches, "file")?;
+    let graph = if matches.get_flag("progress") {
+        let graph = WeightedGraph::parse_with_progress(&text, &mut draw_progress);
+        eprintln!();
+        graph
+    } else {
+        text.parse()
+    };
+
+    graph.map_err(|e| format!("Failed to parse graph: {e}"))
+}
+
+/// Reads the graph file named by argument `name`, or stdin if it's `-`.
+fn read_text(matches: &ArgMatches, name: &str) -> Result<String, String> {
+    let file = matches.get_one::<PathBuf>(name).expect("required");
+    if file.as_os_str() == "-" {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .map(|_| text)
+            .map_err(|e| format!("Failed to read graph from stdin: {e}"))
+    } else {
+        fs::read_to_string(file).map_err(|e| format!("Failed to read graph file: {e}"))
+    }
+}
+
+fn draw_progress(progress: Progress) {
+    const WIDTH: usize = 40;
+    let filled = (progress.percent() / 100.0 * WIDTH as f64) as usize;
+    eprint!(
+        "\r[{}{}] {:3.0}%",
+        "#".repeat(filled),
+        " ".repeat(WIDTH - filled),
+        progress.percent()
+    );
+}
+
+fn edit_graph(name: &str, matches: &ArgMatches, out: &mut dyn Write) -> Result<(), String> {
+    let id = |name| *matches.get_one::<GraphId>(name).expect("required");
+    let mut graph = read_graph(matches)?;
+    let (mutation, failure) = match name {
+        "add-node" => {
+            let value = matches.get_one::<String>("value").expect("required");
+            (
+                Mutation::AddNode {
+                    id: id("id"),
+                    value: value.clone(),
+                },
+                format!("Node {} already exists", id("id")),
+            )
+        }
+        "add-edge" => {
+            let (from, to) = (id("from"), id("to"));
+            let failure = match [from, to]
+                .into_iter()
+                .find(|id| graph.get_node(*id).is_none())
+            {
+                Some(missing) => format!("Node {missing} doesn't exist"),
+                None => format!("Edge {from} -> {to} already exists"),
+            };
+            (Mutation::AddEdge { from, to }, failure)
+        }
+        "rm-node" => (
+            Mutation::RemoveNode { id: id("id") },
+            format!("Node {} doesn't exist", id("id")),
+        ),
+        _ => (
+            Mutation::RemoveEdge {
+                from: id("from"),
+                to: id("to"),
+            },
+            format!("Edge {} -> {} doesn't exist", id("from"), id("to")),
+        ),
+    };
+    if graph.apply(mutation).is_empty() {
+        return Err(failure);
+    }
+
+    let text = graph.serialize();
+    text.parse::<Graph<String>>()
+        .map_err(|e| format!("Edited graph doesn't parse back, not saving it: {e}"))?;
+    let file = matches.get_one::<PathBuf>("file").expect("required");
+    // With --output, the edited graph goes there instead of replacing the file.
+    if matches.get_flag("dry-run")
+        || file.as_os_str() == "-"
+        || matches.get_one::<PathBuf>("output").is_some()
+    {
+        return out.write_all(text.as_bytes()).map_err(write_error);
+    }
+
+    write_atomically(file, &text).map_err(save_error)
+}
+
+/// Saves `contents` to `file` through a temporary file, so that a failure can't leave a
+/// truncated file behind.
+fn write_atomically(file: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let mut temporary = file.to_path_buf().into_os_string();
+    temporary.push(".tmp");
+    fs::write(&temporary, contents).and_then(|_| fs::rename(&temporary, file))
+}
+
+fn save_error(e: io::Error) -> String {
+    format!("Failed to save graph file: {e}")
+}
+
+fn traverse_graph(matches: &ArgMatches, out: &mut dyn Write) -> Result<(), String> {
     let id = *matches
         .get_one::<GraphId>("source")
         .expect("has default value");
+    let graph = read_graph(matches)?;
+    if json_format(matches) {
+        if graph.get_node(id).is_none() {
+            return Err(GraphError::NodeNotFound(id).to_string());
+        }
+        let nodes = graph
+            .bfs_order(id)
+            .into_iter()
+            .map(|id| {
+                let mut neighbours = graph
+                    .get_node(id)
+                    .expect("visited")
+                    .neighbour_ids()
+                    .to_vec();
+                neighbours.sort_unstable();
+                json!({ "id": id, "value": graph[id], "neighbors": neighbours })
+            })
+            .collect::<Vec<_>>();
+        return writeln!(out, "{}", json!(nodes)).map_err(write_error);
+    }
 
-    if let Err(e) = traverse_graph(path, id) {
-        eprintln!("{e}");
+    graph.write_bfs(id, out).map_err(|e| e.to_string())
+}
+
+fn list_components(matches: &ArgMatches, out: &mut dyn Write) -> Result<(), String> {
+    let graph = read_graph(matches)?;
+    let components = if matches.get_flag("strong") {
+        graph.strongly_connected_components()
+    } else {
+        graph.connected_components()
+    };
+    if json_format(matches) {
+        return writeln!(out, "{}", json!(components)).map_err(write_error);
+    }
+    for component in components {
+        writeln!(out, "{}", join_ids(&component, ", ")).map_err(write_error)?;
+    }
+
+    Ok(())
+}
+
+fn list_cycles(matches: &ArgMatches, out: &mut dyn Write) -> Result<(), String> {
+    let cycles = read_graph(matches)?.cycles();
+    if json_format(matches) {
+        writeln!(out, "{}", json!(cycles)).map_err(write_error)?;
+    } else {
+        for cycle in &cycles {
+            writeln!(out, "{} -> {}", join_ids(cycle, " -> "), cycle[0]).map_err(write_error)?;
+        }
+    }
+
+    match cycles.len() {
+        0 => Ok(()),
+        1 => Err("Found 1 cycle".to_string()),
+        count => Err(format!("Found {count} cycles")),
+    }
+}
+
+fn diff_graphs(matches: &ArgMatches, out: &mut dyn Write) -> Result<(), String> {
+    let (ours, theirs) = (read_graph(matches)?, read_graph_arg(matches, "other")?);
+    let diff = ours.diff(&theirs);
+    if json_format(matches) {
+        let nodes = |nodes: &[(GraphId, &String)]| {
+            nodes
+                .iter()
+                .map(|(id, value)| json!({ "id": id, "value": value }))
+                .collect::<Vec<_>>()
+        };
+        let edges = |edges: &[(GraphId, GraphId)]| {
+            edges
+                .iter()
+                .map(|(from, to)| json!({ "from": from, "to": to }))
+                .collect::<Vec<_>>()
+        };
+        let diff = json!({
+            "added_nodes": nodes(&diff.added_nodes),
+            "removed_nodes": nodes(&diff.removed_nodes),
+            "changed_nodes": diff
+                .changed_nodes
+                .iter()
+                .map(|(id, old, new)| json!({ "id": id, "old": old, "new": new }))
+                .collect::<Vec<_>>(),
+            "added_edges": edges(&diff.added_edges),
+            "removed_edges": edges(&diff.removed_edges),
+        });
+        return writeln!(out, "{diff}").map_err(write_error);
+    }
+
+    for (id, value) in &diff.added_nodes {
+        writeln!(out, "+ node {id}: {value}").map_err(write_error)?;
+    }
+    for (id, value) in &diff.removed_nodes {
+        writeln!(out, "- node {id}: {value}").map_err(write_error)?;
+    }
+    for (id, old, new) in &diff.changed_nodes {
+        writeln!(out, "~ node {id}: {old} -> {new}").map_err(write_error)?;
+    }
+    for (from, to) in &diff.added_edges {
+        writeln!(out, "+ edge {from} -> {to}").map_err(write_error)?;
+    }
+    for (from, to) in &diff.removed_edges {
+        writeln!(out, "- edge {from} -> {to}").map_err(write_error)?;
+    }
+
+    Ok(())
+}
+
+fn merge_graphs(matches: &ArgMatches, out: &mut dyn Write) -> Result<(), String> {
+    let policy = match matches.get_one::<String>("on-conflict").map(String::as_str) {
+        Some("ours") => MergePolicy::KeepOurs,
+        Some("theirs") => MergePolicy::KeepTheirs,
+        _ => MergePolicy::Fail,
+    };
+    let mut graph = read_graph(matches)?;
+    graph
+        .merge(read_graph_arg(matches, "other")?, policy)
+        .map_err(|e| format!("Failed to merge graphs: {e}"))?;
+
+    out.write_all(graph.serialize().as_bytes())
+        .map_err(write_error)
+}
+
+fn list_neighbours(matches: &ArgMatches, out: &mut dyn Write) -> Result<(), String> {
+    let id = *matches.get_one::<GraphId>("id").expect("required");
+    let depth = *matches
+        .get_one::<usize>("depth")
+        .expect("has default value");
+    let direction = if matches.get_flag("in") {
+        Direction::Incoming
+    } else if matches.get_flag("both") {
+        Direction::Both
+    } else {
+        Direction::Outgoing
+    };
+    let graph = read_graph(matches)?;
+    let neighbours = graph
+        .neighbourhood(id, depth, direction)
+        .ok_or_else(|| format!("Node {id} doesn't exist"))?;
+
+    if json_format(matches) {
+        let neighbours = neighbours
+            .iter()
+            .map(|(id, depth)| json!({ "id": id, "depth": depth, "value": graph[*id] }))
+            .collect::<Vec<_>>();
+        return writeln!(out, "{}", json!(neighbours)).map_err(write_error);
+    }
+
+    let rows = neighbours
+        .iter()
+        .map(|(id, depth)| vec![id.to_string(), depth.to_string(), graph[*id].clone()]);
+    write_table(
+        out,
+        [vec![
+            "ID".to_string(),
+            "DEPTH".to_string(),
+            "VALUE".to_string(),
+        ]]
+        .into_iter()
+        .chain(rows),
+    )
+}
+
+fn print_node(matches: &ArgMatches, out: &mut dyn Write) -> Result<(), String> {
+    let id = *matches.get_one::<GraphId>("id").expect("required");
+    let graph = read_graph(matches)?;
+    let node = graph
+        .get_node(id)
+        .ok_or_else(|| format!("Node {id} doesn't exist"))?;
+    let mut successors = node.neighbour_ids().to_vec();
+    successors.sort_unstable();
+    let predecessors = graph.predecessors(id);
+
+    if json_format(matches) {
+        let node = json!({
+            "id": id,
+            "value": node.value(),
+            "out": successors,
+            "in": predecessors,
+        });
+        return writeln!(out, "{node}").map_err(write_error);
     }
+
+    write_table(
+        out,
+        [
+            vec!["ID".to_string(), id.to_string()],
+            vec!["Value".to_string(), node.value().to_string()],
+            vec!["Out".to_string(), join_ids(&successors, " ")],
+            vec!["In".to_string(), join_ids(&predecessors, " ")],
+        ],
+    )
 }
 
-fn traverse_graph(file: &PathBuf, id: GraphId) -> Result<(), String> {
-    let graph: Graph<String> = fs::read_to_string(file)
-        .map_err(|e| format!("Failed to read graph file: {e}"))?
-        .parse()
-        .map_err(|e| format!("Failed to parse graph: {e}"))?;
-    graph.bfs(id);
+fn find_path(matches: &ArgMatches, out: &mut dyn Write) -> Result<(), String> {
+    let from = *matches.get_one::<GraphId>("from").expect("required");
+    let to = *matches.get_one::<GraphId>("to").expect("required");
+    let graph = read_weighted_graph(matches)?;
+    let weighted = graph
+        .weighted_edges()
+        .iter()
+        .any(|(_, _, weight)| *weight != 1.0);
+    let path = graph
+        .shortest_path(from, to)
+        .ok_or_else(|| format!("No path from {from} to {to}"))?;
+    let hops = path.len() - 1;
+    if json_format(matches) {
+        let path = if weighted {
+            json!({ "path": *path, "distance": path.cost() })
+        } else {
+            json!({ "path": *path, "distance": hops })
+        };
+        return writeln!(out, "{path}").map_err(write_error);
+    }
+    writeln!(out, "{}", join_ids(&path, " -> ")).map_err(write_error)?;
+    if weighted {
+        let weight = path.cost().expect("Dijkstra paths have a cost");
+        writeln!(out, "Distance: {weight}").map_err(write_error)?;
+    } else {
+        writeln!(out, "Distance: {hops}").map_err(write_error)?;
+    }
 
     Ok(())
 }
+
+fn query_graph(matches: &ArgMatches, out: &mut dyn Write) -> Result<(), String> {
+    let query = matches.get_one::<String>("query").expect("required");
+    let bindings = read_graph(matches)?
+        .query_str(query)
+        .map_err(|e| format!("Failed to run query: {e}"))?;
+    writeln!(out, "{bindings}").map_err(write_error)?;
+
+    Ok(())
+}
+
+fn render_graph(matches: &ArgMatches, out: &mut dyn Write) -> Result<(), String> {
+    let graph = read_graph(matches)?;
+    let path = match matches.get_many::<GraphId>("highlight-path") {
+        Some(ids) => {
+            let ids = ids.copied().collect::<Vec<_>>();
+            graph
+                .shortest_path(ids[0], ids[1])
+                .map(|path| path.into_nodes())
+                .ok_or_else(|| format!("No path from {} to {}", ids[0], ids[1]))?
+        }
+        None => Vec::new(),
+    };
+
+    let rendered = match matches.get_one::<String>("format").map(String::as_str) {
+        Some("dot") => graph.to_dot_with_path(&path).into_bytes(),
+        Some("png") => run_graphviz(&graph.to_dot_with_path(&path))?,
+        _ => graph
+            .to_svg_with_path(&SvgOptions::default(), &path)
+            .into_bytes(),
+    };
+    out.write_all(&rendered).map_err(write_error)
+}
+
+/// Converts DOT to PNG with Graphviz's `dot` executable.
+fn run_graphviz(dot: &str) -> Result<Vec<u8>, String> {
+    let mut child = process::Command::new("dot")
+        .arg("-Tpng")
+        .stdin(process::Stdio::piped())
+        .stdout(process::Stdio::piped())
+        .stderr(process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run Graphviz dot: {e}"))?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(dot.as_bytes())
+        .map_err(|e| format!("Failed to run Graphviz dot: {e}"))?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to run Graphviz dot: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Graphviz dot failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(output.stdout)
+}
+
+fn print_stats(matches: &ArgMatches, out: &mut dyn Write) -> Result<(), String> {
+    let stats = read_graph(matches)?.stats();
+    if json_format(matches) {
+        let degrees = stats
+            .degree_distribution
+            .iter()
+            .map(|(degree, count)| (degree.to_string(), json!(count)))
+            .collect::<serde_json::Map<_, _>>();
+        let stats = json!({
+            "nodes": stats.node_count,
+            "edges": stats.edge_count,
+            "density": stats.density,
+            "degree_distribution": degrees,
+            "components": stats.component_count,
+            "is_dag": stats.is_dag,
+            "assortativity": stats.assortativity,
+            "reciprocity": stats.reciprocity,
+            "sources": stats.source_count,
+            "sinks": stats.sink_count,
+        });
+        writeln!(out, "{stats}").map_err(write_error)?;
+    } else {
+        writeln!(out, "Nodes: {}", stats.node_count).map_err(write_error)?;
+        writeln!(out, "Edges: {}", stats.edge_count).map_err(write_error)?;
+        writeln!(out, "Density: {:.4}", stats.density).map_err(write_error)?;
+        writeln!(out, "Components: {}", stats.component_count).map_err(write_error)?;
+        writeln!(out, "DAG: {}", if stats.is_dag { "yes" } else { "no" }).map_err(write_error)?;
+        if let Some(assortativity) = stats.assortativity {
+            writeln!(out, "Assortativity: {assortativity:.4}").map_err(write_error)?;
+        }
+        if let Some(reciprocity) = stats.reciprocity {
+            writeln!(out, "Reciprocity: {reciprocity:.4}").map_err(write_error)?;
+        }
+        writeln!(out, "Sources: {}", stats.source_count).map_err(write_error)?;
+        writeln!(out, "Sinks: {}", stats.sink_count).map_err(write_error)?;
+        writeln!(out, "Degree distribution:").map_err(write_error)?;
+        for (degree, count) in &stats.degree_distribution {
+            writeln!(out, "  {degree}: {count}").map_err(write_error)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether reports should be written as JSON, as selected by the global `--format` or `--json`.
+fn json_format(matches: &ArgMatches) -> bool {
+    matches.get_flag("json")
+        || matches.get_one::<String>("format").map(String::as_str) == Some("json")
+}
+
+/// Writes rows with every column but the last padded to its widest cell.
+fn write_table(
+    out: &mut dyn Write,
+    rows: impl IntoIterator<Item = Vec<String>>,
+) -> Result<(), String> {
+    let rows = rows.into_iter().collect::<Vec<_>>();
+    let mut widths = Vec::new();
+    for row in &rows {
+        widths.resize(widths.len().max(row.len()), 0);
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    for row in &rows {
+        let mut line = String::new();
+        for (index, cell) in row.iter().enumerate() {
+            if index + 1 == row.len() {
+                line.push_str(cell);
+            } else {
+                line.push_str(&format!("{cell:<width$}  ", width = widths[index]));
+            }
+        }
+        writeln!(out, "{}", line.trim_end()).map_err(write_error)?;
+    }
+
+    Ok(())
+}
+
+fn join_ids(ids: &[GraphId], separator: &str) -> String {
+    ids.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn write_error(e: io::Error) -> String {
+    format!("Failed to write output: {e}")
+}
@@ -0,0 +1,238 @@
+use alloc::{collections::BTreeSet, vec, vec::Vec};
+use core::iter;
+
+use super::{collections::HashMap, Graph, GraphId};
+
+/// A square matrix over the nodes of a graph, stored as sparse `(row, column, value)`
+/// triplets sorted by row and column. Row and column `i` belong to `ids()[i]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphMatrix {
+    ids: Vec<GraphId>,
+    triplets: Vec<(usize, usize, f64)>,
+}
+
+impl GraphMatrix {
+    /// Returns the node ID of every row and column, in ascending order.
+    pub fn ids(&self) -> &[GraphId] {
+        &self.ids
+    }
+
+    /// Returns the row and column index of the node `id`.
+    pub fn index_of(&self, id: GraphId) -> Option<usize> {
+        self.ids.binary_search(&id).ok()
+    }
+
+    /// Returns the non-zero entries as `(row, column, value)`.
+    pub fn triplets(&self) -> &[(usize, usize, f64)] {
+        &self.triplets
+    }
+
+    pub fn to_dense(&self) -> Vec<Vec<f64>> {
+        let mut dense = vec![vec![0.0; self.ids.len()]; self.ids.len()];
+        for &(row, column, value) in &self.triplets {
+            dense[row][column] = value;
+        }
+
+        dense
+    }
+
+    #[cfg(feature = "ndarray")]
+    pub fn to_ndarray(&self) -> ndarray::Array2<f64> {
+        let mut array = ndarray::Array2::zeros((self.ids.len(), self.ids.len()));
+        for &(row, column, value) in &self.triplets {
+            array[[row, column]] = value;
+        }
+
+        array
+    }
+
+    #[cfg(feature = "nalgebra")]
+    pub fn to_nalgebra(&self) -> nalgebra::DMatrix<f64> {
+        let mut matrix = nalgebra::DMatrix::zeros(self.ids.len(), self.ids.len());
+        for &(row, column, value) in &self.triplets {
+            matrix[(row, column)] = value;
+        }
+
+        matrix
+    }
+
+    fn from_entries(
+        ids: Vec<GraphId>,
+        entries: impl IntoIterator<Item = (usize, usize, f64)>,
+    ) -> Self {
+        let mut triplets = entries
+            .into_iter()
+            .filter(|(_, _, value)| *value != 0.0)
+            .collect::<Vec<_>>();
+        triplets.sort_unstable_by_key(|(row, column, _)| (*row, *column));
+
+        Self { ids, triplets }
+    }
+}
+
+/// The matrices below treat the graph as undirected and simple: an edge in either or both
+/// directions joins its endpoints once, and self-loops are ignored.
+impl<T> Graph<T> {
+    pub fn adjacency_matrix(&self) -> GraphMatrix {
+        let (ids, neighbours) = self.undirected_neighbours();
+        let entries = neighbours
+            .iter()
+            .enumerate()
+            .flat_map(|(row, columns)| columns.iter().map(move |column| (row, *column, 1.0)))
+            .collect::<Vec<_>>();
+
+        GraphMatrix::from_entries(ids, entries)
+    }
+
+    /// Returns the diagonal matrix of node degrees.
+    pub fn degree_matrix(&self) -> GraphMatrix {
+        let (ids, neighbours) = self.undirected_neighbours();
+        let entries = neighbours
+            .iter()
+            .enumerate()
+            .map(|(row, columns)| (row, row, columns.len() as f64))
+            .collect::<Vec<_>>();
+
+        GraphMatrix::from_entries(ids, entries)
+    }
+
+    /// Returns the combinatorial Laplacian `L = D - A`.
+    pub fn laplacian_matrix(&self) -> GraphMatrix {
+        let (ids, neighbours) = self.undirected_neighbours();
+        let entries = neighbours
+            .iter()
+            .enumerate()
+            .flat_map(|(row, columns)| {
+                iter::once((row, row, columns.len() as f64))
+                    .chain(columns.iter().map(move |column| (row, *column, -1.0)))
+            })
+            .collect::<Vec<_>>();
+
+        GraphMatrix::from_entries(ids, entries)
+    }
+
+    /// Returns the symmetric normalized Laplacian `I - D^-1/2 A D^-1/2`. Rows of isolated
+    /// nodes are all zero. Only available with the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn normalized_laplacian_matrix(&self) -> GraphMatrix {
+        let (ids, neighbours) = self.undirected_neighbours();
+        let scale = neighbours
+            .iter()
+            .map(|columns| 1.0 / (columns.len() as f64).sqrt())
+            .collect::<Vec<_>>();
+        let entries = neighbours
+            .iter()
+            .enumerate()
+            .flat_map(|(row, columns)| {
+                let diagonal = if columns.is_empty() { 0.0 } else { 1.0 };
+                let scale = &scale;
+                iter::once((row, row, diagonal)).chain(
+                    columns
+                        .iter()
+                        .map(move |column| (row, *column, -scale[row] * scale[*column])),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        GraphMatrix::from_entries(ids, entries)
+    }
+
+    /// Returns the sorted node IDs and, for every node, the sorted indices of its undirected
+    /// neighbours.
+    fn undirected_neighbours(&self) -> (Vec<GraphId>, Vec<BTreeSet<usize>>) {
+        let mut ids = self.nodes.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        let index_of: HashMap<GraphId, usize> =
+            ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+        let mut neighbours = vec![BTreeSet::new(); ids.len()];
+        for edge in self.edges.iter().filter(|edge| edge.from != edge.to) {
+            let (from, to) = (index_of[&edge.from], index_of[&edge.to]);
+            neighbours[from].insert(to);
+            neighbours[to].insert(from);
+        }
+
+        (ids, neighbours)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_graph() -> Graph<()> {
+        Graph::from((
+            [(1, ()), (2, ()), (5, ()), (9, ())],
+            [(1, 2), (2, 1), (2, 5), (5, 5)],
+        ))
+    }
+
+    #[test]
+    fn degree_matrix_diagonal() {
+        let matrix = get_test_graph().degree_matrix();
+        assert_eq!(matrix.ids(), &[1, 2, 5, 9], "IDs mismatch");
+        assert_eq!(
+            matrix.triplets(),
+            &[(0, 0, 1.0), (1, 1, 2.0), (2, 2, 1.0)],
+            "Expected isolated node to be omitted"
+        );
+    }
+
+    #[test]
+    fn laplacian_matrix_dense() {
+        let matrix = get_test_graph().laplacian_matrix();
+        assert_eq!(
+            matrix.to_dense(),
+            vec![
+                vec![1.0, -1.0, 0.0, 0.0],
+                vec![-1.0, 2.0, -1.0, 0.0],
+                vec![0.0, -1.0, 1.0, 0.0],
+                vec![0.0, 0.0, 0.0, 0.0],
+            ],
+            "Laplacian mismatch"
+        );
+        assert!(
+            matrix
+                .to_dense()
+                .iter()
+                .all(|row| row.iter().sum::<f64>() == 0.0),
+            "Expected rows to sum to zero"
+        );
+    }
+
+    #[test]
+    fn adjacency_matrix_symmetric() {
+        let matrix = get_test_graph().adjacency_matrix();
+        let dense = matrix.to_dense();
+        let index = matrix.index_of(5).unwrap();
+        assert_eq!(dense[index][index], 0.0, "Expected self-loop to be ignored");
+        assert_eq!(matrix.triplets().len(), 4, "Entries count mismatch");
+        assert_eq!(matrix.index_of(3), None, "Expected unknown node");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn normalized_laplacian_matrix_values() {
+        let dense = get_test_graph().normalized_laplacian_matrix().to_dense();
+        assert_eq!(dense[1][1], 1.0, "Diagonal mismatch");
+        assert!(
+            (dense[0][1] + 1.0 / 2f64.sqrt()).abs() < 1e-12,
+            "Off-diagonal mismatch"
+        );
+        assert_eq!(dense[3][3], 0.0, "Expected zero row for isolated node");
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn to_ndarray_matches_dense() {
+        let matrix = get_test_graph().laplacian_matrix();
+        assert_eq!(matrix.to_ndarray()[[1, 2]], -1.0, "Entry mismatch");
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn to_nalgebra_matches_dense() {
+        let matrix = get_test_graph().laplacian_matrix();
+        assert_eq!(matrix.to_nalgebra()[(1, 1)], 2.0, "Entry mismatch");
+    }
+}
@@ -0,0 +1,248 @@
+use std::{
+    cmp::Ordering,
+    collections::VecDeque,
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use memmap2::Mmap;
+
+use super::{Graph, GraphId};
+
+/// Identifies a CSR file and its layout version.
+const MAGIC: &[u8; 8] = b"BGCSR\0\0\x01";
+/// Size of the magic bytes and the node and edge counts.
+const HEADER_LEN: usize = 24;
+
+impl<T> Graph<T> {
+    /// Writes the edges in the compressed sparse row layout read by [`MmapGraph`]. Node values
+    /// aren't written.
+    ///
+    /// The layout is the magic bytes `BGCSR\0\0\x01`, followed by little-endian `u64`s: the
+    /// node count `n`, the edge count `m`, the `n` sorted node IDs, `n + 1` offsets into the
+    /// targets, and the `m` target indices, sorted per node.
+    pub fn write_csr(&self, mut writer: impl Write) -> io::Result<()> {
+        let mut ids = self.nodes.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        let mut edges = self
+            .edges
+            .iter()
+            .map(|edge| {
+                let index = |id| ids.binary_search(&id).expect("endpoints exist") as u64;
+                (index(edge.from), index(edge.to))
+            })
+            .collect::<Vec<_>>();
+        edges.sort_unstable();
+
+        let mut offsets = vec![0u64; ids.len() + 1];
+        for (from, _) in &edges {
+            offsets[*from as usize + 1] += 1;
+        }
+        for index in 1..offsets.len() {
+            offsets[index] += offsets[index - 1];
+        }
+
+        writer.write_all(MAGIC)?;
+        let counts = [ids.len() as u64, edges.len() as u64];
+        let targets = edges.iter().map(|(_, to)| *to);
+        for value in counts
+            .into_iter()
+            .chain(ids.iter().copied())
+            .chain(offsets)
+            .chain(targets)
+        {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A read-only graph served straight from a memory-mapped file written by
+/// [`Graph::write_csr`].
+///
+/// Opening only validates the file's size, so startup cost doesn't depend on the size of the
+/// graph, and pages are loaded by the OS as queries touch them. Queries on a file that is
+/// corrupt otherwise may panic.
+#[derive(Debug)]
+pub struct MmapGraph {
+    map: Mmap,
+    node_count: usize,
+    edge_count: usize,
+}
+
+impl MmapGraph {
+    /// Maps the CSR file at `path`, failing with [`io::ErrorKind::InvalidData`] if it isn't
+    /// one.
+    ///
+    /// The file must not be modified while it is mapped.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the map is read-only, and the caller promises not to modify the file while
+        // it is mapped. All reads are bounds-checked against the validated length.
+        let map = unsafe { Mmap::map(&file)? };
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+        if map.len() < HEADER_LEN || &map[..MAGIC.len()] != MAGIC {
+            return Err(invalid("Not a CSR graph file"));
+        }
+
+        let mut graph = Self {
+            map,
+            node_count: 0,
+            edge_count: 0,
+        };
+        graph.node_count = graph.word(1) as usize;
+        graph.edge_count = graph.word(2) as usize;
+        let len = graph
+            .node_count
+            .checked_mul(2)
+            .and_then(|words| words.checked_add(graph.edge_count))
+            .and_then(|words| words.checked_add(1))
+            .and_then(|words| words.checked_mul(8))
+            .and_then(|len| len.checked_add(HEADER_LEN));
+        if len != Some(graph.map.len()) {
+            return Err(invalid("CSR graph file has the wrong size"));
+        }
+
+        Ok(graph)
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    pub fn contains_node(&self, id: GraphId) -> bool {
+        self.index_of(id).is_some()
+    }
+
+    /// Returns the IDs of the targets of `id` in ascending order, or `None` if it doesn't
+    /// exist.
+    pub fn neighbours(&self, id: GraphId) -> Option<impl Iterator<Item = GraphId> + '_> {
+        let index = self.index_of(id)?;
+
+        Some(self.target_indices(index).map(|target| self.id(target)))
+    }
+
+    /// Returns the nodes reachable from `source` in breadth-first order, like
+    /// [`Graph::bfs_order`].
+    pub fn bfs_order(&self, source: GraphId) -> Vec<GraphId> {
+        let Some(source) = self.index_of(source) else {
+            return Vec::new();
+        };
+
+        let mut order = Vec::new();
+        let mut visited = vec![false; self.node_count];
+        visited[source] = true;
+        let mut queue = VecDeque::from([source]);
+        while let Some(index) = queue.pop_front() {
+            order.push(self.id(index));
+            for target in self.target_indices(index) {
+                if !visited[target] {
+                    visited[target] = true;
+                    queue.push_back(target);
+                }
+            }
+        }
+
+        order
+    }
+
+    /// Reads the `index`-th little-endian `u64` of the file.
+    fn word(&self, index: usize) -> u64 {
+        let start = index * 8;
+        let bytes = self.map[start..start + 8].try_into().expect("8 bytes");
+
+        u64::from_le_bytes(bytes)
+    }
+
+    fn id(&self, index: usize) -> GraphId {
+        self.word(3 + index)
+    }
+
+    fn index_of(&self, id: GraphId) -> Option<usize> {
+        let (mut low, mut high) = (0, self.node_count);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            match self.id(middle).cmp(&id) {
+                Ordering::Less => low = middle + 1,
+                Ordering::Greater => high = middle,
+                Ordering::Equal => return Some(middle),
+            }
+        }
+
+        None
+    }
+
+    fn target_indices(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let offsets = 3 + self.node_count;
+        let targets = offsets + self.node_count + 1;
+        let (start, end) = (self.word(offsets + index), self.word(offsets + index + 1));
+
+        (start..end).map(move |offset| self.word(targets + offset as usize) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn write_test_graph(name: &str) -> std::path::PathBuf {
+        let graph = Graph::from((
+            [(10, ()), (20, ()), (30, ()), (40, ()), (50, ())],
+            [(10, 30), (10, 20), (20, 40), (30, 40), (40, 10)],
+        ));
+        let path = std::env::temp_dir().join(format!("bgraph-{name}-{}.csr", std::process::id()));
+        let mut bytes = Vec::new();
+        graph.write_csr(&mut bytes).unwrap();
+        fs::write(&path, bytes).unwrap();
+
+        path
+    }
+
+    #[test]
+    fn mmap_graph_queries() {
+        let path = write_test_graph("queries");
+        let graph = MmapGraph::open(&path).unwrap();
+        assert_eq!(graph.node_count(), 5, "Node count mismatch");
+        assert_eq!(graph.edge_count(), 5, "Edge count mismatch");
+        assert_eq!(
+            graph.neighbours(10).map(Iterator::collect::<Vec<_>>),
+            Some(vec![20, 30]),
+            "Neighbours mismatch"
+        );
+        assert!(graph.neighbours(15).is_none(), "Expected missing node");
+        assert!(graph.contains_node(50), "Expected isolated node");
+        assert_eq!(
+            graph.bfs_order(10),
+            vec![10, 20, 30, 40],
+            "BFS order mismatch"
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn mmap_graph_rejects_invalid_files() {
+        let path = write_test_graph("invalid");
+        let bytes = fs::read(&path).unwrap();
+        fs::write(&path, &bytes[..bytes.len() - 8]).unwrap();
+        let error = MmapGraph::open(&path).unwrap_err();
+        assert_eq!(
+            error.kind(),
+            io::ErrorKind::InvalidData,
+            "Error kind mismatch"
+        );
+        fs::write(&path, b"1 a\n#\n").unwrap();
+        assert!(
+            MmapGraph::open(&path).is_err(),
+            "Expected TGF to be rejected"
+        );
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,320 @@
+use alloc::{vec, vec::Vec};
+
+use super::{
+    collections::{HashMap, HashSet},
+    Edge, Graph, GraphId,
+};
+
+impl<T> Graph<T> {
+    /// Counts the connected induced subgraphs on `size` nodes, 3 or 4, by isomorphism class.
+    /// Entry `i` of the census counts occurrences of pattern `i` of
+    /// [`Graph::motifs`](Graph::motifs) with the same arguments, so censuses of different
+    /// graphs can be compared entry by entry.
+    ///
+    /// If `directed` is `false`, edge directions are ignored. Self-loops are always ignored.
+    ///
+    /// Panics unless `size` is 3 or 4.
+    pub fn motif_census(&self, size: usize, directed: bool) -> Vec<usize> {
+        let table = MotifTable::new(size, directed);
+        debug_span!("motif_census", nodes = self.nodes.len(), size, directed);
+
+        let mut neighbours = HashMap::<GraphId, Vec<GraphId>>::new();
+        for edge in self.edges.iter().filter(|edge| edge.from != edge.to) {
+            neighbours.entry(edge.from).or_default().push(edge.to);
+            neighbours.entry(edge.to).or_default().push(edge.from);
+        }
+        for list in neighbours.values_mut() {
+            list.sort_unstable();
+            list.dedup();
+        }
+
+        let mut census = vec![0; table.classes.len()];
+        let mut ids = neighbours.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        for root in ids {
+            let extension = neighbours[&root]
+                .iter()
+                .copied()
+                .filter(|id| *id > root)
+                .collect();
+            Self::extend_subgraph(
+                &mut vec![root],
+                extension,
+                root,
+                size,
+                &neighbours,
+                &mut |nodes| census[table.class_of(self, nodes)] += 1,
+            );
+        }
+
+        census
+    }
+
+    /// Calls `found` with every connected set of `size` nodes that contains `subgraph` and
+    /// otherwise only nodes greater than `root`, exactly once, with the ESU algorithm.
+    fn extend_subgraph(
+        subgraph: &mut Vec<GraphId>,
+        mut extension: Vec<GraphId>,
+        root: GraphId,
+        size: usize,
+        neighbours: &HashMap<GraphId, Vec<GraphId>>,
+        found: &mut impl FnMut(&[GraphId]),
+    ) {
+        if subgraph.len() == size {
+            found(subgraph);
+            return;
+        }
+
+        let reached = subgraph
+            .iter()
+            .flat_map(|id| &neighbours[id])
+            .chain(subgraph.iter())
+            .copied()
+            .collect::<HashSet<GraphId>>();
+        while let Some(next) = extension.pop() {
+            let mut grown = extension.clone();
+            grown.extend(
+                neighbours[&next]
+                    .iter()
+                    .filter(|id| **id > root && !reached.contains(*id)),
+            );
+            subgraph.push(next);
+            Self::extend_subgraph(subgraph, grown, root, size, neighbours, found);
+            subgraph.pop();
+        }
+    }
+}
+
+impl Graph<()> {
+    /// Returns every connected pattern on `size` nodes, 3 or 4, in the order of
+    /// [`motif_census`](Graph::motif_census). The nodes of every pattern are numbered from 0.
+    ///
+    /// Panics unless `size` is 3 or 4.
+    pub fn motifs(size: usize, directed: bool) -> Vec<Graph<()>> {
+        MotifTable::new(size, directed)
+            .classes
+            .into_iter()
+            .map(|code| {
+                let mut graph = Graph::new();
+                for id in 0..size {
+                    graph.add_node(id as GraphId, ());
+                }
+                for (from, to) in pairs(size).filter(|(from, to)| code & bit(size, *from, *to) != 0)
+                {
+                    graph.add_edge(from as GraphId, to as GraphId);
+                }
+
+                graph
+            })
+            .collect()
+    }
+}
+
+const MAX_MOTIF_SIZE: usize = 4;
+
+/// Numbers the isomorphism classes of connected patterns on `size` nodes. A pattern is coded
+/// by setting bit `from * size + to` for every edge, and a class by its smallest code.
+struct MotifTable {
+    size: usize,
+    directed: bool,
+    /// The smallest code of every class, in ascending order.
+    classes: Vec<u16>,
+    /// The class of every connected code.
+    class_by_code: HashMap<u16, usize>,
+}
+
+impl MotifTable {
+    fn new(size: usize, directed: bool) -> Self {
+        assert!(
+            (3..=MAX_MOTIF_SIZE).contains(&size),
+            "Expected motif size 3 or 4, got {size}"
+        );
+
+        let orders = permutations(size);
+        let mut canonical = HashMap::new();
+        for code in codes(size, directed) {
+            if !is_connected(size, code) {
+                continue;
+            }
+            let smallest = orders
+                .iter()
+                .map(|order| permute(size, code, order))
+                .min()
+                .expect("there is at least one order");
+            canonical.insert(code, smallest);
+        }
+
+        let mut classes = canonical.values().copied().collect::<Vec<_>>();
+        classes.sort_unstable();
+        classes.dedup();
+        let class_by_code = canonical
+            .into_iter()
+            .map(|(code, smallest)| {
+                let class = classes.binary_search(&smallest).expect("class is listed");
+                (code, class)
+            })
+            .collect();
+
+        Self {
+            size,
+            directed,
+            classes,
+            class_by_code,
+        }
+    }
+
+    /// Returns the class of the subgraph induced by `nodes`, which must be connected.
+    fn class_of<T>(&self, graph: &Graph<T>, nodes: &[GraphId]) -> usize {
+        let mut code = 0;
+        for (from, to) in pairs(nodes.len()) {
+            let (a, b) = (nodes[from], nodes[to]);
+            let present = graph.edges.contains(&Edge { from: a, to: b })
+                || !self.directed && graph.edges.contains(&Edge { from: b, to: a });
+            if present {
+                code |= bit(self.size, from, to);
+            }
+        }
+
+        self.class_by_code[&code]
+    }
+}
+
+/// Returns the bit coding the edge `from -> to`.
+fn bit(size: usize, from: usize, to: usize) -> u16 {
+    1 << (from * size + to)
+}
+
+/// Returns every ordered pair of distinct positions below `size`.
+fn pairs(size: usize) -> impl Iterator<Item = (usize, usize)> {
+    (0..size).flat_map(move |from| {
+        (0..size)
+            .filter(move |to| *to != from)
+            .map(move |to| (from, to))
+    })
+}
+
+/// Returns every code without self-loops, symmetric unless `directed`.
+fn codes(size: usize, directed: bool) -> impl Iterator<Item = u16> {
+    let all = pairs(size).fold(0, |code, (from, to)| code | bit(size, from, to));
+    (0..=all).filter(move |code| {
+        code & !all == 0
+            && (directed
+                || pairs(size).all(|(from, to)| {
+                    (code & bit(size, from, to) == 0) == (code & bit(size, to, from) == 0)
+                }))
+    })
+}
+
+/// Returns whether the pattern is weakly connected.
+fn is_connected(size: usize, code: u16) -> bool {
+    let mut reached = 1_u16;
+    let mut stack = vec![0];
+    while let Some(node) = stack.pop() {
+        for other in 0..size {
+            let joined = code & (bit(size, node, other) | bit(size, other, node)) != 0;
+            if joined && reached & (1 << other) == 0 {
+                reached |= 1 << other;
+                stack.push(other);
+            }
+        }
+    }
+
+    reached.count_ones() as usize == size
+}
+
+/// Renumbers the nodes of the pattern, moving node `i` to `order[i]`.
+fn permute(size: usize, code: u16, order: &[usize]) -> u16 {
+    pairs(size)
+        .filter(|(from, to)| code & bit(size, *from, *to) != 0)
+        .fold(0, |permuted, (from, to)| {
+            permuted | bit(size, order[from], order[to])
+        })
+}
+
+/// Returns every ordering of `0..size`.
+fn permutations(size: usize) -> Vec<Vec<usize>> {
+    let mut orders = vec![Vec::new()];
+    for next in 0..size {
+        orders = orders
+            .into_iter()
+            .flat_map(|order| {
+                (0..=order.len()).map(move |position| {
+                    let mut longer = order.clone();
+                    longer.insert(position, next);
+                    longer
+                })
+            })
+            .collect();
+    }
+
+    orders
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn motif_classes_are_complete() {
+        let counts = [(3, false), (4, false), (3, true), (4, true)]
+            .map(|(size, directed)| Graph::motifs(size, directed).len());
+        assert_eq!(counts, [2, 6, 13, 199], "Class count mismatch");
+        assert_eq!(
+            Graph::motifs(3, false)[0].edges.len(),
+            4,
+            "Expected the path before the triangle"
+        );
+    }
+
+    #[test]
+    fn motif_census_counts_subgraphs() {
+        // A triangle with a pendant node and a self-loop, which is ignored.
+        let graph = Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ())],
+            [(1, 2), (2, 3), (3, 1), (3, 4), (4, 4)],
+        ));
+        assert_eq!(
+            graph.motif_census(3, false),
+            vec![2, 1],
+            "Undirected census mismatch"
+        );
+        assert_eq!(
+            graph.motif_census(4, false).iter().sum::<usize>(),
+            1,
+            "Expected a single 4-node subgraph"
+        );
+
+        let directed = graph.motif_census(3, true);
+        let cycle = Graph::motifs(3, true)
+            .iter()
+            .position(|motif| motif.edges.len() == 3 && motif.is_strongly_connected())
+            .unwrap();
+        assert_eq!(directed[cycle], 1, "Expected one directed cycle");
+        assert_eq!(directed.iter().sum::<usize>(), 3, "Subgraph count mismatch");
+    }
+
+    #[test]
+    fn motif_census_matches_relabelled_graph() {
+        let graph = Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ()), (5, ())],
+            [(1, 2), (2, 3), (3, 4), (4, 1), (1, 5), (5, 3), (2, 4)],
+        ));
+        let relabelled = Graph::from((
+            [(50, ()), (40, ()), (30, ()), (20, ()), (10, ())],
+            [
+                (50, 40),
+                (40, 30),
+                (30, 20),
+                (20, 50),
+                (50, 10),
+                (10, 30),
+                (40, 20),
+            ],
+        ));
+        assert_eq!(
+            graph.motif_census(4, true),
+            relabelled.motif_census(4, true),
+            "Expected the census to ignore IDs"
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::ops::Deref;
+
+use super::{collections::HashSet, Edge, Graph, GraphId, WeightedGraph};
+
+/// A graph whose edges belong to named layers over one shared set of nodes, such as the
+/// "follows" and "mentions" relations of a social network.
+///
+/// An edge can be in several layers at once. Read access through `Deref` sees every edge
+/// that is in any layer, and [`layer`](Self::layer) and [`layers`](Self::layers) give views
+/// for analysing layers on their own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiplexGraph<T> {
+    graph: Graph<T>,
+    layers: BTreeMap<String, HashSet<Edge>>,
+}
+
+impl<T> MultiplexGraph<T> {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph::new(),
+            layers: BTreeMap::new(),
+        }
+    }
+
+    pub fn add_node(&mut self, id: GraphId, value: T) {
+        self.graph.add_node(id, value);
+    }
+
+    /// Deletes the node and its edges in every layer.
+    pub fn delete_node(&mut self, id: GraphId) {
+        self.graph.delete_node(id);
+        for edges in self.layers.values_mut() {
+            edges.retain(|edge| edge.from != id && edge.to != id);
+        }
+    }
+
+    /// Adds an edge to `layer`, creating the layer if needed. Does nothing if an endpoint
+    /// doesn't exist.
+    pub fn add_edge(&mut self, layer: &str, from: GraphId, to: GraphId) {
+        if !self.graph.nodes.contains_key(&from) || !self.graph.nodes.contains_key(&to) {
+            return;
+        }
+
+        self.graph.add_edge(from, to);
+        // Looked up by `&str` first so that adding to an existing layer doesn't allocate.
+        if !self.layers.contains_key(layer) {
+            self.layers.insert(layer.into(), HashSet::new());
+        }
+        if let Some(edges) = self.layers.get_mut(layer) {
+            edges.insert(Edge { from, to });
+        }
+    }
+
+    /// Removes the edge from `layer`, keeping it in other layers.
+    pub fn delete_edge(&mut self, layer: &str, from: GraphId, to: GraphId) {
+        let edge = Edge { from, to };
+        if let Some(edges) = self.layers.get_mut(layer) {
+            edges.remove(&edge);
+        }
+        if !self.layers.values().any(|edges| edges.contains(&edge)) {
+            self.graph.delete_edge(from, to);
+        }
+    }
+
+    /// Removes `layer` and every edge that is in no other layer. Returns `false` if there was
+    /// no such layer.
+    pub fn delete_layer(&mut self, layer: &str) -> bool {
+        let Some(edges) = self.layers.remove(layer) else {
+            return false;
+        };
+        for edge in edges {
+            if !self.layers.values().any(|edges| edges.contains(&edge)) {
+                self.graph.delete_edge(edge.from, edge.to);
+            }
+        }
+
+        true
+    }
+
+    /// Returns the names of the layers, sorted.
+    pub fn layer_names(&self) -> impl Iterator<Item = &str> {
+        self.layers.keys().map(String::as_str)
+    }
+
+    /// Returns the names of the layers the edge is in, sorted.
+    pub fn edge_layers(&self, from: GraphId, to: GraphId) -> Vec<&str> {
+        let edge = Edge { from, to };
+        self.layers
+            .iter()
+            .filter(|(_, edges)| edges.contains(&edge))
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Returns a view with all nodes and the edges of `layer`, which has no edges if there is
+    /// no such layer.
+    pub fn layer(&self, layer: &str) -> Graph<&T> {
+        self.layers(&[layer])
+    }
+
+    /// Returns a view with all nodes and the edges that are in any of `layers`.
+    pub fn layers(&self, layers: &[&str]) -> Graph<&T> {
+        let mut graph = self.node_view();
+        for layer in layers {
+            if let Some(edges) = self.layers.get(*layer) {
+                graph.edges.extend(edges.iter().copied());
+            }
+        }
+
+        graph
+    }
+
+    /// Returns a view with all nodes and every edge, weighted by the number of layers it is
+    /// in.
+    pub fn aggregate(&self) -> WeightedGraph<&T> {
+        let mut graph = WeightedGraph::new();
+        for (id, value) in &self.graph.nodes {
+            graph.add_node(*id, value);
+        }
+        for edge in &self.graph.edges {
+            let count = self
+                .layers
+                .values()
+                .filter(|edges| edges.contains(edge))
+                .count();
+            graph.add_edge(edge.from, edge.to, count as f64);
+        }
+
+        graph
+    }
+
+    /// Returns the Jaccard similarity of the edge sets of two layers: the share of edges in
+    /// either layer that are in both. `None` if neither layer has edges.
+    pub fn layer_overlap(&self, first: &str, second: &str) -> Option<f64> {
+        let empty = HashSet::new();
+        let first = self.layers.get(first).unwrap_or(&empty);
+        let second = self.layers.get(second).unwrap_or(&empty);
+        let shared = first.intersection(second).count();
+        let total = first.len() + second.len() - shared;
+
+        (total > 0).then(|| shared as f64 / total as f64)
+    }
+
+    fn node_view(&self) -> Graph<&T> {
+        let mut graph = Graph::new();
+        for (id, value) in &self.graph.nodes {
+            graph.add_node(*id, value);
+        }
+
+        graph
+    }
+}
+
+impl<T> Default for MultiplexGraph<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Deref for MultiplexGraph<T> {
+    type Target = Graph<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Users 1 to 4, who follow each other in a chain and mention each other less often.
+    fn get_test_graph() -> MultiplexGraph<&'static str> {
+        let mut graph = MultiplexGraph::new();
+        for (id, name) in [(1, "ann"), (2, "bob"), (3, "cat"), (4, "dan")] {
+            graph.add_node(id, name);
+        }
+        for (from, to) in [(1, 2), (2, 3), (3, 4)] {
+            graph.add_edge("follows", from, to);
+        }
+        for (from, to) in [(1, 2), (4, 1)] {
+            graph.add_edge("mentions", from, to);
+        }
+
+        graph
+    }
+
+    #[test]
+    fn layers_are_analysed_separately() {
+        let graph = get_test_graph();
+        assert_eq!(
+            graph.layer("follows").bfs_order(1),
+            vec![1, 2, 3, 4],
+            "Follows order mismatch"
+        );
+        assert_eq!(
+            graph.layer("mentions").bfs_order(1),
+            vec![1, 2],
+            "Mentions order mismatch"
+        );
+        assert_eq!(
+            graph.bfs_order(4),
+            vec![4, 1, 2, 3],
+            "Aggregate order mismatch"
+        );
+        assert_eq!(
+            graph.edge_layers(1, 2),
+            vec!["follows", "mentions"],
+            "Layers mismatch"
+        );
+        assert_eq!(
+            graph.aggregate().weighted_edges(),
+            vec![(1, 2, 2.0), (2, 3, 1.0), (3, 4, 1.0), (4, 1, 1.0)],
+            "Aggregate mismatch"
+        );
+        assert_eq!(
+            graph.layer_overlap("follows", "mentions"),
+            Some(0.25),
+            "Overlap mismatch"
+        );
+        assert!(graph.layer("missing").edges.is_empty(), "Expected no edges");
+    }
+
+    #[test]
+    fn deleting_keeps_other_layers() {
+        let mut graph = get_test_graph();
+        graph.delete_edge("mentions", 1, 2);
+        assert!(
+            graph.edges.contains(&Edge { from: 1, to: 2 }),
+            "Expected edge kept for the other layer"
+        );
+        assert!(graph.delete_layer("follows"), "Expected layer deleted");
+        assert_eq!(
+            graph.sorted_edges(),
+            vec![(4, 1)],
+            "Expected only the remaining layer"
+        );
+        graph.delete_node(4);
+        assert!(
+            graph.layer("mentions").edges.is_empty(),
+            "Expected edges of deleted node removed"
+        );
+        assert_eq!(
+            graph.layer_names().collect::<Vec<_>>(),
+            vec!["mentions"],
+            "Names mismatch"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use alloc::{vec, vec::Vec};
+
+use super::{Edge, Graph, GraphId};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Mutation<T> {
+    AddNode { id: GraphId, value: T },
+    RemoveNode { id: GraphId },
+    AddEdge { from: GraphId, to: GraphId },
+    RemoveEdge { from: GraphId, to: GraphId },
+}
+
+impl<T> Graph<T> {
+    /// Applies a single mutation and returns the mutations that revert it, in the order they
+    /// have to be applied. Mutations that don't change the graph return nothing.
+    pub fn apply(&mut self, mutation: Mutation<T>) -> Vec<Mutation<T>> {
+        match mutation {
+            Mutation::AddNode { id, value } => {
+                if self.nodes.contains_key(&id) {
+                    return vec![];
+                }
+                self.add_node(id, value);
+
+                vec![Mutation::RemoveNode { id }]
+            }
+            Mutation::RemoveNode { id } => {
+                let Some((value, detached)) = self.remove_node_full(id) else {
+                    return vec![];
+                };
+
+                core::iter::once(Mutation::AddNode { id, value })
+                    .chain(
+                        detached
+                            .into_iter()
+                            .map(|(from, to)| Mutation::AddEdge { from, to }),
+                    )
+                    .collect()
+            }
+            Mutation::AddEdge { from, to } => {
+                if !self.nodes.contains_key(&from)
+                    || !self.nodes.contains_key(&to)
+                    || !self.edges.insert(Edge { from, to })
+                {
+                    return vec![];
+                }
+
+                vec![Mutation::RemoveEdge { from, to }]
+            }
+            Mutation::RemoveEdge { from, to } => {
+                if !self.edges.remove(&Edge { from, to }) {
+                    return vec![];
+                }
+
+                vec![Mutation::AddEdge { from, to }]
+            }
+        }
+    }
+
+    /// Applies mutations in order and returns the mutations that revert all of them.
+    pub(crate) fn apply_all(&mut self, mutations: Vec<Mutation<T>>) -> Vec<Mutation<T>> {
+        let mut inverses = mutations
+            .into_iter()
+            .map(|mutation| self.apply(mutation))
+            .collect::<Vec<_>>();
+        inverses.reverse();
+
+        inverses.into_iter().flatten().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_add_node_inverse() {
+        let mut graph = Graph::new();
+        let inverse = graph.apply(Mutation::AddNode { id: 1, value: "a" });
+        assert_eq!(inverse, vec![Mutation::RemoveNode { id: 1 }]);
+        assert!(
+            graph
+                .apply(Mutation::AddNode { id: 1, value: "b" })
+                .is_empty(),
+            "Expected existing node to be a no-op"
+        );
+    }
+
+    #[test]
+    fn apply_remove_node_restores_edges() {
+        let original = Graph::from(([(1, "a"), (2, "b"), (3, "c")], [(1, 2), (3, 2), (1, 3)]));
+        let mut graph = original.clone();
+        let inverse = graph.apply(Mutation::RemoveNode { id: 2 });
+        assert_eq!(
+            graph.edges.len(),
+            1,
+            "Expected incident edges to be removed"
+        );
+
+        for mutation in inverse {
+            graph.apply(mutation);
+        }
+        assert_eq!(graph, original, "Expected inverse to restore the graph");
+    }
+
+    #[test]
+    fn apply_invalid_edge_noop() {
+        let mut graph = Graph::from(([(1, "a")], []));
+        assert!(
+            graph.apply(Mutation::AddEdge { from: 1, to: 2 }).is_empty(),
+            "Expected dangling edge to be a no-op"
+        );
+        assert!(
+            graph
+                .apply(Mutation::RemoveEdge { from: 1, to: 1 })
+                .is_empty(),
+            "Expected missing edge removal to be a no-op"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use alloc::{collections::VecDeque, vec::Vec};
+
+use super::{collections::HashMap, Graph, GraphId};
+
+/// Which edges [`Graph::neighbourhood`] follows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Direction {
+    /// Follow edges from their source to their target.
+    #[default]
+    Outgoing,
+    /// Follow edges from their target back to their source.
+    Incoming,
+    /// Follow edges either way.
+    Both,
+}
+
+impl<T> Graph<T> {
+    /// Returns the nodes within `depth` hops of `id` along edges in `direction`, paired with
+    /// their distance and sorted by distance, then ID. The node itself isn't included. `None`
+    /// if `id` doesn't exist.
+    pub fn neighbourhood(
+        &self,
+        id: GraphId,
+        depth: usize,
+        direction: Direction,
+    ) -> Option<Vec<(GraphId, usize)>> {
+        if !self.nodes.contains_key(&id) {
+            return None;
+        }
+        debug_span!("neighbourhood", id, depth);
+
+        let mut adjacency: HashMap<GraphId, Vec<GraphId>> = HashMap::new();
+        for edge in &self.edges {
+            if direction != Direction::Incoming {
+                adjacency.entry(edge.from).or_default().push(edge.to);
+            }
+            if direction != Direction::Outgoing {
+                adjacency.entry(edge.to).or_default().push(edge.from);
+            }
+        }
+
+        let mut distances = HashMap::new();
+        distances.insert(id, 0);
+        let mut queue = VecDeque::from([id]);
+        while let Some(current) = queue.pop_front() {
+            let distance = distances[&current];
+            if distance == depth {
+                continue;
+            }
+            for neighbour in adjacency.get(&current).into_iter().flatten() {
+                if !distances.contains_key(neighbour) {
+                    distances.insert(*neighbour, distance + 1);
+                    queue.push_back(*neighbour);
+                }
+            }
+        }
+
+        let mut neighbourhood = distances
+            .into_iter()
+            .filter(|(neighbour, _)| *neighbour != id)
+            .collect::<Vec<_>>();
+        neighbourhood.sort_unstable_by_key(|(neighbour, distance)| (*distance, *neighbour));
+        debug_event!(reached = neighbourhood.len(), "neighbourhood collected");
+
+        Some(neighbourhood)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_graph() -> Graph<()> {
+        Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ()), (5, ())],
+            [(1, 2), (2, 3), (3, 4), (5, 1), (1, 1)],
+        ))
+    }
+
+    #[test]
+    fn neighbourhood_outgoing_depth() {
+        let graph = get_test_graph();
+        assert_eq!(
+            graph.neighbourhood(1, 1, Direction::Outgoing),
+            Some(vec![(2, 1)]),
+            "Neighbours mismatch"
+        );
+        assert_eq!(
+            graph.neighbourhood(1, 2, Direction::Outgoing),
+            Some(vec![(2, 1), (3, 2)]),
+            "Neighbours mismatch"
+        );
+        assert_eq!(graph.neighbourhood(1, 0, Direction::Both), Some(vec![]));
+    }
+
+    #[test]
+    fn neighbourhood_directions() {
+        let graph = get_test_graph();
+        assert_eq!(
+            graph.neighbourhood(2, 2, Direction::Incoming),
+            Some(vec![(1, 1), (5, 2)]),
+            "Predecessors mismatch"
+        );
+        assert_eq!(
+            graph.neighbourhood(1, 1, Direction::Both),
+            Some(vec![(2, 1), (5, 1)]),
+            "Neighbours mismatch"
+        );
+    }
+
+    #[test]
+    fn neighbourhood_missing_node() {
+        assert_eq!(
+            get_test_graph().neighbourhood(6, 1, Direction::Both),
+            None,
+            "Expected no neighbourhood"
+        );
+    }
+}
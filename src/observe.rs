@@ -0,0 +1,207 @@
+use alloc::vec::Vec;
+use core::ops::Deref;
+#[cfg(feature = "std")]
+use std::sync::mpsc::Sender;
+
+use super::{Edge, Graph, GraphId};
+
+/// Receives a callback for every change applied through an [`ObservedGraph`].
+///
+/// All methods do nothing by default, so implementors only override the events they need.
+pub trait GraphListener<T> {
+    fn on_node_added(&mut self, _id: GraphId, _value: &T) {}
+
+    fn on_node_removed(&mut self, _id: GraphId, _value: &T) {}
+
+    fn on_edge_added(&mut self, _from: GraphId, _to: GraphId) {}
+
+    fn on_edge_removed(&mut self, _from: GraphId, _to: GraphId) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphEvent<T> {
+    NodeAdded { id: GraphId, value: T },
+    NodeRemoved { id: GraphId, value: T },
+    EdgeAdded { from: GraphId, to: GraphId },
+    EdgeRemoved { from: GraphId, to: GraphId },
+}
+
+/// Forwards every change as a [`GraphEvent`]. Events are dropped once the receiver is gone.
+#[cfg(feature = "std")]
+impl<T: Clone> GraphListener<T> for Sender<GraphEvent<T>> {
+    fn on_node_added(&mut self, id: GraphId, value: &T) {
+        let _ = self.send(GraphEvent::NodeAdded {
+            id,
+            value: value.clone(),
+        });
+    }
+
+    fn on_node_removed(&mut self, id: GraphId, value: &T) {
+        let _ = self.send(GraphEvent::NodeRemoved {
+            id,
+            value: value.clone(),
+        });
+    }
+
+    fn on_edge_added(&mut self, from: GraphId, to: GraphId) {
+        let _ = self.send(GraphEvent::EdgeAdded { from, to });
+    }
+
+    fn on_edge_removed(&mut self, from: GraphId, to: GraphId) {
+        let _ = self.send(GraphEvent::EdgeRemoved { from, to });
+    }
+}
+
+/// Wraps a graph and notifies a listener about every mutation that actually changes it.
+///
+/// Read access goes through `Deref`, so the wrapper can be used like the inner graph.
+#[derive(Debug, Clone)]
+pub struct ObservedGraph<T, L> {
+    graph: Graph<T>,
+    listener: L,
+}
+
+impl<T, L: GraphListener<T>> ObservedGraph<T, L> {
+    pub fn new(graph: Graph<T>, listener: L) -> Self {
+        Self { graph, listener }
+    }
+
+    pub fn listener(&self) -> &L {
+        &self.listener
+    }
+
+    pub fn listener_mut(&mut self) -> &mut L {
+        &mut self.listener
+    }
+
+    pub fn into_inner(self) -> (Graph<T>, L) {
+        (self.graph, self.listener)
+    }
+
+    pub fn add_node(&mut self, id: GraphId, value: T) {
+        if !self.graph.nodes.contains_key(&id) {
+            self.listener.on_node_added(id, &value);
+            self.graph.add_node(id, value);
+        }
+    }
+
+    /// Removes the node, reporting the removal of every incident edge before the node itself.
+    pub fn delete_node(&mut self, id: GraphId) {
+        if !self.graph.nodes.contains_key(&id) {
+            return;
+        }
+
+        let mut incident = self
+            .graph
+            .edges
+            .iter()
+            .filter(|edge| edge.from == id || edge.to == id)
+            .copied()
+            .collect::<Vec<_>>();
+        incident.sort_unstable();
+        for edge in incident {
+            self.delete_edge(edge.from, edge.to);
+        }
+
+        if let Some(value) = self.graph.nodes.remove(&id) {
+            self.listener.on_node_removed(id, &value);
+        }
+    }
+
+    pub fn add_edge(&mut self, from: GraphId, to: GraphId) {
+        let edge = Edge { from, to };
+        if !self.graph.edges.contains(&edge) {
+            self.graph.add_edge(from, to);
+            if self.graph.edges.contains(&edge) {
+                self.listener.on_edge_added(from, to);
+            }
+        }
+    }
+
+    pub fn delete_edge(&mut self, from: GraphId, to: GraphId) {
+        if self.graph.edges.remove(&Edge { from, to }) {
+            self.listener.on_edge_removed(from, to);
+        }
+    }
+}
+
+impl<T, L> Deref for ObservedGraph<T, L> {
+    type Target = Graph<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    use std::sync::mpsc;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Counter {
+        nodes: i32,
+        edges: i32,
+    }
+
+    impl GraphListener<&str> for Counter {
+        fn on_node_added(&mut self, _id: GraphId, _value: &&str) {
+            self.nodes += 1;
+        }
+
+        fn on_node_removed(&mut self, _id: GraphId, _value: &&str) {
+            self.nodes -= 1;
+        }
+
+        fn on_edge_added(&mut self, _from: GraphId, _to: GraphId) {
+            self.edges += 1;
+        }
+
+        fn on_edge_removed(&mut self, _from: GraphId, _to: GraphId) {
+            self.edges -= 1;
+        }
+    }
+
+    #[test]
+    fn observed_counts_match_graph() {
+        let mut graph = ObservedGraph::new(Graph::new(), Counter::default());
+        graph.add_node(1, "a");
+        graph.add_node(1, "b");
+        graph.add_node(2, "b");
+        graph.add_edge(1, 2);
+        graph.add_edge(1, 2);
+        graph.add_edge(1, 3);
+        assert_eq!(graph.listener().nodes, 2, "Nodes count mismatch");
+        assert_eq!(graph.listener().edges, 1, "Edges count mismatch");
+
+        graph.delete_node(2);
+        graph.delete_edge(1, 2);
+        assert_eq!(graph.listener().nodes, 1, "Nodes count mismatch");
+        assert_eq!(graph.listener().edges, 0, "Edges count mismatch");
+        assert_eq!(graph.nodes.len(), 1, "Expected wrapper to expose graph");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn observed_events_channel() {
+        let (sender, receiver) = mpsc::channel();
+        let mut graph = ObservedGraph::new(Graph::from(([(1, "a")], [])), sender);
+        graph.add_node(2, "b");
+        graph.add_edge(2, 1);
+        graph.delete_node(1);
+        drop(graph);
+
+        assert_eq!(
+            receiver.iter().collect::<Vec<_>>(),
+            vec![
+                GraphEvent::NodeAdded { id: 2, value: "b" },
+                GraphEvent::EdgeAdded { from: 2, to: 1 },
+                GraphEvent::EdgeRemoved { from: 2, to: 1 },
+                GraphEvent::NodeRemoved { id: 1, value: "a" },
+            ],
+            "Events don't match"
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use alloc::{collections::VecDeque, vec, vec::Vec};
+
+use super::collections::HashMap;
+
+use super::{Graph, GraphId};
+
+const UNREACHABLE: u32 = u32::MAX;
+
+/// Precomputed hop distances to and from a few landmark nodes, answering approximate
+/// distance queries with a scan over the landmarks instead of a graph search.
+///
+/// Landmarks are the nodes with the highest total degree. Estimates are upper bounds that are
+/// exact whenever a shortest path passes through a landmark.
+#[derive(Debug, Clone)]
+pub struct DistanceOracle {
+    landmarks: Vec<GraphId>,
+    index_of: HashMap<GraphId, usize>,
+    to_landmark: Vec<Vec<u32>>,
+    from_landmark: Vec<Vec<u32>>,
+}
+
+impl DistanceOracle {
+    pub fn build<T>(graph: &Graph<T>, num_landmarks: usize) -> Self {
+        let mut ids = graph.nodes.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        let index_of: HashMap<GraphId, usize> = ids
+            .iter()
+            .enumerate()
+            .map(|(index, id)| (*id, index))
+            .collect();
+
+        let mut forward = vec![Vec::new(); ids.len()];
+        let mut backward = vec![Vec::new(); ids.len()];
+        let mut degree = vec![0usize; ids.len()];
+        for edge in &graph.edges {
+            let (from, to) = (index_of[&edge.from], index_of[&edge.to]);
+            forward[from].push(to);
+            backward[to].push(from);
+            degree[from] += 1;
+            degree[to] += 1;
+        }
+
+        let mut by_degree = (0..ids.len()).collect::<Vec<_>>();
+        by_degree.sort_by_key(|index| (core::cmp::Reverse(degree[*index]), *index));
+        by_degree.truncate(num_landmarks);
+
+        Self {
+            landmarks: by_degree.iter().map(|index| ids[*index]).collect(),
+            to_landmark: by_degree
+                .iter()
+                .map(|l| hop_distances(&backward, *l))
+                .collect(),
+            from_landmark: by_degree
+                .iter()
+                .map(|l| hop_distances(&forward, *l))
+                .collect(),
+            index_of,
+        }
+    }
+
+    pub fn landmarks(&self) -> &[GraphId] {
+        &self.landmarks
+    }
+
+    /// Returns an upper bound on the hop distance from `from` to `to`, or `None` if no path
+    /// through a landmark exists.
+    pub fn estimate(&self, from: GraphId, to: GraphId) -> Option<usize> {
+        let (from, to) = (*self.index_of.get(&from)?, *self.index_of.get(&to)?);
+        if from == to {
+            return Some(0);
+        }
+
+        self.to_landmark
+            .iter()
+            .zip(&self.from_landmark)
+            .filter(|(to_l, from_l)| to_l[from] != UNREACHABLE && from_l[to] != UNREACHABLE)
+            .map(|(to_l, from_l)| (to_l[from] + from_l[to]) as usize)
+            .min()
+    }
+
+    /// Returns a lower bound on the hop distance from `from` to `to` derived from the
+    /// triangle inequality.
+    pub fn lower_bound(&self, from: GraphId, to: GraphId) -> Option<usize> {
+        let (from, to) = (*self.index_of.get(&from)?, *self.index_of.get(&to)?);
+
+        self.to_landmark
+            .iter()
+            .zip(&self.from_landmark)
+            .flat_map(|(to_l, from_l)| {
+                let via_target = (to_l[from] != UNREACHABLE && to_l[to] != UNREACHABLE)
+                    .then(|| to_l[from].saturating_sub(to_l[to]));
+                let via_source = (from_l[from] != UNREACHABLE && from_l[to] != UNREACHABLE)
+                    .then(|| from_l[to].saturating_sub(from_l[from]));
+                [via_target, via_source]
+            })
+            .flatten()
+            .max()
+            .map(|bound| bound as usize)
+            .or(Some(0))
+    }
+}
+
+fn hop_distances(adjacency: &[Vec<usize>], source: usize) -> Vec<u32> {
+    let mut distance = vec![UNREACHABLE; adjacency.len()];
+    distance[source] = 0;
+    let mut queue = VecDeque::from([source]);
+    while let Some(current) = queue.pop_front() {
+        for &next in &adjacency[current] {
+            if distance[next] == UNREACHABLE {
+                distance[next] = distance[current] + 1;
+                queue.push_back(next);
+            }
+        }
+    }
+
+    distance
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    use crate::generators;
+
+    use super::*;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn oracle_picks_high_degree_landmarks() {
+        let graph = generators::star(6, |_| ());
+        let oracle = DistanceOracle::build(&graph, 1);
+        assert_eq!(oracle.landmarks(), &[0], "Expected the centre as landmark");
+        assert_eq!(oracle.estimate(1, 2), Some(2), "Estimate mismatch");
+        assert_eq!(oracle.lower_bound(1, 2), Some(0), "Lower bound mismatch");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn oracle_bounds_true_distance() {
+        let graph = generators::grid(5, 5, |_| ());
+        let oracle = DistanceOracle::build(&graph, 3);
+        for from in 0..25 {
+            let tree = graph.shortest_path_tree(from).unwrap();
+            for to in 0..25 {
+                let exact = tree.distance[&to];
+                let estimate = oracle.estimate(from, to).unwrap();
+                let lower = oracle.lower_bound(from, to).unwrap();
+                assert!(
+                    lower <= exact && exact <= estimate,
+                    "Expected {lower} <= {exact} <= {estimate} for {from} -> {to}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn oracle_directed_unreachable() {
+        let graph: Graph<()> = Graph::from(([(1, ()), (2, ()), (3, ())], [(1, 2), (2, 3)]));
+        let oracle = DistanceOracle::build(&graph, 1);
+        assert_eq!(oracle.landmarks(), &[2], "Landmark mismatch");
+        assert_eq!(oracle.estimate(1, 3), Some(2), "Estimate mismatch");
+        assert_eq!(oracle.estimate(3, 1), None, "Expected no path");
+        assert_eq!(oracle.estimate(1, 9), None, "Expected unknown node");
+    }
+}
@@ -0,0 +1,194 @@
+use std::{ops::Range, str::FromStr};
+
+use anyhow::anyhow;
+use nom::{combinator, Finish};
+use rayon::prelude::*;
+
+use super::{edge_list::parse_edges, header::split_header, serde::parse_pairs, Graph, GraphId};
+
+/// Fewest bytes parsed by a task, so that small inputs aren't spread over threads for
+/// nothing.
+const MIN_CHUNK_LEN: usize = if cfg!(test) { 16 } else { 1 << 20 };
+
+impl<T: Default> Graph<T> {
+    /// Parses a plain edge list like [`parse_edge_list`](Self::parse_edge_list), splitting it
+    /// into chunks of whole lines that are parsed on the rayon thread pool. The graph is then
+    /// built from all chunks on the calling thread.
+    ///
+    /// Fails on the first bad line in the input, like the sequential parser.
+    pub fn par_parse_edge_list(bytes: &[u8]) -> anyhow::Result<Self> {
+        debug_span!("par_parse_edge_list", bytes = bytes.len());
+        let chunks = line_chunks(bytes, 1)
+            .into_par_iter()
+            .map(|(range, first_line)| parse_edges(&bytes[range], first_line))
+            .collect::<Vec<_>>();
+        let chunks = chunks.into_iter().collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Self::from_edge_chunks(&chunks))
+    }
+}
+
+impl<T: FromStr + Send> Graph<T> {
+    /// Parses TGF like [`str::parse`], splitting the node and edge lines into chunks that are
+    /// parsed on the rayon thread pool. A [`Header`](crate::Header) is respected and checked
+    /// as by [`parse_with_progress`](Self::parse_with_progress).
+    ///
+    /// Blank lines are skipped, and errors name the line they are on.
+    pub fn par_parse(s: &str) -> anyhow::Result<Self> {
+        debug_span!("par_parse_tgf", bytes = s.len());
+        let (header, body) = split_header(s)?;
+        if let Some(header) = &header {
+            header.check(false)?;
+        }
+        let directed = header.is_none_or(|header| header.directed);
+        let first_line = s[..s.len() - body.len()].matches('\n').count() + 1;
+        let (nodes, node_lines, edges) =
+            split_sections(body).ok_or_else(|| anyhow!("Parse error: missing \"#\" separator"))?;
+        let (nodes, edges) = rayon::join(
+            || parse_lines::<T>(nodes, first_line),
+            || parse_lines::<GraphId>(edges, first_line + node_lines + 1),
+        );
+
+        Ok(Graph::from_sections((nodes?, edges?), directed))
+    }
+}
+
+/// Splits TGF into the text of its node lines, their count, and the text of its edge lines.
+/// `None` if there is no `#` line between them.
+fn split_sections(body: &str) -> Option<(&str, usize, &str)> {
+    let mut start = 0;
+    for (index, line) in body.split_inclusive('\n').enumerate() {
+        if line.trim_end_matches(['\r', '\n']) == "#" {
+            return Some((&body[..start], index, &body[start + line.len()..]));
+        }
+        start += line.len();
+    }
+
+    None
+}
+
+/// Parses TGF node or edge lines in parallel, numbering them from `first_line` in errors.
+fn parse_lines<V: FromStr + Send>(
+    text: &str,
+    first_line: usize,
+) -> anyhow::Result<Vec<(GraphId, V)>> {
+    let chunks = line_chunks(text.as_bytes(), first_line)
+        .into_par_iter()
+        .map(|(range, first_line)| {
+            let mut pairs = Vec::new();
+            for (index, line) in text[range].lines().enumerate() {
+                let (_, pair) = combinator::all_consuming(parse_pairs)(line)
+                    .finish()
+                    .map_err(|e| anyhow!("Parse error on line {}: {e}", first_line + index))?;
+                pairs.extend(pair);
+            }
+
+            Ok(pairs)
+        })
+        .collect::<Vec<anyhow::Result<Vec<_>>>>();
+    // Checked in input order so that the first bad line is the one reported.
+    let chunks = chunks.into_iter().collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(chunks.into_iter().flatten().collect())
+}
+
+/// Splits `bytes` into ranges of whole lines, a few for every thread of the pool, each with
+/// the number of its first line counting from `first_line`.
+fn line_chunks(bytes: &[u8], first_line: usize) -> Vec<(Range<usize>, usize)> {
+    let target = (bytes.len() / (rayon::current_num_threads() * 4)).max(MIN_CHUNK_LEN);
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    while start < bytes.len() {
+        let end = (start + target).min(bytes.len());
+        let end = bytes[end..]
+            .iter()
+            .position(|b| *b == b'\n')
+            .map_or(bytes.len(), |offset| end + offset + 1);
+        ranges.push(start..end);
+        start = end;
+    }
+
+    let counts = ranges
+        .par_iter()
+        .map(|range| bytes[range.clone()].iter().filter(|b| **b == b'\n').count())
+        .collect::<Vec<_>>();
+    let mut line = first_line;
+    ranges
+        .into_iter()
+        .zip(counts)
+        .map(|(range, count)| {
+            let first = line;
+            line += count;
+            (range, first)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generators, SerializeOptions};
+
+    fn get_test_graph() -> Graph<String> {
+        generators::grid(6, 7, |id| format!("node {id}"))
+    }
+
+    #[test]
+    fn par_parse_matches_parse() {
+        let graph = get_test_graph();
+        let text = graph.serialize_with(&SerializeOptions {
+            checksum: true,
+            ..SerializeOptions::default()
+        });
+        assert_eq!(
+            Graph::<String>::par_parse(&text).unwrap(),
+            graph,
+            "Graph mismatch"
+        );
+
+        let body = text.split_once('\n').unwrap().1;
+        let text = format!("%bgraph-tgf version=1 directed=false weighted=false\n{body}");
+        assert_eq!(
+            Graph::<String>::par_parse(&text).unwrap(),
+            text.parse::<Graph<String>>().unwrap(),
+            "Expected reverse edges"
+        );
+    }
+
+    #[test]
+    fn par_parse_reports_first_bad_line() {
+        let mut lines = get_test_graph()
+            .serialize()
+            .lines()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        let last = lines.len() - 1;
+        lines[last] = "x 1".into();
+        lines[60] = "1 y".into();
+        let error = Graph::<String>::par_parse(&lines.join("\n")).unwrap_err();
+        assert!(
+            error.to_string().starts_with("Parse error on line 61:"),
+            "Unexpected error {error}"
+        );
+    }
+
+    #[test]
+    fn par_parse_edge_list_matches_parse_edge_list() {
+        let text = (0..200)
+            .map(|i| format!("# comment {i}\n{i}\t{}\n", (i * 7) % 50))
+            .collect::<String>();
+        assert_eq!(
+            Graph::<u8>::par_parse_edge_list(text.as_bytes()).unwrap(),
+            Graph::<u8>::parse_edge_list(text.as_bytes()).unwrap(),
+            "Graph mismatch"
+        );
+
+        let text = format!("{text}1 2\n3 -4\n5 6\n");
+        let error = Graph::<u8>::par_parse_edge_list(text.as_bytes()).unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "Line 402: Expected a node ID, got \"-4\"",
+            "Message mismatch"
+        );
+    }
+}
@@ -0,0 +1,545 @@
+use alloc::{
+    collections::{btree_map, BTreeMap, VecDeque},
+    vec,
+    vec::Vec,
+};
+use core::{ops::Deref, slice};
+
+use super::collections::{hash_map::Entry, HashMap};
+
+use super::{
+    progress::{ProgressSink, Reporter},
+    CancellationToken, Graph, GraphError, GraphId, SearchFilter,
+};
+
+/// A path found by a search, as its node IDs from source to target and its total cost.
+///
+/// The cost of a path found by a breadth-first search is its number of hops. Read access to
+/// the node IDs goes through `Deref`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Path<W = usize> {
+    nodes: Vec<GraphId>,
+    cost: Option<W>,
+}
+
+impl<W> Path<W> {
+    /// Creates a path through `nodes` without a cost.
+    pub fn new(nodes: Vec<GraphId>) -> Self {
+        Self { nodes, cost: None }
+    }
+
+    pub fn with_cost(nodes: Vec<GraphId>, cost: W) -> Self {
+        Self {
+            nodes,
+            cost: Some(cost),
+        }
+    }
+
+    pub fn cost(&self) -> Option<W>
+    where
+        W: Copy,
+    {
+        self.cost
+    }
+
+    /// Returns the edges along the path as `(from, to)` pairs, in order.
+    pub fn edges(&self) -> impl Iterator<Item = (GraphId, GraphId)> + '_ {
+        self.nodes.windows(2).map(|pair| (pair[0], pair[1]))
+    }
+
+    /// Returns the path from target to source, keeping the cost.
+    pub fn reversed(mut self) -> Self {
+        self.nodes.reverse();
+        self
+    }
+
+    pub fn into_nodes(self) -> Vec<GraphId> {
+        self.nodes
+    }
+}
+
+impl<W> Deref for Path<W> {
+    type Target = [GraphId];
+
+    fn deref(&self) -> &Self::Target {
+        &self.nodes
+    }
+}
+
+impl<W> IntoIterator for Path<W> {
+    type Item = GraphId;
+    type IntoIter = vec::IntoIter<GraphId>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.into_iter()
+    }
+}
+
+impl<'a, W> IntoIterator for &'a Path<W> {
+    type Item = &'a GraphId;
+    type IntoIter = slice::Iter<'a, GraphId>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.iter()
+    }
+}
+
+/// The cost of a cheapest path from a source to every node it can reach, the source
+/// included. The cost of a path found by a breadth-first search is its number of hops.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Distances<W = usize> {
+    costs: BTreeMap<GraphId, W>,
+}
+
+impl<W> Distances<W> {
+    /// Returns the cost of reaching `id`, or `None` if it can't be reached.
+    pub fn get(&self, id: GraphId) -> Option<W>
+    where
+        W: Copy,
+    {
+        self.costs.get(&id).copied()
+    }
+
+    pub fn contains(&self, id: GraphId) -> bool {
+        self.costs.contains_key(&id)
+    }
+
+    /// Returns the IDs of the reachable nodes in ascending order.
+    pub fn reachable(&self) -> impl Iterator<Item = GraphId> + '_ {
+        self.costs.keys().copied()
+    }
+
+    /// Returns the farthest node with its cost, the one with the lowest ID among ties, or
+    /// `None` if nothing is reachable.
+    pub fn max(&self) -> Option<(GraphId, W)>
+    where
+        W: Copy + PartialOrd,
+    {
+        self.iter().fold(
+            None,
+            |farthest: Option<(GraphId, W)>, (id, cost)| match farthest {
+                Some((_, max)) if cost <= max => farthest,
+                _ => Some((id, cost)),
+            },
+        )
+    }
+
+    /// Returns the reachable nodes with their costs in ascending ID order.
+    pub fn iter(&self) -> impl Iterator<Item = (GraphId, W)> + '_
+    where
+        W: Copy,
+    {
+        self.costs.iter().map(|(id, cost)| (*id, *cost))
+    }
+
+    pub fn len(&self) -> usize {
+        self.costs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.costs.is_empty()
+    }
+
+    pub fn into_map(self) -> BTreeMap<GraphId, W> {
+        self.costs
+    }
+}
+
+impl<W> FromIterator<(GraphId, W)> for Distances<W> {
+    fn from_iter<I: IntoIterator<Item = (GraphId, W)>>(iter: I) -> Self {
+        Self {
+            costs: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<W> IntoIterator for Distances<W> {
+    type Item = (GraphId, W);
+    type IntoIter = btree_map::IntoIter<GraphId, W>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.costs.into_iter()
+    }
+}
+
+/// The parent of every node reachable from a source on a cheapest path to it, so that the
+/// path to any of them can be reconstructed without searching again.
+///
+/// The cost of a path found by a breadth-first search is its number of hops.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPathTree<W = usize> {
+    pub(crate) source: GraphId,
+    pub(crate) distance: HashMap<GraphId, W>,
+    pub(crate) parent: HashMap<GraphId, GraphId>,
+}
+
+impl<W> ShortestPathTree<W> {
+    pub(crate) fn new(source: GraphId, zero: W) -> Self {
+        Self {
+            source,
+            distance: HashMap::from([(source, zero)]),
+            parent: HashMap::new(),
+        }
+    }
+
+    pub fn source(&self) -> GraphId {
+        self.source
+    }
+
+    /// Returns the cost of a cheapest path to `id`, or `None` if it can't be reached.
+    pub fn distance(&self, id: GraphId) -> Option<W>
+    where
+        W: Copy,
+    {
+        self.distance.get(&id).copied()
+    }
+
+    /// Returns the node before `id` on its path from the source, or `None` for the source
+    /// and unreachable nodes.
+    pub fn parent(&self, id: GraphId) -> Option<GraphId> {
+        self.parent.get(&id).copied()
+    }
+
+    /// Returns a cheapest path from the source to `target`, or `None` if it can't be reached.
+    pub fn path_to(&self, target: GraphId) -> Option<Path<W>>
+    where
+        W: Copy,
+    {
+        let distance = self.distance(target)?;
+
+        let mut path = vec![target];
+        let mut current = target;
+        while let Some(parent) = self.parent.get(&current) {
+            path.push(*parent);
+            current = *parent;
+        }
+        path.reverse();
+
+        Some(Path::with_cost(path, distance))
+    }
+
+    pub fn into_distances(self) -> Distances<W> {
+        self.distance.into_iter().collect()
+    }
+}
+
+impl<T> Graph<T> {
+    /// Returns a path with the fewest hops from `from` to `to`, including both endpoints.
+    pub fn shortest_path(&self, from: GraphId, to: GraphId) -> Option<Path> {
+        self.shortest_path_tree(from)?.path_to(to)
+    }
+
+    /// Like [`shortest_path`](Self::shortest_path), but avoids the nodes and edges hidden by
+    /// `filter`.
+    pub fn shortest_path_filtered(
+        &self,
+        from: GraphId,
+        to: GraphId,
+        filter: &SearchFilter<'_, T>,
+    ) -> Option<Path> {
+        if !filter.allows_node(self, from) {
+            return None;
+        }
+
+        search_tree(from, &self.filtered_adjacency(filter)).path_to(to)
+    }
+
+    /// Returns the closest node to `source` whose value satisfies `predicate`, `source`
+    /// itself included. Searches breadth-first with neighbours in ascending ID order, so ties
+    /// go to the node found first, and stops at the first match.
+    pub fn find(&self, source: GraphId, predicate: impl Fn(&T) -> bool) -> Option<GraphId> {
+        self.find_path(source, predicate)?.last().copied()
+    }
+
+    /// Like [`find`](Self::find), but returns a path with the fewest hops from `source` to
+    /// the node found.
+    pub fn find_path(&self, source: GraphId, predicate: impl Fn(&T) -> bool) -> Option<Path> {
+        if !self.nodes.contains_key(&source) {
+            return None;
+        }
+
+        let adjacency = self.adjacency();
+        let mut tree = ShortestPathTree::new(source, 0);
+        let mut queue = VecDeque::from([source]);
+        while let Some(id) = queue.pop_front() {
+            if predicate(&self.nodes[&id]) {
+                return tree.path_to(id);
+            }
+            let distance = tree.distance[&id];
+            for &next in adjacency.get(&id).into_iter().flatten() {
+                if let Entry::Vacant(entry) = tree.distance.entry(next) {
+                    entry.insert(distance + 1);
+                    tree.parent.insert(next, id);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns the hop count of a shortest path from `source` to every node it can reach,
+    /// itself included at distance 0. Empty if `source` doesn't exist.
+    ///
+    /// Cheaper than [`shortest_path_tree`](Self::shortest_path_tree) when only the distances
+    /// are needed, as it neither records parents nor sorts neighbours.
+    pub fn distances_from(&self, source: GraphId) -> HashMap<GraphId, usize> {
+        let mut distances = HashMap::new();
+        if !self.nodes.contains_key(&source) {
+            return distances;
+        }
+
+        let mut successors = HashMap::<GraphId, Vec<GraphId>>::new();
+        for edge in &self.edges {
+            successors.entry(edge.from).or_default().push(edge.to);
+        }
+        distances.insert(source, 0);
+        let mut queue = VecDeque::from([source]);
+        while let Some(id) = queue.pop_front() {
+            let distance = distances[&id] + 1;
+            for &next in successors.get(&id).into_iter().flatten() {
+                if let Entry::Vacant(entry) = distances.entry(next) {
+                    entry.insert(distance);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Returns the hop count of a shortest path from every node to every node it can reach,
+    /// itself included at distance 0, keyed by source and then target.
+    pub fn all_pairs_distances(&self) -> BTreeMap<GraphId, Distances> {
+        self.all_pairs_distances_with_progress(&mut |_| {})
+    }
+
+    /// Like [`all_pairs_distances`](Self::all_pairs_distances), reporting the share of source
+    /// nodes searched so far to `progress`.
+    pub fn all_pairs_distances_with_progress(
+        &self,
+        progress: &mut (impl ProgressSink + ?Sized),
+    ) -> BTreeMap<GraphId, Distances> {
+        self.all_pairs_distances_cancellable(progress, &CancellationToken::new())
+            .expect("token is never cancelled")
+    }
+
+    /// Like [`all_pairs_distances_with_progress`](Self::all_pairs_distances_with_progress),
+    /// but stops with [`GraphError::Cancelled`] between searches once `cancel` is set.
+    pub fn all_pairs_distances_cancellable(
+        &self,
+        progress: &mut (impl ProgressSink + ?Sized),
+        cancel: &CancellationToken,
+    ) -> Result<BTreeMap<GraphId, Distances>, GraphError> {
+        debug_span!("all_pairs_distances", nodes = self.nodes.len());
+        let adjacency = self.adjacency();
+        let mut reporter = Reporter::new(progress, self.nodes.len() as u64);
+        let mut distances = BTreeMap::new();
+        for (done, id) in self.nodes.keys().enumerate() {
+            if cancel.is_cancelled() {
+                return Err(GraphError::Cancelled);
+            }
+            reporter.update(done as u64);
+            let tree = search_tree(*id, &adjacency);
+            distances.insert(*id, tree.into_distances());
+        }
+        reporter.finish();
+
+        Ok(distances)
+    }
+
+    /// Returns the hop distances and BFS parents of every node reachable from `source`, or
+    /// `None` if it doesn't exist.
+    pub fn shortest_path_tree(&self, source: GraphId) -> Option<ShortestPathTree> {
+        if !self.nodes.contains_key(&source) {
+            return None;
+        }
+
+        Some(search_tree(source, &self.adjacency()))
+    }
+}
+
+/// Runs a BFS from `source` over `adjacency`.
+fn search_tree(source: GraphId, adjacency: &HashMap<GraphId, Vec<GraphId>>) -> ShortestPathTree {
+    debug_span!("search_tree", source);
+    let mut tree = ShortestPathTree::new(source, 0);
+    let mut queue = VecDeque::from([source]);
+    while let Some(id) = queue.pop_front() {
+        let distance = tree.distance[&id];
+        for &next in adjacency.get(&id).into_iter().flatten() {
+            if let Entry::Vacant(entry) = tree.distance.entry(next) {
+                entry.insert(distance + 1);
+                tree.parent.insert(next, id);
+                queue.push_back(next);
+            }
+        }
+    }
+    debug_event!(reached = tree.distance.len(), "search tree built");
+
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Progress;
+
+    fn get_test_graph() -> Graph<()> {
+        Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ()), (5, ())],
+            [(1, 2), (2, 3), (3, 4), (1, 3), (5, 1)],
+        ))
+    }
+
+    #[test]
+    fn shortest_path_fewest_hops() {
+        let graph = get_test_graph();
+        assert_eq!(
+            graph.shortest_path(1, 4),
+            Some(Path::with_cost(vec![1, 3, 4], 2)),
+            "Path mismatch"
+        );
+        assert_eq!(
+            graph.shortest_path(1, 1),
+            Some(Path::with_cost(vec![1], 0)),
+            "Expected trivial path"
+        );
+    }
+
+    #[test]
+    fn path_edges_and_reversal() {
+        let path = get_test_graph().shortest_path(5, 4).unwrap();
+        assert_eq!(path.cost(), Some(3), "Cost mismatch");
+        assert_eq!(
+            path.edges().collect::<Vec<_>>(),
+            vec![(5, 1), (1, 3), (3, 4)],
+            "Edges mismatch"
+        );
+        assert_eq!(path.last(), Some(&4), "Expected target last");
+        assert_eq!(
+            path.reversed().into_iter().collect::<Vec<_>>(),
+            vec![4, 3, 1, 5],
+            "Reversed path mismatch"
+        );
+    }
+
+    #[test]
+    fn shortest_path_unreachable() {
+        let graph = get_test_graph();
+        assert_eq!(graph.shortest_path(4, 1), None, "Expected no path");
+        assert_eq!(
+            graph.shortest_path(9, 1),
+            None,
+            "Expected no path from missing node"
+        );
+    }
+
+    #[test]
+    fn search_tree_distances() {
+        let tree = get_test_graph().shortest_path_tree(5).unwrap();
+        assert_eq!(
+            tree.distance,
+            HashMap::from([(5, 0), (1, 1), (2, 2), (3, 2), (4, 3)]),
+            "Distances mismatch"
+        );
+    }
+
+    #[test]
+    fn shortest_path_tree_reconstructs_paths() {
+        let tree = get_test_graph().shortest_path_tree(1).unwrap();
+        assert_eq!(tree.parent(4), Some(3), "Parent mismatch");
+        assert_eq!(tree.parent(1), None, "Expected source to have no parent");
+        assert_eq!(
+            tree.path_to(4),
+            Some(Path::with_cost(vec![1, 3, 4], 2)),
+            "Path mismatch"
+        );
+        assert_eq!(tree.path_to(5), None, "Expected no path");
+        assert_eq!(
+            tree.into_distances().reachable().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4],
+            "Reachable nodes mismatch"
+        );
+    }
+
+    #[test]
+    fn find_stops_at_closest_match() {
+        let graph = Graph::from((
+            [
+                (1, "home"),
+                (2, "shop"),
+                (3, "park"),
+                (4, "shop"),
+                (5, "shop"),
+            ],
+            [(1, 3), (3, 2), (1, 4), (4, 5)],
+        ));
+        assert_eq!(
+            graph.find_path(1, |place| *place == "shop"),
+            Some(Path::with_cost(vec![1, 4], 1)),
+            "Path mismatch"
+        );
+        assert_eq!(
+            graph.find(2, |place| *place == "shop"),
+            Some(2),
+            "Expected source"
+        );
+        assert_eq!(
+            graph.find(3, |place| *place == "home"),
+            None,
+            "Expected no match"
+        );
+        assert_eq!(graph.find(9, |_| true), None, "Expected missing source");
+    }
+
+    #[test]
+    fn distances_from_counts_hops() {
+        let graph = get_test_graph();
+        assert_eq!(
+            graph.distances_from(5),
+            HashMap::from([(5, 0), (1, 1), (2, 2), (3, 2), (4, 3)]),
+            "Distances mismatch"
+        );
+        assert!(
+            graph.distances_from(9).is_empty(),
+            "Expected no distances from missing node"
+        );
+    }
+
+    #[test]
+    fn all_pairs_distances_cancelled() {
+        let token = CancellationToken::new();
+        token.cancel();
+        assert!(
+            matches!(
+                get_test_graph().all_pairs_distances_cancellable(&mut |_| {}, &token),
+                Err(GraphError::Cancelled)
+            ),
+            "Expected cancellation"
+        );
+    }
+
+    #[test]
+    fn all_pairs_distances_reports_progress() {
+        let mut reports = Vec::new();
+        let distances = get_test_graph()
+            .all_pairs_distances_with_progress(&mut |progress: Progress| reports.push(progress));
+        assert_eq!(
+            distances[&1],
+            Distances::from_iter([(1, 0), (2, 1), (3, 1), (4, 2)]),
+            "Distances mismatch"
+        );
+        assert_eq!(distances[&1].max(), Some((4, 2)), "Farthest node mismatch");
+        assert_eq!(
+            distances[&4].reachable().collect::<Vec<_>>(),
+            vec![4],
+            "Expected only the node itself"
+        );
+        assert_eq!(reports.len(), 6, "Reports count mismatch");
+        assert_eq!(
+            reports.last(),
+            Some(&Progress { done: 5, total: 5 }),
+            "Expected final report"
+        );
+    }
+}
@@ -0,0 +1,347 @@
+use std::{collections::HashMap, fmt::Display};
+
+use anyhow::anyhow;
+use nom::{
+    branch,
+    bytes::complete::{tag, tag_no_case, take_till},
+    character::complete as cc,
+    combinator, multi, sequence, Finish, IResult,
+};
+use serde_json::{json, Map, Value};
+
+use super::{rewrite::find_matches, Graph, GraphId};
+
+/// A parsed `MATCH ... WHERE ...` query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PatternQuery {
+    paths: Vec<Vec<(Direction, String)>>,
+    conditions: Vec<Condition>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Forward,
+    Backward,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Condition {
+    variable: String,
+    field: Field,
+    operator: Operator,
+    literal: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Id,
+    Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    Contains,
+}
+
+impl<T> Graph<T> {
+    /// Runs a pattern query and returns the matching bindings as a JSON array, with one
+    /// object per match mapping every variable to `{"id", "value"}`.
+    ///
+    /// The syntax is `MATCH (a)->(b)<-(c), (b)->(d) WHERE a.value = "x" AND b.id != 3`.
+    /// Conditions compare `id` or `value` (through `Display`) with a quoted string or a
+    /// number using `=`, `!=` or `CONTAINS`. Distinct variables bind distinct nodes.
+    pub fn query_str(&self, query: &str) -> anyhow::Result<String>
+    where
+        T: Display,
+    {
+        let query = parse_query(query)?;
+
+        let mut variables: Vec<&str> = Vec::new();
+        let mut edges = Vec::new();
+        for path in &query.paths {
+            let mut previous = None;
+            for (direction, variable) in path {
+                let id = match variables.iter().position(|known| known == variable) {
+                    Some(index) => index,
+                    None => {
+                        variables.push(variable);
+                        variables.len() - 1
+                    }
+                } as GraphId;
+                if let Some(previous) = previous {
+                    edges.push(match direction {
+                        Direction::Forward => (previous, id),
+                        Direction::Backward => (id, previous),
+                    });
+                }
+                previous = Some(id);
+            }
+        }
+
+        let mut conditions: HashMap<GraphId, Vec<&Condition>> = HashMap::new();
+        for condition in &query.conditions {
+            let id = variables
+                .iter()
+                .position(|known| *known == condition.variable)
+                .ok_or_else(|| anyhow!("Unknown variable {:?}", condition.variable))?;
+            conditions.entry(id as GraphId).or_default().push(condition);
+        }
+
+        let pattern = (0..variables.len() as GraphId).collect::<Vec<_>>();
+        let matches = find_matches(self, &pattern, &edges, &|id, candidate| {
+            conditions
+                .get(&id)
+                .into_iter()
+                .flatten()
+                .all(|condition| condition.holds(candidate, &self.nodes[&candidate]))
+        });
+
+        Ok(Value::Array(
+            matches
+                .into_iter()
+                .map(|found| {
+                    Value::Object(
+                        found
+                            .iter()
+                            .map(|(variable, id)| {
+                                (
+                                    variables[variable as usize].to_string(),
+                                    json!({ "id": id, "value": self.nodes[&id].to_string() }),
+                                )
+                            })
+                            .collect::<Map<_, _>>(),
+                    )
+                })
+                .collect(),
+        )
+        .to_string())
+    }
+}
+
+impl Condition {
+    fn holds<T: Display>(&self, id: GraphId, value: &T) -> bool {
+        let actual = match self.field {
+            Field::Id => id.to_string(),
+            Field::Value => value.to_string(),
+        };
+        match self.operator {
+            Operator::Equal => actual == self.literal,
+            Operator::NotEqual => actual != self.literal,
+            Operator::Contains => actual.contains(&self.literal),
+        }
+    }
+}
+
+fn parse_query(s: &str) -> anyhow::Result<PatternQuery> {
+    combinator::all_consuming(sequence::delimited(
+        cc::multispace0,
+        combinator::map(
+            sequence::pair(
+                sequence::preceded(
+                    sequence::pair(tag_no_case("MATCH"), cc::multispace1),
+                    multi::separated_list1(separator(","), parse_path),
+                ),
+                combinator::opt(sequence::preceded(
+                    sequence::delimited(cc::multispace1, tag_no_case("WHERE"), cc::multispace1),
+                    multi::separated_list1(
+                        sequence::delimited(cc::multispace1, tag_no_case("AND"), cc::multispace1),
+                        parse_condition,
+                    ),
+                )),
+            ),
+            |(paths, conditions)| PatternQuery {
+                paths,
+                conditions: conditions.unwrap_or_default(),
+            },
+        ),
+        cc::multispace0,
+    ))(s)
+    .finish()
+    .map(|(_, query)| query)
+    .map_err(|e| anyhow!("Invalid query near {:?}", e.input))
+}
+
+/// Parses `(a)->(b)<-(c)`. The first node gets a direction that is never used.
+fn parse_path(s: &str) -> IResult<&str, Vec<(Direction, String)>> {
+    combinator::map(
+        sequence::pair(
+            parse_node,
+            multi::many0(sequence::pair(
+                branch::alt((
+                    combinator::value(Direction::Forward, separator("->")),
+                    combinator::value(Direction::Backward, separator("<-")),
+                )),
+                parse_node,
+            )),
+        ),
+        |(first, rest)| {
+            std::iter::once((Direction::Forward, first))
+                .chain(rest)
+                .collect()
+        },
+    )(s)
+}
+
+fn parse_node(s: &str) -> IResult<&str, String> {
+    sequence::delimited(
+        sequence::pair(cc::char('('), cc::multispace0),
+        parse_identifier,
+        sequence::pair(cc::multispace0, cc::char(')')),
+    )(s)
+}
+
+fn parse_identifier(s: &str) -> IResult<&str, String> {
+    combinator::map(
+        combinator::recognize(sequence::pair(
+            branch::alt((cc::alpha1, tag("_"))),
+            multi::many0(branch::alt((cc::alphanumeric1, tag("_")))),
+        )),
+        str::to_string,
+    )(s)
+}
+
+fn parse_condition(s: &str) -> IResult<&str, Condition> {
+    combinator::map(
+        sequence::tuple((
+            parse_identifier,
+            sequence::preceded(
+                cc::char('.'),
+                branch::alt((
+                    combinator::value(Field::Id, tag_no_case("id")),
+                    combinator::value(Field::Value, tag_no_case("value")),
+                )),
+            ),
+            branch::alt((
+                combinator::value(Operator::NotEqual, separator("!=")),
+                combinator::value(Operator::Equal, separator("=")),
+                combinator::value(
+                    Operator::Contains,
+                    sequence::delimited(cc::multispace1, tag_no_case("CONTAINS"), cc::multispace1),
+                ),
+            )),
+            branch::alt((
+                combinator::map(
+                    sequence::delimited(cc::char('"'), take_till(|c| c == '"'), cc::char('"')),
+                    str::to_string,
+                ),
+                combinator::map(cc::u64, |number| number.to_string()),
+            )),
+        )),
+        |(variable, field, operator, literal)| Condition {
+            variable,
+            field,
+            operator,
+            literal,
+        },
+    )(s)
+}
+
+fn separator<'a>(token: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, &'a str> {
+    sequence::delimited(cc::multispace0, tag(token), cc::multispace0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_graph() -> Graph<String> {
+        Graph::from((
+            [
+                (1, "January".to_string()),
+                (2, "March".to_string()),
+                (3, "April".to_string()),
+                (5, "December".to_string()),
+                (6, "June".to_string()),
+                (7, "September".to_string()),
+            ],
+            [(1, 2), (3, 2), (5, 1), (5, 3), (6, 3), (7, 5), (7, 6)],
+        ))
+    }
+
+    #[test]
+    fn parse_query_paths_and_conditions() {
+        let query =
+            parse_query("match (a)->(b) <- (c),(b)  WHERE a.id != 3 AND c.value CONTAINS \"J\"")
+                .unwrap();
+        assert_eq!(
+            query.paths,
+            vec![
+                vec![
+                    (Direction::Forward, "a".to_string()),
+                    (Direction::Forward, "b".to_string()),
+                    (Direction::Backward, "c".to_string()),
+                ],
+                vec![(Direction::Forward, "b".to_string())],
+            ],
+            "Paths mismatch"
+        );
+        assert_eq!(
+            query.conditions[1],
+            Condition {
+                variable: "c".to_string(),
+                field: Field::Value,
+                operator: Operator::Contains,
+                literal: "J".to_string(),
+            },
+            "Condition mismatch"
+        );
+    }
+
+    #[test]
+    fn parse_query_invalid() {
+        assert!(
+            parse_query("MATCH (a)->").is_err(),
+            "Expected dangling arrow"
+        );
+        assert!(
+            parse_query("MATCH (a) WHERE a.name = \"x\"").is_err(),
+            "Expected unknown field"
+        );
+    }
+
+    #[test]
+    fn query_str_bindings() {
+        let graph = get_test_graph();
+        let result = graph
+            .query_str("MATCH (a)->(b)->(c) WHERE a.value = \"September\"")
+            .unwrap();
+        let result: Value = serde_json::from_str(&result).unwrap();
+        let chains = result
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|binding| {
+                ["a", "b", "c"].map(|variable| binding[variable]["id"].as_u64().unwrap())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            chains,
+            vec![[7, 5, 1], [7, 5, 3], [7, 6, 3]],
+            "Bindings mismatch"
+        );
+        assert_eq!(result[0]["b"]["value"], "December", "Value mismatch");
+    }
+
+    #[test]
+    fn query_str_backward_edges() {
+        let graph = get_test_graph();
+        assert_eq!(
+            graph
+                .query_str("MATCH (a)->(b)<-(c) WHERE b.id = 2")
+                .unwrap(),
+            r#"[{"a":{"id":1,"value":"January"},"b":{"id":2,"value":"March"},"c":{"id":3,"value":"April"}},{"a":{"id":3,"value":"April"},"b":{"id":2,"value":"March"},"c":{"id":1,"value":"January"}}]"#,
+            "JSON mismatch"
+        );
+    }
+
+    #[test]
+    fn query_str_unknown_variable() {
+        let graph = get_test_graph();
+        assert!(
+            graph.query_str("MATCH (a) WHERE b.id = 1").is_err(),
+            "Expected unknown variable error"
+        );
+    }
+}
@@ -0,0 +1,617 @@
+use alloc::{vec, vec::Vec};
+
+use super::collections::{HashMap, HashSet};
+
+use super::{Graph, GraphId};
+
+/// A combinatorial planar embedding: the clockwise order of neighbours around every node.
+///
+/// Edge directions are ignored, so every edge appears in the rotation of both endpoints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanarEmbedding {
+    rotation: HashMap<GraphId, Vec<GraphId>>,
+}
+
+impl PlanarEmbedding {
+    /// Returns the neighbours of `id` in clockwise order, or an empty slice for unknown nodes.
+    pub fn neighbours(&self, id: GraphId) -> &[GraphId] {
+        self.rotation.get(&id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns every face as the cycle of nodes along its boundary. A node appears more than
+    /// once on a face when the boundary passes through it several times, e.g. at a cut vertex.
+    pub fn faces(&self) -> Vec<Vec<GraphId>> {
+        let mut ids = self.rotation.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+
+        let mut visited = HashSet::new();
+        let mut faces = Vec::new();
+        for from in ids {
+            for &to in &self.rotation[&from] {
+                if visited.contains(&(from, to)) {
+                    continue;
+                }
+
+                let mut face = Vec::new();
+                let (mut a, mut b) = (from, to);
+                while visited.insert((a, b)) {
+                    face.push(a);
+                    (a, b) = (b, self.counterclockwise(b, a));
+                }
+                faces.push(face);
+            }
+        }
+
+        faces
+    }
+
+    /// Returns the neighbour of `id` that comes right before `neighbour` in clockwise order.
+    fn counterclockwise(&self, id: GraphId, neighbour: GraphId) -> GraphId {
+        let rotation = &self.rotation[&id];
+        let index = rotation
+            .iter()
+            .position(|other| *other == neighbour)
+            .expect("Embedding contains both halves of every edge");
+
+        rotation[(index + rotation.len() - 1) % rotation.len()]
+    }
+}
+
+impl<T> Graph<T> {
+    /// Checks whether the graph can be drawn in the plane without crossing edges. Directions,
+    /// self-loops and parallel edges don't affect planarity and are ignored.
+    pub fn is_planar(&self) -> bool {
+        self.planar_embedding().is_some()
+    }
+
+    /// Returns a planar embedding found by the left-right planarity test, or `None` if the
+    /// graph isn't planar.
+    pub fn planar_embedding(&self) -> Option<PlanarEmbedding> {
+        let mut ids = self.nodes.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        let index_of: HashMap<GraphId, usize> =
+            ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+        let mut adjacency = vec![Vec::new(); ids.len()];
+        for edge in self.edges.iter().filter(|edge| edge.from != edge.to) {
+            let (from, to) = (index_of[&edge.from], index_of[&edge.to]);
+            adjacency[from].push(to);
+            adjacency[to].push(from);
+        }
+        for neighbours in &mut adjacency {
+            neighbours.sort_unstable();
+            neighbours.dedup();
+        }
+
+        let rotation = LeftRight::new(adjacency).run()?;
+
+        Some(PlanarEmbedding {
+            rotation: rotation
+                .into_iter()
+                .enumerate()
+                .map(|(v, neighbours)| (ids[v], neighbours.into_iter().map(|w| ids[w]).collect()))
+                .collect(),
+        })
+    }
+}
+
+type HalfEdge = (usize, usize);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Interval {
+    low: Option<HalfEdge>,
+    high: Option<HalfEdge>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ConflictPair {
+    left: Interval,
+    right: Interval,
+}
+
+impl Interval {
+    fn is_empty(&self) -> bool {
+        self.low.is_none() && self.high.is_none()
+    }
+}
+
+impl ConflictPair {
+    fn swap(&mut self) {
+        core::mem::swap(&mut self.left, &mut self.right);
+    }
+}
+
+/// State of the left-right planarity test (Brandes, "The Left-Right Planarity Test"),
+/// following the structure of the networkx implementation. Nodes are dense indices.
+struct LeftRight {
+    adjacency: Vec<Vec<usize>>,
+    height: Vec<Option<usize>>,
+    parent_edge: Vec<Option<HalfEdge>>,
+    roots: Vec<usize>,
+    oriented: Vec<Vec<usize>>,
+    oriented_set: HashSet<HalfEdge>,
+    ordered: Vec<Vec<usize>>,
+    lowpt: HashMap<HalfEdge, usize>,
+    lowpt2: HashMap<HalfEdge, usize>,
+    nesting_depth: HashMap<HalfEdge, i64>,
+    lowpt_edge: HashMap<HalfEdge, HalfEdge>,
+    reference: HashMap<HalfEdge, HalfEdge>,
+    side: HashMap<HalfEdge, i64>,
+    stack_bottom: HashMap<HalfEdge, usize>,
+    stack: Vec<ConflictPair>,
+    left_ref: Vec<usize>,
+    right_ref: Vec<usize>,
+    clockwise: HashMap<HalfEdge, usize>,
+    counterclockwise: HashMap<HalfEdge, usize>,
+    first: Vec<Option<usize>>,
+}
+
+impl LeftRight {
+    fn new(adjacency: Vec<Vec<usize>>) -> Self {
+        let count = adjacency.len();
+        Self {
+            adjacency,
+            height: vec![None; count],
+            parent_edge: vec![None; count],
+            roots: Vec::new(),
+            oriented: vec![Vec::new(); count],
+            oriented_set: HashSet::new(),
+            ordered: vec![Vec::new(); count],
+            lowpt: HashMap::new(),
+            lowpt2: HashMap::new(),
+            nesting_depth: HashMap::new(),
+            lowpt_edge: HashMap::new(),
+            reference: HashMap::new(),
+            side: HashMap::new(),
+            stack_bottom: HashMap::new(),
+            stack: Vec::new(),
+            left_ref: vec![0; count],
+            right_ref: vec![0; count],
+            clockwise: HashMap::new(),
+            counterclockwise: HashMap::new(),
+            first: vec![None; count],
+        }
+    }
+
+    /// Returns the clockwise rotation of every node, or `None` if the graph isn't planar.
+    fn run(mut self) -> Option<Vec<Vec<usize>>> {
+        let count = self.adjacency.len();
+        let edge_count = self.adjacency.iter().map(Vec::len).sum::<usize>() / 2;
+        if count > 2 && edge_count > 3 * count - 6 {
+            return None;
+        }
+
+        for v in 0..count {
+            if self.height[v].is_none() {
+                self.height[v] = Some(0);
+                self.roots.push(v);
+                self.orient(v);
+            }
+        }
+
+        for v in 0..count {
+            self.ordered[v] = self.sorted_by_nesting_depth(v);
+        }
+        for root in self.roots.clone() {
+            if !self.test(root) {
+                return None;
+            }
+        }
+
+        let edges = self.oriented_set.iter().copied().collect::<Vec<_>>();
+        for edge in edges {
+            let sign = self.sign(edge);
+            *self.nesting_depth.get_mut(&edge).unwrap() *= sign;
+        }
+        for v in 0..count {
+            self.ordered[v] = self.sorted_by_nesting_depth(v);
+            let mut previous = None;
+            for w in self.ordered[v].clone() {
+                self.add_half_edge_cw(v, w, previous);
+                previous = Some(w);
+            }
+        }
+        for root in self.roots.clone() {
+            self.embed(root);
+        }
+
+        Some(
+            (0..count)
+                .map(|v| {
+                    let mut rotation = Vec::new();
+                    if let Some(first) = self.first[v] {
+                        let mut w = first;
+                        loop {
+                            rotation.push(w);
+                            w = self.clockwise[&(v, w)];
+                            if w == first {
+                                break;
+                            }
+                        }
+                    }
+                    rotation
+                })
+                .collect(),
+        )
+    }
+
+    fn sorted_by_nesting_depth(&self, v: usize) -> Vec<usize> {
+        let mut neighbours = self.oriented[v].clone();
+        neighbours.sort_by_key(|w| self.nesting_depth[&(v, *w)]);
+
+        neighbours
+    }
+
+    /// Orients the graph along a DFS and computes lowpoints and nesting depths.
+    fn orient(&mut self, v: usize) {
+        let parent = self.parent_edge[v];
+        let height = self.height[v].unwrap();
+        for w in self.adjacency[v].clone() {
+            if self.oriented_set.contains(&(v, w)) || self.oriented_set.contains(&(w, v)) {
+                continue;
+            }
+
+            let vw = (v, w);
+            self.oriented[v].push(w);
+            self.oriented_set.insert(vw);
+            self.lowpt.insert(vw, height);
+            self.lowpt2.insert(vw, height);
+            match self.height[w] {
+                None => {
+                    self.parent_edge[w] = Some(vw);
+                    self.height[w] = Some(height + 1);
+                    self.orient(w);
+                }
+                Some(back) => {
+                    self.lowpt.insert(vw, back);
+                }
+            }
+
+            let (low, low2) = (self.lowpt[&vw], self.lowpt2[&vw]);
+            let chordal = i64::from(low2 < height);
+            self.nesting_depth.insert(vw, 2 * low as i64 + chordal);
+
+            if let Some(e) = parent {
+                let (parent_low, parent_low2) = (self.lowpt[&e], self.lowpt2[&e]);
+                if low < parent_low {
+                    self.lowpt2.insert(e, parent_low.min(low2));
+                    self.lowpt.insert(e, low);
+                } else if low > parent_low {
+                    self.lowpt2.insert(e, parent_low2.min(low));
+                } else {
+                    self.lowpt2.insert(e, parent_low2.min(low2));
+                }
+            }
+        }
+    }
+
+    /// Checks the left-right constraints of every edge below `v`.
+    fn test(&mut self, v: usize) -> bool {
+        let parent = self.parent_edge[v];
+        let height = self.height[v].unwrap();
+        for w in self.ordered[v].clone() {
+            let ei = (v, w);
+            self.stack_bottom.insert(ei, self.stack.len());
+            if self.parent_edge[w] == Some(ei) {
+                if !self.test(w) {
+                    return false;
+                }
+            } else {
+                self.lowpt_edge.insert(ei, ei);
+                self.stack.push(ConflictPair {
+                    left: Interval::default(),
+                    right: Interval {
+                        low: Some(ei),
+                        high: Some(ei),
+                    },
+                });
+            }
+
+            if self.lowpt[&ei] < height {
+                let e = parent.unwrap();
+                if w == self.ordered[v][0] {
+                    self.lowpt_edge.insert(e, self.lowpt_edge[&ei]);
+                } else if !self.add_constraints(ei, e) {
+                    return false;
+                }
+            }
+        }
+
+        if let Some(e) = parent {
+            self.remove_back_edges(e);
+        }
+
+        true
+    }
+
+    fn add_constraints(&mut self, ei: HalfEdge, e: HalfEdge) -> bool {
+        let mut pair = ConflictPair::default();
+        loop {
+            let mut q = self.stack.pop().unwrap();
+            if !q.left.is_empty() {
+                q.swap();
+            }
+            if !q.left.is_empty() {
+                return false;
+            }
+
+            if self.lowpt[&q.right.low.unwrap()] > self.lowpt[&e] {
+                if pair.right.is_empty() {
+                    pair.right = q.right;
+                } else {
+                    self.set_reference(pair.right.low, q.right.high);
+                }
+                pair.right.low = q.right.low;
+            } else {
+                self.set_reference(q.right.low, Some(self.lowpt_edge[&e]));
+            }
+
+            if self.stack.len() == self.stack_bottom[&ei] {
+                break;
+            }
+        }
+
+        while let Some(top) = self.stack.last() {
+            if !self.conflicting(top.left, ei) && !self.conflicting(top.right, ei) {
+                break;
+            }
+
+            let mut q = self.stack.pop().unwrap();
+            if self.conflicting(q.right, ei) {
+                q.swap();
+            }
+            if self.conflicting(q.right, ei) {
+                return false;
+            }
+
+            self.set_reference(pair.right.low, q.right.high);
+            if q.right.low.is_some() {
+                pair.right.low = q.right.low;
+            }
+            if pair.left.is_empty() {
+                pair.left = q.left;
+            } else {
+                self.set_reference(pair.left.low, q.left.high);
+            }
+            pair.left.low = q.left.low;
+        }
+
+        if !pair.left.is_empty() || !pair.right.is_empty() {
+            self.stack.push(pair);
+        }
+
+        true
+    }
+
+    fn remove_back_edges(&mut self, e: HalfEdge) {
+        let u = e.0;
+        let height = self.height[u].unwrap();
+        while let Some(top) = self.stack.last() {
+            if self.lowest(top) != height {
+                break;
+            }
+            let pair = self.stack.pop().unwrap();
+            if let Some(low) = pair.left.low {
+                self.side.insert(low, -1);
+            }
+        }
+
+        if let Some(mut pair) = self.stack.pop() {
+            while let Some(high) = pair.left.high.filter(|high| high.1 == u) {
+                pair.left.high = self.reference.get(&high).copied();
+            }
+            if let (None, Some(low)) = (pair.left.high, pair.left.low) {
+                self.set_reference(Some(low), pair.right.low);
+                self.side.insert(low, -1);
+                pair.left.low = None;
+            }
+
+            while let Some(high) = pair.right.high.filter(|high| high.1 == u) {
+                pair.right.high = self.reference.get(&high).copied();
+            }
+            if let (None, Some(low)) = (pair.right.high, pair.right.low) {
+                self.set_reference(Some(low), pair.left.low);
+                self.side.insert(low, -1);
+                pair.right.low = None;
+            }
+            self.stack.push(pair);
+        }
+
+        if self.lowpt[&e] < height {
+            if let Some(top) = self.stack.last() {
+                let (left, right) = (top.left.high, top.right.high);
+                let highest = match (left, right) {
+                    (Some(l), Some(r)) if self.lowpt[&l] > self.lowpt[&r] => left,
+                    (Some(_), None) => left,
+                    _ => right,
+                };
+                self.set_reference(Some(e), highest);
+            }
+        }
+    }
+
+    /// Inserts the reverse half-edges so that back edges wrap around the correct side.
+    fn embed(&mut self, v: usize) {
+        for w in self.ordered[v].clone() {
+            let ei = (v, w);
+            if self.parent_edge[w] == Some(ei) {
+                self.add_half_edge_ccw(w, v, self.first[w]);
+                self.left_ref[v] = w;
+                self.right_ref[v] = w;
+                self.embed(w);
+            } else if self.side.get(&ei).copied().unwrap_or(1) == 1 {
+                self.add_half_edge_cw(w, v, Some(self.right_ref[w]));
+            } else {
+                self.add_half_edge_ccw(w, v, Some(self.left_ref[w]));
+                self.left_ref[w] = v;
+            }
+        }
+    }
+
+    /// Resolves the side of `edge` relative to its reference chain.
+    fn sign(&mut self, edge: HalfEdge) -> i64 {
+        let mut chain = vec![edge];
+        while let Some(next) = self.reference.get(chain.last().unwrap()) {
+            chain.push(*next);
+        }
+        for i in (0..chain.len() - 1).rev() {
+            let sign = self.side.get(&chain[i + 1]).copied().unwrap_or(1);
+            *self.side.entry(chain[i]).or_insert(1) *= sign;
+            self.reference.remove(&chain[i]);
+        }
+
+        self.side.get(&edge).copied().unwrap_or(1)
+    }
+
+    fn set_reference(&mut self, edge: Option<HalfEdge>, target: Option<HalfEdge>) {
+        if let Some(edge) = edge {
+            match target {
+                Some(target) => self.reference.insert(edge, target),
+                None => self.reference.remove(&edge),
+            };
+        }
+    }
+
+    fn conflicting(&self, interval: Interval, edge: HalfEdge) -> bool {
+        interval
+            .high
+            .is_some_and(|high| self.lowpt[&high] > self.lowpt[&edge])
+    }
+
+    fn lowest(&self, pair: &ConflictPair) -> usize {
+        match (pair.left.low, pair.right.low) {
+            (None, Some(right)) => self.lowpt[&right],
+            (Some(left), None) => self.lowpt[&left],
+            (Some(left), Some(right)) => self.lowpt[&left].min(self.lowpt[&right]),
+            (None, None) => usize::MAX,
+        }
+    }
+
+    /// Inserts `end` right after `reference` in the clockwise rotation of `start`.
+    fn add_half_edge_cw(&mut self, start: usize, end: usize, reference: Option<usize>) {
+        match reference {
+            None => {
+                self.clockwise.insert((start, end), end);
+                self.counterclockwise.insert((start, end), end);
+                self.first[start] = Some(end);
+            }
+            Some(reference) => {
+                let next = self.clockwise[&(start, reference)];
+                self.clockwise.insert((start, reference), end);
+                self.clockwise.insert((start, end), next);
+                self.counterclockwise.insert((start, end), reference);
+                self.counterclockwise.insert((start, next), end);
+            }
+        }
+    }
+
+    /// Inserts `end` right before `reference` in the clockwise rotation of `start`.
+    fn add_half_edge_ccw(&mut self, start: usize, end: usize, reference: Option<usize>) {
+        match reference {
+            None => self.add_half_edge_cw(start, end, None),
+            Some(reference) => {
+                let previous = self.counterclockwise[&(start, reference)];
+                self.add_half_edge_cw(start, end, Some(previous));
+                if self.first[start] == Some(reference) {
+                    self.first[start] = Some(end);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    use crate::generators;
+    use crate::ConnectedGraph;
+
+    use super::*;
+
+    /// Checks Euler's formula for the embedding of `graph`. Face tracing finds a separate outer
+    /// face for every component with edges, so `V - E + F = 2C - I` for `I` isolated nodes.
+    fn assert_euler(graph: &Graph<()>) {
+        let embedding = graph.planar_embedding().expect("Expected a planar graph");
+        let edges = graph
+            .edges
+            .iter()
+            .filter(|edge| edge.from != edge.to)
+            .map(|edge| (edge.from.min(edge.to), edge.from.max(edge.to)))
+            .collect::<HashSet<_>>();
+        let isolated = graph
+            .nodes
+            .keys()
+            .filter(|id| embedding.neighbours(**id).is_empty())
+            .count();
+        let components = ConnectedGraph::new(graph.clone()).component_count();
+        assert_eq!(
+            graph.nodes.len() + embedding.faces().len() + isolated,
+            edges.len() + 2 * components,
+            "Expected the embedding to satisfy Euler's formula"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn planar_graphs() {
+        for graph in [
+            generators::grid(4, 5, |_| ()),
+            generators::complete(4, |_| ()),
+            generators::cycle(7, |_| ()),
+            generators::balanced_tree(2, 3, |_| ()),
+            generators::complete_bipartite(2, 5, |_| ()),
+        ] {
+            assert!(graph.is_planar(), "Expected a planar graph");
+            assert_euler(&graph);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn kuratowski_graphs_not_planar() {
+        assert!(
+            !generators::complete(5, |_| ()).is_planar(),
+            "Expected K5 to be non-planar"
+        );
+        assert!(
+            !generators::complete_bipartite(3, 3, |_| ()).is_planar(),
+            "Expected K3,3 to be non-planar"
+        );
+        assert!(
+            !generators::torus(3, 3, |_| ()).is_planar(),
+            "Expected the 3x3 torus to be non-planar"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn petersen_not_planar() {
+        let mut graph = generators::cycle(5, |_| ());
+        for i in 0..5 {
+            graph.add_node(i + 5, ());
+        }
+        for i in 0..5 {
+            for (from, to) in [(i, i + 5), (i + 5, (i + 2) % 5 + 5)] {
+                graph.add_edge(from, to);
+                graph.add_edge(to, from);
+            }
+        }
+        assert!(
+            !graph.is_planar(),
+            "Expected the Petersen graph to be non-planar"
+        );
+    }
+
+    #[test]
+    fn embedding_ignores_direction_and_loops() {
+        let graph: Graph<()> = Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ()), (5, ())],
+            [(1, 2), (2, 3), (3, 1), (1, 1), (3, 4)],
+        ));
+        assert_euler(&graph);
+        let embedding = graph.planar_embedding().unwrap();
+        assert_eq!(embedding.neighbours(3).len(), 3, "Neighbours mismatch");
+        assert!(embedding.neighbours(5).is_empty(), "Expected isolated node");
+        assert_eq!(embedding.faces().len(), 2, "Faces count mismatch");
+    }
+}
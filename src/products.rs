@@ -0,0 +1,166 @@
+use super::{Graph, GraphError, GraphId};
+
+/// Returns the ID of the node of a graph product made of node `left` of the first graph and
+/// node `right` of the second: `left` in the high 32 bits and `right` in the low ones.
+///
+/// `None` if either ID doesn't fit in 32 bits.
+pub fn product_id(left: GraphId, right: GraphId) -> Option<GraphId> {
+    (left <= MAX_PART && right <= MAX_PART).then(|| combine(left, right))
+}
+
+const MAX_PART: GraphId = u32::MAX as GraphId;
+
+/// Like [`product_id`], for IDs already checked to fit.
+fn combine(left: GraphId, right: GraphId) -> GraphId {
+    (left << 32) | right
+}
+
+/// Splits the ID of a node of a graph product back into the IDs of its two parts, as
+/// `(left, right)`.
+pub fn product_parts(id: GraphId) -> (GraphId, GraphId) {
+    (id >> 32, id & GraphId::from(u32::MAX))
+}
+
+impl<T: Clone> Graph<T> {
+    /// Returns the Cartesian product with `other`: a node for every pair of nodes, with
+    /// [composite IDs](product_id) and both values, and an edge wherever one part follows an
+    /// edge of its graph while the other stays put. Combining the state graphs of independent
+    /// components this way gives the states of the whole system, one component moving at a
+    /// time.
+    ///
+    /// Fails with [`GraphError::ProductIdOutOfRange`] if an ID of either graph doesn't fit in
+    /// 32 bits.
+    pub fn cartesian_product<U: Clone>(
+        &self,
+        other: &Graph<U>,
+    ) -> Result<Graph<(T, U)>, GraphError> {
+        let mut product = self.product_nodes(other)?;
+        for edge in &self.edges {
+            for right in other.nodes.keys() {
+                product.add_edge(combine(edge.from, *right), combine(edge.to, *right));
+            }
+        }
+        for edge in &other.edges {
+            for left in self.nodes.keys() {
+                product.add_edge(combine(*left, edge.from), combine(*left, edge.to));
+            }
+        }
+
+        Ok(product)
+    }
+
+    /// Returns the tensor (categorical) product with `other`: a node for every pair of nodes,
+    /// like [`cartesian_product`](Self::cartesian_product), and an edge wherever both parts
+    /// follow an edge of their graph at once.
+    ///
+    /// Fails with [`GraphError::ProductIdOutOfRange`] if an ID of either graph doesn't fit in
+    /// 32 bits.
+    pub fn tensor_product<U: Clone>(&self, other: &Graph<U>) -> Result<Graph<(T, U)>, GraphError> {
+        let mut product = self.product_nodes(other)?;
+        for left in &self.edges {
+            for right in &other.edges {
+                product.add_edge(combine(left.from, right.from), combine(left.to, right.to));
+            }
+        }
+
+        Ok(product)
+    }
+
+    /// Returns the nodes of a product with `other`, after checking that every ID fits.
+    fn product_nodes<U: Clone>(&self, other: &Graph<U>) -> Result<Graph<(T, U)>, GraphError> {
+        debug_span!(
+            "product",
+            left = self.nodes.len(),
+            right = other.nodes.len()
+        );
+        if let Some(id) = self
+            .nodes
+            .keys()
+            .chain(other.nodes.keys())
+            .find(|id| **id > MAX_PART)
+        {
+            return Err(GraphError::ProductIdOutOfRange(*id));
+        }
+
+        let mut product = Graph::new();
+        for (left, left_value) in &self.nodes {
+            for (right, right_value) in &other.nodes {
+                let value = (left_value.clone(), right_value.clone());
+                product.add_node(combine(*left, *right), value);
+            }
+        }
+
+        Ok(product)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A switch that can be turned on and off, and a counter that only goes up.
+    fn get_test_graphs() -> (Graph<&'static str>, Graph<u8>) {
+        (
+            Graph::from(([(0, "off"), (1, "on")], [(0, 1), (1, 0)])),
+            Graph::from(([(0, 0), (1, 1), (2, 2)], [(0, 1), (1, 2)])),
+        )
+    }
+
+    #[test]
+    fn product_ids_round_trip() {
+        let id = product_id(7, u32::MAX as GraphId).unwrap();
+        assert_eq!(
+            product_parts(id),
+            (7, u32::MAX as GraphId),
+            "Parts mismatch"
+        );
+        assert_eq!(product_id(1 << 32, 0), None, "Expected too large ID");
+    }
+
+    #[test]
+    fn product_rejects_large_ids() {
+        let (switch, _) = get_test_graphs();
+        let large = Graph::from(([(1 << 40, ())], []));
+        assert!(
+            matches!(
+                switch.cartesian_product(&large),
+                Err(GraphError::ProductIdOutOfRange(id)) if id == 1 << 40
+            ),
+            "Expected too large ID to fail"
+        );
+        assert!(
+            large.tensor_product(&switch).is_err(),
+            "Expected too large ID to fail"
+        );
+    }
+
+    #[test]
+    fn cartesian_product_moves_one_part() {
+        let (switch, counter) = get_test_graphs();
+        let product = switch.cartesian_product(&counter).unwrap();
+        assert_eq!(product.nodes.len(), 6, "Node count mismatch");
+        assert_eq!(product.edges.len(), 2 * 3 + 2 * 2, "Edge count mismatch");
+        assert_eq!(product[combine(1, 2)], ("on", 2), "Value mismatch");
+        assert_eq!(
+            product.predecessors(combine(1, 1)),
+            vec![combine(0, 1), combine(1, 0)],
+            "Expected one part to move at a time"
+        );
+    }
+
+    #[test]
+    fn tensor_product_moves_both_parts() {
+        let (switch, counter) = get_test_graphs();
+        let product = switch.tensor_product(&counter).unwrap();
+        assert_eq!(
+            product.sorted_edges(),
+            vec![
+                (combine(0, 0), combine(1, 1)),
+                (combine(0, 1), combine(1, 2)),
+                (combine(1, 0), combine(0, 1)),
+                (combine(1, 1), combine(0, 2)),
+            ],
+            "Edges mismatch"
+        );
+    }
+}
@@ -0,0 +1,99 @@
+/// How far a long-running operation has got. The unit depends on the operation: bytes for
+/// parsing, source nodes for all-pairs shortest paths and iterations for PageRank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    pub done: u64,
+    pub total: u64,
+}
+
+impl Progress {
+    /// Returns the completed share of the work between 0 and 100. Work with nothing to do is
+    /// complete.
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.done as f64 * 100.0 / self.total as f64
+        }
+    }
+}
+
+/// Receives progress updates from operations such as
+/// [`Graph::parse_with_progress`](crate::Graph::parse_with_progress).
+///
+/// Updates are only sent when the whole percentage advances, so a sink is called about a
+/// hundred times per operation at most, and always last with `done == total` if the
+/// operation succeeds. Closures taking a [`Progress`] are sinks.
+pub trait ProgressSink {
+    fn report(&mut self, progress: Progress);
+}
+
+impl<F: FnMut(Progress)> ProgressSink for F {
+    fn report(&mut self, progress: Progress) {
+        self(progress);
+    }
+}
+
+/// Forwards updates to a sink, skipping those that don't advance the whole percentage.
+pub(crate) struct Reporter<'a, S: ProgressSink + ?Sized> {
+    sink: &'a mut S,
+    total: u64,
+    last_percent: Option<u64>,
+}
+
+impl<'a, S: ProgressSink + ?Sized> Reporter<'a, S> {
+    pub(crate) fn new(sink: &'a mut S, total: u64) -> Self {
+        Self {
+            sink,
+            total,
+            last_percent: None,
+        }
+    }
+
+    pub(crate) fn update(&mut self, done: u64) {
+        let percent = (done.min(self.total) * 100)
+            .checked_div(self.total)
+            .unwrap_or(100);
+        if self.last_percent.is_none_or(|last| percent > last) {
+            self.last_percent = Some(percent);
+            self.sink.report(Progress {
+                done: done.min(self.total),
+                total: self.total,
+            });
+        }
+    }
+
+    pub(crate) fn finish(&mut self) {
+        self.update(self.total);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use super::*;
+
+    #[test]
+    fn reporter_skips_small_steps() {
+        let mut reports = Vec::new();
+        let mut sink = |progress: Progress| reports.push(progress.done);
+        let mut reporter = Reporter::new(&mut sink, 1000);
+        for done in 0..1000 {
+            reporter.update(done);
+        }
+        reporter.finish();
+        assert_eq!(reports.len(), 101, "Reports count mismatch");
+        assert_eq!(reports[..3], [0, 10, 20], "Reports mismatch");
+        assert_eq!(reports.last(), Some(&1000), "Expected final report");
+    }
+
+    #[test]
+    fn reporter_empty_work() {
+        let mut reports = Vec::new();
+        let mut sink = |progress: Progress| reports.push(progress);
+        Reporter::new(&mut sink, 0).finish();
+        assert_eq!(reports, vec![Progress { done: 0, total: 0 }]);
+        assert_eq!(reports[0].percent(), 100.0, "Expected complete work");
+    }
+}
@@ -0,0 +1,136 @@
+use alloc::vec::Vec;
+
+use super::{
+    collections::{HashMap, HashSet},
+    Graph, GraphId,
+};
+
+impl<T> Graph<T> {
+    /// Returns the nodes without any edges in ascending ID order. A node with a self-loop
+    /// isn't isolated.
+    pub fn isolated_nodes(&self) -> Vec<GraphId> {
+        let connected = self
+            .edges
+            .iter()
+            .flat_map(|edge| [edge.from, edge.to])
+            .collect::<HashSet<_>>();
+        let mut isolated = self
+            .nodes
+            .keys()
+            .copied()
+            .filter(|id| !connected.contains(id))
+            .collect::<Vec<_>>();
+        isolated.sort_unstable();
+
+        isolated
+    }
+
+    /// Removes the nodes without any edges, returning them with their values in ascending ID
+    /// order.
+    pub fn remove_isolated_nodes(&mut self) -> Vec<(GraphId, T)> {
+        self.isolated_nodes()
+            .into_iter()
+            .map(|id| (id, self.nodes.remove(&id).expect("isolated nodes exist")))
+            .collect()
+    }
+
+    /// Repeatedly removes the nodes with a degree below `min_degree` until every node left has
+    /// at least that degree, returning the removed nodes with their values in ascending ID
+    /// order. What is left is the `min_degree`-core of the graph.
+    ///
+    /// The degree of a node is its number of incoming plus outgoing edges, so a self-loop
+    /// counts twice.
+    pub fn prune_by_degree(&mut self, min_degree: usize) -> Vec<(GraphId, T)> {
+        debug_span!("prune_by_degree", nodes = self.nodes.len(), min_degree);
+        let mut degree: HashMap<GraphId, usize> = self.nodes.keys().map(|id| (*id, 0)).collect();
+        let mut incident: HashMap<GraphId, Vec<GraphId>> = HashMap::new();
+        for edge in &self.edges {
+            for (id, other) in [(edge.from, edge.to), (edge.to, edge.from)] {
+                *degree.get_mut(&id).expect("edges only join existing nodes") += 1;
+                incident.entry(id).or_default().push(other);
+            }
+        }
+
+        let mut removed = HashSet::new();
+        let mut pending = degree
+            .iter()
+            .filter_map(|(id, degree)| (*degree < min_degree).then_some(*id))
+            .collect::<Vec<_>>();
+        while let Some(id) = pending.pop() {
+            if !removed.insert(id) {
+                continue;
+            }
+            for other in incident.get(&id).into_iter().flatten() {
+                let degree = degree.get_mut(other).expect("counted above");
+                *degree -= 1;
+                if *degree < min_degree && !removed.contains(other) {
+                    pending.push(*other);
+                }
+            }
+        }
+        debug_event!(removed = removed.len(), "pruned nodes");
+
+        self.edges
+            .retain(|edge| !removed.contains(&edge.from) && !removed.contains(&edge.to));
+        let mut removed = removed
+            .into_iter()
+            .map(|id| (id, self.nodes.remove(&id).expect("removed nodes exist")))
+            .collect::<Vec<_>>();
+        removed.sort_unstable_by_key(|(id, _)| *id);
+
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn isolated_nodes_exclude_self_loops() {
+        let mut graph = Graph::from((
+            [(1, "a"), (2, "b"), (3, "c"), (4, "d"), (5, "e")],
+            [(1, 2), (3, 3)],
+        ));
+        assert_eq!(
+            graph.isolated_nodes(),
+            vec![4, 5],
+            "Isolated nodes mismatch"
+        );
+        assert_eq!(
+            graph.remove_isolated_nodes(),
+            vec![(4, "d"), (5, "e")],
+            "Removed nodes mismatch"
+        );
+        assert_eq!(graph.nodes.len(), 3, "Node count mismatch");
+        assert!(
+            graph.isolated_nodes().is_empty(),
+            "Expected no isolated nodes left"
+        );
+    }
+
+    #[test]
+    fn prune_by_degree_reaches_fixed_point() {
+        // A triangle with a path hanging off it: removing 5 drops 4 below the threshold.
+        let mut graph = Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ()), (5, ())],
+            [(1, 2), (2, 3), (3, 1), (3, 4), (4, 5)],
+        ));
+        assert_eq!(
+            graph.prune_by_degree(2),
+            vec![(4, ()), (5, ())],
+            "Removed nodes mismatch"
+        );
+        assert_eq!(
+            graph.sorted_edges(),
+            vec![(1, 2), (2, 3), (3, 1)],
+            "Expected the triangle to remain"
+        );
+        assert_eq!(
+            graph.prune_by_degree(3).len(),
+            3,
+            "Expected every node to be removed"
+        );
+        assert!(graph.edges.is_empty(), "Expected no edges left");
+    }
+}
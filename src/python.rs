@@ -0,0 +1,261 @@
+//! Python bindings. Build a wheel with
+//! `maturin build --features python,pyo3/extension-module`; see `pyproject.toml`.
+
+use pyo3::{
+    exceptions::{PyKeyError, PyValueError},
+    prelude::*,
+};
+
+use super::{Graph, GraphId, Path};
+
+/// A graph with string values, exported to Python as `basic_graph_lib.Graph`.
+#[pyclass(name = "Graph", module = "basic_graph_lib", eq)]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PyGraph {
+    graph: Graph<String>,
+}
+
+#[pymethods]
+impl PyGraph {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a graph from TGF.
+    #[staticmethod]
+    fn from_tgf(text: &str) -> PyResult<Self> {
+        text.parse()
+            .map(|graph| Self { graph })
+            .map_err(|e| PyValueError::new_err(format!("Failed to parse graph: {e}")))
+    }
+
+    fn to_tgf(&self) -> String {
+        self.graph.serialize()
+    }
+
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        Graph::from_json(json)
+            .map(|graph| Self { graph })
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    fn to_json(&self) -> String {
+        self.graph.to_json()
+    }
+
+    fn add_node(&mut self, id: GraphId, value: String) {
+        self.graph.add_node(id, value);
+    }
+
+    fn remove_node(&mut self, id: GraphId) {
+        self.graph.delete_node(id);
+    }
+
+    fn add_edge(&mut self, from: GraphId, to: GraphId) {
+        self.graph.add_edge(from, to);
+    }
+
+    fn remove_edge(&mut self, from: GraphId, to: GraphId) {
+        self.graph.delete_edge(from, to);
+    }
+
+    /// Returns the sorted IDs of all nodes.
+    fn ids(&self) -> Vec<GraphId> {
+        let mut ids = self.graph.nodes.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+
+        ids
+    }
+
+    /// Returns all edges as sorted `(from, to)` tuples.
+    fn edges(&self) -> Vec<(GraphId, GraphId)> {
+        let mut edges = self
+            .graph
+            .edges
+            .iter()
+            .map(|edge| (edge.from, edge.to))
+            .collect::<Vec<_>>();
+        edges.sort_unstable();
+
+        edges
+    }
+
+    /// Returns the sorted targets of the node's outgoing edges.
+    fn neighbours(&self, id: GraphId) -> Vec<GraphId> {
+        self.graph.adjacency().remove(&id).unwrap_or_default()
+    }
+
+    fn predecessors(&self, id: GraphId) -> Vec<GraphId> {
+        self.graph.predecessors(id)
+    }
+
+    #[getter]
+    fn edge_count(&self) -> usize {
+        self.graph.edges.len()
+    }
+
+    /// Returns the nodes reachable from `source` in breadth-first order.
+    fn bfs(&self, source: GraphId) -> Vec<GraphId> {
+        self.graph.bfs_order(source)
+    }
+
+    /// Returns the IDs along a shortest path, or `None` if `to` is unreachable.
+    fn shortest_path(&self, from: GraphId, to: GraphId) -> Option<Vec<GraphId>> {
+        self.graph.shortest_path(from, to).map(Path::into_nodes)
+    }
+
+    /// Returns the weakly connected components as sorted lists, ordered by their smallest ID.
+    fn connected_components(&self) -> Vec<Vec<GraphId>> {
+        self.graph.connected_components()
+    }
+
+    fn strongly_connected_components(&self) -> Vec<Vec<GraphId>> {
+        self.graph.strongly_connected_components()
+    }
+
+    /// Returns every elementary cycle, each starting at its smallest ID.
+    fn cycles(&self) -> Vec<Vec<GraphId>> {
+        self.graph.cycles()
+    }
+
+    fn is_planar(&self) -> bool {
+        self.graph.is_planar()
+    }
+
+    /// Returns the faces of a planar embedding, or `None` if the graph isn't planar.
+    fn planar_faces(&self) -> Option<Vec<Vec<GraphId>>> {
+        self.graph
+            .planar_embedding()
+            .map(|embedding| embedding.faces())
+    }
+
+    /// Returns the sorted node IDs and the dense adjacency matrix of the undirected graph.
+    fn adjacency_matrix(&self) -> (Vec<GraphId>, Vec<Vec<f64>>) {
+        let matrix = self.graph.adjacency_matrix();
+        (matrix.ids().to_vec(), matrix.to_dense())
+    }
+
+    /// Returns the sorted node IDs and the dense Laplacian of the undirected graph.
+    fn laplacian_matrix(&self) -> (Vec<GraphId>, Vec<Vec<f64>>) {
+        let matrix = self.graph.laplacian_matrix();
+        (matrix.ids().to_vec(), matrix.to_dense())
+    }
+
+    /// Runs a `MATCH` pattern query and returns its bindings as a list of dictionaries.
+    fn query<'py>(&self, py: Python<'py>, query: &str) -> PyResult<Bound<'py, PyAny>> {
+        let bindings = self
+            .graph
+            .query_str(query)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        py.import("json")?.call_method1("loads", (bindings,))
+    }
+
+    fn __len__(&self) -> usize {
+        self.graph.nodes.len()
+    }
+
+    fn __contains__(&self, id: GraphId) -> bool {
+        self.graph.nodes.contains_key(&id)
+    }
+
+    fn __getitem__(&self, id: GraphId) -> PyResult<String> {
+        self.graph
+            .nodes
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| PyKeyError::new_err(id))
+    }
+
+    fn __str__(&self) -> String {
+        self.graph.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<Graph with {} nodes and {} edges>",
+            self.graph.nodes.len(),
+            self.graph.edges.len()
+        )
+    }
+}
+
+impl From<Graph<String>> for PyGraph {
+    fn from(graph: Graph<String>) -> Self {
+        Self { graph }
+    }
+}
+
+impl From<PyGraph> for Graph<String> {
+    fn from(graph: PyGraph) -> Self {
+        graph.graph
+    }
+}
+
+#[pymodule]
+fn basic_graph_lib(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyGraph>()
+}
+
+#[cfg(test)]
+mod tests {
+    use pyo3::py_run;
+
+    use super::*;
+
+    fn get_test_graph() -> PyGraph {
+        PyGraph::from_tgf("1 a\n2 b\n3 c\n4 d\n5 e\n#\n1 3\n1 2\n2 4\n3 4\n4 1").unwrap()
+    }
+
+    #[test]
+    fn traversal_and_components() {
+        let graph = get_test_graph();
+        assert_eq!(graph.bfs(1), vec![1, 2, 3, 4], "Order mismatch");
+        assert_eq!(graph.shortest_path(3, 2), Some(vec![3, 4, 1, 2]));
+        assert_eq!(
+            graph.connected_components(),
+            vec![vec![1, 2, 3, 4], vec![5]],
+            "Components mismatch"
+        );
+        assert!(graph.is_planar(), "Expected planar graph");
+    }
+
+    #[test]
+    fn python_api() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let graph = Bound::new(py, get_test_graph()).unwrap();
+            py_run!(
+                py,
+                graph,
+                r#"
+assert len(graph) == 5 and 5 in graph and 6 not in graph
+assert graph[2] == "b"
+assert graph.edges()[:2] == [(1, 2), (1, 3)]
+graph.remove_node(4)
+assert graph.edge_count == 2
+assert graph.query('MATCH (a)->(b) WHERE b.value = "c"') == [
+    {"a": {"id": 1, "value": "a"}, "b": {"id": 3, "value": "c"}}
+]
+try:
+    graph[4]
+    raise AssertionError("Expected KeyError")
+except KeyError:
+    pass
+"#
+            );
+        });
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let graph = get_test_graph();
+        assert_eq!(
+            PyGraph::from_json(&graph.to_json()).unwrap(),
+            graph,
+            "Graph doesn't match"
+        );
+        assert!(PyGraph::from_tgf("1 a").is_err(), "Expected parse error");
+    }
+}
@@ -0,0 +1,216 @@
+use alloc::vec::Vec;
+
+use super::collections::{HashMap, HashSet};
+
+use super::{Graph, GraphId, GraphNode};
+
+/// A fluent multi-hop query created by [`Graph::query`].
+///
+/// The query holds a list of current nodes and every step maps it to a new list, in the
+/// spirit of Gremlin traversals. Like in Gremlin, a node reached along several paths appears
+/// once per path until [`dedup`](Query::dedup) is called.
+#[derive(Debug, Clone)]
+pub struct Query<'a, T> {
+    graph: &'a Graph<T>,
+    outgoing: HashMap<GraphId, Vec<GraphId>>,
+    incoming: HashMap<GraphId, Vec<GraphId>>,
+    current: Vec<GraphId>,
+}
+
+impl<T> Graph<T> {
+    /// Starts a query with no current nodes; use [`Query::from`] or [`Query::all`] next.
+    pub fn query(&self) -> Query<'_, T> {
+        Query {
+            graph: self,
+            outgoing: self.adjacency(),
+            incoming: self.reverse_adjacency(),
+            current: Vec::new(),
+        }
+    }
+}
+
+impl<'a, T> Query<'a, T> {
+    /// Replaces the current nodes with `id`, or with nothing if it doesn't exist.
+    pub fn from(mut self, id: GraphId) -> Self {
+        self.current = Vec::from_iter(self.graph.nodes.contains_key(&id).then_some(id));
+        self
+    }
+
+    /// Replaces the current nodes with every node of the graph in ID order.
+    pub fn all(mut self) -> Self {
+        self.current = self.graph.nodes.keys().copied().collect();
+        self.current.sort_unstable();
+        self
+    }
+
+    /// Steps along outgoing edges.
+    pub fn out(mut self) -> Self {
+        self.current = step(&self.current, &self.outgoing);
+        self
+    }
+
+    /// Steps along incoming edges, i.e. to predecessors.
+    pub fn in_(mut self) -> Self {
+        self.current = step(&self.current, &self.incoming);
+        self
+    }
+
+    /// Steps along edges in both directions, successors first.
+    pub fn both(mut self) -> Self {
+        self.current = self
+            .current
+            .iter()
+            .flat_map(|id| {
+                let outgoing = self.outgoing.get(id).into_iter().flatten();
+                let incoming = self.incoming.get(id).into_iter().flatten();
+                outgoing.chain(incoming).copied()
+            })
+            .collect();
+        self
+    }
+
+    /// Keeps only the current nodes accepted by `predicate`.
+    pub fn filter(mut self, predicate: impl Fn(&GraphNode<&'a T>) -> bool) -> Self {
+        let current = core::mem::take(&mut self.current);
+        self.current = current
+            .into_iter()
+            .filter(|id| predicate(&self.node(*id)))
+            .collect();
+        self
+    }
+
+    /// Drops repeated nodes, keeping the first occurrence of each.
+    pub fn dedup(mut self) -> Self {
+        let mut seen = HashSet::new();
+        self.current.retain(|id| seen.insert(*id));
+        self
+    }
+
+    /// Keeps at most `count` current nodes.
+    pub fn limit(mut self, count: usize) -> Self {
+        self.current.truncate(count);
+        self
+    }
+
+    pub fn count(&self) -> usize {
+        self.current.len()
+    }
+
+    pub fn ids(self) -> Vec<GraphId> {
+        self.current
+    }
+
+    pub fn collect(self) -> Vec<GraphNode<&'a T>> {
+        self.current.iter().map(|id| self.node(*id)).collect()
+    }
+
+    fn node(&self, id: GraphId) -> GraphNode<&'a T> {
+        GraphNode {
+            id,
+            value: &self.graph.nodes[&id],
+            neighbours: self.outgoing.get(&id).cloned().unwrap_or_default(),
+        }
+    }
+}
+
+fn step(current: &[GraphId], adjacency: &HashMap<GraphId, Vec<GraphId>>) -> Vec<GraphId> {
+    current
+        .iter()
+        .flat_map(|id| adjacency.get(id).into_iter().flatten().copied())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_graph() -> Graph<String> {
+        Graph::from((
+            [
+                (1, "January".to_string()),
+                (2, "March".to_string()),
+                (3, "April".to_string()),
+                (4, "May".to_string()),
+                (5, "December".to_string()),
+                (6, "June".to_string()),
+                (7, "September".to_string()),
+            ],
+            [
+                (1, 2),
+                (3, 2),
+                (4, 3),
+                (5, 1),
+                (5, 3),
+                (6, 3),
+                (6, 1),
+                (7, 5),
+                (7, 6),
+                (7, 1),
+            ],
+        ))
+    }
+
+    #[test]
+    fn query_multi_hop_filter() {
+        let graph = get_test_graph();
+        let nodes = graph
+            .query()
+            .from(7)
+            .out()
+            .filter(|n| n.value().contains('J'))
+            .out()
+            .collect();
+        assert_eq!(
+            nodes.iter().map(|n| *n.id()).collect::<Vec<_>>(),
+            vec![2, 1, 3],
+            "Expected successors of January and June"
+        );
+        assert_eq!(nodes[0].value().as_str(), "March", "Value mismatch");
+    }
+
+    #[test]
+    fn query_keeps_paths_until_dedup() {
+        let graph = get_test_graph();
+        assert_eq!(
+            graph.query().from(7).out().out().ids(),
+            vec![2, 1, 3, 1, 3],
+            "Expected one entry per path"
+        );
+        assert_eq!(
+            graph.query().from(7).out().out().dedup().ids(),
+            vec![2, 1, 3],
+            "Expected repeated nodes dropped"
+        );
+    }
+
+    #[test]
+    fn query_in_and_both() {
+        let graph = get_test_graph();
+        assert_eq!(
+            graph.query().from(3).in_().ids(),
+            vec![4, 5, 6],
+            "Predecessors mismatch"
+        );
+        assert_eq!(
+            graph.query().from(5).both().ids(),
+            vec![1, 3, 7],
+            "Neighbours mismatch"
+        );
+        assert_eq!(graph.query().from(9).out().count(), 0, "Expected no nodes");
+    }
+
+    #[test]
+    fn query_all_limit() {
+        let graph = get_test_graph();
+        assert_eq!(
+            graph
+                .query()
+                .all()
+                .filter(|n| n.neighbour_ids().is_empty())
+                .ids(),
+            vec![2],
+            "Expected only sinks"
+        );
+        assert_eq!(graph.query().all().limit(2).ids(), vec![1, 2]);
+    }
+}
@@ -0,0 +1,112 @@
+use alloc::vec::Vec;
+
+use super::{collections::HashMap, Edge, Graph, GraphId, WeightedGraph};
+
+/// Identifies a group of nodes merged by [`Graph::quotient`]. It becomes the ID of the merged
+/// node.
+pub type GroupId = GraphId;
+
+impl<T> Graph<T> {
+    /// Merges the nodes of every group into a single node, e.g. people into their departments.
+    /// Every merged node holds the sorted IDs of its members and gets the ID of its group.
+    ///
+    /// All edges between the members of two groups become a single edge, weighted by their
+    /// number. Edges within a group become a self-loop on it, which can be deleted if unwanted.
+    pub fn quotient(&self, group_of: impl Fn(GraphId) -> GroupId) -> WeightedGraph<Vec<GraphId>> {
+        self.quotient_by(group_of, |_, _| 1.0)
+    }
+
+    /// Like [`quotient`](Self::quotient), but weights every merged edge by the sum of `weight`
+    /// over the edges it replaces.
+    pub(crate) fn quotient_by(
+        &self,
+        group_of: impl Fn(GraphId) -> GroupId,
+        weight: impl Fn(GraphId, GraphId) -> f64,
+    ) -> WeightedGraph<Vec<GraphId>> {
+        debug_span!("quotient", nodes = self.nodes.len());
+        let groups = self
+            .nodes
+            .keys()
+            .map(|id| (*id, group_of(*id)))
+            .collect::<HashMap<_, _>>();
+
+        let mut members = HashMap::<GroupId, Vec<GraphId>>::new();
+        for (id, group) in &groups {
+            members.entry(*group).or_default().push(*id);
+        }
+        let mut totals = HashMap::<Edge, f64>::new();
+        for edge in &self.edges {
+            let merged = Edge {
+                from: groups[&edge.from],
+                to: groups[&edge.to],
+            };
+            *totals.entry(merged).or_default() += weight(edge.from, edge.to);
+        }
+
+        let mut quotient = WeightedGraph::new();
+        for (group, mut ids) in members {
+            ids.sort_unstable();
+            quotient.add_node(group, ids);
+        }
+        for (edge, total) in totals {
+            quotient.add_edge(edge.from, edge.to, total);
+        }
+        debug_event!(groups = quotient.nodes.len(), "quotient built");
+
+        quotient
+    }
+}
+
+impl<T> WeightedGraph<T> {
+    /// Like [`Graph::quotient`], but weights every merged edge by the total weight of the
+    /// edges it replaces.
+    pub fn quotient(&self, group_of: impl Fn(GraphId) -> GroupId) -> WeightedGraph<Vec<GraphId>> {
+        self.quotient_by(group_of, |from, to| {
+            self.weight(from, to).expect("every edge has a weight")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// People numbered by department: 1x in department 1, 2x in department 2.
+    fn get_test_graph() -> Graph<&'static str> {
+        Graph::from((
+            [
+                (11, "ann"),
+                (12, "bob"),
+                (21, "cid"),
+                (22, "dan"),
+                (30, "eve"),
+            ],
+            [(11, 21), (12, 21), (12, 22), (11, 12), (22, 11)],
+        ))
+    }
+
+    #[test]
+    fn quotient_merges_groups() {
+        let quotient = get_test_graph().quotient(|id| id / 10);
+        assert_eq!(quotient[1], vec![11, 12], "Members mismatch");
+        assert_eq!(quotient[3], vec![30], "Expected singleton group");
+        assert_eq!(
+            quotient.weighted_edges(),
+            vec![(1, 1, 1.0), (1, 2, 3.0), (2, 1, 1.0)],
+            "Edges mismatch"
+        );
+    }
+
+    #[test]
+    fn weighted_quotient_sums_weights() {
+        let mut graph = WeightedGraph::from(get_test_graph());
+        graph.add_edge(12, 22, 2.5);
+        let quotient = graph.quotient(|id| id / 10);
+        assert_eq!(quotient.weight(1, 2), Some(4.5), "Weight mismatch");
+        assert_eq!(
+            graph.quotient(|id| id).weighted_edges(),
+            graph.weighted_edges(),
+            "Expected identity grouping to keep edges"
+        );
+    }
+}
@@ -0,0 +1,312 @@
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use core::mem;
+
+use super::{
+    collections::HashMap,
+    progress::{ProgressSink, Reporter},
+    CancellationToken, Convergence, Graph, GraphError, GraphId, IterationBudget,
+};
+
+impl<T> Graph<T> {
+    /// Computes PageRank by power iteration, with `damping` as the probability of following an
+    /// edge rather than jumping to a random node (usually 0.85). Ranks sum to 1; nodes without
+    /// outgoing edges spread their rank over all nodes.
+    pub fn page_rank(&self, damping: f64, iterations: usize) -> BTreeMap<GraphId, f64> {
+        self.page_rank_with_progress(damping, iterations, &mut |_| {})
+    }
+
+    /// Like [`page_rank`](Self::page_rank), reporting the iterations done so far to
+    /// `progress`.
+    pub fn page_rank_with_progress(
+        &self,
+        damping: f64,
+        iterations: usize,
+        progress: &mut (impl ProgressSink + ?Sized),
+    ) -> BTreeMap<GraphId, f64> {
+        self.page_rank_cancellable(damping, iterations, progress, &CancellationToken::new())
+            .expect("token is never cancelled")
+    }
+
+    /// Like [`page_rank_with_progress`](Self::page_rank_with_progress), but stops with
+    /// [`GraphError::Cancelled`] between iterations once `cancel` is set.
+    pub fn page_rank_cancellable(
+        &self,
+        damping: f64,
+        iterations: usize,
+        progress: &mut (impl ProgressSink + ?Sized),
+        cancel: &CancellationToken,
+    ) -> Result<BTreeMap<GraphId, f64>, GraphError> {
+        let budget = IterationBudget::new()
+            .max_iterations(iterations)
+            .tolerance(0.0);
+        self.run_page_rank(damping, &budget, progress, cancel)
+            .map(|convergence| convergence.result)
+    }
+
+    /// Like [`page_rank`](Self::page_rank), but iterates until the ranks change by at most
+    /// the budget's tolerance or its iterations or time run out.
+    pub fn page_rank_with_budget(
+        &self,
+        damping: f64,
+        budget: &IterationBudget,
+    ) -> Convergence<BTreeMap<GraphId, f64>> {
+        self.run_page_rank(damping, budget, &mut |_| {}, &CancellationToken::new())
+            .expect("token is never cancelled")
+    }
+
+    fn run_page_rank(
+        &self,
+        damping: f64,
+        budget: &IterationBudget,
+        progress: &mut (impl ProgressSink + ?Sized),
+        cancel: &CancellationToken,
+    ) -> Result<Convergence<BTreeMap<GraphId, f64>>, GraphError> {
+        debug_span!("page_rank", nodes = self.nodes.len(), budget.max_iterations);
+        let (ids, targets) = self.indexed_targets();
+        let count = ids.len() as f64;
+        let mut ranks = vec![1.0 / count; ids.len()];
+        let convergence = iterate(budget, progress, cancel, || {
+            let dangling = (0..ids.len())
+                .filter(|index| targets[*index].is_empty())
+                .map(|index| ranks[index])
+                .sum::<f64>();
+            let mut next = vec![(1.0 - damping + damping * dangling) / count; ids.len()];
+            for (index, rank) in ranks.iter().enumerate() {
+                let share = damping * rank / targets[index].len() as f64;
+                for target in &targets[index] {
+                    next[*target] += share;
+                }
+            }
+
+            change(&mem::replace(&mut ranks, next), &ranks)
+        })?;
+
+        Ok(convergence.with(ids.into_iter().zip(ranks).collect()))
+    }
+
+    /// Computes hub and authority scores with Kleinberg's HITS algorithm, each normalized to
+    /// sum to 1. Good hubs point to good authorities, and good authorities are pointed to by
+    /// good hubs.
+    pub fn hits(&self, budget: &IterationBudget) -> Convergence<HitsScores> {
+        debug_span!("hits", nodes = self.nodes.len(), budget.max_iterations);
+        let (ids, targets) = self.indexed_targets();
+        let mut hubs = vec![1.0 / ids.len() as f64; ids.len()];
+        let mut authorities = hubs.clone();
+        let convergence = iterate(budget, &mut |_| {}, &CancellationToken::new(), || {
+            let mut next_authorities = vec![0.0; ids.len()];
+            for (index, hub) in hubs.iter().enumerate() {
+                for target in &targets[index] {
+                    next_authorities[*target] += hub;
+                }
+            }
+            normalize(&mut next_authorities);
+            let mut next_hubs = targets
+                .iter()
+                .map(|targets| targets.iter().map(|target| next_authorities[*target]).sum())
+                .collect::<Vec<f64>>();
+            normalize(&mut next_hubs);
+
+            change(&mem::replace(&mut hubs, next_hubs), &hubs)
+                + change(
+                    &mem::replace(&mut authorities, next_authorities),
+                    &authorities,
+                )
+        })
+        .expect("token is never cancelled");
+
+        convergence.with(HitsScores {
+            hubs: ids.iter().copied().zip(hubs).collect(),
+            authorities: ids.into_iter().zip(authorities).collect(),
+        })
+    }
+
+    /// Returns the sorted node IDs and, for every node by index, the indices of its targets.
+    fn indexed_targets(&self) -> (Vec<GraphId>, Vec<Vec<usize>>) {
+        let mut ids = self.nodes.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        let index_of = ids
+            .iter()
+            .enumerate()
+            .map(|(index, id)| (*id, index))
+            .collect::<HashMap<_, _>>();
+        let mut targets = vec![Vec::new(); ids.len()];
+        for edge in &self.edges {
+            targets[index_of[&edge.from]].push(index_of[&edge.to]);
+        }
+
+        (ids, targets)
+    }
+}
+
+/// Hub and authority scores computed by [`Graph::hits`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HitsScores {
+    pub hubs: BTreeMap<GraphId, f64>,
+    pub authorities: BTreeMap<GraphId, f64>,
+}
+
+/// Runs `step`, which returns how much an iteration changed the scores, until the change is
+/// within the budget's tolerance or the budget runs out. The result is filled in by the caller.
+pub(crate) fn iterate(
+    budget: &IterationBudget,
+    progress: &mut (impl ProgressSink + ?Sized),
+    cancel: &CancellationToken,
+    mut step: impl FnMut() -> f64,
+) -> Result<Convergence<()>, GraphError> {
+    let deadline = budget.start();
+    let mut reporter = Reporter::new(progress, budget.max_iterations as u64);
+    let mut convergence = Convergence {
+        result: (),
+        iterations: 0,
+        converged: false,
+    };
+    while convergence.iterations < budget.max_iterations && !deadline.expired() {
+        if cancel.is_cancelled() {
+            return Err(GraphError::Cancelled);
+        }
+        reporter.update(convergence.iterations as u64);
+        let change = step();
+        convergence.iterations += 1;
+        if change <= budget.tolerance {
+            convergence.converged = true;
+            break;
+        }
+    }
+    reporter.finish();
+    debug_event!(
+        iterations = convergence.iterations,
+        converged = convergence.converged,
+        "iteration stopped"
+    );
+
+    Ok(convergence)
+}
+
+/// Sum of absolute differences between two score vectors.
+fn change(old: &[f64], new: &[f64]) -> f64 {
+    old.iter()
+        .zip(new)
+        .map(|(old, new)| (new - old).abs())
+        .sum()
+}
+
+/// Scales scores to sum to 1, leaving all-zero scores alone.
+fn normalize(scores: &mut [f64]) {
+    let total = scores.iter().sum::<f64>();
+    if total > 0.0 {
+        for score in scores {
+            *score /= total;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Progress;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "Expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn page_rank_cycle_is_uniform() {
+        let graph = Graph::from(([(1, ()), (2, ()), (3, ())], [(1, 2), (2, 3), (3, 1)]));
+        for rank in graph.page_rank(0.85, 20).into_values() {
+            assert_close(rank, 1.0 / 3.0);
+        }
+    }
+
+    #[test]
+    fn page_rank_favours_linked_nodes() {
+        let graph = Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ())],
+            [(1, 4), (2, 4), (3, 4)],
+        ));
+        let mut reports = Vec::new();
+        let ranks = graph
+            .page_rank_with_progress(0.85, 50, &mut |progress: Progress| reports.push(progress));
+        assert_close(ranks.values().sum(), 1.0);
+        assert!(ranks[&4] > ranks[&1], "Expected linked node to rank higher");
+        assert_close(ranks[&1], ranks[&2]);
+        assert_eq!(
+            reports.last(),
+            Some(&Progress {
+                done: 50,
+                total: 50
+            }),
+            "Expected final report"
+        );
+    }
+
+    #[test]
+    fn page_rank_cancelled_midway() {
+        let graph = Graph::from(([(1, ()), (2, ())], [(1, 2)]));
+        let token = CancellationToken::new();
+        let mut iterations = 0;
+        let result = graph.page_rank_cancellable(
+            0.85,
+            100,
+            &mut |_| {
+                iterations += 1;
+                if iterations == 3 {
+                    token.cancel();
+                }
+            },
+            &token,
+        );
+        assert!(
+            matches!(result, Err(GraphError::Cancelled)),
+            "Expected cancellation"
+        );
+        assert_eq!(iterations, 3, "Expected no iterations after cancelling");
+    }
+
+    #[test]
+    fn page_rank_with_budget_converges() {
+        let graph = Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ())],
+            [(1, 2), (2, 3), (3, 1), (4, 1)],
+        ));
+        let convergence = graph.page_rank_with_budget(0.85, &IterationBudget::new());
+        assert!(convergence.converged, "Expected convergence");
+        assert!(convergence.iterations < 100, "Expected to stop early");
+        assert_close(convergence.result.values().sum(), 1.0);
+
+        let short = graph.page_rank_with_budget(0.85, &IterationBudget::new().max_iterations(2));
+        assert_eq!(short.iterations, 2, "Iterations mismatch");
+        assert!(!short.converged, "Expected budget to run out");
+    }
+
+    #[test]
+    fn hits_scores() {
+        let graph = Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ())],
+            [(1, 3), (1, 4), (2, 3)],
+        ));
+        let convergence = graph.hits(&IterationBudget::new());
+        assert!(convergence.converged, "Expected convergence");
+        let scores = convergence.result;
+        assert!(
+            scores.hubs[&1] > scores.hubs[&2],
+            "Expected node linking to more authorities to be a better hub"
+        );
+        assert!(
+            scores.authorities[&3] > scores.authorities[&4],
+            "Expected node linked from more hubs to be a better authority"
+        );
+        assert_close(scores.hubs[&3], 0.0);
+        assert_close(scores.authorities.values().sum(), 1.0);
+    }
+
+    #[test]
+    fn page_rank_empty_graph() {
+        assert!(
+            Graph::<()>::new().page_rank(0.85, 10).is_empty(),
+            "Expected no ranks"
+        );
+    }
+}
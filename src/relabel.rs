@@ -0,0 +1,109 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::{error::Error, fmt};
+
+use super::{Graph, GraphId};
+
+/// Several nodes that a relabelling would give the same ID, the lowest such ID if there are
+/// several collisions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollisionError {
+    /// The ID the nodes would share.
+    pub id: GraphId,
+    /// The original IDs of the nodes, sorted.
+    pub sources: Vec<GraphId>,
+}
+
+impl<T: Clone> Graph<T> {
+    /// Returns a copy of the graph with every node ID replaced by `mapping(id)`, e.g. to bring
+    /// graphs from different sources into a shared ID space before
+    /// [merging](Self::merge) them.
+    ///
+    /// Fails without building anything if `mapping` gives several nodes the same ID.
+    pub fn relabel(
+        &self,
+        mapping: impl Fn(GraphId) -> GraphId,
+    ) -> Result<Graph<T>, CollisionError> {
+        let mut sources = BTreeMap::<GraphId, Vec<GraphId>>::new();
+        for (id, _) in self.sorted_nodes() {
+            sources.entry(mapping(id)).or_default().push(id);
+        }
+        if let Some((id, sources)) = sources.iter().find(|(_, sources)| sources.len() > 1) {
+            return Err(CollisionError {
+                id: *id,
+                sources: sources.clone(),
+            });
+        }
+
+        let new_ids = sources
+            .into_iter()
+            .map(|(new, old)| (old[0], new))
+            .collect::<BTreeMap<_, _>>();
+        let mut graph = Graph::new();
+        for (id, value) in &self.nodes {
+            graph.add_node(new_ids[id], value.clone());
+        }
+        for edge in &self.edges {
+            graph.add_edge(new_ids[&edge.from], new_ids[&edge.to]);
+        }
+
+        Ok(graph)
+    }
+}
+
+impl fmt::Display for CollisionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Nodes")?;
+        for (index, id) in self.sources.iter().enumerate() {
+            write!(f, "{}{id}", if index == 0 { " " } else { ", " })?;
+        }
+
+        write!(f, " would all become node {}", self.id)
+    }
+}
+
+impl Error for CollisionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MergePolicy;
+
+    fn get_test_graph() -> Graph<&'static str> {
+        Graph::from(([(1, "a"), (2, "b"), (3, "c")], [(1, 2), (2, 3), (3, 3)]))
+    }
+
+    #[test]
+    fn relabel_moves_nodes_and_edges() {
+        let relabelled = get_test_graph().relabel(|id| id + 100).unwrap();
+        assert_eq!(
+            relabelled,
+            Graph::from((
+                [(101, "a"), (102, "b"), (103, "c")],
+                [(101, 102), (102, 103), (103, 103)]
+            )),
+            "Relabelled graph mismatch"
+        );
+
+        let mut merged = get_test_graph();
+        merged.merge(relabelled, MergePolicy::Fail).unwrap();
+        assert_eq!(merged.nodes.len(), 6, "Expected disjoint ID spaces");
+    }
+
+    #[test]
+    fn relabel_detects_collisions() {
+        let error = get_test_graph().relabel(|id| id.min(2)).unwrap_err();
+        assert_eq!(
+            error,
+            CollisionError {
+                id: 2,
+                sources: vec![2, 3]
+            },
+            "Collision mismatch"
+        );
+        assert_eq!(
+            error.to_string(),
+            "Nodes 2, 3 would all become node 2",
+            "Message mismatch"
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+};
+
+use super::{Graph, GraphId};
+
+struct Charset {
+    top_left: char,
+    top_right: char,
+    bottom_left: char,
+    bottom_right: char,
+    horizontal: char,
+    vertical: char,
+    branch: &'static str,
+    last_branch: &'static str,
+}
+
+const ASCII: Charset = Charset {
+    top_left: '+',
+    top_right: '+',
+    bottom_left: '+',
+    bottom_right: '+',
+    horizontal: '-',
+    vertical: '|',
+    branch: "|->",
+    last_branch: "'->",
+};
+
+const UNICODE: Charset = Charset {
+    top_left: '┌',
+    top_right: '┐',
+    bottom_left: '└',
+    bottom_right: '┘',
+    horizontal: '─',
+    vertical: '│',
+    branch: "├─▶",
+    last_branch: "└─▶",
+};
+
+const GAP: usize = 3;
+
+impl<T> Graph<T> {
+    /// Draws the graph as boxes with outgoing arrows using plain ASCII characters.
+    ///
+    /// DAGs are laid out in layers so every arrow points to a lower row, other graphs are
+    /// placed on a square grid ordered by ID.
+    pub fn render_ascii(&self) -> String
+    where
+        T: Display,
+    {
+        self.render(&ASCII)
+    }
+
+    /// Same as [`Graph::render_ascii`], but draws with Unicode box-drawing characters.
+    pub fn render_unicode(&self) -> String
+    where
+        T: Display,
+    {
+        self.render(&UNICODE)
+    }
+
+    fn render(&self, charset: &Charset) -> String
+    where
+        T: Display,
+    {
+        let mut adjacency: BTreeMap<GraphId, Vec<GraphId>> = BTreeMap::new();
+        for edge in &self.edges {
+            adjacency.entry(edge.from).or_default().push(edge.to);
+        }
+        for neighbours in adjacency.values_mut() {
+            neighbours.sort_unstable();
+        }
+
+        let labels: HashMap<GraphId, String> = self
+            .nodes
+            .iter()
+            .map(|(id, value)| (*id, format!("{id}: {value}")))
+            .collect();
+        let width = labels
+            .values()
+            .map(|label| label.chars().count() + 4)
+            .chain(self.edges.iter().map(|edge| edge.to.to_string().len() + 6))
+            .max()
+            .unwrap_or_default();
+
+        let rows = self.dag_layers().unwrap_or_else(|| self.grid_rows());
+        rows.iter()
+            .map(|row| {
+                let mut lines = vec![String::new(); 3];
+                let arrow_lines = row
+                    .iter()
+                    .map(|id| adjacency.get(id).map_or(0, Vec::len))
+                    .max()
+                    .unwrap_or_default();
+                lines.resize(3 + arrow_lines, String::new());
+
+                for (column, id) in row.iter().enumerate() {
+                    let label = &labels[id];
+                    let padding = width - 4 - label.chars().count();
+                    let horizontal = charset.horizontal.to_string().repeat(width - 2);
+                    let cells = [
+                        format!("{}{horizontal}{}", charset.top_left, charset.top_right),
+                        format!(
+                            "{v} {label}{} {v}",
+                            " ".repeat(padding),
+                            v = charset.vertical
+                        ),
+                        format!(
+                            "{}{horizontal}{}",
+                            charset.bottom_left, charset.bottom_right
+                        ),
+                    ]
+                    .into_iter()
+                    .chain(adjacency.get(id).into_iter().flat_map(|neighbours| {
+                        neighbours.iter().enumerate().map(|(index, to)| {
+                            let branch = if index + 1 == neighbours.len() {
+                                charset.last_branch
+                            } else {
+                                charset.branch
+                            };
+                            format!("  {branch} {to}")
+                        })
+                    }))
+                    .collect::<Vec<String>>();
+
+                    for (index, line) in lines.iter_mut().enumerate() {
+                        if let Some(cell) = cells.get(index) {
+                            pad_to(line, column * (width + GAP));
+                            line.push_str(cell);
+                        }
+                    }
+                }
+
+                lines
+                    .into_iter()
+                    .map(|line| line.trim_end().to_string())
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            })
+            .collect::<Vec<String>>()
+            .join("\n\n")
+    }
+
+    pub(crate) fn dag_layers(&self) -> Option<Vec<Vec<GraphId>>> {
+        let mut in_degree: HashMap<GraphId, usize> = self.nodes.keys().map(|id| (*id, 0)).collect();
+        for edge in &self.edges {
+            *in_degree.entry(edge.to).or_default() += 1;
+        }
+
+        // Holds the longest distance from a source found so far, which is only final once the
+        // node is ready.
+        let mut layer_of: HashMap<GraphId, usize> = HashMap::new();
+        let mut ready: Vec<GraphId> = in_degree
+            .iter()
+            .filter_map(|(id, degree)| (*degree == 0).then_some(*id))
+            .collect();
+        let mut processed = 0;
+        while let Some(id) = ready.pop() {
+            processed += 1;
+            let layer = *layer_of.entry(id).or_default();
+            for edge in self.edges.iter().filter(|edge| edge.from == id) {
+                let next = layer_of.entry(edge.to).or_default();
+                *next = (*next).max(layer + 1);
+                let degree = in_degree.get_mut(&edge.to).expect("counted above");
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.push(edge.to);
+                }
+            }
+        }
+
+        // Nodes on or behind a cycle never become ready.
+        if processed != self.nodes.len() {
+            return None;
+        }
+
+        let mut layers: BTreeMap<usize, Vec<GraphId>> = BTreeMap::new();
+        for (id, layer) in layer_of {
+            layers.entry(layer).or_default().push(id);
+        }
+
+        Some(
+            layers
+                .into_values()
+                .map(|mut layer| {
+                    layer.sort_unstable();
+                    layer
+                })
+                .collect(),
+        )
+    }
+
+    fn grid_rows(&self) -> Vec<Vec<GraphId>> {
+        let mut ids = self.nodes.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        let columns = (ids.len() as f64).sqrt().ceil().max(1.0) as usize;
+
+        ids.chunks(columns).map(<[GraphId]>::to_vec).collect()
+    }
+}
+
+fn pad_to(line: &mut String, width: usize) {
+    let len = line.chars().count();
+    if len < width {
+        line.push_str(&" ".repeat(width - len));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_ascii_dag() {
+        let graph: Graph<&str> = Graph::from(([(1, "a"), (2, "b"), (3, "c")], [(1, 2), (1, 3)]));
+        assert_eq!(
+            graph.render_ascii(),
+            "+------+\n\
+             | 1: a |\n\
+             +------+\n  \
+               |-> 2\n  \
+               '-> 3\n\
+             \n\
+             +------+   +------+\n\
+             | 2: b |   | 3: c |\n\
+             +------+   +------+",
+            "Rendered output mismatch"
+        );
+    }
+
+    #[test]
+    fn render_unicode_cycle_uses_grid() {
+        let graph: Graph<&str> = Graph::from(([(1, "a"), (2, "b")], [(1, 2), (2, 1)]));
+        assert_eq!(
+            graph.render_unicode(),
+            "┌──────┐   ┌──────┐\n\
+             │ 1: a │   │ 2: b │\n\
+             └──────┘   └──────┘\n  \
+               └─▶ 2      └─▶ 1",
+            "Rendered output mismatch"
+        );
+    }
+
+    #[test]
+    fn render_empty() {
+        let graph: Graph<&str> = Graph::new();
+        assert_eq!(graph.render_ascii(), "", "Expected empty output");
+    }
+
+    #[test]
+    fn dag_layers_longest_path() {
+        let graph: Graph<()> = Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ())],
+            [(1, 2), (2, 3), (1, 3), (4, 3)],
+        ));
+        assert_eq!(
+            graph.dag_layers(),
+            Some(vec![vec![1, 4], vec![2], vec![3]]),
+            "Layers mismatch"
+        );
+    }
+
+    #[test]
+    fn dag_layers_cycle() {
+        let graph: Graph<()> = Graph::from(([(1, ()), (2, ())], [(1, 2), (2, 1)]));
+        assert_eq!(graph.dag_layers(), None, "Expected no layers for a cycle");
+
+        let graph: Graph<()> = Graph::from((
+            [(1, ()), (2, ()), (3, ())],
+            [(3, 1), (3, 2), (1, 2), (2, 1)],
+        ));
+        assert_eq!(
+            graph.dag_layers(),
+            None,
+            "Expected no layers for a cycle behind a source"
+        );
+    }
+}
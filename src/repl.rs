@@ -0,0 +1,319 @@
+use std::{env, io::Write, path::PathBuf};
+
+use basic_graph_lib::{Graph, GraphId, Mutation};
+use clap::ArgMatches;
+use rustyline::{
+    completion::Completer, error::ReadlineError, highlight::Highlighter, hint::Hinter,
+    history::DefaultHistory, validate::Validator, Context, Editor, Helper,
+};
+
+use super::{id_arg, join_ids, read_graph, save_error, write_atomically, write_error};
+
+const HISTORY_FILE: &str = ".bgraph_history";
+
+/// Completes command names, and node IDs for every word after the command.
+struct ReplHelper {
+    commands: Vec<String>,
+    ids: Vec<String>,
+}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let start = line[..pos].rfind(' ').map_or(0, |index| index + 1);
+        let prefix = &line[start..pos];
+        let candidates = if start == 0 {
+            &self.commands
+        } else {
+            &self.ids
+        };
+
+        Ok((
+            start,
+            candidates
+                .iter()
+                .filter(|candidate| candidate.starts_with(prefix))
+                .cloned()
+                .collect(),
+        ))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+/// Interactive session on a single graph file.
+struct Session {
+    graph: Graph<String>,
+    file: PathBuf,
+    /// Mutations that revert the edits made so far, latest last.
+    undo: Vec<Vec<Mutation<String>>>,
+    unsaved: bool,
+    /// Set when quitting was refused because of unsaved changes, so that the next quit goes
+    /// through.
+    warned: bool,
+}
+
+enum Flow {
+    Continue,
+    Quit,
+}
+
+pub fn run_repl(matches: &ArgMatches, out: &mut dyn Write) -> Result<(), String> {
+    let file = matches.get_one::<PathBuf>("file").expect("required");
+    if file.as_os_str() == "-" {
+        return Err("The REPL reads commands from stdin, so it needs a graph file".to_string());
+    }
+    let mut session = Session {
+        graph: read_graph(matches)?,
+        file: file.clone(),
+        undo: Vec::new(),
+        unsaved: false,
+        warned: false,
+    };
+
+    let mut commands = repl_command();
+    let mut editor = Editor::<ReplHelper, DefaultHistory>::new()
+        .map_err(|e| format!("Failed to start REPL: {e}"))?;
+    editor.set_helper(Some(ReplHelper {
+        commands: commands
+            .get_subcommands()
+            .map(|command| command.get_name().to_string())
+            .collect(),
+        ids: Vec::new(),
+    }));
+    let history = env::var_os("HOME").map(|home| PathBuf::from(home).join(HISTORY_FILE));
+    if let Some(history) = &history {
+        // A missing history file just means this is the first session.
+        let _ = editor.load_history(history);
+    }
+
+    loop {
+        if let Some(helper) = editor.helper_mut() {
+            helper.ids = session.ids();
+        }
+        let line = match editor.readline("graph> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => "quit".to_string(),
+            Err(e) => return Err(format!("Failed to read command: {e}")),
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(line.as_str());
+
+        let flow = match commands.try_get_matches_from_mut(line.split_whitespace()) {
+            Ok(matches) => session.run(&matches, out),
+            Err(e) => {
+                let _ = e.print();
+                Ok(Flow::Continue)
+            }
+        };
+        match flow {
+            Ok(Flow::Continue) => {}
+            Ok(Flow::Quit) => break,
+            Err(e) => eprintln!("{e}"),
+        }
+        out.flush().map_err(write_error)?;
+    }
+
+    if let Some(history) = &history {
+        editor
+            .save_history(history)
+            .map_err(|e| format!("Failed to save command history: {e}"))?;
+    }
+
+    Ok(())
+}
+
+fn repl_command() -> clap::Command {
+    clap::Command::new("repl")
+        .multicall(true)
+        .subcommand_required(true)
+        .subcommand(
+            clap::Command::new("bfs")
+                .about("Traverses graph from provided starting node")
+                .arg(id_arg("source", "Starting node ID")),
+        )
+        .subcommand(
+            clap::Command::new("neighbors")
+                .about("Lists the targets of a node's outgoing edges")
+                .arg(id_arg("id", "Node ID")),
+        )
+        .subcommand(
+            clap::Command::new("path")
+                .about("Prints a shortest path and its hop count")
+                .arg(id_arg("from", "Start node ID"))
+                .arg(id_arg("to", "Target node ID")),
+        )
+        .subcommand(
+            clap::Command::new("add-node")
+                .about("Adds a node")
+                .arg(id_arg("id", "Node ID"))
+                .arg(clap::arg!(<value>... "Node value")),
+        )
+        .subcommand(
+            clap::Command::new("add-edge")
+                .about("Adds an edge between existing nodes")
+                .arg(id_arg("from", "Source node ID"))
+                .arg(id_arg("to", "Target node ID")),
+        )
+        .subcommand(
+            clap::Command::new("rm-node")
+                .about("Removes a node and its edges")
+                .arg(id_arg("id", "Node ID")),
+        )
+        .subcommand(
+            clap::Command::new("rm-edge")
+                .about("Removes an edge")
+                .arg(id_arg("from", "Source node ID"))
+                .arg(id_arg("to", "Target node ID")),
+        )
+        .subcommand(clap::Command::new("undo").about("Reverts the last edit"))
+        .subcommand(clap::Command::new("show").about("Prints the graph as TGF"))
+        .subcommand(
+            clap::Command::new("save")
+                .about("Saves the graph, to the opened file unless another one is given")
+                .arg(
+                    clap::arg!([file] "Path to TGF file")
+                        .value_parser(clap::value_parser!(PathBuf)),
+                ),
+        )
+        .subcommand(
+            clap::Command::new("quit")
+                .alias("exit")
+                .about("Leaves the REPL, asking again if there are unsaved changes"),
+        )
+}
+
+impl Session {
+    fn run(&mut self, matches: &ArgMatches, out: &mut dyn Write) -> Result<Flow, String> {
+        let (name, matches) = matches.subcommand().expect("subcommand is required");
+        let id = |name| *matches.get_one::<GraphId>(name).expect("required");
+        if name != "quit" {
+            self.warned = false;
+        }
+        match name {
+            "bfs" => self
+                .graph
+                .write_bfs(id("source"), out)
+                .map_err(|e| e.to_string())?,
+            "neighbors" => {
+                let node = self
+                    .graph
+                    .get_node(id("id"))
+                    .ok_or_else(|| format!("Node {} doesn't exist", id("id")))?;
+                let mut neighbours = node.neighbour_ids().to_vec();
+                neighbours.sort_unstable();
+                writeln!(out, "{}", join_ids(&neighbours, " ")).map_err(write_error)?;
+            }
+            "path" => {
+                let (from, to) = (id("from"), id("to"));
+                let path = self
+                    .graph
+                    .shortest_path(from, to)
+                    .ok_or_else(|| format!("No path from {from} to {to}"))?;
+                writeln!(out, "{}", join_ids(&path, " -> ")).map_err(write_error)?;
+                writeln!(out, "Distance: {}", path.len() - 1).map_err(write_error)?;
+            }
+            "add-node" => {
+                let value = matches
+                    .get_many::<String>("value")
+                    .expect("required")
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                self.edit(
+                    Mutation::AddNode {
+                        id: id("id"),
+                        value,
+                    },
+                    format!("Node {} already exists", id("id")),
+                )?;
+            }
+            "add-edge" => {
+                let (from, to) = (id("from"), id("to"));
+                let failure = match [from, to]
+                    .into_iter()
+                    .find(|id| self.graph.get_node(*id).is_none())
+                {
+                    Some(missing) => format!("Node {missing} doesn't exist"),
+                    None => format!("Edge {from} -> {to} already exists"),
+                };
+                self.edit(Mutation::AddEdge { from, to }, failure)?;
+            }
+            "rm-node" => self.edit(
+                Mutation::RemoveNode { id: id("id") },
+                format!("Node {} doesn't exist", id("id")),
+            )?,
+            "rm-edge" => self.edit(
+                Mutation::RemoveEdge {
+                    from: id("from"),
+                    to: id("to"),
+                },
+                format!("Edge {} -> {} doesn't exist", id("from"), id("to")),
+            )?,
+            "undo" => {
+                let reverts = self.undo.pop().ok_or("Nothing to undo")?;
+                for mutation in reverts {
+                    self.graph.apply(mutation);
+                }
+                self.unsaved = true;
+            }
+            "show" => out
+                .write_all(self.graph.serialize().as_bytes())
+                .map_err(write_error)?,
+            "save" => {
+                let file = matches.get_one::<PathBuf>("file").unwrap_or(&self.file);
+                write_atomically(file, self.graph.serialize()).map_err(save_error)?;
+                if *file == self.file {
+                    self.unsaved = false;
+                }
+                writeln!(out, "Saved {}", file.display()).map_err(write_error)?;
+            }
+            "quit" => {
+                if self.unsaved && !self.warned {
+                    self.warned = true;
+                    return Err("There are unsaved changes, quit again to discard them".to_string());
+                }
+                return Ok(Flow::Quit);
+            }
+            _ => unreachable!("subcommands are listed in repl_command"),
+        }
+
+        Ok(Flow::Continue)
+    }
+
+    /// Applies `mutation`, failing with `failure` if it doesn't change the graph.
+    fn edit(&mut self, mutation: Mutation<String>, failure: String) -> Result<(), String> {
+        let reverts = self.graph.apply(mutation);
+        if reverts.is_empty() {
+            return Err(failure);
+        }
+        self.undo.push(reverts);
+        self.unsaved = true;
+
+        Ok(())
+    }
+
+    fn ids(&self) -> Vec<String> {
+        let mut ids = self.graph.iter().map(|(id, _)| id).collect::<Vec<_>>();
+        ids.sort_unstable();
+
+        ids.iter().map(ToString::to_string).collect()
+    }
+}
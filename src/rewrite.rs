@@ -0,0 +1,301 @@
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+use core::ops::Index;
+
+use super::collections::HashSet;
+
+use super::{Edge, Graph, GraphId, Mutation};
+
+type Predicate<T> = Box<dyn Fn(&T) -> bool>;
+type Replacement<T> = Box<dyn Fn(&Match, &Graph<T>) -> Vec<Mutation<T>>>;
+
+/// A rewriting rule: a pattern subgraph and a replacement that turns a match into mutations.
+///
+/// Pattern nodes may restrict the values they match. Pattern edges must exist in the graph,
+/// but the graph may have more edges between matched nodes than the pattern asks for.
+pub struct Rule<T> {
+    nodes: BTreeMap<GraphId, Option<Predicate<T>>>,
+    edges: Vec<(GraphId, GraphId)>,
+    replacement: Replacement<T>,
+}
+
+/// Maps the IDs of pattern nodes to the graph nodes they matched.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Match {
+    mapping: BTreeMap<GraphId, GraphId>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RewriteMode {
+    /// Rewrites only the first match of the first rule that matches.
+    First,
+    /// Rewrites every match of every rule, in rule order. A match is skipped if it shares a
+    /// node with a match rewritten earlier in the same call or no longer holds.
+    All,
+}
+
+impl<T> Rule<T> {
+    /// Creates a rule with an empty pattern. `replacement` receives the match and the graph
+    /// as it is right before the rewrite.
+    pub fn new(replacement: impl Fn(&Match, &Graph<T>) -> Vec<Mutation<T>> + 'static) -> Self {
+        Self {
+            nodes: BTreeMap::new(),
+            edges: Vec::new(),
+            replacement: Box::new(replacement),
+        }
+    }
+
+    /// Adds a pattern node matching any value.
+    pub fn any_node(mut self, id: GraphId) -> Self {
+        self.nodes.insert(id, None);
+        self
+    }
+
+    /// Adds a pattern node matching only values accepted by `predicate`.
+    pub fn node(mut self, id: GraphId, predicate: impl Fn(&T) -> bool + 'static) -> Self {
+        self.nodes.insert(id, Some(Box::new(predicate)));
+        self
+    }
+
+    /// Adds a pattern edge. Endpoints that weren't declared match any node.
+    pub fn edge(mut self, from: GraphId, to: GraphId) -> Self {
+        for id in [from, to] {
+            self.nodes.entry(id).or_insert(None);
+        }
+        self.edges.push((from, to));
+        self
+    }
+
+    /// Returns every match in `graph`. Distinct pattern nodes always match distinct graph
+    /// nodes, and matches are ordered by the graph IDs assigned to pattern nodes in ID order.
+    pub fn matches(&self, graph: &Graph<T>) -> Vec<Match> {
+        let pattern = self.nodes.keys().copied().collect::<Vec<_>>();
+        find_matches(graph, &pattern, &self.edges, &|id, candidate| {
+            self.accepts(graph, id, candidate)
+        })
+    }
+
+    fn accepts(&self, graph: &Graph<T>, id: GraphId, candidate: GraphId) -> bool {
+        graph.nodes.get(&candidate).is_some_and(|value| {
+            self.nodes[&id]
+                .as_ref()
+                .is_none_or(|predicate| predicate(value))
+        })
+    }
+
+    fn still_matches(&self, graph: &Graph<T>, found: &Match) -> bool {
+        found.iter().all(|(id, candidate)| {
+            self.accepts(graph, id, candidate) && edges_hold(graph, &self.edges, found, id)
+        })
+    }
+}
+
+/// Finds every injective mapping of `pattern` nodes to graph nodes that `accepts` and that
+/// maps every pattern edge to a graph edge, ordered like [`Rule::matches`].
+pub(crate) fn find_matches<T>(
+    graph: &Graph<T>,
+    pattern: &[GraphId],
+    edges: &[(GraphId, GraphId)],
+    accepts: &dyn Fn(GraphId, GraphId) -> bool,
+) -> Vec<Match> {
+    let mut candidates = graph.nodes.keys().copied().collect::<Vec<_>>();
+    candidates.sort_unstable();
+
+    let mut matches = Vec::new();
+    extend(
+        graph,
+        pattern,
+        edges,
+        accepts,
+        &candidates,
+        &mut Match::default(),
+        &mut matches,
+    );
+
+    matches
+}
+
+fn extend<T>(
+    graph: &Graph<T>,
+    pattern: &[GraphId],
+    edges: &[(GraphId, GraphId)],
+    accepts: &dyn Fn(GraphId, GraphId) -> bool,
+    candidates: &[GraphId],
+    partial: &mut Match,
+    matches: &mut Vec<Match>,
+) {
+    let Some((&next, rest)) = pattern.split_first() else {
+        matches.push(partial.clone());
+        return;
+    };
+
+    for &candidate in candidates {
+        if partial.mapping.values().any(|id| *id == candidate) || !accepts(next, candidate) {
+            continue;
+        }
+
+        partial.mapping.insert(next, candidate);
+        if edges_hold(graph, edges, partial, next) {
+            extend(graph, rest, edges, accepts, candidates, partial, matches);
+        }
+        partial.mapping.remove(&next);
+    }
+}
+
+/// Checks every pattern edge between the pattern node `id` and nodes that are already mapped.
+fn edges_hold<T>(
+    graph: &Graph<T>,
+    edges: &[(GraphId, GraphId)],
+    partial: &Match,
+    id: GraphId,
+) -> bool {
+    edges
+        .iter()
+        .filter(|(from, to)| *from == id || *to == id)
+        .all(|(from, to)| match (partial.get(*from), partial.get(*to)) {
+            (Some(from), Some(to)) => graph.edges.contains(&Edge { from, to }),
+            _ => true,
+        })
+}
+
+impl Match {
+    pub fn get(&self, pattern_id: GraphId) -> Option<GraphId> {
+        self.mapping.get(&pattern_id).copied()
+    }
+
+    /// Iterates over `(pattern ID, graph ID)` pairs in pattern ID order.
+    pub fn iter(&self) -> impl Iterator<Item = (GraphId, GraphId)> + '_ {
+        self.mapping.iter().map(|(pattern, id)| (*pattern, *id))
+    }
+}
+
+impl Index<GraphId> for Match {
+    type Output = GraphId;
+
+    fn index(&self, pattern_id: GraphId) -> &Self::Output {
+        self.mapping
+            .get(&pattern_id)
+            .unwrap_or_else(|| panic!("No pattern node with ID {pattern_id}"))
+    }
+}
+
+impl<T> Graph<T> {
+    /// Rewrites matches of `rules` and returns how many rewrites were applied.
+    pub fn apply_rules(&mut self, rules: &[Rule<T>], mode: RewriteMode) -> usize {
+        let mut rewritten = 0;
+        let mut used = HashSet::new();
+        for rule in rules {
+            for found in rule.matches(self) {
+                if found.iter().any(|(_, id)| used.contains(&id))
+                    || !rule.still_matches(self, &found)
+                {
+                    continue;
+                }
+
+                let mutations = (rule.replacement)(&found, self);
+                self.apply_all(mutations);
+                used.extend(found.iter().map(|(_, id)| id));
+                rewritten += 1;
+                if mode == RewriteMode::First {
+                    return rewritten;
+                }
+            }
+        }
+
+        rewritten
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `not(not(x))` becomes `x`: parents of the outer `not` are linked to `x` directly.
+    fn double_negation() -> Rule<&'static str> {
+        Rule::new(|found, graph| {
+            let (outer, inner, operand) = (found[1], found[2], found[3]);
+            let mut mutations = graph
+                .edges()
+                .filter(|(_, to)| *to == outer)
+                .map(|(from, _)| Mutation::AddEdge { from, to: operand })
+                .collect::<Vec<_>>();
+            mutations.push(Mutation::RemoveNode { id: outer });
+            mutations.push(Mutation::RemoveNode { id: inner });
+            mutations
+        })
+        .node(1, |value| *value == "not")
+        .node(2, |value| *value == "not")
+        .edge(1, 2)
+        .edge(2, 3)
+    }
+
+    fn get_test_graph() -> Graph<&'static str> {
+        Graph::from((
+            [
+                (1, "and"),
+                (2, "not"),
+                (3, "not"),
+                (4, "a"),
+                (5, "not"),
+                (6, "not"),
+                (7, "b"),
+            ],
+            [(1, 2), (2, 3), (3, 4), (1, 5), (5, 6), (6, 7)],
+        ))
+    }
+
+    #[test]
+    fn matches_respect_predicates() {
+        let graph = get_test_graph();
+        let matches = double_negation().matches(&graph);
+        assert_eq!(
+            matches.iter().map(|found| found[3]).collect::<Vec<_>>(),
+            vec![4, 7],
+            "Matched operands mismatch"
+        );
+        assert_eq!(matches[0].get(4), None, "Expected unknown pattern node");
+    }
+
+    #[test]
+    fn apply_rules_all() {
+        let mut graph = get_test_graph();
+        let rewritten = graph.apply_rules(&[double_negation()], RewriteMode::All);
+        assert_eq!(rewritten, 2, "Rewrites count mismatch");
+        assert_eq!(
+            graph,
+            Graph::from(([(1, "and"), (4, "a"), (7, "b")], [(1, 4), (1, 7)])),
+            "Graph doesn't match"
+        );
+    }
+
+    #[test]
+    fn apply_rules_first() {
+        let mut graph = get_test_graph();
+        let rewritten = graph.apply_rules(&[double_negation()], RewriteMode::First);
+        assert_eq!(rewritten, 1, "Rewrites count mismatch");
+        assert_eq!(graph.nodes.len(), 5, "Nodes count mismatch");
+        assert!(graph.nodes.contains_key(&5), "Expected second match kept");
+    }
+
+    #[test]
+    fn apply_rules_skips_overlapping_matches() {
+        let mut graph = Graph::from((
+            [(1, "not"), (2, "not"), (3, "not"), (4, "a")],
+            [(1, 2), (2, 3), (3, 4)],
+        ));
+        assert_eq!(
+            double_negation().matches(&graph).len(),
+            2,
+            "Matches count mismatch"
+        );
+        assert_eq!(
+            graph.apply_rules(&[double_negation()], RewriteMode::All),
+            1,
+            "Expected overlapping match to be skipped"
+        );
+        assert_eq!(
+            graph,
+            Graph::from(([(3, "not"), (4, "a")], [(3, 4)])),
+            "Graph doesn't match"
+        );
+    }
+}
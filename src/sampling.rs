@@ -0,0 +1,293 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    seq::SliceRandom,
+    Rng,
+};
+
+use super::{Graph, GraphId};
+
+impl<T> Graph<T> {
+    /// Picks a node with probability proportional to `weight`, given its ID and value.
+    ///
+    /// Returns `None` if no weight is positive, or if any is negative or NaN.
+    pub fn random_node_weighted<R>(
+        &self,
+        rng: &mut R,
+        weight: impl Fn(GraphId, &T) -> f64,
+    ) -> Option<GraphId>
+    where
+        R: Rng + ?Sized,
+    {
+        let ids = self.sorted_ids();
+        let weights = ids.iter().map(|id| weight(*id, &self.nodes[id]));
+        let index = WeightedIndex::new(weights).ok()?;
+
+        Some(ids[index.sample(rng)])
+    }
+
+    /// Picks an edge with probability proportional to `weight`, given its endpoints, like
+    /// [`random_node_weighted`](Self::random_node_weighted).
+    pub fn random_edge_weighted<R>(
+        &self,
+        rng: &mut R,
+        weight: impl Fn(GraphId, GraphId) -> f64,
+    ) -> Option<(GraphId, GraphId)>
+    where
+        R: Rng + ?Sized,
+    {
+        let edges = self.sorted_edges();
+        let weights = edges.iter().map(|(from, to)| weight(*from, *to));
+        let index = WeightedIndex::new(weights).ok()?;
+
+        Some(edges[index.sample(rng)])
+    }
+
+    fn sorted_ids(&self) -> Vec<GraphId> {
+        let mut ids = self.nodes.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+
+        ids
+    }
+}
+
+impl<T: Clone> Graph<T> {
+    /// Returns the subgraph induced by `count` nodes chosen uniformly at random, or by all of
+    /// them if there are fewer.
+    pub fn sample_nodes<R>(&self, count: usize, rng: &mut R) -> Graph<T>
+    where
+        R: Rng + ?Sized,
+    {
+        let ids = self.sorted_ids();
+        let chosen = ids.choose_multiple(rng, count).copied().collect();
+
+        self.induced(&chosen)
+    }
+
+    /// Returns `count` edges chosen uniformly at random, or all of them if there are fewer,
+    /// along with their endpoints.
+    pub fn sample_edges<R>(&self, count: usize, rng: &mut R) -> Graph<T>
+    where
+        R: Rng + ?Sized,
+    {
+        let edges = self.sorted_edges();
+        let mut sample = Graph::new();
+        for &(from, to) in edges.choose_multiple(rng, count) {
+            for id in [from, to] {
+                sample.add_node(id, self.nodes[&id].clone());
+            }
+            sample.add_edge(from, to);
+        }
+
+        sample
+    }
+
+    /// Returns the subgraph induced by up to `max_nodes` nodes reached by snowball sampling:
+    /// starting from a random node, every newly reached node recruits up to `neighbours` of
+    /// its neighbours in either direction, chosen at random. If the snowball stops growing
+    /// before `max_nodes`, it starts again from another random node.
+    pub fn snowball_sample<R>(&self, max_nodes: usize, neighbours: usize, rng: &mut R) -> Graph<T>
+    where
+        R: Rng + ?Sized,
+    {
+        let mut adjacency = self.adjacency();
+        for (to, predecessors) in self.reverse_adjacency() {
+            let neighbours = adjacency.entry(to).or_default();
+            neighbours.extend(predecessors);
+            neighbours.sort_unstable();
+            neighbours.dedup();
+        }
+
+        let chosen = self.spread(max_nodes, &adjacency, rng, |candidates, rng| {
+            candidates.partial_shuffle(rng, neighbours);
+            candidates.truncate(neighbours);
+        });
+
+        self.induced(&chosen)
+    }
+
+    /// Returns the subgraph induced by up to `max_nodes` nodes reached by forest-fire
+    /// sampling: starting from a random node, every newly burned node sets fire to a random
+    /// number of its unburned successors, geometrically distributed with mean `p / (1 - p)`.
+    /// If the fire dies out before `max_nodes`, it starts again from another random node.
+    ///
+    /// Panics unless `0 <= p < 1`.
+    pub fn forest_fire_sample<R>(&self, max_nodes: usize, p: f64, rng: &mut R) -> Graph<T>
+    where
+        R: Rng + ?Sized,
+    {
+        assert!((0.0..1.0).contains(&p), "Expected p in [0, 1), got {p}");
+
+        let chosen = self.spread(max_nodes, &self.adjacency(), rng, |candidates, rng| {
+            let mut count = 0;
+            while count < candidates.len() && rng.gen_bool(p) {
+                count += 1;
+            }
+            candidates.partial_shuffle(rng, count);
+            candidates.truncate(count);
+        });
+
+        self.induced(&chosen)
+    }
+
+    /// Grows a set of up to `max_nodes` nodes from random seeds in waves, letting `pick`
+    /// narrow down the unreached neighbours of every newly reached node.
+    fn spread<R>(
+        &self,
+        max_nodes: usize,
+        adjacency: &HashMap<GraphId, Vec<GraphId>>,
+        rng: &mut R,
+        mut pick: impl FnMut(&mut Vec<GraphId>, &mut R),
+    ) -> HashSet<GraphId>
+    where
+        R: Rng + ?Sized,
+    {
+        // Seeds are taken in a random order fixed up front, skipping nodes that an earlier
+        // wave reached, so every node is considered as a seed at most once.
+        let mut seeds = self.sorted_ids();
+        seeds.shuffle(rng);
+        let mut seeds = seeds.into_iter();
+        let max_nodes = max_nodes.min(self.nodes.len());
+        let mut chosen = HashSet::new();
+        while chosen.len() < max_nodes {
+            let seed = seeds
+                .find(|id| !chosen.contains(id))
+                .expect("fewer chosen than nodes");
+            chosen.insert(seed);
+
+            let mut wave = vec![seed];
+            while !wave.is_empty() && chosen.len() < max_nodes {
+                let mut next = Vec::new();
+                for id in wave {
+                    let mut candidates = adjacency
+                        .get(&id)
+                        .into_iter()
+                        .flatten()
+                        .copied()
+                        .filter(|candidate| !chosen.contains(candidate))
+                        .collect::<Vec<_>>();
+                    pick(&mut candidates, rng);
+                    for candidate in candidates {
+                        if chosen.len() < max_nodes && chosen.insert(candidate) {
+                            next.push(candidate);
+                        }
+                    }
+                }
+                wave = next;
+            }
+        }
+
+        chosen
+    }
+
+    /// Returns the nodes in `ids` with the edges between them.
+    fn induced(&self, ids: &HashSet<GraphId>) -> Graph<T> {
+        let mut graph = Graph::new();
+        for id in ids {
+            graph.add_node(*id, self.nodes[id].clone());
+        }
+        for edge in &self.edges {
+            if ids.contains(&edge.from) && ids.contains(&edge.to) {
+                graph.add_edge(edge.from, edge.to);
+            }
+        }
+
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::generators::barabasi_albert;
+
+    fn get_test_graph() -> Graph<GraphId> {
+        barabasi_albert(60, 2, &mut StdRng::seed_from_u64(1), |id| id)
+    }
+
+    #[test]
+    fn weighted_selection_follows_weights() {
+        let graph = Graph::from(([(1, 0.0), (2, 1.0), (3, 3.0)], [(1, 2), (2, 3), (3, 1)]));
+        let mut rng = StdRng::seed_from_u64(6);
+        let mut counts = HashMap::new();
+        for _ in 0..4000 {
+            let id = graph.random_node_weighted(&mut rng, |_, score| *score);
+            *counts.entry(id).or_insert(0) += 1;
+        }
+        assert_eq!(
+            counts.get(&Some(1)),
+            None,
+            "Expected zero weight never picked"
+        );
+        assert!(
+            (2700..3300).contains(&counts[&Some(3)]),
+            "Expected node 3 about three times as often, got {counts:?}"
+        );
+
+        let edge = graph.random_edge_weighted(&mut rng, |from, _| graph[from]);
+        assert_ne!(edge, Some((1, 2)), "Expected zero weight never picked");
+        assert_eq!(
+            graph.random_node_weighted(&mut rng, |_, _| -1.0),
+            None,
+            "Expected negative weights to be rejected"
+        );
+    }
+
+    #[test]
+    fn sample_nodes_induces_subgraph() {
+        let graph = get_test_graph();
+        let sample = graph.sample_nodes(10, &mut StdRng::seed_from_u64(3));
+        assert_eq!(sample.nodes.len(), 10, "Node count mismatch");
+        assert!(
+            sample.edges.iter().all(|edge| graph.edges.contains(edge)),
+            "Expected only edges of the graph"
+        );
+        assert_eq!(
+            sample,
+            graph.sample_nodes(10, &mut StdRng::seed_from_u64(3)),
+            "Expected same sample for same seed"
+        );
+        assert_eq!(
+            graph.sample_nodes(100, &mut StdRng::seed_from_u64(3)),
+            graph,
+            "Expected whole graph"
+        );
+    }
+
+    #[test]
+    fn sample_edges_keeps_endpoints() {
+        let graph = get_test_graph();
+        let sample = graph.sample_edges(15, &mut StdRng::seed_from_u64(4));
+        assert_eq!(sample.edges.len(), 15, "Edge count mismatch");
+        assert!(
+            sample
+                .nodes
+                .iter()
+                .all(|(id, value)| graph.nodes.get(id) == Some(value)),
+            "Expected nodes of the graph with their values"
+        );
+    }
+
+    #[test]
+    fn snowball_and_forest_fire_reach_max_nodes() {
+        let graph = get_test_graph();
+        let snowball = graph.snowball_sample(20, 2, &mut StdRng::seed_from_u64(5));
+        assert_eq!(snowball.nodes.len(), 20, "Snowball size mismatch");
+        assert_eq!(
+            snowball.connected_components().len(),
+            1,
+            "Expected a single snowball"
+        );
+
+        let fire = graph.forest_fire_sample(20, 0.7, &mut StdRng::seed_from_u64(5));
+        assert_eq!(fire.nodes.len(), 20, "Forest fire size mismatch");
+        assert_eq!(
+            graph.forest_fire_sample(100, 0.0, &mut StdRng::seed_from_u64(5)),
+            graph,
+            "Expected restarts to reach every node"
+        );
+    }
+}
@@ -1,30 +1,288 @@
-use std::{fmt::Display, str::FromStr};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt::Display, str::FromStr};
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
 
 use anyhow::anyhow;
+#[cfg(feature = "std")]
+use anyhow::Context;
 use nom::{
+    branch,
     character::complete as cc,
     combinator,
     error::{Error as NError, ErrorKind, ParseError},
     multi, sequence, Finish, IResult,
 };
 
-use super::{Graph, GraphId};
+#[cfg(feature = "compression")]
+use super::{compression, Compression};
+use super::{
+    header::{crc32, split_header, Header},
+    progress::{ProgressSink, Reporter},
+    Graph, GraphId, WeightedGraph,
+};
+
+/// Options for writing TGF.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SerializeOptions {
+    /// Writes the weight of every edge after its target, in the position of a TGF edge
+    /// label. Edges of a graph without weights are written with weight 1.
+    pub weighted: bool,
+    /// Starts the output with a [`Header`] describing it.
+    pub header: bool,
+    /// Adds a CRC32 checksum of everything after the header to the header, which parsers
+    /// verify. Implies [`header`](Self::header).
+    pub checksum: bool,
+    /// Compression used by [`Graph::write_to`].
+    #[cfg(feature = "compression")]
+    pub compression: Compression,
+}
 
 impl<T> Graph<T> {
+    /// Writes the graph as TGF, with nodes and edges sorted by ID so that the output is stable.
+    pub fn serialize(&self) -> String
+    where
+        T: Display,
+    {
+        self.serialize_with(&SerializeOptions::default())
+    }
+
+    /// Like [`serialize`](Self::serialize), in the dialect chosen by `options`.
+    pub fn serialize_with(&self, options: &SerializeOptions) -> String
+    where
+        T: Display,
+    {
+        self.serialize_by(options, T::to_string)
+    }
+
+    /// Like [`serialize_with`](Self::serialize_with), writing every node value as the text
+    /// returned by `format` instead of requiring `T: Display`, e.g. to embed values as JSON.
+    /// The text must not contain line breaks. [`parse_with`](Self::parse_with) reads it back.
+    pub fn serialize_by(
+        &self,
+        options: &SerializeOptions,
+        format: impl FnMut(&T) -> String,
+    ) -> String {
+        let edges = self
+            .sorted_edges()
+            .into_iter()
+            .map(|(from, to)| (from, to, 1.0))
+            .collect();
+
+        write_tgf(self, edges, options, format)
+    }
+
+    /// Writes the graph to `writer` like [`serialize_with`](Self::serialize_with), compressed
+    /// as chosen by `options` with the `compression` feature.
+    #[cfg(feature = "std")]
+    pub fn write_to(&self, writer: impl Write, options: &SerializeOptions) -> io::Result<()>
+    where
+        T: Display,
+    {
+        let text = self.serialize_with(options);
+        #[cfg(feature = "compression")]
+        let written = options.compression.write_all(writer, text.as_bytes());
+        #[cfg(not(feature = "compression"))]
+        let written = { writer }.write_all(text.as_bytes());
+
+        written
+    }
+}
+
+impl<T> WeightedGraph<T> {
+    /// Writes the graph as TGF with the weight of every edge, which [`str::parse`] reads back.
     pub fn serialize(&self) -> String
     where
         T: Display,
     {
-        self.nodes
-            .iter()
-            .map(|(id, value)| format!("{id} {value}\n"))
-            .chain(["#\n".to_string()])
-            .chain(
-                self.edges
-                    .iter()
-                    .map(|edge| format!("{} {}\n", edge.from, edge.to)),
-            )
-            .collect()
+        self.serialize_with(&SerializeOptions {
+            weighted: true,
+            ..SerializeOptions::default()
+        })
+    }
+
+    /// Like [`serialize`](Self::serialize), in the dialect chosen by `options`. Weights are
+    /// dropped unless [`SerializeOptions::weighted`] is set.
+    pub fn serialize_with(&self, options: &SerializeOptions) -> String
+    where
+        T: Display,
+    {
+        write_tgf(self, self.weighted_edges(), options, T::to_string)
+    }
+}
+
+/// Writes the nodes sorted by ID with their values as text returned by `format`, followed by
+/// `edges`, which are expected to be sorted.
+fn write_tgf<T>(
+    graph: &Graph<T>,
+    edges: Vec<(GraphId, GraphId, f64)>,
+    options: &SerializeOptions,
+    mut format: impl FnMut(&T) -> String,
+) -> String {
+    let body = graph
+        .sorted_nodes()
+        .into_iter()
+        .map(|(id, value)| format!("{id} {}\n", format(value)))
+        .chain(["#\n".to_string()])
+        .chain(edges.into_iter().map(|(from, to, weight)| {
+            if options.weighted {
+                format!("{from} {to} {weight}\n")
+            } else {
+                format!("{from} {to}\n")
+            }
+        }))
+        .collect::<String>();
+    if !options.header && !options.checksum {
+        return body;
+    }
+
+    let mut header = Header::new::<T>(options.weighted);
+    if options.checksum {
+        header.checksum = Some(crc32(body.as_bytes()));
+    }
+
+    format!("{header}\n{body}")
+}
+
+impl<T: FromStr> Graph<T> {
+    /// Reads TGF from `reader` like [`str::parse`]. With the `compression` feature, gzip and
+    /// zstd input is recognized by its first bytes and decompressed.
+    #[cfg(feature = "std")]
+    pub fn from_reader(reader: impl Read) -> anyhow::Result<Self> {
+        #[cfg(feature = "compression")]
+        let bytes = compression::read_to_end(reader)?;
+        #[cfg(not(feature = "compression"))]
+        let bytes = {
+            let mut bytes = Vec::new();
+            { reader }.read_to_end(&mut bytes)?;
+            bytes
+        };
+
+        String::from_utf8(bytes)
+            .context("Graph isn't valid UTF-8")?
+            .parse()
+    }
+
+    /// Parses TGF like [`str::parse`], reporting the share of bytes parsed to `progress`.
+    ///
+    /// A [`Header`] is checked before parsing, failing on weighted text.
+    pub fn parse_with_progress(
+        s: &str,
+        progress: &mut (impl ProgressSink + ?Sized),
+    ) -> anyhow::Result<Self> {
+        debug_span!("parse_tgf", bytes = s.len());
+        #[cfg(all(feature = "tracing", feature = "std"))]
+        let start = std::time::Instant::now();
+
+        let (header, body) = split_header(s)?;
+        if let Some(header) = &header {
+            header.check(false)?;
+        }
+        let directed = header.is_none_or(|header| header.directed);
+        let mut reporter = Reporter::new(progress, s.len() as u64);
+        let offset = s.len() - body.len();
+        let (nodes, edges) = parse_sections_with::<T, GraphId>(body, &mut |done| {
+            reporter.update((offset + done) as u64)
+        })?;
+        let graph = Graph::from_sections((nodes, edges), directed);
+        reporter.finish();
+        #[cfg(feature = "std")]
+        debug_event!(
+            nodes = graph.nodes.len(),
+            edges = graph.edges.len(),
+            elapsed = ?start.elapsed(),
+            "parsed TGF"
+        );
+        // Without std there is no clock to time parsing with.
+        #[cfg(not(feature = "std"))]
+        debug_event!(
+            nodes = graph.nodes.len(),
+            edges = graph.edges.len(),
+            "parsed TGF"
+        );
+
+        Ok(graph)
+    }
+}
+
+impl<T> Graph<T> {
+    /// Builds a graph from parsed TGF, adding the reverse of every edge unless `directed`.
+    pub(crate) fn from_sections((nodes, edges): Sections<T, GraphId>, directed: bool) -> Self {
+        let mut graph = Graph::new();
+        for (id, value) in nodes {
+            graph.add_node(id, value);
+        }
+        for (from, to) in edges {
+            graph.add_edge(from, to);
+            if !directed {
+                graph.add_edge(to, from);
+            }
+        }
+
+        graph
+    }
+}
+
+impl<T> Graph<T> {
+    /// Parses TGF like [`str::parse`], reading every node value with `parse` instead of
+    /// requiring `T: FromStr`, e.g. to read values embedded as JSON. `parse` gets the whole
+    /// rest of the line after the ID.
+    ///
+    /// Fails with the first error returned by `parse`.
+    pub fn parse_with(
+        s: &str,
+        mut parse: impl FnMut(&str) -> anyhow::Result<T>,
+    ) -> anyhow::Result<Self> {
+        debug_span!("parse_with", bytes = s.len());
+        let (header, body) = split_header(s)?;
+        if let Some(header) = &header {
+            header.check(false)?;
+        }
+        let directed = header.is_none_or(|header| header.directed);
+        let mut error = None;
+        let sections = parse_sections_by(
+            body,
+            |raw| match parse(raw) {
+                Ok(value) => Ok(("", value)),
+                Err(e) => {
+                    error = Some(e.context(format!("Invalid node value {raw:?}")));
+                    Err(nom::Err::Failure(NError::from_error_kind(
+                        raw,
+                        ErrorKind::Fail,
+                    )))
+                }
+            },
+            parse_value::<GraphId>,
+            &mut |_| {},
+        );
+        let sections = sections.map_err(|e| error.unwrap_or(e))?;
+
+        Ok(Graph::from_sections(sections, directed))
+    }
+}
+
+impl<'a> Graph<&'a str> {
+    /// Parses TGF like [`str::parse`] into a graph whose node values are slices of `s`, saving
+    /// an allocation per node when `s` outlives the graph anyway.
+    pub fn parse_borrowed(s: &'a str) -> anyhow::Result<Self> {
+        debug_span!("parse_borrowed", bytes = s.len());
+        let (header, body) = split_header(s)?;
+        if let Some(header) = &header {
+            header.check(false)?;
+        }
+        let directed = header.is_none_or(|header| header.directed);
+        let sections = parse_sections_by(
+            body,
+            |value| Ok(("", value)),
+            parse_value::<GraphId>,
+            &mut |_| {},
+        )?;
+
+        Ok(Graph::from_sections(sections, directed))
     }
 }
 
@@ -32,36 +290,154 @@ impl<T: FromStr> FromStr for Graph<T> {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        sequence::separated_pair(
-            parse_pairs,
-            sequence::delimited(cc::line_ending, cc::char('#'), cc::line_ending),
-            parse_pairs,
-        )(s)
-        .finish()
-        .map(|(_, (nodes, edges))| {
-            let mut graph = Graph::new();
-            for (id, value) in nodes {
-                graph.add_node(id, value);
-            }
-            for (from, to) in edges {
-                graph.add_edge(from, to);
+        Graph::parse_with_progress(s, &mut |_| {})
+    }
+}
+
+/// Reads TGF whose edge lines may carry a weight after the target, as written by
+/// [`WeightedGraph::serialize`]. Edges without a weight get weight 1.
+impl<T: FromStr> FromStr for WeightedGraph<T> {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        WeightedGraph::parse_with_progress(s, &mut |_| {})
+    }
+}
+
+impl<T: FromStr> WeightedGraph<T> {
+    /// Parses weighted TGF like [`str::parse`], reporting the share of bytes parsed to
+    /// `progress`.
+    pub fn parse_with_progress(
+        s: &str,
+        progress: &mut (impl ProgressSink + ?Sized),
+    ) -> anyhow::Result<Self> {
+        let (header, body) = split_header(s)?;
+        if let Some(header) = &header {
+            header.check(true)?;
+        }
+        let directed = header.is_none_or(|header| header.directed);
+        let mut reporter = Reporter::new(progress, s.len() as u64);
+        let offset = s.len() - body.len();
+        let (nodes, edges) = parse_sections_with::<T, WeightedTarget>(body, &mut |done| {
+            reporter.update((offset + done) as u64)
+        })?;
+        let mut graph = WeightedGraph::new();
+        for (id, value) in nodes {
+            graph.add_node(id, value);
+        }
+        for (from, target) in edges {
+            graph.add_edge(from, target.to, target.weight);
+            if !directed {
+                graph.add_edge(target.to, from, target.weight);
             }
+        }
+        reporter.finish();
 
-            graph
-        })
-        .map_err(|e| anyhow!("Parse error: {e}"))
+        Ok(graph)
     }
 }
 
-fn parse_pairs<T: FromStr>(s: &str) -> IResult<&str, Vec<(GraphId, T)>> {
-    multi::separated_list0(
-        cc::line_ending,
-        sequence::separated_pair(
-            cc::u64,
-            cc::space1,
-            combinator::map_parser(cc::not_line_ending, parse_value),
-        ),
-    )(s)
+/// The part of a weighted TGF edge line after the source ID.
+struct WeightedTarget {
+    to: GraphId,
+    weight: f64,
+}
+
+impl FromStr for WeightedTarget {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let to = parts
+            .next()
+            .ok_or_else(|| anyhow!("Missing target"))?
+            .parse()?;
+        let weight = match parts.next() {
+            None => 1.0,
+            Some(weight) => weight
+                .parse()
+                .map_err(|_| anyhow!("Expected a numeric weight, got {weight:?}"))?,
+        };
+        if parts.next().is_some() {
+            return Err(anyhow!("Unexpected trailing data in {s:?}"));
+        }
+
+        Ok(Self { to, weight })
+    }
+}
+
+pub(crate) type Sections<N, E> = (Vec<(GraphId, N)>, Vec<(GraphId, E)>);
+
+/// Splits TGF text into its node lines and edge lines, parsing everything after the leading
+/// ID of every line with `FromStr`.
+pub(crate) fn parse_sections<N: FromStr, E: FromStr>(s: &str) -> anyhow::Result<Sections<N, E>> {
+    parse_sections_with(s, &mut |_| {})
+}
+
+/// Like [`parse_sections`], but calls `on_progress` with the number of bytes parsed after
+/// every line.
+fn parse_sections_with<N: FromStr, E: FromStr>(
+    s: &str,
+    on_progress: &mut impl FnMut(usize),
+) -> anyhow::Result<Sections<N, E>> {
+    parse_sections_by(s, parse_value, parse_value, on_progress)
+}
+
+/// Like [`parse_sections_with`], parsing everything after the leading ID of node lines with
+/// `node` and of edge lines with `edge`.
+fn parse_sections_by<'a, N, E>(
+    s: &'a str,
+    mut node: impl FnMut(&'a str) -> IResult<&'a str, N>,
+    mut edge: impl FnMut(&'a str) -> IResult<&'a str, E>,
+    on_progress: &mut impl FnMut(usize),
+) -> anyhow::Result<Sections<N, E>> {
+    let mut on_line = |rest: &str| on_progress(s.len() - rest.len());
+    let mut sections = |input: &'a str| -> IResult<&'a str, Sections<N, E>> {
+        let (input, nodes) = parse_pairs_by(input, &mut node, &mut on_line)?;
+        // Without nodes, the `#` line is the first line, as in a serialized empty graph.
+        let (input, _) = sequence::delimited(
+            combinator::cond(!nodes.is_empty(), cc::line_ending),
+            cc::char('#'),
+            branch::alt((cc::line_ending, combinator::eof)),
+        )(input)?;
+        let (input, edges) = parse_pairs_by(input, &mut edge, &mut on_line)?;
+
+        Ok((input, (nodes, edges)))
+    };
+
+    sections(s)
+        .finish()
+        .map(|(_, sections)| sections)
+        .map_err(|e| anyhow!("Parse error: {e}"))
+}
+
+#[cfg(any(test, feature = "rayon", feature = "tokio"))]
+pub(crate) fn parse_pairs<T: FromStr>(s: &str) -> IResult<&str, Vec<(GraphId, T)>> {
+    parse_pairs_by(s, &mut parse_value, &mut |_| {})
+}
+
+/// Parses lines of an ID followed by a value parsed with `value`, calling `on_line` with the
+/// remaining input after every pair.
+fn parse_pairs_by<'a, T>(
+    s: &'a str,
+    value: &mut impl FnMut(&'a str) -> IResult<&'a str, T>,
+    on_line: &mut impl FnMut(&'a str),
+) -> IResult<&'a str, Vec<(GraphId, T)>> {
+    let mut pair = sequence::separated_pair(
+        cc::u64,
+        cc::space1,
+        combinator::map_parser(cc::not_line_ending, value),
+    );
+    // Binding the result drops the list parser before `pair`, which it borrows.
+    #[allow(clippy::let_and_return)]
+    let pairs = multi::separated_list0(cc::line_ending, |input| {
+        let (rest, pair) = pair(input)?;
+        on_line(rest);
+
+        Ok((rest, pair))
+    })(s);
+
+    pairs
 }
 
 fn parse_value<T: FromStr>(s: &str) -> IResult<&str, T> {
@@ -72,9 +448,12 @@ fn parse_value<T: FromStr>(s: &str) -> IResult<&str, T> {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::{HashMap, HashSet};
+    use anyhow::Context;
 
-    use crate::Edge;
+    use crate::{
+        collections::{HashMap, HashSet},
+        Edge, IntegrityError,
+    };
 
     use super::*;
 
@@ -211,4 +590,260 @@ mod tests {
             "Expected serialized graph to parse"
         );
     }
+
+    #[test]
+    fn serialize_graph_sorted() {
+        let graph = Graph::from(([(3, "c"), (1, "a"), (2, "b")], [(3, 1), (1, 3), (1, 2)]));
+        assert_eq!(
+            graph.serialize(),
+            "1 a\n2 b\n3 c\n#\n1 2\n1 3\n3 1\n",
+            "Expected sorted output"
+        );
+    }
+
+    #[test]
+    fn serialize_weighted_round_trip() {
+        let mut graph = WeightedGraph::new();
+        for (id, value) in [(1, "a".to_string()), (2, "b".to_string())] {
+            graph.add_node(id, value);
+        }
+        graph.add_edge(2, 1, 0.1);
+        graph.add_edge(1, 2, -3.0);
+        let serialized = graph.serialize();
+        assert_eq!(
+            serialized, "1 a\n2 b\n#\n1 2 -3\n2 1 0.1\n",
+            "Serialized mismatch"
+        );
+        assert_eq!(
+            serialized.parse::<WeightedGraph<String>>().ok(),
+            Some(graph),
+            "Expected weights to survive round trip"
+        );
+    }
+
+    #[test]
+    fn serialize_with_options() {
+        let graph = Graph::from(([(1, "a"), (2, "b")], [(1, 2)]));
+        let options = SerializeOptions {
+            weighted: true,
+            ..SerializeOptions::default()
+        };
+        assert_eq!(graph.serialize_with(&options), "1 a\n2 b\n#\n1 2 1\n");
+        let weighted = WeightedGraph::from(graph);
+        assert_eq!(
+            weighted.serialize_with(&SerializeOptions::default()),
+            "1 a\n2 b\n#\n1 2\n",
+            "Expected weights to be dropped"
+        );
+    }
+
+    #[test]
+    fn parse_weighted_default_and_invalid() {
+        let graph = "1 a\n2 b\n#\n1 2\n2 1 2.5"
+            .parse::<WeightedGraph<String>>()
+            .unwrap();
+        assert_eq!(graph.weight(1, 2), Some(1.0), "Expected default weight");
+        assert_eq!(graph.weight(2, 1), Some(2.5), "Weight mismatch");
+        assert!(
+            "1 a\n2 b\n#\n1 2 heavy"
+                .parse::<WeightedGraph<String>>()
+                .is_err(),
+            "Expected non-numeric weight to fail"
+        );
+    }
+
+    #[test]
+    fn parse_with_header() {
+        let graph = Graph::from(([(1, "a".to_string()), (2, "b".to_string())], [(1, 2)]));
+        let options = SerializeOptions {
+            header: true,
+            ..SerializeOptions::default()
+        };
+        let serialized = graph.serialize_with(&options);
+        assert!(
+            serialized.starts_with("%bgraph-tgf version=1 directed=true weighted=false"),
+            "Expected header, got {serialized:?}"
+        );
+        assert_eq!(serialized.parse::<Graph<String>>().ok(), Some(graph));
+
+        let borrowed = Graph::from(([(1, "a"), (2, "b")], [(1, 2)]));
+        assert_eq!(
+            borrowed
+                .serialize_with(&options)
+                .parse::<Graph<String>>()
+                .map(|graph| graph.nodes.len())
+                .ok(),
+            Some(2),
+            "Expected node type to be advisory"
+        );
+    }
+
+    #[test]
+    fn parse_header_mismatch() {
+        let weighted = "%bgraph-tgf version=1 weighted=true\n1 a\n2 b\n#\n1 2 3";
+        assert!(
+            weighted.parse::<Graph<String>>().is_err(),
+            "Expected weights not to be dropped silently"
+        );
+        assert_eq!(
+            weighted
+                .parse::<WeightedGraph<String>>()
+                .unwrap()
+                .weight(1, 2),
+            Some(3.0),
+            "Weight mismatch"
+        );
+
+        let undirected = "%bgraph-tgf version=1 directed=false\n1 a\n2 b\n#\n1 2"
+            .parse::<Graph<String>>()
+            .unwrap();
+        assert!(
+            undirected.edges.contains(&Edge { from: 1, to: 2 })
+                && undirected.edges.contains(&Edge { from: 2, to: 1 }),
+            "Expected edge in both directions"
+        );
+    }
+
+    #[test]
+    fn serialize_by_parse_with_round_trip() {
+        #[derive(Debug, PartialEq)]
+        struct Payload {
+            name: String,
+            tags: Vec<u64>,
+        }
+
+        let graph = Graph::from((
+            [
+                (
+                    1,
+                    Payload {
+                        name: "a b".into(),
+                        tags: vec![1, 2],
+                    },
+                ),
+                (
+                    2,
+                    Payload {
+                        name: "c".into(),
+                        tags: vec![],
+                    },
+                ),
+            ],
+            [(1, 2)],
+        ));
+        let options = SerializeOptions {
+            header: true,
+            ..SerializeOptions::default()
+        };
+        let serialized = graph.serialize_by(&options, |payload| {
+            serde_json::json!({ "name": payload.name, "tags": payload.tags }).to_string()
+        });
+        let parse = |raw: &str| {
+            let value = serde_json::from_str::<serde_json::Value>(raw)?;
+            Ok(Payload {
+                name: value["name"].as_str().context("Missing name")?.into(),
+                tags: serde_json::from_value(value["tags"].clone())?,
+            })
+        };
+        assert_eq!(
+            Graph::parse_with(&serialized, parse).unwrap(),
+            graph,
+            "Round trip mismatch"
+        );
+
+        let error = Graph::parse_with("1 {\"tags\": []}\n#\n", parse).unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "Invalid node value \"{\\\"tags\\\": []}\": Missing name",
+            "Expected the error of the parser"
+        );
+    }
+
+    #[test]
+    fn parse_borrowed_slices_input() {
+        let text = "%bgraph-tgf version=1 directed=false type=u32\n1 first node\n2 b\n#\n1 2";
+        let graph = Graph::parse_borrowed(text).unwrap();
+        assert_eq!(
+            graph,
+            Graph::from(([(1, "first node"), (2, "b")], [(1, 2), (2, 1)])),
+            "Graph mismatch"
+        );
+        assert!(
+            text.as_bytes().as_ptr_range().contains(&graph[1].as_ptr()),
+            "Expected value borrowed from the input"
+        );
+        assert!(
+            Graph::parse_borrowed("%bgraph-tgf version=1 weighted=true\n1 a\n#\n1 1 2").is_err(),
+            "Expected weights not to be dropped silently"
+        );
+        assert!(
+            Graph::parse_borrowed("1 a\n#\n1 x").is_err(),
+            "Expected invalid edge to fail"
+        );
+    }
+
+    #[test]
+    fn parse_with_checksum() {
+        let graph = Graph::from(([(1, "a".to_string()), (2, "b".to_string())], [(1, 2)]));
+        let options = SerializeOptions {
+            checksum: true,
+            ..SerializeOptions::default()
+        };
+        let serialized = graph.serialize_with(&options);
+        assert!(
+            serialized.contains(" checksum="),
+            "Expected checksum in header, got {serialized:?}"
+        );
+        assert_eq!(serialized.parse::<Graph<String>>().ok(), Some(graph));
+
+        let truncated = &serialized[..serialized.len() - 2];
+        let error = truncated.parse::<Graph<String>>().unwrap_err();
+        assert!(
+            error.downcast_ref::<IntegrityError>().is_some(),
+            "Expected integrity error, got {error}"
+        );
+    }
+
+    #[test]
+    fn empty_graph_round_trip() {
+        let graph = Graph::<String>::new();
+        assert_eq!(graph.serialize(), "#\n", "Serialized mismatch");
+        assert_eq!(
+            graph.serialize().parse::<Graph<String>>().ok(),
+            Some(graph.clone()),
+            "Expected empty graph to parse back"
+        );
+        let options = SerializeOptions {
+            header: true,
+            checksum: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            graph.serialize_with(&options).parse::<Graph<String>>().ok(),
+            Some(graph),
+            "Expected empty graph with header to parse back"
+        );
+        let edgeless = "1 a\n#".parse::<Graph<String>>().unwrap();
+        assert_eq!(edgeless.nodes.len(), 1, "Expected node without edges");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn write_to_from_reader() {
+        let graph = Graph::from(([(1, "a".to_string()), (2, "b".to_string())], [(2, 1)]));
+        let mut bytes = Vec::new();
+        graph
+            .write_to(&mut bytes, &SerializeOptions::default())
+            .unwrap();
+        assert_eq!(
+            bytes,
+            graph.serialize().as_bytes(),
+            "Written bytes mismatch"
+        );
+        assert_eq!(
+            Graph::<String>::from_reader(bytes.as_slice()).ok(),
+            Some(graph),
+            "Expected graph to read back"
+        );
+    }
 }
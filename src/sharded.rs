@@ -0,0 +1,352 @@
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+    thread,
+};
+
+use super::{snapshot::spread_id, Graph, GraphId};
+
+/// A graph split into shards by a hash of the node ID, each behind its own lock, so threads
+/// working on different shards don't wait for each other.
+///
+/// All methods take `&self`, so the graph can be shared between threads, e.g. in an `Arc`.
+/// Every edge is stored in the shard of its source. Operations that touch several shards lock
+/// them in ascending order, so they can't deadlock.
+#[derive(Debug)]
+pub struct ShardedGraph<T> {
+    shards: Vec<RwLock<Shard<T>>>,
+}
+
+#[derive(Debug)]
+struct Shard<T> {
+    nodes: HashMap<GraphId, T>,
+    targets: HashMap<GraphId, HashSet<GraphId>>,
+}
+
+impl<T> Default for Shard<T> {
+    fn default() -> Self {
+        Self {
+            nodes: HashMap::new(),
+            targets: HashMap::new(),
+        }
+    }
+}
+
+impl<T> ShardedGraph<T> {
+    /// Creates an empty graph with `shard_count` shards, at least one.
+    pub fn new(shard_count: usize) -> Self {
+        Self {
+            shards: (0..shard_count.max(1))
+                .map(|_| RwLock::new(Shard::default()))
+                .collect(),
+        }
+    }
+
+    /// Splits `graph` into `shard_count` shards.
+    pub fn from_graph(graph: Graph<T>, shard_count: usize) -> Self {
+        let sharded = Self::new(shard_count);
+        let edges = graph
+            .edges
+            .iter()
+            .map(|edge| (edge.from, edge.to))
+            .collect::<Vec<_>>();
+        for (id, value) in graph.nodes {
+            sharded.add_node(id, value);
+        }
+        for (from, to) in edges {
+            sharded.add_edge(from, to);
+        }
+
+        sharded
+    }
+
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Returns the index of the shard that holds `id`.
+    pub fn shard_of(&self, id: GraphId) -> usize {
+        (spread_id(id) % self.shards.len() as u64) as usize
+    }
+
+    /// Adds a node. Returns `false` if it already exists.
+    pub fn add_node(&self, id: GraphId, value: T) -> bool {
+        let mut shard = self.write(self.shard_of(id));
+        if shard.nodes.contains_key(&id) {
+            return false;
+        }
+        shard.nodes.insert(id, value);
+
+        true
+    }
+
+    /// Adds an edge. Returns `false` if it already exists or an endpoint doesn't.
+    pub fn add_edge(&self, from: GraphId, to: GraphId) -> bool {
+        let (source, target) = (self.shard_of(from), self.shard_of(to));
+        let mut guards = self.write_many([source, target]);
+        if !guards[&target].nodes.contains_key(&to) {
+            return false;
+        }
+        let shard = guards.get_mut(&source).expect("locked");
+        if !shard.nodes.contains_key(&from) {
+            return false;
+        }
+
+        shard.targets.entry(from).or_default().insert(to)
+    }
+
+    /// Removes a node along with its edges, returning its value. Locks every shard, since
+    /// edges pointing to the node may be stored anywhere.
+    pub fn delete_node(&self, id: GraphId) -> Option<T> {
+        let mut guards = self.write_many(0..self.shards.len());
+        let shard = guards.get_mut(&self.shard_of(id)).expect("locked");
+        let value = shard.nodes.remove(&id)?;
+        shard.targets.remove(&id);
+        for shard in guards.values_mut() {
+            for targets in shard.targets.values_mut() {
+                targets.remove(&id);
+            }
+        }
+
+        Some(value)
+    }
+
+    /// Removes an edge. Returns `false` if it doesn't exist.
+    pub fn delete_edge(&self, from: GraphId, to: GraphId) -> bool {
+        let mut shard = self.write(self.shard_of(from));
+        shard
+            .targets
+            .get_mut(&from)
+            .is_some_and(|targets| targets.remove(&to))
+    }
+
+    pub fn contains_node(&self, id: GraphId) -> bool {
+        self.read(self.shard_of(id)).nodes.contains_key(&id)
+    }
+
+    pub fn get(&self, id: GraphId) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.read(self.shard_of(id)).nodes.get(&id).cloned()
+    }
+
+    /// Returns the sorted targets of `id`, or `None` if it doesn't exist.
+    pub fn neighbours(&self, id: GraphId) -> Option<Vec<GraphId>> {
+        let shard = self.read(self.shard_of(id));
+        shard.nodes.get(&id)?;
+
+        Some(sorted_targets(&shard, id))
+    }
+
+    pub fn node_count(&self) -> usize {
+        (0..self.shards.len())
+            .map(|index| self.read(index).nodes.len())
+            .sum()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        (0..self.shards.len())
+            .map(|index| {
+                self.read(index)
+                    .targets
+                    .values()
+                    .map(HashSet::len)
+                    .sum::<usize>()
+            })
+            .sum()
+    }
+
+    /// Returns the nodes reachable from `source` with their distances, sorted by distance and
+    /// then ID, or `None` if `source` doesn't exist.
+    ///
+    /// Every level of the search expands the frontier of each shard on its own thread. Shards
+    /// are only locked while their part of a level is expanded, so changes made by other
+    /// threads during the search may or may not be seen.
+    pub fn parallel_bfs(&self, source: GraphId) -> Option<Vec<(GraphId, usize)>>
+    where
+        T: Send + Sync,
+    {
+        if !self.contains_node(source) {
+            return None;
+        }
+
+        let mut distances = HashMap::from([(source, 0)]);
+        let mut frontier = vec![source];
+        let mut distance = 0;
+        while !frontier.is_empty() {
+            distance += 1;
+            let mut by_shard = vec![Vec::new(); self.shards.len()];
+            for id in frontier {
+                by_shard[self.shard_of(id)].push(id);
+            }
+
+            let found = thread::scope(|scope| {
+                let handles = by_shard
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, ids)| !ids.is_empty())
+                    .map(|(index, ids)| {
+                        scope.spawn(move || {
+                            let shard = self.read(index);
+                            ids.iter()
+                                .flat_map(|id| sorted_targets(&shard, *id))
+                                .collect::<Vec<_>>()
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                handles
+                    .into_iter()
+                    .flat_map(|handle| handle.join().expect("BFS worker panicked"))
+                    .collect::<Vec<_>>()
+            });
+
+            frontier = Vec::new();
+            for id in found {
+                if let Entry::Vacant(entry) = distances.entry(id) {
+                    entry.insert(distance);
+                    frontier.push(id);
+                }
+            }
+        }
+
+        let mut reached = distances.into_iter().collect::<Vec<_>>();
+        reached.sort_unstable_by_key(|(id, distance)| (*distance, *id));
+
+        Some(reached)
+    }
+
+    /// Copies the nodes and edges into a regular graph.
+    pub fn to_graph(&self) -> Graph<T>
+    where
+        T: Clone,
+    {
+        let guards = (0..self.shards.len())
+            .map(|index| self.read(index))
+            .collect::<Vec<_>>();
+        let mut graph = Graph::new();
+        for shard in &guards {
+            for (id, value) in &shard.nodes {
+                graph.add_node(*id, value.clone());
+            }
+        }
+        for shard in &guards {
+            for (from, targets) in &shard.targets {
+                for to in targets {
+                    graph.add_edge(*from, *to);
+                }
+            }
+        }
+
+        graph
+    }
+
+    fn read(&self, index: usize) -> RwLockReadGuard<'_, Shard<T>> {
+        self.shards[index]
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write(&self, index: usize) -> RwLockWriteGuard<'_, Shard<T>> {
+        self.shards[index]
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Write-locks the given shards in ascending order, keyed by index.
+    fn write_many(
+        &self,
+        indices: impl IntoIterator<Item = usize>,
+    ) -> HashMap<usize, RwLockWriteGuard<'_, Shard<T>>> {
+        let mut indices = indices.into_iter().collect::<Vec<_>>();
+        indices.sort_unstable();
+        indices.dedup();
+
+        indices
+            .into_iter()
+            .map(|index| (index, self.write(index)))
+            .collect()
+    }
+}
+
+fn sorted_targets<T>(shard: &Shard<T>, id: GraphId) -> Vec<GraphId> {
+    let mut targets = shard
+        .targets
+        .get(&id)
+        .into_iter()
+        .flatten()
+        .copied()
+        .collect::<Vec<_>>();
+    targets.sort_unstable();
+
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[test]
+    fn sharded_concurrent_inserts() {
+        let graph = Arc::new(ShardedGraph::new(4));
+        let handles = (0..4)
+            .map(|worker| {
+                let graph = Arc::clone(&graph);
+                thread::spawn(move || {
+                    for id in (worker * 100)..(worker * 100 + 100) {
+                        graph.add_node(id, id * 10);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        for id in 1..400 {
+            assert!(
+                graph.add_edge(id - 1, id),
+                "Expected edge {} -> {id}",
+                id - 1
+            );
+        }
+
+        assert_eq!(graph.node_count(), 400, "Node count mismatch");
+        assert_eq!(graph.edge_count(), 399, "Edge count mismatch");
+        assert_eq!(graph.get(42), Some(420), "Value mismatch");
+        assert!(!graph.add_edge(1, 400), "Expected missing endpoint to fail");
+    }
+
+    #[test]
+    fn sharded_parallel_bfs_matches_graph() {
+        let graph = Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ()), (5, ()), (6, ())],
+            [(1, 2), (1, 3), (2, 4), (3, 4), (4, 5), (6, 1)],
+        ));
+        let sharded = ShardedGraph::from_graph(graph.clone(), 3);
+        assert_eq!(
+            sharded.parallel_bfs(1),
+            Some(vec![(1, 0), (2, 1), (3, 1), (4, 2), (5, 3)]),
+            "BFS mismatch"
+        );
+        assert_eq!(sharded.parallel_bfs(7), None, "Expected missing source");
+        assert_eq!(sharded.to_graph(), graph, "Round trip mismatch");
+    }
+
+    #[test]
+    fn sharded_delete_node_removes_incoming_edges() {
+        let sharded = ShardedGraph::from_graph(
+            Graph::from(([(1, ()), (2, ()), (3, ())], [(1, 2), (3, 2), (2, 3)])),
+            2,
+        );
+        assert_eq!(
+            sharded.delete_node(2),
+            Some(()),
+            "Expected node to be removed"
+        );
+        assert_eq!(sharded.edge_count(), 0, "Expected edges to be removed");
+        assert_eq!(sharded.neighbours(1), Some(vec![]), "Neighbours mismatch");
+        assert!(!sharded.delete_edge(1, 2), "Expected edge to be gone");
+    }
+}
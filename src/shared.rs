@@ -0,0 +1,194 @@
+use std::{
+    ops::Deref,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use arc_swap::ArcSwap;
+
+use super::{Graph, GraphId, Mutation, Snapshot};
+
+/// A graph shared between any number of readers and a single writer at a time.
+///
+/// Readers [`load`](Self::load) the latest published [`Snapshot`] without locking and keep
+/// seeing that version for as long as they hold it. The writer changes a private draft through
+/// a [`GraphWriter`], and its changes become visible to new readers all at once on
+/// [`commit`](GraphWriter::commit).
+#[derive(Debug)]
+pub struct SharedGraph<T> {
+    /// The latest version and its graph.
+    published: ArcSwap<(u64, Arc<Graph<T>>)>,
+    writer: Mutex<()>,
+}
+
+/// Exclusive access to the next version of a [`SharedGraph`], returned by
+/// [`SharedGraph::write`]. Dropping it without committing discards the changes.
+///
+/// Read access through `Deref` sees the draft, including uncommitted changes.
+#[derive(Debug)]
+pub struct GraphWriter<'a, T> {
+    shared: &'a SharedGraph<T>,
+    draft: Arc<Graph<T>>,
+    version: u64,
+    changed: bool,
+    _guard: MutexGuard<'a, ()>,
+}
+
+impl<T> SharedGraph<T> {
+    pub fn new(graph: Graph<T>) -> Self {
+        Self {
+            published: ArcSwap::from_pointee((0, Arc::new(graph))),
+            writer: Mutex::new(()),
+        }
+    }
+
+    /// Returns the latest published version.
+    pub fn load(&self) -> Snapshot<T> {
+        let (version, graph) = &**self.published.load();
+
+        Snapshot::new(*version, Arc::clone(graph))
+    }
+
+    /// Starts the next version, waiting for the current writer to finish if there is one.
+    pub fn write(&self) -> GraphWriter<'_, T> {
+        let guard = self
+            .writer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let (version, graph) = &**self.published.load();
+
+        GraphWriter {
+            shared: self,
+            draft: Arc::clone(graph),
+            version: *version,
+            changed: false,
+            _guard: guard,
+        }
+    }
+}
+
+impl<T: Clone> GraphWriter<'_, T> {
+    /// Applies a mutation to the draft. The draft copies the published graph on the first
+    /// change, so readers are never blocked.
+    pub fn apply(&mut self, mutation: Mutation<T>) {
+        if !Arc::make_mut(&mut self.draft).apply(mutation).is_empty() {
+            self.changed = true;
+        }
+    }
+
+    pub fn add_node(&mut self, id: GraphId, value: T) {
+        self.apply(Mutation::AddNode { id, value });
+    }
+
+    pub fn delete_node(&mut self, id: GraphId) {
+        self.apply(Mutation::RemoveNode { id });
+    }
+
+    pub fn add_edge(&mut self, from: GraphId, to: GraphId) {
+        self.apply(Mutation::AddEdge { from, to });
+    }
+
+    pub fn delete_edge(&mut self, from: GraphId, to: GraphId) {
+        self.apply(Mutation::RemoveEdge { from, to });
+    }
+}
+
+impl<T> GraphWriter<'_, T> {
+    /// Publishes the draft as the next version if anything changed, and returns the version
+    /// readers now load.
+    pub fn commit(self) -> u64 {
+        if !self.changed {
+            return self.version;
+        }
+
+        let version = self.version + 1;
+        self.shared.published.store(Arc::new((version, self.draft)));
+
+        version
+    }
+}
+
+impl<T> Deref for GraphWriter<'_, T> {
+    type Target = Graph<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.draft
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::mpsc, thread};
+
+    use super::*;
+
+    fn get_test_graph() -> Graph<u64> {
+        Graph::from(([(1, 10), (2, 20)], [(1, 2)]))
+    }
+
+    #[test]
+    fn readers_see_committed_versions_only() {
+        let shared = SharedGraph::new(get_test_graph());
+        let before = shared.load();
+        let mut writer = shared.write();
+        writer.add_node(3, 30);
+        writer.add_edge(2, 3);
+        assert_eq!(writer.nodes.len(), 3, "Expected writer to see its draft");
+        assert_eq!(shared.load().nodes.len(), 2, "Expected draft to be hidden");
+        assert_eq!(writer.commit(), 1, "Version mismatch");
+
+        let after = shared.load();
+        assert_eq!(after.version(), 1, "Expected readers to see the commit");
+        assert_eq!(after.nodes.len(), 3, "Node count mismatch");
+        assert_eq!(
+            *before,
+            get_test_graph(),
+            "Expected old snapshot to be kept"
+        );
+    }
+
+    #[test]
+    fn dropped_writer_discards_changes() {
+        let shared = SharedGraph::new(get_test_graph());
+        let mut writer = shared.write();
+        writer.delete_node(1);
+        drop(writer);
+        let mut writer = shared.write();
+        writer.add_edge(1, 2);
+        assert_eq!(writer.commit(), 0, "Expected no-op commit to keep version");
+        assert_eq!(*shared.load(), get_test_graph(), "Graph mismatch");
+    }
+
+    #[test]
+    fn readers_on_other_threads() {
+        let shared = Arc::new(SharedGraph::new(get_test_graph()));
+        let (sender, receiver) = mpsc::channel();
+        let reader = {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || {
+                let snapshot = shared.load();
+                sender.send(()).unwrap();
+                thread::park();
+                // Every version has as many edges as nodes minus one.
+                let latest = shared.load();
+                (
+                    snapshot.nodes.len(),
+                    latest.nodes.len() - latest.edges.len(),
+                )
+            })
+        };
+
+        receiver.recv().unwrap();
+        let mut writer = shared.write();
+        for id in 3..10 {
+            writer.add_node(id, id * 10);
+            writer.add_edge(id - 1, id);
+        }
+        writer.commit();
+        reader.thread().unpark();
+        assert_eq!(
+            reader.join().unwrap(),
+            (2, 1),
+            "Expected consistent versions"
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use core::mem;
+
+use super::{
+    collections::{HashMap, HashSet},
+    rank::iterate,
+    CancellationToken, Convergence, Graph, GraphId, IterationBudget,
+};
+
+impl<T> Graph<T> {
+    /// Computes the SimRank similarity of every pair of distinct nodes, keyed as
+    /// `(smaller, larger)`: two nodes are similar if they are pointed to by similar nodes.
+    /// A node is fully similar to itself, and one without incoming edges to no other node.
+    ///
+    /// `decay` is the factor `C` in `(0, 1)`, usually 0.8, by which similarity fades with
+    /// every step back along the edges. Takes time quadratic in the number of nodes per
+    /// iteration, so see [`simrank_among`](Self::simrank_among) for large graphs.
+    pub fn simrank(
+        &self,
+        decay: f64,
+        budget: &IterationBudget,
+    ) -> Convergence<BTreeMap<(GraphId, GraphId), f64>> {
+        let mut ids = self.nodes.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+
+        self.run_simrank(decay, budget, &ids, ids.clone())
+    }
+
+    /// Like [`simrank`](Self::simrank), but only returns the pairs of nodes in `query`.
+    ///
+    /// The similarity after `k` iterations only depends on nodes at most `k` steps back along
+    /// the edges, so only those take part, bounded by the budget's iterations.
+    pub fn simrank_among(
+        &self,
+        decay: f64,
+        budget: &IterationBudget,
+        query: &[GraphId],
+    ) -> Convergence<BTreeMap<(GraphId, GraphId), f64>> {
+        let mut query = query
+            .iter()
+            .copied()
+            .filter(|id| self.nodes.contains_key(id))
+            .collect::<Vec<_>>();
+        query.sort_unstable();
+        query.dedup();
+
+        let predecessors = self.reverse_adjacency();
+        let mut involved = query.iter().copied().collect::<HashSet<_>>();
+        let mut frontier = query.clone();
+        for _ in 0..budget.max_iterations {
+            frontier = frontier
+                .iter()
+                .flat_map(|id| predecessors.get(id).into_iter().flatten())
+                .copied()
+                .filter(|id| involved.insert(*id))
+                .collect();
+            if frontier.is_empty() {
+                break;
+            }
+        }
+        let mut ids = involved.into_iter().collect::<Vec<_>>();
+        ids.sort_unstable();
+
+        self.run_simrank(decay, budget, &ids, query)
+    }
+
+    /// Iterates SimRank over the subgraph induced by `ids` and returns the pairs of `query`.
+    fn run_simrank(
+        &self,
+        decay: f64,
+        budget: &IterationBudget,
+        ids: &[GraphId],
+        query: Vec<GraphId>,
+    ) -> Convergence<BTreeMap<(GraphId, GraphId), f64>> {
+        assert!(
+            decay > 0.0 && decay < 1.0,
+            "Expected decay in (0, 1), got {decay}"
+        );
+        debug_span!("simrank", nodes = ids.len(), budget.max_iterations);
+
+        let index_of = ids
+            .iter()
+            .enumerate()
+            .map(|(index, id)| (*id, index))
+            .collect::<HashMap<_, _>>();
+        let mut sources = vec![Vec::new(); ids.len()];
+        for edge in &self.edges {
+            if let (Some(from), Some(to)) = (index_of.get(&edge.from), index_of.get(&edge.to)) {
+                sources[*to].push(*from);
+            }
+        }
+
+        let count = ids.len();
+        let mut scores = vec![0.0; count * count];
+        for index in 0..count {
+            scores[index * count + index] = 1.0;
+        }
+        let convergence = iterate(budget, &mut |_| {}, &CancellationToken::new(), || {
+            let current = &scores;
+            let mut next = vec![0.0; count * count];
+            for a in 0..count {
+                next[a * count + a] = 1.0;
+                for b in a + 1..count {
+                    if sources[a].is_empty() || sources[b].is_empty() {
+                        continue;
+                    }
+                    let total = sources[a]
+                        .iter()
+                        .flat_map(|i| sources[b].iter().map(move |j| current[i * count + j]))
+                        .sum::<f64>();
+                    let score = decay * total / (sources[a].len() * sources[b].len()) as f64;
+                    next[a * count + b] = score;
+                    next[b * count + a] = score;
+                }
+            }
+
+            mem::replace(&mut scores, next)
+                .iter()
+                .zip(&scores)
+                .map(|(old, new)| (new - old).abs())
+                .sum()
+        })
+        .expect("token is never cancelled");
+
+        let mut pairs = BTreeMap::new();
+        for (position, a) in query.iter().enumerate() {
+            for b in &query[position + 1..] {
+                pairs.insert((*a, *b), scores[index_of[a] * count + index_of[b]]);
+            }
+        }
+
+        convergence.with(pairs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_graph() -> Graph<()> {
+        Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ()), (5, ()), (6, ())],
+            [(1, 2), (1, 3), (2, 4), (3, 5), (6, 5)],
+        ))
+    }
+
+    #[test]
+    fn simrank_follows_common_sources() {
+        let convergence = get_test_graph().simrank(0.8, &IterationBudget::new());
+        assert!(convergence.converged, "Expected convergence");
+        let scores = convergence.result;
+        assert!(
+            (scores[&(2, 3)] - 0.8).abs() < 1e-9,
+            "Expected nodes with the same source to be similar"
+        );
+        // 5 has a second source, 6, which isn't similar to 2.
+        assert!(
+            (scores[&(4, 5)] - 0.8 * 0.8 / 2.0).abs() < 1e-9,
+            "Score mismatch, got {}",
+            scores[&(4, 5)]
+        );
+        assert_eq!(
+            scores[&(1, 6)],
+            0.0,
+            "Expected no similarity without sources"
+        );
+        assert_eq!(scores.len(), 15, "Expected every pair once");
+    }
+
+    #[test]
+    fn simrank_among_matches_full() {
+        let graph = get_test_graph();
+        let budget = IterationBudget::new();
+        let full = graph.simrank(0.8, &budget).result;
+        let restricted = graph.simrank_among(0.8, &budget, &[5, 4, 9]).result;
+        assert_eq!(
+            restricted.keys().collect::<Vec<_>>(),
+            vec![&(4, 5)],
+            "Expected only the query pair"
+        );
+        assert!(
+            (restricted[&(4, 5)] - full[&(4, 5)]).abs() < 1e-9,
+            "Expected same score as the full computation"
+        );
+    }
+}
@@ -0,0 +1,394 @@
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::ops::Deref;
+
+use once_cell::race::OnceBox;
+
+use super::{
+    collections::{HashMap, HashSet},
+    Edge, Graph, GraphId, Mutation,
+};
+
+/// Number of shards of a [`VersionedGraph`]. A change copies one or a few shards, so more
+/// shards make changes cheaper and snapshots a little more expensive.
+const SHARD_COUNT: usize = 64;
+
+/// An immutable view of a graph at some version. Cloning a snapshot is cheap and clones can be
+/// sent to other threads.
+///
+/// Snapshots of a [`VersionedGraph`] share its storage and assemble it into a [`Graph`] the
+/// first time they are read through `Deref`, once for all snapshots of the same version.
+#[derive(Debug, Clone)]
+pub struct Snapshot<T> {
+    version: u64,
+    storage: Storage<T>,
+}
+
+#[derive(Debug, Clone)]
+enum Storage<T> {
+    Graph(Arc<Graph<T>>),
+    Shards(Shards<T>, Arc<OnceBox<Graph<T>>>),
+}
+
+/// A graph that hands out snapshots in constant time.
+///
+/// Nodes are split into shards by ID, each shared with the snapshots taken since its last
+/// change. The first change to a shard after taking a snapshot copies only that shard, and
+/// removing a node also copies the shards of its neighbours.
+///
+/// Read access through `Deref` assembles the shards into a [`Graph`], which takes O(V + E)
+/// time after every change, so batch changes before reading.
+#[derive(Debug, Clone)]
+pub struct VersionedGraph<T> {
+    version: u64,
+    shards: Shards<T>,
+    graph: Arc<OnceBox<Graph<T>>>,
+}
+
+/// Nodes split into shards by ID, each behind an `Arc` so that copies share unchanged shards.
+#[derive(Debug, Clone)]
+struct Shards<T>(Vec<Arc<Shard<T>>>);
+
+/// The nodes of one shard with the edges that touch them.
+#[derive(Debug, Clone)]
+struct Shard<T> {
+    nodes: HashMap<GraphId, T>,
+    /// Targets of the edges leaving nodes of the shard.
+    targets: HashMap<GraphId, HashSet<GraphId>>,
+    /// Sources of the edges entering nodes of the shard.
+    sources: HashMap<GraphId, HashSet<GraphId>>,
+}
+
+impl<T: Clone> Graph<T> {
+    /// Returns a frozen copy of the graph at version 0, since a plain graph doesn't track
+    /// versions.
+    ///
+    /// This copies every node and edge, taking O(V + E) time and memory on every call. To
+    /// take snapshots in constant time while the graph keeps changing, keep it in a
+    /// [`VersionedGraph`] instead.
+    pub fn snapshot(&self) -> Snapshot<T> {
+        Snapshot::new(0, Arc::new(self.clone()))
+    }
+}
+
+impl<T> Snapshot<T> {
+    pub(crate) fn new(version: u64, graph: Arc<Graph<T>>) -> Self {
+        Self {
+            version,
+            storage: Storage::Graph(graph),
+        }
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+}
+
+impl<T: Clone> Deref for Snapshot<T> {
+    type Target = Graph<T>;
+
+    fn deref(&self) -> &Self::Target {
+        match &self.storage {
+            Storage::Graph(graph) => graph,
+            Storage::Shards(shards, graph) => graph.get_or_init(|| Box::new(shards.assemble())),
+        }
+    }
+}
+
+impl<T: Clone> VersionedGraph<T> {
+    pub fn new(graph: Graph<T>) -> Self {
+        Self {
+            version: 0,
+            shards: Shards::from_graph(&graph),
+            graph: Arc::new(OnceBox::with_value(Box::new(graph))),
+        }
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Returns a snapshot of the current version, sharing every shard, in time proportional
+    /// to the fixed number of shards.
+    pub fn snapshot(&self) -> Snapshot<T> {
+        Snapshot {
+            version: self.version,
+            storage: Storage::Shards(self.shards.clone(), Arc::clone(&self.graph)),
+        }
+    }
+
+    pub fn into_inner(self) -> Graph<T> {
+        match self.graph.get() {
+            Some(graph) => graph.clone(),
+            None => self.shards.assemble(),
+        }
+    }
+
+    /// Applies a mutation, bumping the version if it changed the graph.
+    pub fn apply(&mut self, mutation: Mutation<T>) {
+        if self.shards.apply(mutation) {
+            self.version += 1;
+            self.graph = Arc::default();
+        }
+    }
+
+    pub fn add_node(&mut self, id: GraphId, value: T) {
+        self.apply(Mutation::AddNode { id, value });
+    }
+
+    pub fn delete_node(&mut self, id: GraphId) {
+        self.apply(Mutation::RemoveNode { id });
+    }
+
+    pub fn add_edge(&mut self, from: GraphId, to: GraphId) {
+        self.apply(Mutation::AddEdge { from, to });
+    }
+
+    pub fn delete_edge(&mut self, from: GraphId, to: GraphId) {
+        self.apply(Mutation::RemoveEdge { from, to });
+    }
+}
+
+impl<T: Clone> Deref for VersionedGraph<T> {
+    type Target = Graph<T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.graph.get_or_init(|| Box::new(self.shards.assemble()))
+    }
+}
+
+impl<T: Clone> Shards<T> {
+    fn from_graph(graph: &Graph<T>) -> Self {
+        let mut shards = (0..SHARD_COUNT)
+            .map(|_| Shard {
+                nodes: HashMap::new(),
+                targets: HashMap::new(),
+                sources: HashMap::new(),
+            })
+            .collect::<Vec<_>>();
+        for (id, value) in &graph.nodes {
+            shards[shard_of(*id)].nodes.insert(*id, value.clone());
+        }
+        for edge in &graph.edges {
+            let shard = &mut shards[shard_of(edge.from)];
+            shard.targets.entry(edge.from).or_default().insert(edge.to);
+            let shard = &mut shards[shard_of(edge.to)];
+            shard.sources.entry(edge.to).or_default().insert(edge.from);
+        }
+
+        Self(shards.into_iter().map(Arc::new).collect())
+    }
+
+    fn get(&self, id: GraphId) -> &Shard<T> {
+        &self.0[shard_of(id)]
+    }
+
+    /// Returns the shard of `id` for writing, copying it first if a snapshot shares it.
+    fn get_mut(&mut self, id: GraphId) -> &mut Shard<T> {
+        Arc::make_mut(&mut self.0[shard_of(id)])
+    }
+
+    /// Applies a mutation like [`Graph::apply`], returning whether it changed anything.
+    /// Shards are only copied when they change.
+    fn apply(&mut self, mutation: Mutation<T>) -> bool {
+        match mutation {
+            Mutation::AddNode { id, value } => {
+                if self.get(id).nodes.contains_key(&id) {
+                    return false;
+                }
+                self.get_mut(id).nodes.insert(id, value);
+            }
+            Mutation::RemoveNode { id } => {
+                let shard = self.get(id);
+                if !shard.nodes.contains_key(&id) {
+                    return false;
+                }
+                let targets = shard.targets.get(&id).cloned().unwrap_or_default();
+                let sources = shard.sources.get(&id).cloned().unwrap_or_default();
+                let shard = self.get_mut(id);
+                shard.nodes.remove(&id);
+                shard.targets.remove(&id);
+                shard.sources.remove(&id);
+                for target in targets.into_iter().filter(|target| *target != id) {
+                    remove_link(&mut self.get_mut(target).sources, target, id);
+                }
+                for source in sources.into_iter().filter(|source| *source != id) {
+                    remove_link(&mut self.get_mut(source).targets, source, id);
+                }
+            }
+            Mutation::AddEdge { from, to } => {
+                let exists = self
+                    .get(from)
+                    .targets
+                    .get(&from)
+                    .is_some_and(|targets| targets.contains(&to));
+                if exists
+                    || !self.get(from).nodes.contains_key(&from)
+                    || !self.get(to).nodes.contains_key(&to)
+                {
+                    return false;
+                }
+                self.get_mut(from)
+                    .targets
+                    .entry(from)
+                    .or_default()
+                    .insert(to);
+                self.get_mut(to).sources.entry(to).or_default().insert(from);
+            }
+            Mutation::RemoveEdge { from, to } => {
+                let exists = self
+                    .get(from)
+                    .targets
+                    .get(&from)
+                    .is_some_and(|targets| targets.contains(&to));
+                if !exists {
+                    return false;
+                }
+                remove_link(&mut self.get_mut(from).targets, from, to);
+                remove_link(&mut self.get_mut(to).sources, to, from);
+            }
+        }
+
+        true
+    }
+
+    fn assemble(&self) -> Graph<T> {
+        let mut graph = Graph::new();
+        for shard in &self.0 {
+            graph
+                .nodes
+                .extend(shard.nodes.iter().map(|(id, value)| (*id, value.clone())));
+            graph
+                .edges
+                .extend(shard.targets.iter().flat_map(|(from, targets)| {
+                    targets.iter().map(|to| Edge {
+                        from: *from,
+                        to: *to,
+                    })
+                }));
+        }
+
+        graph
+    }
+}
+
+/// Removes `other` from the links of `id`, dropping the entry once it has none.
+fn remove_link(links: &mut HashMap<GraphId, HashSet<GraphId>>, id: GraphId, other: GraphId) {
+    if let Some(others) = links.get_mut(&id) {
+        others.remove(&other);
+        if others.is_empty() {
+            links.remove(&id);
+        }
+    }
+}
+
+/// Returns the index of the shard that holds `id`.
+fn shard_of(id: GraphId) -> usize {
+    (spread_id(id) % SHARD_COUNT as u64) as usize
+}
+
+/// Scrambles the bits of an ID with the finalizer of SplitMix64, so that sequential IDs
+/// spread over all shards.
+pub(crate) fn spread_id(id: GraphId) -> u64 {
+    let mut hash = id;
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+
+    hash ^ (hash >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    fn get_test_graph() -> Graph<String> {
+        Graph::from(([(1, "a".to_string()), (2, "b".to_string())], [(1, 2)]))
+    }
+
+    #[test]
+    fn graph_snapshot_is_frozen() {
+        let mut graph = get_test_graph();
+        let snapshot = graph.snapshot();
+        graph.delete_node(2);
+        assert_eq!(
+            *snapshot,
+            get_test_graph(),
+            "Expected snapshot to be unchanged"
+        );
+    }
+
+    #[test]
+    fn versioned_snapshots_share_until_write() {
+        let mut graph = VersionedGraph::new(get_test_graph());
+        let first = graph.snapshot();
+        graph.add_node(3, "c".to_string());
+        graph.add_edge(2, 3);
+        let second = graph.snapshot();
+        let Storage::Shards(first_shards, _) = &first.storage else {
+            panic!("Expected sharded snapshot");
+        };
+        let Storage::Shards(second_shards, _) = &second.storage else {
+            panic!("Expected sharded snapshot");
+        };
+        let changed = [shard_of(2), shard_of(3)];
+        for (index, (old, new)) in first_shards.0.iter().zip(&second_shards.0).enumerate() {
+            assert_eq!(
+                Arc::ptr_eq(old, new),
+                !changed.contains(&index),
+                "Expected only changed shard {index} to be copied"
+            );
+        }
+
+        assert_eq!(graph.version(), 2, "Version mismatch");
+        assert_eq!(first.version(), 0, "Snapshot version mismatch");
+        assert_eq!(
+            *first,
+            get_test_graph(),
+            "Expected snapshot to be unchanged"
+        );
+        assert_eq!(graph.nodes.len(), 3, "Expected writer to see changes");
+        assert_eq!(*second, *graph, "Expected snapshot of the latest version");
+    }
+
+    #[test]
+    fn versioned_matches_graph() {
+        let mut expected = get_test_graph();
+        let mut graph = VersionedGraph::new(get_test_graph());
+        let mutations = [
+            Mutation::AddNode {
+                id: 3,
+                value: "c".to_string(),
+            },
+            Mutation::AddEdge { from: 3, to: 3 },
+            Mutation::AddEdge { from: 2, to: 3 },
+            Mutation::AddEdge { from: 3, to: 1 },
+            Mutation::RemoveEdge { from: 1, to: 2 },
+            Mutation::RemoveNode { id: 3 },
+            Mutation::AddEdge { from: 2, to: 1 },
+        ];
+        for mutation in mutations {
+            expected.apply(mutation.clone());
+            graph.apply(mutation);
+            assert_eq!(*graph, expected, "Graph mismatch");
+        }
+        assert_eq!(graph.into_inner(), expected, "Graph mismatch");
+    }
+
+    #[test]
+    fn versioned_noop_keeps_version() {
+        let mut graph = VersionedGraph::new(get_test_graph());
+        graph.add_edge(1, 2);
+        graph.delete_node(9);
+        assert_eq!(graph.version(), 0, "Expected no-op changes to keep version");
+    }
+
+    #[test]
+    fn snapshot_readable_from_other_thread() {
+        let mut graph = VersionedGraph::new(get_test_graph());
+        let snapshot = graph.snapshot();
+        let reader = thread::spawn(move || snapshot.nodes.len());
+        graph.delete_node(1);
+        assert_eq!(reader.join().unwrap(), 2, "Expected snapshot node count");
+    }
+}
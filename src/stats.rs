@@ -0,0 +1,307 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+
+use super::{Graph, GraphId, UnionFind};
+
+/// Summary statistics of a graph, as returned by [`Graph::stats`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphStats {
+    pub node_count: usize,
+    pub edge_count: usize,
+    /// Edges divided by the number of possible directed edges without self-loops, `n(n - 1)`.
+    /// Zero for graphs with fewer than two nodes.
+    pub density: f64,
+    /// Number of nodes for every total (in + out) degree. Self-loops count twice.
+    pub degree_distribution: BTreeMap<usize, usize>,
+    /// Number of weakly connected components.
+    pub component_count: usize,
+    pub is_dag: bool,
+    /// Degree assortativity coefficient, see [`Graph::degree_assortativity`]. Only available
+    /// with the `std` feature, which provides the square root it needs.
+    #[cfg(feature = "std")]
+    pub assortativity: Option<f64>,
+    /// Fraction of mutual edges, see [`Graph::reciprocity`].
+    pub reciprocity: Option<f64>,
+    /// Number of nodes without incoming edges.
+    pub source_count: usize,
+    /// Number of nodes without outgoing edges.
+    pub sink_count: usize,
+}
+
+impl<T> Graph<T> {
+    pub fn stats(&self) -> GraphStats {
+        debug_span!("stats", nodes = self.nodes.len(), edges = self.edges.len());
+        let node_count = self.nodes.len();
+        let edge_count = self.edges.len();
+        let density = if node_count < 2 {
+            0.0
+        } else {
+            edge_count as f64 / (node_count * (node_count - 1)) as f64
+        };
+
+        let degrees = self.total_degrees();
+
+        let mut components = UnionFind::new();
+        for id in self.nodes.keys() {
+            components.insert(*id);
+        }
+        for edge in &self.edges {
+            components.union(edge.from, edge.to);
+        }
+
+        let mut degree_distribution = BTreeMap::new();
+        for degree in degrees.into_values() {
+            *degree_distribution.entry(degree).or_default() += 1;
+        }
+
+        GraphStats {
+            node_count,
+            edge_count,
+            density,
+            degree_distribution,
+            component_count: components.set_count(),
+            is_dag: self.is_dag(),
+            #[cfg(feature = "std")]
+            assortativity: self.degree_assortativity(),
+            reciprocity: self.reciprocity(),
+            source_count: self.sources().len(),
+            sink_count: self.sinks().len(),
+        }
+    }
+
+    /// Returns the fraction of edges whose reverse edge also exists, ignoring self-loops, or
+    /// `None` if there are no other edges. 1 for an undirected graph stored with both
+    /// directions of every edge.
+    pub fn reciprocity(&self) -> Option<f64> {
+        let edges = self
+            .edges
+            .iter()
+            .filter(|edge| edge.from != edge.to)
+            .count();
+        let mutual = self.mutual_edges().count();
+
+        (edges > 0).then(|| (2 * mutual) as f64 / edges as f64)
+    }
+
+    /// Returns the nodes without incoming edges, sorted. A self-loop counts as incoming, and
+    /// isolated nodes are both sources and sinks.
+    pub fn sources(&self) -> Vec<GraphId> {
+        let targets = self
+            .edges
+            .iter()
+            .map(|edge| edge.to)
+            .collect::<BTreeSet<_>>();
+
+        self.nodes_except(&targets)
+    }
+
+    /// Returns the nodes without outgoing edges, sorted, like [`sources`](Self::sources).
+    pub fn sinks(&self) -> Vec<GraphId> {
+        let origins = self
+            .edges
+            .iter()
+            .map(|edge| edge.from)
+            .collect::<BTreeSet<_>>();
+
+        self.nodes_except(&origins)
+    }
+
+    fn nodes_except(&self, excluded: &BTreeSet<GraphId>) -> Vec<GraphId> {
+        let mut ids = self
+            .nodes
+            .keys()
+            .copied()
+            .filter(|id| !excluded.contains(id))
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+
+        ids
+    }
+
+    /// Returns the degree assortativity coefficient: the Pearson correlation between the total
+    /// (in + out) degrees at the two ends of every edge, in `[-1, 1]`. Positive values mean
+    /// that nodes tend to link to nodes of similar degree, negative ones that hubs link to
+    /// leaves. An undirected graph stored with both directions of every edge gets the usual
+    /// undirected coefficient.
+    ///
+    /// Returns `None` if there are no edges or all of them join nodes of the same degrees.
+    /// Only available with the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn degree_assortativity(&self) -> Option<f64> {
+        let degrees = self.total_degrees();
+        let count = self.edges.len() as f64;
+        let pairs = self
+            .edges
+            .iter()
+            .map(|edge| (degrees[&edge.from] as f64, degrees[&edge.to] as f64))
+            .collect::<Vec<_>>();
+        let mean = |value: &dyn Fn(f64, f64) -> f64| {
+            pairs
+                .iter()
+                .map(|(from, to)| value(*from, *to))
+                .sum::<f64>()
+                / count
+        };
+
+        let (mean_from, mean_to) = (mean(&|from, _| from), mean(&|_, to| to));
+        let covariance = mean(&|from, to| from * to) - mean_from * mean_to;
+        let spread = ((mean(&|from, _| from * from) - mean_from * mean_from)
+            * (mean(&|_, to| to * to) - mean_to * mean_to))
+            .sqrt();
+        let coefficient = covariance / spread;
+
+        coefficient
+            .is_finite()
+            .then_some(coefficient.clamp(-1.0, 1.0))
+    }
+
+    /// Returns the mean total (in + out) degree of the neighbours of every node, in either
+    /// direction and each counted once. Nodes without neighbours get 0.
+    pub fn average_neighbour_degree(&self) -> BTreeMap<GraphId, f64> {
+        let degrees = self.total_degrees();
+        let mut neighbours = self
+            .nodes
+            .keys()
+            .map(|id| (*id, BTreeSet::new()))
+            .collect::<BTreeMap<_, _>>();
+        for edge in self.edges.iter().filter(|edge| edge.from != edge.to) {
+            neighbours.entry(edge.from).or_default().insert(edge.to);
+            neighbours.entry(edge.to).or_default().insert(edge.from);
+        }
+
+        neighbours
+            .into_iter()
+            .map(|(id, neighbours)| {
+                let total = neighbours.iter().map(|other| degrees[other]).sum::<usize>();
+                (id, total as f64 / neighbours.len().max(1) as f64)
+            })
+            .collect()
+    }
+
+    /// Returns the total (in + out) degree of every node. Self-loops count twice.
+    fn total_degrees(&self) -> BTreeMap<GraphId, usize> {
+        let mut degrees = self
+            .nodes
+            .keys()
+            .map(|id| (*id, 0))
+            .collect::<BTreeMap<_, usize>>();
+        for edge in &self.edges {
+            *degrees.entry(edge.from).or_default() += 1;
+            *degrees.entry(edge.to).or_default() += 1;
+        }
+
+        degrees
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_graph() -> Graph<()> {
+        Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ()), (5, ())],
+            [(1, 2), (1, 3), (2, 3), (4, 4)],
+        ))
+    }
+
+    #[test]
+    fn stats_counts() {
+        let stats = get_test_graph().stats();
+        assert_eq!(stats.node_count, 5, "Nodes count mismatch");
+        assert_eq!(stats.edge_count, 4, "Edges count mismatch");
+        assert_eq!(stats.density, 0.2, "Density mismatch");
+        assert_eq!(stats.component_count, 3, "Components count mismatch");
+        assert!(!stats.is_dag, "Expected self-loop to be a cycle");
+
+        let cyclic = Graph::from((
+            [(1, ()), (2, ()), (3, ())],
+            [(3, 1), (3, 2), (1, 2), (2, 1)],
+        ));
+        assert!(
+            !cyclic.stats().is_dag,
+            "Expected cycle behind a source to be found"
+        );
+    }
+
+    #[test]
+    fn stats_degree_distribution() {
+        let stats = get_test_graph().stats();
+        assert_eq!(
+            stats.degree_distribution,
+            BTreeMap::from([(0, 1), (2, 4)]),
+            "Distribution mismatch"
+        );
+    }
+
+    #[test]
+    fn stats_empty_graph() {
+        let stats = Graph::<()>::new().stats();
+        assert_eq!(stats.density, 0.0, "Expected zero density");
+        assert!(stats.degree_distribution.is_empty(), "Expected no degrees");
+        assert!(stats.is_dag, "Expected empty graph to be a DAG");
+        #[cfg(feature = "std")]
+        assert_eq!(stats.assortativity, None, "Expected no assortativity");
+        assert_eq!(stats.reciprocity, None, "Expected no reciprocity");
+    }
+
+    #[test]
+    fn reciprocity_sources_and_sinks() {
+        // 1 and 2 follow each other, 3 follows 1, 4 only follows itself, 5 is isolated.
+        let graph = Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ()), (5, ())],
+            [(1, 2), (2, 1), (3, 1), (4, 4)],
+        ));
+        let stats = graph.stats();
+        assert_eq!(stats.reciprocity, Some(2.0 / 3.0), "Reciprocity mismatch");
+        assert_eq!(graph.sources(), vec![3, 5], "Sources mismatch");
+        assert_eq!(graph.sinks(), vec![5], "Sinks mismatch");
+        assert_eq!(
+            (stats.source_count, stats.sink_count),
+            (2, 1),
+            "Counts mismatch"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn degree_assortativity_of_star_and_pairs() {
+        // A star stored in both directions: the hub only links to leaves.
+        let star = Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ())],
+            [(1, 2), (2, 1), (1, 3), (3, 1), (1, 4), (4, 1)],
+        ));
+        let coefficient = star.degree_assortativity().unwrap();
+        assert!(
+            (coefficient + 1.0).abs() < 1e-9,
+            "Expected perfect disassortativity, got {coefficient}"
+        );
+
+        let path = Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ())],
+            [(1, 2), (2, 1), (2, 3), (3, 2), (3, 4), (4, 3)],
+        ));
+        let coefficient = path.degree_assortativity().unwrap();
+        assert!(coefficient < 0.0, "Expected negative, got {coefficient}");
+        assert_eq!(
+            get_test_graph().degree_assortativity(),
+            None,
+            "Expected no variance among equal degrees"
+        );
+    }
+
+    #[test]
+    fn average_neighbour_degree_uses_both_directions() {
+        let graph = Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ())],
+            [(1, 2), (1, 3), (3, 2), (2, 2)],
+        ));
+        assert_eq!(
+            graph.average_neighbour_degree(),
+            BTreeMap::from([(1, 3.0), (2, 2.0), (3, 3.0), (4, 0.0)]),
+            "Average neighbour degree mismatch"
+        );
+    }
+}
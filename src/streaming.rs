@@ -0,0 +1,194 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+};
+
+use anyhow::{anyhow, Context};
+
+use super::{connectivity::sorted, header::Header, GraphId, UnionFind};
+
+/// A TGF file analysed in passes over its lines instead of being loaded into a [`Graph`], for
+/// graphs whose edges don't fit in memory.
+///
+/// Every pass reads the file again, keeping one line in memory at a time. Analyses keep
+/// state per node but never per edge, so unlike [`Graph`], duplicate edge lines are counted
+/// every time they appear. A [`Header`] is respected, but its checksum isn't verified.
+///
+/// [`Graph`]: crate::Graph
+#[derive(Debug, Clone)]
+pub struct EdgeStream {
+    path: PathBuf,
+}
+
+/// A line of a file read by [`EdgeStream::pass`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamItem {
+    Node(GraphId),
+    Edge { from: GraphId, to: GraphId },
+}
+
+impl EdgeStream {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Reads the file once, calling `visit` with every node and then every edge in file order.
+    pub fn pass(&self, mut visit: impl FnMut(StreamItem)) -> anyhow::Result<()> {
+        debug_span!("edge_stream_pass", path = %self.path.display());
+        let file = File::open(&self.path)
+            .with_context(|| format!("Failed to open {}", self.path.display()))?;
+        let mut directed = true;
+        let mut in_edges = false;
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            let line = line.trim_end();
+            let context = || format!("Line {}", index + 1);
+            if index == 0 && line.starts_with('%') {
+                directed = line.parse::<Header>().with_context(context)?.directed;
+                continue;
+            }
+            if line == "#" {
+                in_edges = true;
+                continue;
+            }
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut parts = line.splitn(3, ' ');
+            let mut id = || -> anyhow::Result<GraphId> {
+                let part = parts.next().ok_or_else(|| anyhow!("Missing ID"))?;
+                part.parse()
+                    .map_err(|_| anyhow!("Expected a node ID, got {part:?}"))
+            };
+            if in_edges {
+                let (from, to) = (id().with_context(context)?, id().with_context(context)?);
+                visit(StreamItem::Edge { from, to });
+                if !directed {
+                    visit(StreamItem::Edge { from: to, to: from });
+                }
+            } else {
+                visit(StreamItem::Node(id().with_context(context)?));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Counts the incoming and outgoing edges of every node, as `(in, out)`. Edges with a
+    /// missing endpoint are skipped, as [`Graph::add_edge`](crate::Graph::add_edge) would.
+    pub fn degrees(&self) -> anyhow::Result<BTreeMap<GraphId, (usize, usize)>> {
+        // TGF lists all nodes before the first edge, so a single pass is enough.
+        let mut degrees = BTreeMap::new();
+        self.pass(|item| match item {
+            StreamItem::Node(id) => {
+                degrees.insert(id, (0, 0));
+            }
+            StreamItem::Edge { from, to } => {
+                if degrees.contains_key(&from) && degrees.contains_key(&to) {
+                    degrees.get_mut(&to).expect("checked").0 += 1;
+                    degrees.get_mut(&from).expect("checked").1 += 1;
+                }
+            }
+        })?;
+
+        Ok(degrees)
+    }
+
+    /// Returns the weakly connected components like
+    /// [`Graph::connected_components`](crate::Graph::connected_components), merging them with
+    /// a union-find over the node IDs during a single pass.
+    pub fn connected_components(&self) -> anyhow::Result<Vec<Vec<GraphId>>> {
+        let mut ids = Vec::new();
+        let mut components = UnionFind::new();
+        self.pass(|item| match item {
+            StreamItem::Node(id) => {
+                if components.find(id).is_none() {
+                    ids.push(id);
+                    components.insert(id);
+                }
+            }
+            StreamItem::Edge { from, to } => {
+                components.union(from, to);
+            }
+        })?;
+
+        let mut grouped: HashMap<GraphId, Vec<GraphId>> = HashMap::new();
+        for id in ids {
+            let root = components.find(id).expect("inserted");
+            grouped.entry(root).or_default().push(id);
+        }
+
+        Ok(sorted(grouped.into_values().collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::Graph;
+
+    fn write_test_file(name: &str, text: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("bgraph-{name}-{}.tgf", std::process::id()));
+        fs::write(&path, text).unwrap();
+
+        path
+    }
+
+    #[test]
+    fn stream_matches_graph() {
+        let graph = Graph::from((
+            [(1, "a"), (2, "b"), (3, "c"), (4, "d"), (5, "e")],
+            [(1, 2), (2, 1), (3, 2), (4, 5)],
+        ));
+        let path = write_test_file("stream", &graph.serialize());
+        let stream = EdgeStream::new(&path);
+        assert_eq!(
+            stream.connected_components().unwrap(),
+            graph.connected_components(),
+            "Components mismatch"
+        );
+        assert_eq!(
+            stream.degrees().unwrap(),
+            BTreeMap::from([
+                (1, (1, 1)),
+                (2, (2, 1)),
+                (3, (0, 1)),
+                (4, (0, 1)),
+                (5, (1, 0))
+            ]),
+            "Degrees mismatch"
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn stream_undirected_header() {
+        let path = write_test_file(
+            "stream-undirected",
+            "%bgraph-tgf version=1 directed=false weighted=true\n1 a\n2 b\n#\n1 2 0.5\n1 3 1\n",
+        );
+        let degrees = EdgeStream::new(&path).degrees().unwrap();
+        assert_eq!(
+            degrees,
+            BTreeMap::from([(1, (1, 1)), (2, (1, 1))]),
+            "Expected edge in both directions and missing node to be skipped"
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn stream_invalid_line() {
+        let path = write_test_file("stream-invalid", "1 a\n#\n1 x\n");
+        let error = EdgeStream::new(&path).degrees().unwrap_err();
+        assert!(
+            format!("{error:#}").contains("Line 3"),
+            "Expected line number, got {error:#}"
+        );
+        fs::remove_file(path).unwrap();
+    }
+}
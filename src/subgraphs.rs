@@ -0,0 +1,246 @@
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+use super::{
+    collections::{HashMap, HashSet},
+    Graph, GraphId,
+};
+
+impl<T> Graph<T> {
+    /// Lazily enumerates every connected set of at most `max_size` nodes, each exactly once
+    /// and sorted, e.g. to extract subgraph features. Nodes count as connected through edges
+    /// in either direction.
+    ///
+    /// The number of such sets grows exponentially with `max_size`, so at most `limit` are
+    /// returned; [`ConnectedSubgraphs::is_capped`] tells whether any were left out. Sets are
+    /// grouped by their smallest node, in ascending order, and each set is followed by the
+    /// ones grown from it.
+    pub fn connected_subgraphs(&self, max_size: usize, limit: usize) -> ConnectedSubgraphs {
+        debug_span!(
+            "connected_subgraphs",
+            nodes = self.nodes.len(),
+            max_size,
+            limit
+        );
+        let mut neighbours = self
+            .nodes
+            .keys()
+            .map(|id| (*id, Vec::new()))
+            .collect::<HashMap<_, _>>();
+        for edge in self.edges.iter().filter(|edge| edge.from != edge.to) {
+            neighbours.entry(edge.from).or_default().push(edge.to);
+            neighbours.entry(edge.to).or_default().push(edge.from);
+        }
+        for list in neighbours.values_mut() {
+            list.sort_unstable();
+            list.dedup();
+        }
+        let mut roots = neighbours.keys().copied().collect::<Vec<_>>();
+        // Popped from the back, so the smallest root comes first.
+        roots.sort_unstable_by(|a, b| b.cmp(a));
+
+        ConnectedSubgraphs {
+            neighbours,
+            roots: if max_size == 0 { Vec::new() } else { roots },
+            max_size,
+            remaining: limit,
+            capped: false,
+            root: 0,
+            subgraph: Vec::new(),
+            stack: Vec::new(),
+        }
+    }
+}
+
+/// Iterator over the connected node sets of a graph, as returned by
+/// [`Graph::connected_subgraphs`].
+///
+/// Works like the ESU algorithm, growing every set only by nodes greater than its smallest
+/// one that aren't next to the set it was grown from, so that every set is found once.
+#[derive(Debug, Clone)]
+pub struct ConnectedSubgraphs {
+    neighbours: HashMap<GraphId, Vec<GraphId>>,
+    roots: Vec<GraphId>,
+    max_size: usize,
+    remaining: usize,
+    capped: bool,
+    /// Smallest node of the sets currently being grown.
+    root: GraphId,
+    subgraph: Vec<GraphId>,
+    /// One frame for every set on the path to the current one that can still grow.
+    stack: Vec<Frame>,
+}
+
+#[derive(Debug, Clone)]
+struct Frame {
+    /// Nodes the set can still be grown by.
+    extension: Vec<GraphId>,
+    /// The set and all its neighbours.
+    reached: HashSet<GraphId>,
+}
+
+impl ConnectedSubgraphs {
+    /// Returns whether the enumeration stopped at the limit with sets left out.
+    pub fn is_capped(&self) -> bool {
+        self.capped
+    }
+
+    /// Moves to the next set, returning `false` once there is none.
+    fn advance(&mut self) -> bool {
+        loop {
+            let depth = self.stack.len();
+            let Some(frame) = self.stack.last_mut() else {
+                break;
+            };
+            let Some(next) = frame.extension.pop() else {
+                self.stack.pop();
+                continue;
+            };
+
+            let root = self.root;
+            let mut extension = frame.extension.clone();
+            extension.extend(
+                self.neighbours[&next]
+                    .iter()
+                    .filter(|id| **id > root && !frame.reached.contains(*id)),
+            );
+            let reached = (depth + 1 < self.max_size).then(|| {
+                let mut reached = frame.reached.clone();
+                reached.extend(&self.neighbours[&next]);
+                reached
+            });
+            self.subgraph.truncate(depth);
+            self.subgraph.push(next);
+            if let Some(reached) = reached {
+                self.stack.push(Frame { extension, reached });
+            }
+
+            return true;
+        }
+
+        let Some(root) = self.roots.pop() else {
+            return false;
+        };
+        self.root = root;
+        self.subgraph.clear();
+        self.subgraph.push(root);
+        if self.max_size > 1 {
+            let neighbours = &self.neighbours[&root];
+            let mut reached = neighbours.iter().copied().collect::<HashSet<_>>();
+            reached.insert(root);
+            self.stack.push(Frame {
+                extension: neighbours.iter().copied().filter(|id| *id > root).collect(),
+                reached,
+            });
+        }
+
+        true
+    }
+}
+
+impl Iterator for ConnectedSubgraphs {
+    type Item = Vec<GraphId>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            self.capped = self.capped || self.advance();
+            self.roots.clear();
+            self.stack.clear();
+            return None;
+        }
+        if !self.advance() {
+            return None;
+        }
+
+        self.remaining -= 1;
+        let mut subgraph = self.subgraph.clone();
+        subgraph.sort_unstable();
+
+        Some(subgraph)
+    }
+}
+
+impl FusedIterator for ConnectedSubgraphs {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A triangle 1, 2, 3 with a tail 3 -> 4, and an isolated node 5.
+    fn get_test_graph() -> Graph<()> {
+        Graph::from((
+            [(1, ()), (2, ()), (3, ()), (4, ()), (5, ())],
+            [(1, 2), (2, 3), (3, 1), (4, 3), (4, 4)],
+        ))
+    }
+
+    #[test]
+    fn connected_subgraphs_enumerates_once() {
+        let mut subgraphs = get_test_graph()
+            .connected_subgraphs(3, usize::MAX)
+            .collect::<Vec<_>>();
+        subgraphs.sort();
+        assert_eq!(
+            subgraphs,
+            vec![
+                vec![1],
+                vec![1, 2],
+                vec![1, 2, 3],
+                vec![1, 3],
+                vec![1, 3, 4],
+                vec![2],
+                vec![2, 3],
+                vec![2, 3, 4],
+                vec![3],
+                vec![3, 4],
+                vec![4],
+                vec![5],
+            ],
+            "Subgraphs mismatch"
+        );
+        assert_eq!(
+            get_test_graph()
+                .connected_subgraphs(4, usize::MAX)
+                .filter(|subgraph| subgraph.len() == 4)
+                .count(),
+            1,
+            "Expected the whole component once"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn connected_subgraphs_match_motif_census() {
+        let grid = crate::generators::grid(4, 4, |_| ());
+        for size in [3, 4] {
+            assert_eq!(
+                grid.connected_subgraphs(size, usize::MAX)
+                    .filter(|subgraph| subgraph.len() == size)
+                    .count(),
+                grid.motif_census(size, false).iter().sum::<usize>(),
+                "Expected as many sets as the motif census counts"
+            );
+        }
+    }
+
+    #[test]
+    fn connected_subgraphs_stops_at_limit() {
+        let graph = get_test_graph();
+        let mut capped = graph.connected_subgraphs(2, 3);
+        assert_eq!(
+            capped.by_ref().collect::<Vec<_>>(),
+            vec![vec![1], vec![1, 3], vec![1, 2]],
+            "Expected lazy order"
+        );
+        assert!(capped.is_capped(), "Expected sets left out");
+
+        let mut exact = graph.connected_subgraphs(1, 5);
+        assert_eq!(exact.by_ref().count(), 5, "Expected single nodes");
+        assert!(!exact.is_capped(), "Expected nothing left out");
+        assert_eq!(
+            graph.connected_subgraphs(0, 10).count(),
+            0,
+            "Expected no empty set"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use alloc::{vec, vec::Vec};
+
+use super::{
+    canonical::{count, ranks},
+    collections::HashMap,
+    Graph, GraphId, WeightedGraph,
+};
+
+impl<T> Graph<T> {
+    /// Returns a sketch of the graph with at most `max_nodes` nodes, e.g. to draw an overview
+    /// of a graph too large to show whole. A budget of 0 is treated as 1.
+    ///
+    /// Nodes are grouped by their in- and out-degree rounded down to a power of two. As long
+    /// as the sketch stays within budget, groups are then split by the groups their
+    /// neighbours are in, looking one hop further every round. If even the degree groups are
+    /// too many, nodes are grouped by total degree, and the smallest groups are merged into
+    /// one if needed.
+    ///
+    /// Every node of the sketch holds the sorted IDs of its members and has the ID of the
+    /// smallest one. Edges are weighted by the number of edges they stand for, as in
+    /// [`quotient`](Self::quotient).
+    pub fn summarize(&self, max_nodes: usize) -> WeightedGraph<Vec<GraphId>> {
+        debug_span!("summarize", nodes = self.nodes.len(), max_nodes);
+        let max_nodes = max_nodes.max(1);
+        let ids = self
+            .sorted_nodes()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+        let index = ids
+            .iter()
+            .enumerate()
+            .map(|(index, id)| (*id, index))
+            .collect::<HashMap<_, _>>();
+        let mut successors = vec![Vec::new(); ids.len()];
+        let mut predecessors = vec![Vec::new(); ids.len()];
+        for edge in &self.edges {
+            successors[index[&edge.from]].push(index[&edge.to]);
+            predecessors[index[&edge.to]].push(index[&edge.from]);
+        }
+
+        let mut colours = ranks((0..ids.len()).map(|node| {
+            (
+                bucket(predecessors[node].len()),
+                bucket(successors[node].len()),
+            )
+        }));
+        if count(&colours) > max_nodes {
+            colours = ranks(
+                (0..ids.len())
+                    .map(|node| bucket(predecessors[node].len() + successors[node].len())),
+            );
+            colours = merge_smallest(&colours, max_nodes);
+        }
+        loop {
+            let signatures = (0..ids.len()).map(|node| {
+                let neighbours = |others: &[usize]| {
+                    let mut groups = others
+                        .iter()
+                        .map(|other| colours[*other])
+                        .collect::<Vec<_>>();
+                    groups.sort_unstable();
+                    groups.dedup();
+                    groups
+                };
+
+                (
+                    colours[node],
+                    neighbours(&successors[node]),
+                    neighbours(&predecessors[node]),
+                )
+            });
+            let refined = ranks(signatures);
+            let groups = count(&refined);
+            if groups > max_nodes || groups == count(&colours) {
+                break;
+            }
+            colours = refined;
+        }
+
+        // Nodes are in ID order, so the first node of every colour is its smallest member.
+        let mut group_ids = vec![None; count(&colours)];
+        for (node, colour) in colours.iter().enumerate() {
+            group_ids[*colour].get_or_insert(ids[node]);
+        }
+        let sketch =
+            self.quotient(|id| group_ids[colours[index[&id]]].expect("every colour has a member"));
+        debug_event!(groups = sketch.nodes.len(), "summarized");
+
+        sketch
+    }
+}
+
+/// Rounds a degree down to a power of two, as the exponent plus one, or 0 for no edges.
+fn bucket(degree: usize) -> u32 {
+    degree.checked_ilog2().map_or(0, |log| log + 1)
+}
+
+/// Merges the smallest colour classes into one until at most `max` remain, keeping the
+/// lowest colour for ties in size.
+fn merge_smallest(colours: &[usize], max: usize) -> Vec<usize> {
+    let mut sizes = vec![0; count(colours)];
+    for colour in colours {
+        sizes[*colour] += 1;
+    }
+    if sizes.len() <= max {
+        return colours.to_vec();
+    }
+
+    let mut by_size = (0..sizes.len()).collect::<Vec<_>>();
+    by_size.sort_by_key(|colour| (core::cmp::Reverse(sizes[*colour]), *colour));
+    let mut kept = vec![max - 1; sizes.len()];
+    for (rank, colour) in by_size.iter().take(max - 1).enumerate() {
+        kept[*colour] = rank;
+    }
+
+    colours.iter().map(|colour| kept[*colour]).collect()
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::generators;
+
+    #[test]
+    fn summarize_groups_similar_nodes() {
+        let graph = generators::star(7, |_| ());
+        let sketch = graph.summarize(10);
+        assert_eq!(sketch.nodes.len(), 2, "Expected hub and leaves");
+        assert_eq!(sketch[0], vec![0], "Hub mismatch");
+        assert_eq!(sketch[1], vec![1, 2, 3, 4, 5, 6], "Leaves mismatch");
+        assert_eq!(
+            sketch
+                .weighted_edges()
+                .iter()
+                .map(|(.., weight)| weight)
+                .sum::<f64>(),
+            graph.edges.len() as f64,
+            "Expected every edge counted"
+        );
+    }
+
+    #[test]
+    fn summarize_refines_within_budget() {
+        // Degrees alone can't tell the ends of the path from its middle.
+        let graph = generators::path(6, |_| ());
+        assert_eq!(
+            graph.summarize(2).nodes.len(),
+            2,
+            "Expected ends and middle"
+        );
+        let sketch = graph.summarize(6);
+        assert_eq!(sketch[0], vec![0, 5], "Expected both ends together");
+        assert_eq!(
+            sketch[1],
+            vec![1, 4],
+            "Expected nodes next to the ends together"
+        );
+        assert_eq!(sketch[2], vec![2, 3], "Expected centre together");
+        assert!(
+            graph.summarize(1).nodes.len() == 1 && graph.summarize(0).nodes.len() == 1,
+            "Expected a single group"
+        );
+    }
+
+    #[test]
+    fn summarize_respects_budget() {
+        let graph = generators::balanced_tree(2, 6, |_| ());
+        for max_nodes in 1..20 {
+            assert!(
+                graph.summarize(max_nodes).nodes.len() <= max_nodes,
+                "Expected at most {max_nodes} nodes"
+            );
+        }
+        assert!(
+            Graph::<()>::new().summarize(4).nodes.is_empty(),
+            "Expected empty sketch"
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use std::{collections::HashSet, fmt::Write};
+
+use super::{
+    layout::{self, ForceDirectedOptions, LayeredOptions, PlanarOptions},
+    Graph, GraphId, NodeAttributes, NodeLabel,
+};
+
+const HIGHLIGHT: &str = "red";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SvgLayout {
+    ForceDirected(ForceDirectedOptions),
+    Layered(LayeredOptions),
+    /// Falls back to the default force-directed layout if the graph isn't planar.
+    Planar(PlanarOptions),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SvgOptions {
+    pub layout: SvgLayout,
+    pub node_radius: f32,
+    pub margin: f32,
+    pub font_size: f32,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            layout: SvgLayout::Layered(LayeredOptions::default()),
+            node_radius: 24.0,
+            margin: 40.0,
+            font_size: 12.0,
+        }
+    }
+}
+
+impl<T> Graph<T> {
+    /// Draws the graph as a standalone SVG document with arrowed edges and nodes styled as
+    /// their values describe through [`NodeLabel`]. Tooltips become SVG titles.
+    pub fn to_svg(&self, options: &SvgOptions) -> String
+    where
+        T: NodeLabel,
+    {
+        self.to_svg_with_path(options, &[])
+    }
+
+    /// Like [`to_svg`](Self::to_svg), but draws the nodes of `path` and the edges between
+    /// consecutive ones in red, e.g. to show a [`shortest_path`](Self::shortest_path).
+    pub fn to_svg_with_path(&self, options: &SvgOptions, path: &[GraphId]) -> String
+    where
+        T: NodeLabel,
+    {
+        self.to_svg_by(options, path, |_, value| NodeAttributes::of(value))
+    }
+
+    /// Like [`to_svg_with_path`](Self::to_svg_with_path), styling every node as returned by
+    /// `attributes` for its ID and value instead of through [`NodeLabel`].
+    pub fn to_svg_by(
+        &self,
+        options: &SvgOptions,
+        path: &[GraphId],
+        mut attributes: impl FnMut(GraphId, &T) -> NodeAttributes,
+    ) -> String {
+        let path_nodes = path.iter().copied().collect::<HashSet<_>>();
+        let path_edges = path
+            .windows(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect::<HashSet<_>>();
+
+        let positions = match options.layout {
+            SvgLayout::ForceDirected(layout_options) => {
+                layout::fruchterman_reingold(self, layout_options)
+            }
+            SvgLayout::Layered(layout_options) => layout::layered(self, layout_options),
+            SvgLayout::Planar(layout_options) => layout::planar(self, layout_options)
+                .unwrap_or_else(|| {
+                    layout::fruchterman_reingold(self, ForceDirectedOptions::default())
+                }),
+        };
+
+        let (min_x, min_y, max_x, max_y) = positions.values().fold(
+            (0.0f32, 0.0f32, 0.0f32, 0.0f32),
+            |(min_x, min_y, max_x, max_y), (x, y)| {
+                (min_x.min(*x), min_y.min(*y), max_x.max(*x), max_y.max(*y))
+            },
+        );
+        let padding = options.margin + options.node_radius;
+        let point = |id| {
+            let (x, y) = positions[&id];
+            (x - min_x + padding, y - min_y + padding)
+        };
+        let width = max_x - min_x + 2.0 * padding;
+        let height = max_y - min_y + 2.0 * padding;
+        let radius = options.node_radius;
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width:.1}" height="{height:.1}" viewBox="0 0 {width:.1} {height:.1}">"#
+        );
+        svg.push_str(concat!(
+            "<defs>\n",
+            r#"<marker id="arrow" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="8" markerHeight="8" orient="auto-start-reverse">"#,
+            "\n",
+            r#"<path d="M 0 0 L 10 5 L 0 10 z" fill="black"/>"#,
+            "\n</marker>\n</defs>\n",
+        ));
+        if !path_edges.is_empty() {
+            let _ = writeln!(
+                svg,
+                r#"<defs><marker id="arrow-highlight" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="8" markerHeight="8" orient="auto-start-reverse"><path d="M 0 0 L 10 5 L 0 10 z" fill="{HIGHLIGHT}"/></marker></defs>"#
+            );
+        }
+
+        for (from, to) in self.sorted_edges() {
+            let (stroke, marker) = if path_edges.contains(&(from, to)) {
+                (HIGHLIGHT, "arrow-highlight")
+            } else {
+                ("black", "arrow")
+            };
+            let (x1, y1) = point(from);
+            if from == to {
+                let _ = writeln!(
+                    svg,
+                    r#"<path d="M {:.1} {:.1} C {:.1} {:.1}, {:.1} {:.1}, {:.1} {:.1}" fill="none" stroke="{stroke}" marker-end="url(#{marker})"/>"#,
+                    x1 - radius * 0.5,
+                    y1 - radius * 0.87,
+                    x1 - radius * 1.5,
+                    y1 - radius * 3.0,
+                    x1 + radius * 1.5,
+                    y1 - radius * 3.0,
+                    x1 + radius * 0.5,
+                    y1 - radius * 0.87,
+                );
+                continue;
+            }
+
+            let (x2, y2) = point(to);
+            let (dx, dy) = (x2 - x1, y2 - y1);
+            let length = (dx * dx + dy * dy).sqrt().max(f32::EPSILON);
+            let (ux, uy) = (dx / length, dy / length);
+            let _ = writeln!(
+                svg,
+                r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="{stroke}" marker-end="url(#{marker})"/>"#,
+                x1 + ux * radius,
+                y1 + uy * radius,
+                x2 - ux * radius,
+                y2 - uy * radius,
+            );
+        }
+
+        for (id, value) in self.sorted_nodes() {
+            let (x, y) = point(id);
+            let stroke = if path_nodes.contains(&id) {
+                HIGHLIGHT
+            } else {
+                "black"
+            };
+            let NodeAttributes {
+                label,
+                tooltip,
+                color,
+            } = attributes(id, value);
+            let title = tooltip
+                .map(|tooltip| format!("<title>{}</title>", escape(&tooltip)))
+                .unwrap_or_default();
+            let fill = color.as_deref().map_or_else(|| "white".into(), escape);
+            let _ = writeln!(
+                svg,
+                r#"<g id="node-{id}">{title}<circle cx="{x:.1}" cy="{y:.1}" r="{radius:.1}" fill="{fill}" stroke="{stroke}"/><text x="{x:.1}" y="{y:.1}" font-size="{:.1}" text-anchor="middle" dominant-baseline="central">{}</text></g>"#,
+                options.font_size,
+                escape(&label),
+            );
+        }
+        svg.push_str("</svg>\n");
+
+        svg
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            '\'' => "&apos;".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_svg_contains_nodes_and_edges() {
+        let graph: Graph<&str> = Graph::from(([(1, "a"), (2, "b")], [(1, 2)]));
+        let svg = graph.to_svg(&SvgOptions::default());
+        assert!(svg.starts_with("<svg"), "Expected an SVG document");
+        assert!(
+            svg.trim_end().ends_with("</svg>"),
+            "Expected a closed SVG document"
+        );
+        assert_eq!(svg.matches("<circle").count(), 2, "Nodes count mismatch");
+        assert_eq!(svg.matches("<line").count(), 1, "Edges count mismatch");
+        assert!(svg.contains(">a</text>"), "Expected node label");
+    }
+
+    #[test]
+    fn to_svg_escapes_labels() {
+        let graph: Graph<&str> = Graph::from(([(1, "<a & b>")], []));
+        let svg = graph.to_svg(&SvgOptions::default());
+        assert!(
+            svg.contains(">&lt;a &amp; b&gt;</text>"),
+            "Expected escaped label"
+        );
+    }
+
+    #[test]
+    fn to_svg_by_adds_tooltip_and_color() {
+        let graph: Graph<(u32, &str)> = Graph::from(([(1, (3, "a")), (2, (0, "b"))], [(1, 2)]));
+        let svg = graph.to_svg_by(&SvgOptions::default(), &[], |_, (load, name)| {
+            NodeAttributes {
+                label: name.to_string(),
+                tooltip: Some(format!("load {load}")),
+                color: (*load > 0).then(|| "#ffcc00".into()),
+            }
+        });
+        assert!(
+            svg.contains(r#"<g id="node-1"><title>load 3</title><circle"#),
+            "Expected tooltip"
+        );
+        assert!(svg.contains(r##"fill="#ffcc00""##), "Expected node color");
+        assert_eq!(
+            svg.matches(r#"fill="white""#).count(),
+            1,
+            "Expected default fill"
+        );
+    }
+
+    #[test]
+    fn to_svg_self_loop() {
+        let graph: Graph<&str> = Graph::from(([(1, "a")], [(1, 1)]));
+        let svg = graph.to_svg(&SvgOptions {
+            layout: SvgLayout::ForceDirected(ForceDirectedOptions::default()),
+            ..SvgOptions::default()
+        });
+        assert_eq!(
+            svg.matches("<path d=\"M 0").count(),
+            1,
+            "Expected only the marker path"
+        );
+        assert_eq!(svg.matches("<path").count(), 2, "Expected a loop path");
+    }
+
+    #[test]
+    fn to_svg_with_path_highlights() {
+        let graph: Graph<&str> = Graph::from(([(1, "a"), (2, "b"), (3, "c")], [(1, 2), (2, 3)]));
+        let svg = graph.to_svg_with_path(&SvgOptions::default(), &[1, 2]);
+        assert_eq!(
+            svg.matches(r#"stroke="red" marker-end="url(#arrow-highlight)""#)
+                .count(),
+            1,
+            "Expected one highlighted edge"
+        );
+        assert_eq!(
+            svg.matches(r#"fill="white" stroke="red""#).count(),
+            2,
+            "Expected two highlighted nodes"
+        );
+        assert!(
+            !graph
+                .to_svg(&SvgOptions::default())
+                .contains("arrow-highlight"),
+            "Expected no highlight marker"
+        );
+    }
+
+    #[test]
+    fn to_svg_empty() {
+        let graph: Graph<&str> = Graph::new();
+        let svg = graph.to_svg(&SvgOptions::default());
+        assert!(!svg.contains("<circle"), "Expected no nodes");
+    }
+}
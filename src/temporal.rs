@@ -0,0 +1,562 @@
+use alloc::{
+    collections::BinaryHeap,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{
+    cmp::Reverse,
+    fmt::{self, Display},
+    ops::Deref,
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Context};
+use serde_json::{json, Value};
+
+use super::{
+    collections::HashMap,
+    json::{array, id_field, parse_nodes},
+    serde::parse_sections,
+    Edge, Graph, GraphId,
+};
+
+pub type Timestamp = i64;
+
+/// A half-open validity interval `[start, end)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
+impl Interval {
+    pub fn new(start: Timestamp, end: Timestamp) -> Self {
+        Self { start, end }
+    }
+
+    /// An interval covering only the moment `t`.
+    pub fn instant(t: Timestamp) -> Self {
+        Self::new(t, t.saturating_add(1))
+    }
+
+    /// An interval starting at `t` that never ends.
+    pub fn since(t: Timestamp) -> Self {
+        Self::new(t, Timestamp::MAX)
+    }
+
+    pub fn contains(&self, t: Timestamp) -> bool {
+        self.start <= t && t < self.end
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+}
+
+/// A graph whose edges exist only during their validity intervals.
+///
+/// An edge can be valid during several intervals, e.g. one per recorded interaction. Read
+/// access through `Deref` sees every edge that is valid at any time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemporalGraph<T> {
+    graph: Graph<T>,
+    intervals: HashMap<Edge, Vec<Interval>>,
+}
+
+impl<T> TemporalGraph<T> {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph::new(),
+            intervals: HashMap::new(),
+        }
+    }
+
+    pub fn add_node(&mut self, id: GraphId, value: T) {
+        self.graph.add_node(id, value);
+    }
+
+    pub fn delete_node(&mut self, id: GraphId) {
+        self.graph.delete_node(id);
+        self.intervals
+            .retain(|edge, _| edge.from != id && edge.to != id);
+    }
+
+    /// Adds an edge valid during `interval`. Does nothing if an endpoint doesn't exist.
+    pub fn add_edge(&mut self, from: GraphId, to: GraphId, interval: Interval) {
+        if !self.graph.nodes.contains_key(&from) || !self.graph.nodes.contains_key(&to) {
+            return;
+        }
+
+        self.graph.add_edge(from, to);
+        let intervals = self.intervals.entry(Edge { from, to }).or_default();
+        if !intervals.contains(&interval) {
+            intervals.push(interval);
+            intervals.sort_unstable();
+        }
+    }
+
+    pub fn add_edge_at(&mut self, from: GraphId, to: GraphId, t: Timestamp) {
+        self.add_edge(from, to, Interval::instant(t));
+    }
+
+    /// Removes the edge together with all its intervals.
+    pub fn delete_edge(&mut self, from: GraphId, to: GraphId) {
+        self.graph.delete_edge(from, to);
+        self.intervals.remove(&Edge { from, to });
+    }
+
+    pub fn intervals(&self, from: GraphId, to: GraphId) -> &[Interval] {
+        self.intervals
+            .get(&Edge { from, to })
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Adds an edge that expires `ttl` time units after `now`, for use with
+    /// [`TemporalGraph::evict_expired`].
+    pub fn insert_edge_with_ttl(
+        &mut self,
+        from: GraphId,
+        to: GraphId,
+        now: Timestamp,
+        ttl: Timestamp,
+    ) {
+        self.add_edge(from, to, Interval::new(now, now.saturating_add(ttl)));
+    }
+
+    /// Drops every interval that ended at or before `now`, and every edge left without
+    /// intervals. Returns the removed edges.
+    pub fn evict_expired(&mut self, now: Timestamp) -> Vec<(GraphId, GraphId)> {
+        let mut evicted = vec![];
+        self.intervals.retain(|edge, intervals| {
+            intervals.retain(|interval| interval.end > now);
+            if intervals.is_empty() {
+                evicted.push((edge.from, edge.to));
+            }
+            !intervals.is_empty()
+        });
+        for (from, to) in &evicted {
+            self.graph.delete_edge(*from, *to);
+        }
+        evicted.sort_unstable();
+
+        evicted
+    }
+
+    /// Returns a view with all nodes and the edges valid at `t`.
+    pub fn at(&self, t: Timestamp) -> Graph<&T> {
+        self.filtered(|interval| interval.contains(t))
+    }
+
+    /// Returns a view with all nodes and the edges valid at any moment of `window`.
+    pub fn during(&self, window: Interval) -> Graph<&T> {
+        self.filtered(|interval| interval.overlaps(&window))
+    }
+
+    /// Computes the earliest time every node can be reached from `source` when leaving it at
+    /// `start`. Edges are traversed instantly and only while valid, so arrival times never
+    /// decrease along a path.
+    pub fn earliest_arrival(
+        &self,
+        source: GraphId,
+        start: Timestamp,
+    ) -> HashMap<GraphId, Timestamp> {
+        self.time_respecting_search(source, start)
+            .into_iter()
+            .map(|(id, (arrival, _))| (id, arrival))
+            .collect()
+    }
+
+    /// Returns the time-respecting path from `from` to `to` that arrives the earliest, as
+    /// `(node, arrival time)` pairs starting with `(from, start)`.
+    pub fn temporal_path(
+        &self,
+        from: GraphId,
+        to: GraphId,
+        start: Timestamp,
+    ) -> Option<Vec<(GraphId, Timestamp)>> {
+        let search = self.time_respecting_search(from, start);
+        let mut current = to;
+        let mut path = vec![];
+        loop {
+            let (arrival, parent) = search.get(&current)?;
+            path.push((current, *arrival));
+            match parent {
+                Some(parent) => current = *parent,
+                None => break,
+            }
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    fn filtered(&self, keep: impl Fn(&Interval) -> bool) -> Graph<&T> {
+        let mut graph = Graph::new();
+        for (id, value) in &self.graph.nodes {
+            graph.add_node(*id, value);
+        }
+        for (edge, intervals) in &self.intervals {
+            if intervals.iter().any(&keep) {
+                graph.add_edge(edge.from, edge.to);
+            }
+        }
+
+        graph
+    }
+
+    fn time_respecting_search(
+        &self,
+        source: GraphId,
+        start: Timestamp,
+    ) -> HashMap<GraphId, (Timestamp, Option<GraphId>)> {
+        let mut outgoing: HashMap<GraphId, Vec<(GraphId, &[Interval])>> = HashMap::new();
+        for (edge, intervals) in &self.intervals {
+            outgoing
+                .entry(edge.from)
+                .or_default()
+                .push((edge.to, intervals));
+        }
+
+        let mut best: HashMap<GraphId, (Timestamp, Option<GraphId>)> = HashMap::new();
+        if !self.graph.nodes.contains_key(&source) {
+            return best;
+        }
+        best.insert(source, (start, None));
+        let mut queue = BinaryHeap::from([Reverse((start, source))]);
+        while let Some(Reverse((arrival, id))) = queue.pop() {
+            if best[&id].0 < arrival {
+                continue;
+            }
+
+            for (to, intervals) in outgoing.get(&id).into_iter().flatten() {
+                let departure = intervals
+                    .iter()
+                    .filter(|interval| interval.end > arrival)
+                    .map(|interval| interval.start.max(arrival))
+                    .min();
+                let Some(departure) = departure else {
+                    continue;
+                };
+                if best.get(to).is_none_or(|(known, _)| departure < *known) {
+                    best.insert(*to, (departure, Some(id)));
+                    queue.push(Reverse((departure, *to)));
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Serializes to TGF where every edge line carries its interval as a label:
+    /// `from to start end`, `from to start` for instants, and `from to start -` for open
+    /// intervals. Edges valid during several intervals are written once per interval.
+    pub fn serialize(&self) -> String
+    where
+        T: Display,
+    {
+        let edges = self.sorted_intervals();
+        let mut nodes = self.graph.nodes.iter().collect::<Vec<_>>();
+        nodes.sort_unstable_by_key(|(id, _)| **id);
+        nodes
+            .into_iter()
+            .map(|(id, value)| format!("{id} {value}\n"))
+            .chain(["#\n".to_string()])
+            .chain(edges.into_iter().map(|(from, to, interval)| {
+                format!("{from} {}\n", TemporalTarget { to, interval })
+            }))
+            .collect()
+    }
+
+    pub fn to_json(&self) -> String
+    where
+        T: Display,
+    {
+        json!({
+            "nodes": self.graph.nodes_to_json(),
+            "edges": self
+                .sorted_intervals()
+                .into_iter()
+                .map(|(from, to, interval)| {
+                    let end = (interval.end != Timestamp::MAX).then_some(interval.end);
+                    json!({ "from": from, "to": to, "start": interval.start, "end": end })
+                })
+                .collect::<Vec<_>>(),
+        })
+        .to_string()
+    }
+
+    /// Parses the format written by [`TemporalGraph::to_json`], where a `null` end marks an
+    /// open interval.
+    pub fn from_json(s: &str) -> anyhow::Result<Self>
+    where
+        T: FromStr,
+    {
+        let document: Value = serde_json::from_str(s).context("Invalid JSON")?;
+        let mut graph = TemporalGraph::new();
+        for (id, value) in parse_nodes(&document)? {
+            graph.add_node(id, value);
+        }
+        for edge in array(&document, "edges")? {
+            let start = edge
+                .get("start")
+                .and_then(Value::as_i64)
+                .ok_or_else(|| anyhow!("Expected \"start\" to be a timestamp in {edge}"))?;
+            let end = match edge.get("end") {
+                None | Some(Value::Null) => Timestamp::MAX,
+                Some(end) => end
+                    .as_i64()
+                    .ok_or_else(|| anyhow!("Expected \"end\" to be a timestamp in {edge}"))?,
+            };
+            graph.add_edge(
+                id_field(edge, "from")?,
+                id_field(edge, "to")?,
+                Interval::new(start, end),
+            );
+        }
+
+        Ok(graph)
+    }
+
+    fn sorted_intervals(&self) -> Vec<(GraphId, GraphId, Interval)> {
+        let mut edges = self
+            .intervals
+            .iter()
+            .flat_map(|(edge, intervals)| {
+                intervals
+                    .iter()
+                    .map(|interval| (edge.from, edge.to, *interval))
+            })
+            .collect::<Vec<_>>();
+        edges.sort_unstable();
+
+        edges
+    }
+}
+
+impl<T> Default for TemporalGraph<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Deref for TemporalGraph<T> {
+    type Target = Graph<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.graph
+    }
+}
+
+impl<T: FromStr> FromStr for TemporalGraph<T> {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (nodes, edges) = parse_sections::<T, TemporalTarget>(s)?;
+        let mut graph = TemporalGraph::new();
+        for (id, value) in nodes {
+            graph.add_node(id, value);
+        }
+        for (from, target) in edges {
+            graph.add_edge(from, target.to, target.interval);
+        }
+
+        Ok(graph)
+    }
+}
+
+/// The part of a temporal TGF edge line after the source ID.
+struct TemporalTarget {
+    to: GraphId,
+    interval: Interval,
+}
+
+impl Display for TemporalTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Interval { start, end } = self.interval;
+        if end == Timestamp::MAX {
+            write!(f, "{} {start} -", self.to)
+        } else if Interval::instant(start) == self.interval {
+            write!(f, "{} {start}", self.to)
+        } else {
+            write!(f, "{} {start} {end}", self.to)
+        }
+    }
+}
+
+impl FromStr for TemporalTarget {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let to = parts
+            .next()
+            .ok_or_else(|| anyhow!("Missing target"))?
+            .parse()?;
+        let start: Timestamp = parts
+            .next()
+            .ok_or_else(|| anyhow!("Missing timestamp"))?
+            .parse()?;
+        let interval = match parts.next() {
+            None => Interval::instant(start),
+            Some("-") => Interval::since(start),
+            Some(end) => Interval::new(start, end.parse()?),
+        };
+        if parts.next().is_some() {
+            return Err(anyhow!("Unexpected trailing data in {s:?}"));
+        }
+
+        Ok(Self { to, interval })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_graph() -> TemporalGraph<String> {
+        let mut graph = TemporalGraph::new();
+        for id in 1..=4 {
+            graph.add_node(id, format!("n{id}"));
+        }
+        graph.add_edge_at(1, 2, 1);
+        graph.add_edge_at(2, 3, 5);
+        graph.add_edge_at(2, 3, 2);
+        graph.add_edge(3, 4, Interval::new(3, 6));
+        graph.add_edge(1, 4, Interval::since(10));
+
+        graph
+    }
+
+    #[test]
+    fn at_filters_edges() {
+        let graph = get_test_graph();
+        assert_eq!(
+            graph.at(2).edges().collect::<Vec<_>>(),
+            vec![(2, 3)],
+            "Edges at 2 mismatch"
+        );
+        assert_eq!(graph.at(4).edges().count(), 1, "Edges count at 4 mismatch");
+        assert_eq!(
+            graph.at(100).edges().count(),
+            1,
+            "Edges count at 100 mismatch"
+        );
+        assert_eq!(graph.at(100).nodes.len(), 4, "Expected all nodes");
+    }
+
+    #[test]
+    fn during_window() {
+        let graph = get_test_graph();
+        assert_eq!(
+            graph.during(Interval::new(1, 3)).edges().count(),
+            2,
+            "Edges count mismatch"
+        );
+    }
+
+    #[test]
+    fn earliest_arrival_respects_time() {
+        let graph = get_test_graph();
+        assert_eq!(
+            graph.earliest_arrival(1, 0),
+            HashMap::from([(1, 0), (2, 1), (3, 2), (4, 3)]),
+            "Arrival times mismatch"
+        );
+        assert_eq!(
+            graph.earliest_arrival(1, 2),
+            HashMap::from([(1, 2), (4, 10)]),
+            "Expected missed edges to be unusable"
+        );
+    }
+
+    #[test]
+    fn temporal_path_earliest() {
+        let graph = get_test_graph();
+        assert_eq!(
+            graph.temporal_path(1, 4, 0),
+            Some(vec![(1, 0), (2, 1), (3, 2), (4, 3)]),
+            "Path mismatch"
+        );
+        assert_eq!(graph.temporal_path(4, 1, 0), None, "Expected no path");
+    }
+
+    #[test]
+    fn delete_node_drops_intervals() {
+        let mut graph = get_test_graph();
+        graph.delete_node(2);
+        assert!(
+            graph.intervals(2, 3).is_empty(),
+            "Expected intervals to be removed"
+        );
+        assert_eq!(graph.intervals.len(), 2, "Intervals count mismatch");
+    }
+
+    #[test]
+    fn evict_expired_ttl_edges() {
+        let mut graph = get_test_graph();
+        graph.insert_edge_with_ttl(4, 1, 5, 10);
+        graph.insert_edge_with_ttl(2, 3, 5, 10);
+        assert_eq!(
+            graph.evict_expired(6),
+            vec![(1, 2), (3, 4)],
+            "Evicted edges mismatch"
+        );
+        assert_eq!(
+            graph.intervals(2, 3),
+            &[Interval::new(5, 15)],
+            "Expected only live intervals"
+        );
+
+        assert_eq!(
+            graph.evict_expired(15),
+            vec![(2, 3), (4, 1)],
+            "Evicted edges mismatch"
+        );
+        assert_eq!(
+            graph.edges().collect::<Vec<_>>(),
+            vec![(1, 4)],
+            "Expected only the open edge to remain"
+        );
+    }
+
+    #[test]
+    fn tgf_round_trip() {
+        let graph = get_test_graph();
+        let serialized = graph.serialize();
+        assert!(
+            serialized.contains("\n1 4 10 -\n"),
+            "Expected open interval"
+        );
+        assert!(
+            serialized.contains("\n3 4 3 6\n"),
+            "Expected closed interval"
+        );
+        assert_eq!(
+            serialized.parse::<TemporalGraph<String>>().ok(),
+            Some(graph),
+            "Expected graph to survive a round trip"
+        );
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let graph = get_test_graph();
+        assert_eq!(
+            TemporalGraph::from_json(&graph.to_json()).ok(),
+            Some(graph),
+            "Expected graph to survive a round trip"
+        );
+    }
+
+    #[test]
+    fn parse_invalid_interval() {
+        assert!(
+            "1 a\n2 b\n#\n1 2 x"
+                .parse::<TemporalGraph<String>>()
+                .is_err(),
+            "Expected invalid timestamp"
+        );
+    }
+}
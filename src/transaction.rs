@@ -0,0 +1,137 @@
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+use super::{Graph, GraphId, Mutation};
+
+/// Staged edits on a graph, created by [`Graph::transaction`].
+///
+/// Every edit is applied immediately so later steps can read their effect, and reverted in
+/// reverse order unless the transaction commits.
+#[derive(Debug)]
+pub struct Transaction<'a, T> {
+    graph: &'a mut Graph<T>,
+    undo: Vec<Vec<Mutation<T>>>,
+    committed: bool,
+}
+
+impl<T> Graph<T> {
+    /// Runs `f` on a transaction and keeps its edits only if it returns `Ok`.
+    ///
+    /// On `Err`, or if `f` panics, the graph is restored to the state it had before the call.
+    pub fn transaction<R, E>(
+        &mut self,
+        f: impl FnOnce(&mut Transaction<'_, T>) -> Result<R, E>,
+    ) -> Result<R, E> {
+        let mut transaction = Transaction {
+            graph: self,
+            undo: Vec::new(),
+            committed: false,
+        };
+        let result = f(&mut transaction);
+        transaction.committed = result.is_ok();
+
+        result
+    }
+}
+
+impl<T> Transaction<'_, T> {
+    pub fn apply(&mut self, mutation: Mutation<T>) {
+        let inverse = self.graph.apply(mutation);
+        if !inverse.is_empty() {
+            self.undo.push(inverse);
+        }
+    }
+
+    pub fn add_node(&mut self, id: GraphId, value: T) {
+        self.apply(Mutation::AddNode { id, value });
+    }
+
+    pub fn delete_node(&mut self, id: GraphId) {
+        self.apply(Mutation::RemoveNode { id });
+    }
+
+    pub fn add_edge(&mut self, from: GraphId, to: GraphId) {
+        self.apply(Mutation::AddEdge { from, to });
+    }
+
+    pub fn delete_edge(&mut self, from: GraphId, to: GraphId) {
+        self.apply(Mutation::RemoveEdge { from, to });
+    }
+}
+
+impl<T> Deref for Transaction<'_, T> {
+    type Target = Graph<T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.graph
+    }
+}
+
+impl<T> Drop for Transaction<'_, T> {
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+
+        while let Some(inverse) = self.undo.pop() {
+            self.graph.apply_all(inverse);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::{self, AssertUnwindSafe};
+
+    use super::*;
+
+    fn get_test_graph() -> Graph<&'static str> {
+        Graph::from(([(1, "a"), (2, "b"), (3, "c")], [(1, 2), (2, 3)]))
+    }
+
+    #[test]
+    fn transaction_commit() {
+        let mut graph = get_test_graph();
+        let result: Result<usize, ()> = graph.transaction(|txn| {
+            txn.delete_node(2);
+            txn.add_node(4, "d");
+            txn.add_edge(1, 4);
+            Ok(txn.nodes.len())
+        });
+        assert_eq!(result, Ok(3), "Expected closure result");
+        assert_eq!(
+            graph,
+            Graph::from(([(1, "a"), (3, "c"), (4, "d")], [(1, 4)])),
+            "Expected edits to be committed"
+        );
+    }
+
+    #[test]
+    fn transaction_rollback_on_error() {
+        let mut graph = get_test_graph();
+        let result = graph.transaction(|txn| {
+            txn.delete_node(2);
+            txn.add_node(4, "d");
+            txn.add_edge(4, 1);
+            if txn.get_node(2).is_none() {
+                return Err("node 2 is gone");
+            }
+            Ok(())
+        });
+        assert_eq!(result, Err("node 2 is gone"), "Expected closure error");
+        assert_eq!(graph, get_test_graph(), "Expected edits to be rolled back");
+    }
+
+    #[test]
+    fn transaction_rollback_on_panic() {
+        let mut graph = get_test_graph();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let _: Result<(), ()> = graph.transaction(|txn| {
+                txn.delete_edge(1, 2);
+                panic!("failed midway");
+            });
+        }));
+        assert!(result.is_err(), "Expected panic to propagate");
+        assert_eq!(graph, get_test_graph(), "Expected edits to be rolled back");
+    }
+}
@@ -0,0 +1,184 @@
+use alloc::{vec, vec::Vec};
+use core::future::Future;
+
+use futures_util::{stream, StreamExt, TryStreamExt};
+
+use super::{collections::HashSet, Graph, GraphId};
+
+/// Breadth-first traversal of a graph that isn't held in memory, such as one behind a
+/// database or an HTTP API. Neighbours are fetched through a user-supplied async function
+/// while the driver keeps track of visited nodes, depth and the number of fetches in flight.
+///
+/// The driver is runtime-agnostic: it only polls the futures it's given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AsyncTraversal {
+    max_depth: Option<usize>,
+    max_nodes: Option<usize>,
+    concurrency: usize,
+}
+
+impl AsyncTraversal {
+    /// Creates an unbounded traversal that runs up to 8 fetches at a time.
+    pub fn new() -> Self {
+        Self {
+            max_depth: None,
+            max_nodes: None,
+            concurrency: 8,
+        }
+    }
+
+    /// Stops expanding nodes `depth` hops away from the start.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Stops discovering nodes once `count` nodes have been visited.
+    pub fn max_nodes(mut self, count: usize) -> Self {
+        self.max_nodes = Some(count);
+        self
+    }
+
+    pub fn concurrency(mut self, limit: usize) -> Self {
+        assert!(limit > 0, "Expected a positive concurrency limit");
+        self.concurrency = limit;
+        self
+    }
+
+    /// Traverses from `start` and returns the explored part of the graph, with every node's
+    /// value set to its hop distance from `start`. Edges are those reported by `neighbours`
+    /// between visited nodes.
+    ///
+    /// Nodes of the same depth are fetched concurrently, but the result doesn't depend on
+    /// the order in which fetches complete. The first error aborts the traversal.
+    pub async fn run<F, Fut, E>(&self, start: GraphId, mut neighbours: F) -> Result<Graph<usize>, E>
+    where
+        F: FnMut(GraphId) -> Fut,
+        Fut: Future<Output = Result<Vec<GraphId>, E>>,
+    {
+        let mut graph = Graph::new();
+        graph.add_node(start, 0);
+        let mut visited: HashSet<GraphId> = HashSet::from([start]);
+        let mut frontier = vec![start];
+        let mut depth = 0;
+        while !frontier.is_empty() && self.max_depth.is_none_or(|max| depth < max) {
+            let fetched = stream::iter(frontier.iter().map(|&id| {
+                let fetch = neighbours(id);
+                async move { fetch.await.map(|found| (id, found)) }
+            }))
+            .buffered(self.concurrency)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+            depth += 1;
+            let mut next = Vec::new();
+            for (id, found) in fetched {
+                for neighbour in found {
+                    if !visited.contains(&neighbour)
+                        && self.max_nodes.is_none_or(|max| visited.len() < max)
+                    {
+                        visited.insert(neighbour);
+                        graph.add_node(neighbour, depth);
+                        next.push(neighbour);
+                    }
+                    graph.add_edge(id, neighbour);
+                }
+            }
+            frontier = next;
+        }
+
+        Ok(graph)
+    }
+}
+
+impl Default for AsyncTraversal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, collections::HashMap, rc::Rc};
+
+    use super::*;
+
+    /// An "external" binary tree where node `n` links to `2n` and `2n + 1`.
+    async fn children(id: GraphId) -> Result<Vec<GraphId>, String> {
+        tokio::task::yield_now().await;
+        Ok(vec![2 * id, 2 * id + 1])
+    }
+
+    #[tokio::test]
+    async fn run_respects_max_depth() {
+        let graph = AsyncTraversal::new()
+            .max_depth(2)
+            .run(1, children)
+            .await
+            .unwrap();
+        assert_eq!(graph.nodes.len(), 7, "Nodes count mismatch");
+        assert_eq!(graph[5], 2, "Depth mismatch");
+        assert!(graph.get_node(8).is_none(), "Expected depth limit");
+        assert_eq!(graph.edges.len(), 6, "Edges count mismatch");
+    }
+
+    #[tokio::test]
+    async fn run_visits_cycles_once() {
+        let edges = HashMap::from([(1, vec![2, 3]), (2, vec![3, 1]), (3, vec![1])]);
+        let calls = Cell::new(0);
+        let graph = AsyncTraversal::new()
+            .run(1, |id| {
+                calls.set(calls.get() + 1);
+                let found = edges[&id].clone();
+                async move { Ok::<_, String>(found) }
+            })
+            .await
+            .unwrap();
+        assert_eq!(calls.get(), 3, "Expected every node to be fetched once");
+        assert_eq!(graph.edges.len(), 5, "Edges count mismatch");
+    }
+
+    #[tokio::test]
+    async fn run_limits_concurrency() {
+        let in_flight = Rc::new(Cell::new(0));
+        let peak = Rc::new(Cell::new(0));
+        let graph = AsyncTraversal::new()
+            .max_depth(4)
+            .concurrency(3)
+            .run(1, |id| {
+                let (in_flight, peak) = (in_flight.clone(), peak.clone());
+                async move {
+                    in_flight.set(in_flight.get() + 1);
+                    peak.set(peak.get().max(in_flight.get()));
+                    let found = children(id).await;
+                    in_flight.set(in_flight.get() - 1);
+                    found
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!(graph.nodes.len(), 31, "Nodes count mismatch");
+        assert_eq!(peak.get(), 3, "Expected at most 3 fetches at a time");
+    }
+
+    #[tokio::test]
+    async fn run_stops_on_error_and_node_limit() {
+        let error = AsyncTraversal::new()
+            .run(1, |id| async move {
+                if id == 3 {
+                    Err(format!("Node {id} is unavailable"))
+                } else {
+                    children(id).await
+                }
+            })
+            .await;
+        assert_eq!(error, Err("Node 3 is unavailable".to_string()));
+
+        let graph = AsyncTraversal::new()
+            .max_nodes(5)
+            .run(1, children)
+            .await
+            .unwrap();
+        assert_eq!(graph.nodes.len(), 5, "Expected node limit");
+    }
+}
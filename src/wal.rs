@@ -0,0 +1,342 @@
+use std::{
+    fmt::{self, Display},
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, Write},
+    ops::Deref,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Context};
+
+use super::{Graph, GraphId, Mutation};
+
+/// Writes a mutation as a line of an operation log, e.g. `add-node 1 value` or
+/// `rm-edge 1 2`.
+impl<T: Display> Display for Mutation<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mutation::AddNode { id, value } => write!(f, "add-node {id} {value}"),
+            Mutation::RemoveNode { id } => write!(f, "rm-node {id}"),
+            Mutation::AddEdge { from, to } => write!(f, "add-edge {from} {to}"),
+            Mutation::RemoveEdge { from, to } => write!(f, "rm-edge {from} {to}"),
+        }
+    }
+}
+
+impl<T: FromStr> FromStr for Mutation<T> {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (operation, rest) = s.split_once(' ').unwrap_or((s, ""));
+        let mut parts = rest.splitn(2, ' ');
+        let mut id = || -> anyhow::Result<GraphId> {
+            let part = parts.next().filter(|part| !part.is_empty());
+            let part = part.ok_or_else(|| anyhow!("Missing node ID in {s:?}"))?;
+            part.parse()
+                .map_err(|_| anyhow!("Expected a node ID, got {part:?}"))
+        };
+
+        let mutation = match operation {
+            "add-node" => {
+                let id = id()?;
+                let value = parts.next().unwrap_or_default();
+                let value = value
+                    .parse()
+                    .map_err(|_| anyhow!("Invalid value {value:?} of node {id}"))?;
+                Mutation::AddNode { id, value }
+            }
+            "rm-node" => Mutation::RemoveNode { id: id()? },
+            "add-edge" => Mutation::AddEdge {
+                from: id()?,
+                to: id()?,
+            },
+            "rm-edge" => Mutation::RemoveEdge {
+                from: id()?,
+                to: id()?,
+            },
+            _ => return Err(anyhow!("Unknown operation {operation:?}")),
+        };
+        if parts.next().is_some() {
+            return Err(anyhow!("Unexpected trailing data in {s:?}"));
+        }
+
+        Ok(mutation)
+    }
+}
+
+impl<T: FromStr> Graph<T> {
+    /// Applies every mutation of an operation log, one per line, and returns how many were
+    /// read.
+    ///
+    /// A last line without a line break is ignored, since it is what a crash in the middle of
+    /// appending leaves behind.
+    pub fn replay(&mut self, mut log: impl BufRead) -> anyhow::Result<usize> {
+        let mut count = 0;
+        let mut line = String::new();
+        while log.read_line(&mut line)? > 0 {
+            let Some(entry) = line.strip_suffix('\n') else {
+                break;
+            };
+            let entry = entry.trim_end_matches('\r');
+            if !entry.is_empty() {
+                let mutation = entry
+                    .parse()
+                    .with_context(|| format!("Log entry {}", count + 1))?;
+                self.apply(mutation);
+                count += 1;
+            }
+            line.clear();
+        }
+
+        Ok(count)
+    }
+}
+
+/// A graph persisted as a TGF snapshot and an operation log of the changes since.
+///
+/// Every change is appended to the log instead of rewriting the snapshot. Once the log has
+/// [`compact_every`](Self::compact_every) entries, or on [`compact`](Self::compact), the graph
+/// is written to a new snapshot and the log starts over. Read access goes through `Deref`.
+#[derive(Debug)]
+pub struct PersistentGraph<T> {
+    graph: Graph<T>,
+    snapshot: PathBuf,
+    log_path: PathBuf,
+    log: File,
+    entries: usize,
+    compact_every: Option<usize>,
+}
+
+impl<T: Display + FromStr> PersistentGraph<T> {
+    /// Loads the snapshot and replays the log, either of which may not exist yet.
+    pub fn open(snapshot: impl Into<PathBuf>, log: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let (snapshot, log_path) = (snapshot.into(), log.into());
+        let mut graph = match fs::read_to_string(&snapshot) {
+            Ok(text) => text
+                .parse()
+                .with_context(|| format!("Failed to parse {}", snapshot.display()))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Graph::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let text = match fs::read_to_string(&log_path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let entries = graph
+            .replay(text.as_bytes())
+            .with_context(|| format!("Failed to replay {}", log_path.display()))?;
+        let log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)?;
+        // Drop a torn last entry, so that the next one starts on a line of its own.
+        let complete = text.rfind('\n').map_or(0, |end| end + 1);
+        if complete < text.len() {
+            log.set_len(complete as u64)?;
+        }
+
+        Ok(Self {
+            graph,
+            snapshot,
+            log_path,
+            log,
+            entries,
+            compact_every: None,
+        })
+    }
+
+    /// Compacts automatically once the log has `entries` entries.
+    pub fn compact_every(mut self, entries: usize) -> Self {
+        self.compact_every = Some(entries);
+        self
+    }
+
+    /// Applies a mutation and appends it to the log if it changed the graph. Returns the
+    /// mutations that revert it, like [`Graph::apply`].
+    pub fn apply(&mut self, mutation: Mutation<T>) -> io::Result<Vec<Mutation<T>>> {
+        let entry = format!("{mutation}\n");
+        let inverse = self.graph.apply(mutation);
+        if inverse.is_empty() {
+            return Ok(inverse);
+        }
+
+        self.log.write_all(entry.as_bytes())?;
+        self.log.sync_data()?;
+        self.entries += 1;
+        if self
+            .compact_every
+            .is_some_and(|limit| self.entries >= limit)
+        {
+            self.compact()?;
+        }
+
+        Ok(inverse)
+    }
+
+    /// Writes the graph to the snapshot and empties the log.
+    ///
+    /// Replaying a log onto the snapshot it led to doesn't change the graph, since every
+    /// logged mutation changed the graph when it was made. So a crash between writing the
+    /// snapshot and emptying the log loses nothing.
+    pub fn compact(&mut self) -> io::Result<()> {
+        let temporary = temporary_path(&self.snapshot);
+        fs::write(&temporary, self.graph.serialize())?;
+        File::open(&temporary)?.sync_all()?;
+        fs::rename(&temporary, &self.snapshot)?;
+
+        self.log = File::create(&self.log_path)?;
+        self.log.sync_all()?;
+        self.entries = 0;
+
+        Ok(())
+    }
+
+    /// Returns the number of entries in the log.
+    pub fn log_len(&self) -> usize {
+        self.entries
+    }
+
+    pub fn into_inner(self) -> Graph<T> {
+        self.graph
+    }
+}
+
+impl<T> Deref for PersistentGraph<T> {
+    type Target = Graph<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.graph
+    }
+}
+
+fn temporary_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_paths(name: &str) -> (PathBuf, PathBuf) {
+        let directory = std::env::temp_dir();
+        let prefix = format!("bgraph-{name}-{}", std::process::id());
+        let paths = (
+            directory.join(format!("{prefix}.tgf")),
+            directory.join(format!("{prefix}.log")),
+        );
+        let _ = fs::remove_file(&paths.0);
+        let _ = fs::remove_file(&paths.1);
+
+        paths
+    }
+
+    #[test]
+    fn mutation_round_trip() {
+        let mutations = [
+            Mutation::AddNode {
+                id: 1,
+                value: "two words".to_string(),
+            },
+            Mutation::RemoveNode { id: 1 },
+            Mutation::AddEdge { from: 1, to: 2 },
+            Mutation::RemoveEdge { from: 2, to: 1 },
+        ];
+        for mutation in mutations {
+            assert_eq!(
+                mutation.to_string().parse::<Mutation<String>>().ok(),
+                Some(mutation),
+                "Round trip mismatch"
+            );
+        }
+        assert!(
+            "add-edge 1".parse::<Mutation<String>>().is_err(),
+            "Expected missing target to fail"
+        );
+    }
+
+    #[test]
+    fn replay_ignores_torn_entry() {
+        let mut graph = Graph::<String>::new();
+        let log = "add-node 1 a\nadd-node 2 b\nadd-edge 1 2\nrm-node 2\nadd-ed";
+        assert_eq!(graph.replay(log.as_bytes()).unwrap(), 4, "Count mismatch");
+        assert_eq!(graph, Graph::from(([(1, "a".to_string())], [])));
+        assert!(
+            graph.replay("remove 1\n".as_bytes()).is_err(),
+            "Expected unknown operation to fail"
+        );
+    }
+
+    #[test]
+    fn persistent_graph_survives_reopening() {
+        let (snapshot, log) = test_paths("wal");
+        let mut graph = PersistentGraph::<String>::open(&snapshot, &log)
+            .unwrap()
+            .compact_every(3);
+        graph
+            .apply(Mutation::AddNode {
+                id: 1,
+                value: "a".to_string(),
+            })
+            .unwrap();
+        graph
+            .apply(Mutation::AddNode {
+                id: 2,
+                value: "b".to_string(),
+            })
+            .unwrap();
+        graph.apply(Mutation::RemoveNode { id: 3 }).unwrap();
+        assert_eq!(graph.log_len(), 2, "Expected no-op not to be logged");
+        assert!(!snapshot.exists(), "Expected no snapshot before compaction");
+
+        graph.apply(Mutation::AddEdge { from: 1, to: 2 }).unwrap();
+        assert_eq!(graph.log_len(), 0, "Expected compaction");
+        graph
+            .apply(Mutation::RemoveEdge { from: 1, to: 2 })
+            .unwrap();
+        let expected = graph.into_inner();
+
+        let mut file = OpenOptions::new().append(true).open(&log).unwrap();
+        file.write_all(b"add-no").unwrap();
+
+        let mut reopened = PersistentGraph::<String>::open(&snapshot, &log).unwrap();
+        assert_eq!(reopened.log_len(), 1, "Log length mismatch");
+        assert_eq!(*reopened, expected, "Expected graph to survive reopening");
+        reopened.apply(Mutation::RemoveNode { id: 2 }).unwrap();
+        assert_eq!(
+            fs::read_to_string(&log).unwrap(),
+            "rm-edge 1 2\nrm-node 2\n",
+            "Expected torn entry to be dropped"
+        );
+        fs::remove_file(snapshot).unwrap();
+        fs::remove_file(log).unwrap();
+    }
+
+    #[test]
+    fn empty_snapshot_survives_reopening() {
+        let (snapshot, log) = test_paths("wal-empty");
+        let mut graph = PersistentGraph::<String>::open(&snapshot, &log).unwrap();
+        graph
+            .apply(Mutation::AddNode {
+                id: 1,
+                value: "a".to_string(),
+            })
+            .unwrap();
+        graph.apply(Mutation::RemoveNode { id: 1 }).unwrap();
+        graph.compact().unwrap();
+        drop(graph);
+
+        let reopened = PersistentGraph::<String>::open(&snapshot, &log);
+        fs::remove_file(snapshot).unwrap();
+        fs::remove_file(log).unwrap();
+        assert_eq!(
+            reopened.unwrap().into_inner(),
+            Graph::new(),
+            "Expected empty graph to survive reopening"
+        );
+    }
+}
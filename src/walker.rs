@@ -0,0 +1,108 @@
+use alloc::{collections::VecDeque, vec::Vec};
+
+use super::{collections::HashSet, Graph, GraphId};
+
+/// A breadth-first traversal that keeps its own state and only borrows the graph for every
+/// step, so the graph can be changed between steps, e.g. to update the value of the node
+/// just visited.
+///
+/// Every step looks up the successors of the node it returns as they are at that moment,
+/// visiting them in ascending ID order. Nodes deleted before their turn are skipped.
+#[derive(Debug, Clone)]
+pub struct BfsWalker {
+    queue: VecDeque<GraphId>,
+    discovered: HashSet<GraphId>,
+}
+
+impl BfsWalker {
+    /// Starts a traversal at `source`, which is visited first if it exists at that point.
+    pub fn new(source: GraphId) -> Self {
+        let mut discovered = HashSet::new();
+        discovered.insert(source);
+
+        Self {
+            queue: VecDeque::from([source]),
+            discovered,
+        }
+    }
+
+    /// Returns the next node in breadth-first order, or `None` once the traversal is done.
+    ///
+    /// Takes time linear in the number of edges, as the graph keeps no adjacency index.
+    pub fn next<T>(&mut self, graph: &Graph<T>) -> Option<GraphId> {
+        let id = loop {
+            let id = self.queue.pop_front()?;
+            if graph.nodes.contains_key(&id) {
+                break id;
+            }
+        };
+
+        let mut successors = graph
+            .edges
+            .iter()
+            .filter(|edge| edge.from == id)
+            .map(|edge| edge.to)
+            .filter(|to| !self.discovered.contains(to))
+            .collect::<Vec<_>>();
+        successors.sort_unstable();
+        self.discovered.extend(&successors);
+        self.queue.extend(successors);
+
+        Some(id)
+    }
+
+    /// Returns whether `id` has been visited or is waiting to be.
+    pub fn is_discovered(&self, id: GraphId) -> bool {
+        self.discovered.contains(&id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_graph() -> Graph<u32> {
+        Graph::from((
+            [(1, 0), (2, 0), (3, 0), (4, 0), (5, 0)],
+            [(1, 3), (1, 2), (2, 4), (3, 4), (4, 5)],
+        ))
+    }
+
+    #[test]
+    fn walker_allows_mutation_between_steps() {
+        let mut graph = get_test_graph();
+        let mut walker = BfsWalker::new(1);
+        let mut order = Vec::new();
+        let mut depth = 0;
+        while let Some(id) = walker.next(&graph) {
+            order.push(id);
+            graph[id] = depth;
+            depth += 1;
+        }
+        assert_eq!(order, graph.bfs_order(1), "Expected BFS order");
+        assert_eq!(graph[5], 4, "Expected values written during the walk");
+    }
+
+    #[test]
+    fn walker_sees_changes_to_the_graph() {
+        let mut graph = get_test_graph();
+        let mut walker = BfsWalker::new(1);
+        assert_eq!(walker.next(&graph), Some(1), "Expected source first");
+        graph.delete_node(2);
+        graph.add_edge(3, 5);
+        assert_eq!(
+            walker.next(&graph),
+            Some(3),
+            "Expected deleted node skipped"
+        );
+        assert!(walker.is_discovered(5), "Expected new edge followed");
+        assert_eq!(walker.next(&graph), Some(4), "Order mismatch");
+        assert_eq!(walker.next(&graph), Some(5), "Order mismatch");
+        assert_eq!(walker.next(&graph), None, "Expected traversal to end");
+        assert_eq!(
+            BfsWalker::new(9).next(&graph),
+            None,
+            "Expected missing source"
+        );
+    }
+}
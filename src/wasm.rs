@@ -0,0 +1,153 @@
+//! JavaScript bindings. Build with `wasm-pack` (or `cargo rustc --crate-type cdylib`) for
+//! `wasm32-unknown-unknown` with the `wasm` feature enabled.
+
+use wasm_bindgen::prelude::*;
+
+use super::{Graph, GraphId, Path};
+
+/// A graph with string values, exported to JavaScript as `Graph`. IDs are `bigint`s on the
+/// JavaScript side.
+#[wasm_bindgen(js_name = Graph)]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WasmGraph {
+    graph: Graph<String>,
+}
+
+#[wasm_bindgen(js_class = Graph)]
+impl WasmGraph {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses the format written by [`WasmGraph::to_json`].
+    #[wasm_bindgen(js_name = fromJson)]
+    pub fn from_json(json: &str) -> Result<WasmGraph, JsError> {
+        Graph::from_json(json)
+            .map(|graph| Self { graph })
+            .map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> String {
+        self.graph.to_json()
+    }
+
+    #[wasm_bindgen(js_name = addNode)]
+    pub fn add_node(&mut self, id: GraphId, value: String) {
+        self.graph.add_node(id, value);
+    }
+
+    #[wasm_bindgen(js_name = removeNode)]
+    pub fn remove_node(&mut self, id: GraphId) {
+        self.graph.delete_node(id);
+    }
+
+    #[wasm_bindgen(js_name = addEdge)]
+    pub fn add_edge(&mut self, from: GraphId, to: GraphId) {
+        self.graph.add_edge(from, to);
+    }
+
+    #[wasm_bindgen(js_name = removeEdge)]
+    pub fn remove_edge(&mut self, from: GraphId, to: GraphId) {
+        self.graph.delete_edge(from, to);
+    }
+
+    /// Returns the value of the node, or `undefined` for unknown nodes.
+    pub fn value(&self, id: GraphId) -> Option<String> {
+        self.graph.nodes.get(&id).cloned()
+    }
+
+    /// Returns the sorted targets of the node's outgoing edges.
+    pub fn neighbours(&self, id: GraphId) -> Vec<GraphId> {
+        self.graph.adjacency().remove(&id).unwrap_or_default()
+    }
+
+    /// Returns the sorted IDs of all nodes.
+    pub fn ids(&self) -> Vec<GraphId> {
+        let mut ids = self.graph.nodes.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+
+        ids
+    }
+
+    #[wasm_bindgen(getter, js_name = nodeCount)]
+    pub fn node_count(&self) -> usize {
+        self.graph.nodes.len()
+    }
+
+    #[wasm_bindgen(getter, js_name = edgeCount)]
+    pub fn edge_count(&self) -> usize {
+        self.graph.edges.len()
+    }
+
+    /// Returns the nodes reachable from `source` in breadth-first order, visiting neighbours
+    /// in ascending ID order. Empty if `source` doesn't exist.
+    pub fn bfs(&self, source: GraphId) -> Vec<GraphId> {
+        self.graph.bfs_order(source)
+    }
+
+    /// Returns the IDs along a shortest path from `from` to `to`, or `undefined` if `to` is
+    /// unreachable.
+    #[wasm_bindgen(js_name = shortestPath)]
+    pub fn shortest_path(&self, from: GraphId, to: GraphId) -> Option<Vec<GraphId>> {
+        self.graph.shortest_path(from, to).map(Path::into_nodes)
+    }
+}
+
+impl From<Graph<String>> for WasmGraph {
+    fn from(graph: Graph<String>) -> Self {
+        Self { graph }
+    }
+}
+
+impl From<WasmGraph> for Graph<String> {
+    fn from(graph: WasmGraph) -> Self {
+        graph.graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_graph() -> WasmGraph {
+        let mut graph = WasmGraph::new();
+        for id in 1..=5 {
+            graph.add_node(id, format!("n{id}"));
+        }
+        for (from, to) in [(1, 3), (1, 2), (2, 4), (3, 4), (4, 1)] {
+            graph.add_edge(from, to);
+        }
+
+        graph
+    }
+
+    #[test]
+    fn bfs_visits_neighbours_in_order() {
+        let graph = get_test_graph();
+        assert_eq!(graph.bfs(1), vec![1, 2, 3, 4], "Order mismatch");
+        assert_eq!(graph.bfs(5), vec![5], "Expected only the source");
+        assert!(graph.bfs(9).is_empty(), "Expected no nodes");
+    }
+
+    #[test]
+    fn edits_and_shortest_path() {
+        let mut graph = get_test_graph();
+        assert_eq!(graph.shortest_path(1, 4), Some(vec![1, 2, 4]));
+        graph.remove_edge(2, 4);
+        graph.remove_node(3);
+        assert_eq!(graph.shortest_path(1, 4), None, "Expected no path");
+        assert_eq!(graph.ids(), vec![1, 2, 4, 5], "IDs mismatch");
+        assert_eq!(graph.edge_count(), 2, "Edges count mismatch");
+        assert_eq!(graph.value(2).as_deref(), Some("n2"), "Value mismatch");
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let graph = get_test_graph();
+        let parsed = WasmGraph::from_json(&graph.to_json()).unwrap();
+        assert_eq!(parsed, graph, "Graph doesn't match");
+        assert_eq!(parsed.neighbours(1), vec![2, 3], "Neighbours mismatch");
+    }
+}
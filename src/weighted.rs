@@ -0,0 +1,143 @@
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+use super::{collections::HashMap, Edge, Graph, GraphId};
+
+/// A graph whose edges carry a numeric weight, such as a distance or capacity.
+///
+/// Read access through `Deref` sees the graph without weights.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedGraph<T> {
+    graph: Graph<T>,
+    weights: HashMap<Edge, f64>,
+}
+
+impl<T> WeightedGraph<T> {
+    pub fn new() -> Self {
+        Self {
+            graph: Graph::new(),
+            weights: HashMap::new(),
+        }
+    }
+
+    pub fn add_node(&mut self, id: GraphId, value: T) {
+        self.graph.add_node(id, value);
+    }
+
+    pub fn delete_node(&mut self, id: GraphId) {
+        self.graph.delete_node(id);
+        self.weights
+            .retain(|edge, _| edge.from != id && edge.to != id);
+    }
+
+    /// Adds an edge with `weight`, replacing the weight if the edge already exists. Does
+    /// nothing if an endpoint doesn't exist.
+    pub fn add_edge(&mut self, from: GraphId, to: GraphId, weight: f64) {
+        if !self.graph.nodes.contains_key(&from) || !self.graph.nodes.contains_key(&to) {
+            return;
+        }
+
+        self.graph.add_edge(from, to);
+        self.weights.insert(Edge { from, to }, weight);
+    }
+
+    pub fn delete_edge(&mut self, from: GraphId, to: GraphId) {
+        self.graph.delete_edge(from, to);
+        self.weights.remove(&Edge { from, to });
+    }
+
+    /// Returns the weight of the edge, or `None` if it doesn't exist.
+    pub fn weight(&self, from: GraphId, to: GraphId) -> Option<f64> {
+        self.weights.get(&Edge { from, to }).copied()
+    }
+
+    /// Returns all edges with their weights as `(from, to, weight)`, sorted by endpoints.
+    pub fn weighted_edges(&self) -> Vec<(GraphId, GraphId, f64)> {
+        let mut edges = self
+            .weights
+            .iter()
+            .map(|(edge, weight)| (edge.from, edge.to, *weight))
+            .collect::<Vec<_>>();
+        edges.sort_unstable_by_key(|(from, to, _)| (*from, *to));
+
+        edges
+    }
+
+    /// Drops the weights, keeping the nodes and edges.
+    pub fn into_graph(self) -> Graph<T> {
+        self.graph
+    }
+}
+
+impl<T> Default for WeightedGraph<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Deref for WeightedGraph<T> {
+    type Target = Graph<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.graph
+    }
+}
+
+/// Gives every edge of the graph weight 1.
+impl<T> From<Graph<T>> for WeightedGraph<T> {
+    fn from(graph: Graph<T>) -> Self {
+        let weights = graph.edges.iter().map(|edge| (*edge, 1.0)).collect();
+
+        Self { graph, weights }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_graph() -> WeightedGraph<&'static str> {
+        let mut graph = WeightedGraph::new();
+        for (id, value) in [(1, "a"), (2, "b"), (3, "c")] {
+            graph.add_node(id, value);
+        }
+        graph.add_edge(1, 2, 2.5);
+        graph.add_edge(2, 3, -1.0);
+        graph.add_edge(3, 1, 4.0);
+
+        graph
+    }
+
+    #[test]
+    fn add_edge_replaces_weight() {
+        let mut graph = get_test_graph();
+        graph.add_edge(1, 2, 7.0);
+        graph.add_edge(1, 4, 1.0);
+        assert_eq!(graph.weight(1, 2), Some(7.0), "Weight mismatch");
+        assert_eq!(
+            graph.weight(1, 4),
+            None,
+            "Expected missing node to be ignored"
+        );
+        assert_eq!(graph.edges().count(), 3, "Edge count mismatch");
+    }
+
+    #[test]
+    fn delete_node_drops_weights() {
+        let mut graph = get_test_graph();
+        graph.delete_node(1);
+        graph.delete_edge(2, 3);
+        assert_eq!(graph.weighted_edges(), vec![], "Expected no edges");
+        assert_eq!(graph.weight(3, 1), None, "Expected weight to be dropped");
+    }
+
+    #[test]
+    fn from_graph_has_unit_weights() {
+        let graph = WeightedGraph::from(Graph::from(([(1, ()), (2, ())], [(1, 2), (2, 1)])));
+        assert_eq!(
+            graph.weighted_edges(),
+            vec![(1, 2, 1.0), (2, 1, 1.0)],
+            "Weighted edges mismatch"
+        );
+    }
+}
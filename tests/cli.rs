@@ -0,0 +1,465 @@
+#![cfg(feature = "cli")]
+
+use std::{
+    fs,
+    io::{Read, Write},
+    path::PathBuf,
+    process::{Command, Output, Stdio},
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+/// Writes `text` to a test file unique to this process.
+fn test_file(name: &str, text: &str) -> PathBuf {
+    let file = std::env::temp_dir().join(format!("bgraph-cli-{name}-{}.tgf", std::process::id()));
+    fs::write(&file, text).unwrap();
+
+    file
+}
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_bgraph-cli"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Runs the CLI with `input` on stdin.
+fn run_with_input(args: &[&str], input: &str) -> Output {
+    run_command(
+        Command::new(env!("CARGO_BIN_EXE_bgraph-cli")).args(args),
+        input,
+    )
+}
+
+fn run_command(command: &mut Command, input: &str) -> Output {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(
+        output.status.success(),
+        "Expected success: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn output_can_replace_input() {
+    let file = test_file("output", "1 a\n2 b\n#\n1 2\n");
+    let other = test_file("output-other", "1 a\n3 c\n#\n3 1\n");
+    let path = file.to_str().unwrap();
+    let output = run(&["add-node", path, "4", "d", "-o", path]);
+    assert_eq!(stdout(&output), "", "Expected nothing on stdout");
+    assert_eq!(
+        fs::read_to_string(&file).unwrap(),
+        "1 a\n2 b\n4 d\n#\n1 2\n",
+        "Expected edited graph in the output file"
+    );
+
+    let output = run(&["merge", path, other.to_str().unwrap(), "-o", path]);
+    stdout(&output);
+    assert_eq!(
+        fs::read_to_string(&file).unwrap(),
+        "1 a\n2 b\n3 c\n4 d\n#\n1 2\n3 1\n",
+        "Expected merged graph in the output file"
+    );
+
+    let output = run(&["node", "-", "1", "-o", path]);
+    assert!(!output.status.success(), "Expected invalid input to fail");
+    let text = fs::read_to_string(&file);
+    fs::remove_file(&file).unwrap();
+    fs::remove_file(&other).unwrap();
+    assert_eq!(
+        text.unwrap(),
+        "1 a\n2 b\n3 c\n4 d\n#\n1 2\n3 1\n",
+        "Expected failed command to keep the output file"
+    );
+}
+
+#[test]
+fn rm_node_deletes_last_node() {
+    let file = test_file("rm", "1 a\n2 b\n#\n1 2\n");
+    for id in ["1", "2"] {
+        stdout(&run(&["rm-node", file.to_str().unwrap(), id]));
+    }
+
+    let text = fs::read_to_string(&file);
+    fs::remove_file(&file).unwrap();
+    assert_eq!(text.unwrap(), "#\n", "Expected an empty graph");
+}
+
+#[test]
+fn path_reports_hops_or_weight() {
+    let unweighted = test_file("path", "1 a\n2 b\n3 c\n#\n1 2\n2 3\n1 3\n");
+    let weighted = test_file(
+        "path-weighted",
+        "1 a\n2 b\n3 c\n#\n1 2 5\n2 3 1.5\n1 3 10\n",
+    );
+    let unweighted_path = run(&["path", unweighted.to_str().unwrap(), "1", "3"]);
+    let weighted_path = run(&["path", weighted.to_str().unwrap(), "1", "3"]);
+    let missing = run(&["path", unweighted.to_str().unwrap(), "3", "1"]);
+    fs::remove_file(&unweighted).unwrap();
+    fs::remove_file(&weighted).unwrap();
+
+    assert_eq!(
+        stdout(&unweighted_path),
+        "1 -> 3\nDistance: 1\n",
+        "Expected hop count"
+    );
+    assert_eq!(
+        stdout(&weighted_path),
+        "1 -> 2 -> 3\nDistance: 6.5\n",
+        "Expected total weight"
+    );
+    assert!(!missing.status.success(), "Expected missing path to fail");
+    assert_eq!(
+        String::from_utf8_lossy(&missing.stderr),
+        "No path from 3 to 1\n",
+        "Error mismatch"
+    );
+}
+
+#[test]
+fn stats_prints_summary() {
+    let file = test_file("stats", "1 a\n2 b\n3 c\n4 d\n#\n1 2\n2 3\n3 2\n");
+    let output = run(&["stats", file.to_str().unwrap()]);
+    fs::remove_file(&file).unwrap();
+    assert_eq!(
+        stdout(&output),
+        "Nodes: 4\nEdges: 3\nDensity: 0.2500\nComponents: 2\nDAG: no\n\
+         Assortativity: -0.8660\nReciprocity: 0.6667\nSources: 2\nSinks: 1\n\
+         Degree distribution:\n  0: 1\n  1: 1\n  2: 1\n  3: 1\n",
+        "Stats mismatch"
+    );
+}
+
+#[test]
+fn components_and_cycles() {
+    let file = test_file("components", "1 a\n2 b\n3 c\n4 d\n#\n1 2\n2 3\n3 2\n");
+    let path = file.to_str().unwrap();
+    let weak = run(&["components", path]);
+    let strong = run(&["components", "--strong", path]);
+    let cycles = run(&["cycles", path]);
+    fs::remove_file(&file).unwrap();
+
+    assert_eq!(stdout(&weak), "1, 2, 3\n4\n", "Components mismatch");
+    assert_eq!(
+        stdout(&strong),
+        "1\n2, 3\n4\n",
+        "Strong components mismatch"
+    );
+    assert_eq!(cycles.status.code(), Some(1), "Expected cycles to fail");
+    assert_eq!(
+        String::from_utf8_lossy(&cycles.stdout),
+        "2 -> 3 -> 2\n",
+        "Cycles mismatch"
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&cycles.stderr),
+        "Found 1 cycle\n",
+        "Error mismatch"
+    );
+}
+
+#[test]
+fn cycles_succeeds_on_dag() {
+    let file = test_file("dag", "1 a\n2 b\n#\n1 2\n");
+    let output = run(&["cycles", file.to_str().unwrap()]);
+    fs::remove_file(&file).unwrap();
+    assert_eq!(stdout(&output), "", "Expected no cycles");
+}
+
+#[test]
+fn reads_graph_from_stdin() {
+    let output = run_with_input(&["add-edge", "-", "2", "1"], "1 a\n2 b\n#\n1 2\n");
+    assert_eq!(
+        stdout(&output),
+        "1 a\n2 b\n#\n1 2\n2 1\n",
+        "Expected edited graph on stdout"
+    );
+
+    let output = run_with_input(&["components", "-"], "1 a\n2 b\n#\n");
+    assert_eq!(stdout(&output), "1\n2\n", "Components mismatch");
+}
+
+#[test]
+fn render_highlights_path() {
+    let file = test_file("render", "1 a\n2 b\n3 c\n4 d\n#\n1 2\n2 3\n3 2\n");
+    let path = file.to_str().unwrap();
+    let dot = run(&[
+        "render",
+        path,
+        "--format",
+        "dot",
+        "--highlight-path",
+        "1",
+        "3",
+    ]);
+    let svg = run(&["render", path]);
+    fs::remove_file(&file).unwrap();
+
+    assert_eq!(
+        stdout(&dot),
+        "digraph {\n    1 [label=\"a\", color=red];\n    2 [label=\"b\", color=red];\n    \
+         3 [label=\"c\", color=red];\n    4 [label=\"d\"];\n    1 -> 2 [color=red];\n    \
+         2 -> 3 [color=red];\n    3 -> 2;\n}\n",
+        "DOT mismatch"
+    );
+    assert!(stdout(&svg).starts_with("<svg "), "Expected SVG by default");
+}
+
+#[test]
+fn edit_commands() {
+    let file = test_file("edit", "1 a\n2 b\n#\n1 2\n");
+    let path = file.to_str().unwrap();
+    let dry_run = run(&["add-edge", path, "2", "1", "--dry-run"]);
+    let missing = run(&["add-edge", path, "1", "3"]);
+    let duplicate = run(&["add-node", path, "1", "x"]);
+    stdout(&run(&["rm-edge", path, "1", "2"]));
+    let text = fs::read_to_string(&file);
+    fs::remove_file(&file).unwrap();
+
+    assert_eq!(
+        stdout(&dry_run),
+        "1 a\n2 b\n#\n1 2\n2 1\n",
+        "Expected edited graph on stdout"
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&missing.stderr),
+        "Node 3 doesn't exist\n",
+        "Error mismatch"
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&duplicate.stderr),
+        "Node 1 already exists\n",
+        "Error mismatch"
+    );
+    assert!(
+        !missing.status.success() && !duplicate.status.success(),
+        "Expected failed edits to fail"
+    );
+    assert_eq!(
+        text.unwrap(),
+        "1 a\n2 b\n#\n",
+        "Expected only the removed edge to change the file"
+    );
+}
+
+#[test]
+fn diff_and_merge() {
+    let file = test_file("diff", "1 a\n2 b\n3 c\n#\n1 2\n2 3\n");
+    let other = test_file("diff-other", "1 a\n2 x\n5 e\n#\n1 2\n2 5\n");
+    let (path, other_path) = (file.to_str().unwrap(), other.to_str().unwrap());
+    let diff = run(&["diff", path, other_path]);
+    let json = run(&["diff", "--json", path, other_path]);
+    let conflict = run(&["merge", path, other_path]);
+    let theirs = run(&["merge", path, other_path, "--on-conflict", "theirs"]);
+    let ours = run(&["merge", path, other_path, "--on-conflict", "ours"]);
+    fs::remove_file(&file).unwrap();
+    fs::remove_file(&other).unwrap();
+
+    assert_eq!(
+        stdout(&diff),
+        "+ node 5: e\n- node 3: c\n~ node 2: b -> x\n+ edge 2 -> 5\n- edge 2 -> 3\n",
+        "Diff mismatch"
+    );
+    assert_eq!(
+        stdout(&json),
+        "{\"added_edges\":[{\"from\":2,\"to\":5}],\"added_nodes\":[{\"id\":5,\"value\":\"e\"}],\
+         \"changed_nodes\":[{\"id\":2,\"new\":\"x\",\"old\":\"b\"}],\
+         \"removed_edges\":[{\"from\":2,\"to\":3}],\"removed_nodes\":[{\"id\":3,\"value\":\"c\"}]}\n",
+        "JSON diff mismatch"
+    );
+    assert!(!conflict.status.success(), "Expected conflict to fail");
+    assert_eq!(
+        String::from_utf8_lossy(&conflict.stderr),
+        "Failed to merge graphs: Conflicting values for node 2\n",
+        "Error mismatch"
+    );
+    assert_eq!(
+        stdout(&theirs),
+        "1 a\n2 x\n3 c\n5 e\n#\n1 2\n2 3\n2 5\n",
+        "Expected their value"
+    );
+    assert_eq!(
+        stdout(&ours),
+        "1 a\n2 b\n3 c\n5 e\n#\n1 2\n2 3\n2 5\n",
+        "Expected our value"
+    );
+}
+
+#[test]
+fn repl_edits_and_saves() {
+    let file = test_file("repl", "1 a\n2 b\n#\n1 2\n");
+    let home = std::env::temp_dir().join(format!("bgraph-cli-home-{}", std::process::id()));
+    fs::create_dir_all(&home).unwrap();
+    let input = "add-node 3 c\nadd-edge 2 3\npath 1 3\nrm-node 1\nundo\nneighbors 2\n\
+                 add-edge 2 3\nsave\nquit\n";
+    let output = run_command(
+        Command::new(env!("CARGO_BIN_EXE_bgraph-cli"))
+            .args(["repl", file.to_str().unwrap()])
+            .env("HOME", &home),
+        input,
+    );
+    let text = fs::read_to_string(&file);
+    fs::remove_file(&file).unwrap();
+    fs::remove_dir_all(&home).unwrap();
+
+    assert_eq!(
+        stdout(&output),
+        format!("1 -> 2 -> 3\nDistance: 2\n3\nSaved {}\n", file.display()),
+        "Output mismatch"
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Edge 2 -> 3 already exists\n",
+        "Expected failed edit to be reported"
+    );
+    assert_eq!(
+        text.unwrap(),
+        "1 a\n2 b\n3 c\n#\n1 2\n2 3\n",
+        "Expected saved graph"
+    );
+}
+
+#[test]
+fn node_and_neighbors() {
+    let file = test_file("node", "1 a\n2 b\n3 c\n4 d\n#\n1 2\n2 3\n3 2\n");
+    let path = file.to_str().unwrap();
+    let node = run(&["node", path, "2"]);
+    let missing = run(&["node", path, "9"]);
+    let incoming = run(&["neighbors", path, "2", "--in"]);
+    let both = run(&["neighbors", path, "2", "--both"]);
+    let deep = run(&["neighbors", path, "1", "--depth", "2"]);
+    fs::remove_file(&file).unwrap();
+
+    assert_eq!(
+        stdout(&node),
+        "ID     2\nValue  b\nOut    3\nIn     1 3\n",
+        "Node mismatch"
+    );
+    assert!(!missing.status.success(), "Expected missing node to fail");
+    assert_eq!(
+        stdout(&incoming),
+        "ID  DEPTH  VALUE\n1   1      a\n3   1      c\n",
+        "Incoming neighbors mismatch"
+    );
+    assert_eq!(
+        stdout(&both),
+        "ID  DEPTH  VALUE\n1   1      a\n3   1      c\n",
+        "Neighbors mismatch"
+    );
+    assert_eq!(
+        stdout(&deep),
+        "ID  DEPTH  VALUE\n2   1      b\n3   2      c\n",
+        "Expected neighbors up to depth 2"
+    );
+}
+
+#[test]
+fn json_format() {
+    let file = test_file("json", "1 a\n2 b\n3 c\n4 d\n#\n1 2\n2 3\n3 2\n");
+    let path = file.to_str().unwrap();
+    let stats = run(&["--format", "json", "stats", path]);
+    let components = run(&["components", path, "--json"]);
+    let shortest = run(&["--format", "json", "path", path, "1", "3"]);
+    let node = run(&["--format", "json", "node", path, "2"]);
+    let neighbors = run(&["--format", "json", "neighbors", path, "2"]);
+    let plain = run(&["--format", "plain", "components", path]);
+    let edit = run(&["--format", "json", "add-node", path, "5", "e", "--dry-run"]);
+    fs::remove_file(&file).unwrap();
+
+    assert_eq!(
+        stdout(&stats),
+        "{\"assortativity\":-0.8660254037844375,\"components\":2,\
+         \"degree_distribution\":{\"0\":1,\"1\":1,\"2\":1,\"3\":1},\"density\":0.25,\
+         \"edges\":3,\"is_dag\":false,\"nodes\":4,\"reciprocity\":0.6666666666666666,\
+         \"sinks\":1,\"sources\":2}\n",
+        "Stats mismatch"
+    );
+    assert_eq!(
+        stdout(&components),
+        "[[1,2,3],[4]]\n",
+        "Components mismatch"
+    );
+    assert_eq!(
+        stdout(&shortest),
+        "{\"distance\":2,\"path\":[1,2,3]}\n",
+        "Path mismatch"
+    );
+    assert_eq!(
+        stdout(&node),
+        "{\"id\":2,\"in\":[1,3],\"out\":[3],\"value\":\"b\"}\n",
+        "Node mismatch"
+    );
+    assert_eq!(
+        stdout(&neighbors),
+        "[{\"depth\":1,\"id\":3,\"value\":\"c\"}]\n",
+        "Neighbors mismatch"
+    );
+    assert_eq!(stdout(&plain), "1, 2, 3\n4\n", "Expected plain output");
+    assert_eq!(
+        stdout(&edit),
+        "1 a\n2 b\n3 c\n4 d\n5 e\n#\n1 2\n2 3\n3 2\n",
+        "Expected graphs to stay TGF"
+    );
+}
+
+#[test]
+fn watch_reruns_on_change() {
+    let file = test_file("watch", "1 a\n2 b\n#\n1 2\n");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_bgraph-cli"))
+        .args(["components", file.to_str().unwrap(), "--watch"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut reader = child.stdout.take().unwrap();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut buffer = [0; 1024];
+        while let Ok(read @ 1..) = reader.read(&mut buffer) {
+            if sender.send(buffer[..read].to_vec()).is_err() {
+                break;
+            }
+        }
+    });
+    let mut output = Vec::new();
+    let mut wait_for = |expected: &str| {
+        while !String::from_utf8_lossy(&output).ends_with(expected) {
+            match receiver.recv_timeout(Duration::from_secs(10)) {
+                Ok(bytes) => output.extend(bytes),
+                Err(_) => return false,
+            }
+        }
+        true
+    };
+
+    let first = wait_for("\x1b[2J\x1b[H1, 2\n");
+    fs::write(&file, "1 a\n2 b\n#\n").unwrap();
+    let second = first && wait_for("\x1b[2J\x1b[H1\n2\n");
+    child.kill().unwrap();
+    child.wait().unwrap();
+    fs::remove_file(&file).unwrap();
+    assert!(first, "Expected components before the change");
+    assert!(second, "Expected components after the change");
+
+    let stdin = run_with_input(&["components", "-", "--watch"], "1 a\n#\n");
+    assert!(!stdin.status.success(), "Expected watching stdin to fail");
+}